@@ -15,8 +15,9 @@ use super::{
     payments::{
         distribution::PaymentDistributionRow, filters::PaymentFilterRow, metrics::PaymentMetricRow,
     },
-    query::{Aggregate, ToSql, Window},
+    query::{Aggregate, QueryBuilder, QueryResult, ToSql, Window},
     refunds::{filters::RefundFilterRow, metrics::RefundMetricRow},
+    reports::ReportRow,
     sdk_events::{filters::SdkEventFilter, metrics::SdkEventMetricRow},
     types::{AnalyticsCollection, AnalyticsDataSource, LoadRow, QueryExecutionError},
 };
@@ -166,6 +167,19 @@ impl super::refunds::metrics::RefundMetricAnalytics for ClickhouseClient {}
 impl super::refunds::filters::RefundFilterAnalytics for ClickhouseClient {}
 impl super::frm::metrics::FrmMetricAnalytics for ClickhouseClient {}
 impl super::frm::filters::FrmFilterAnalytics for ClickhouseClient {}
+impl super::reports::ReportAnalytics for ClickhouseClient {
+    fn add_report_columns(
+        builder: &mut QueryBuilder<Self>,
+        columns: &[&'static str],
+    ) -> QueryResult<()> {
+        for column in columns {
+            builder.add_select_column(*column)?;
+            // Grouping by all the columns drops the rows that have been cancelled out
+            builder.add_group_by_clause(*column)?;
+        }
+        Ok(())
+    }
+}
 impl super::sdk_events::filters::SdkEventFilterAnalytics for ClickhouseClient {}
 impl super::sdk_events::metrics::SdkEventMetricAnalytics for ClickhouseClient {}
 impl super::sdk_events::events::SdkEventsFilterAnalytics for ClickhouseClient {}
@@ -284,6 +298,28 @@ impl TryInto<RefundMetricRow> for serde_json::Value {
     }
 }
 
+impl TryInto<ReportRow> for serde_json::Value {
+    type Error = Report<ParsingError>;
+
+    fn try_into(self) -> Result<ReportRow, Self::Error> {
+        match self {
+            Self::Object(values) => Ok(ReportRow(
+                values
+                    .into_iter()
+                    .filter_map(|(column, value)| match value {
+                        Self::Null => None,
+                        Self::String(value) => Some((column, value)),
+                        value => Some((column, value.to_string())),
+                    })
+                    .collect(),
+            )),
+            _ => Err(report!(ParsingError::StructParseFailure(
+                "Failed to parse ReportRow in clickhouse results",
+            ))),
+        }
+    }
+}
+
 impl TryInto<RefundFilterRow> for serde_json::Value {
     type Error = Report<ParsingError>;
 
@@ -587,3 +623,29 @@ pub enum ClickhouseError {
     #[error("Clickhouse response error")]
     ResponseError,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_report_row_from_clickhouse_result() {
+        let row: ReportRow = serde_json::json!({
+            "payment_id": "pay_1",
+            "amount": 100,
+            "error_code": null,
+        })
+        .try_into()
+        .unwrap();
+
+        assert_eq!(row.get("payment_id"), Some("pay_1"));
+        assert_eq!(row.get("amount"), Some("100"));
+        // Null values are omitted from the row
+        assert_eq!(row.get("error_code"), None);
+        assert_eq!(row.0.len(), 2);
+
+        let result: Result<ReportRow, _> = serde_json::json!(["pay_1"]).try_into();
+        assert!(result.is_err());
+    }
+}
@@ -12,6 +12,12 @@ pub enum AnalyticsError {
     UnknownError,
     #[error("Access Forbidden Analytics Error")]
     AccessForbiddenError,
+    #[error("Report not found")]
+    ReportNotFound,
+    #[error("Report is not generated yet")]
+    ReportNotGenerated,
+    #[error("Report has expired")]
+    ReportExpired,
 }
 
 impl ErrorSwitch<ApiErrorResponse> for AnalyticsError {
@@ -32,6 +38,21 @@ impl ErrorSwitch<ApiErrorResponse> for AnalyticsError {
             Self::AccessForbiddenError => {
                 ApiErrorResponse::Unauthorized(ApiError::new("IR", 0, "Access Forbidden", None))
             }
+            Self::ReportNotFound => {
+                ApiErrorResponse::NotFound(ApiError::new("IR", 1, "Report not found", None))
+            }
+            Self::ReportNotGenerated => ApiErrorResponse::BadRequest(ApiError::new(
+                "IR",
+                2,
+                "Report is not generated yet, try again later",
+                None,
+            )),
+            Self::ReportExpired => ApiErrorResponse::NotFound(ApiError::new(
+                "IR",
+                3,
+                "Report has expired, generate it again",
+                None,
+            )),
         }
     }
 }
@@ -16,6 +16,7 @@ pub mod payment_intents;
 pub mod payments;
 mod query;
 pub mod refunds;
+pub mod reports;
 pub mod sdk_events;
 pub mod search;
 mod sqlx;
@@ -52,7 +53,7 @@ use api_models::analytics::{
     sdk_events::{
         SdkEventDimensions, SdkEventFilters, SdkEventMetrics, SdkEventMetricsBucketIdentifier,
     },
-    Distribution, Granularity, TimeRange,
+    Distribution, Granularity, ReportRequest, TimeRange,
 };
 use clickhouse::ClickhouseClient;
 pub use clickhouse::ClickhouseConfig;
@@ -846,6 +847,25 @@ impl AnalyticsProvider {
         }
     }
 
+    pub async fn generate_report(
+        &self,
+        kind: reports::ReportKind,
+        request: &ReportRequest,
+        auth: &AuthInfo,
+        config: &reports::InProcessReportConfig,
+        format: reports::ReportFormat,
+        sink: &mut dyn reports::ReportSink,
+    ) -> types::ReportsResult<()> {
+        match self {
+            Self::Sqlx(pool) | Self::CombinedSqlx(pool, _) => {
+                reports::generate_report(pool, kind, request, auth, config, format, sink).await
+            }
+            Self::Clickhouse(pool) | Self::CombinedCkh(_, pool) => {
+                reports::generate_report(pool, kind, request, auth, config, format, sink).await
+            }
+        }
+    }
+
     pub async fn from_conf(
         config: &AnalyticsConfig,
         tenant: &dyn storage_impl::config::TenantConfig,
@@ -955,6 +975,20 @@ pub struct ReportConfig {
     pub refund_function: String,
     pub dispute_function: String,
     pub region: String,
+    #[serde(default)]
+    pub backend: reports::ReportBackend,
+    #[serde(default)]
+    pub in_process: reports::InProcessReportConfig,
+}
+
+impl ReportConfig {
+    pub fn get_function(&self, kind: reports::ReportKind) -> &str {
+        match kind {
+            reports::ReportKind::Payment => &self.payment_function,
+            reports::ReportKind::Refund => &self.refund_function,
+            reports::ReportKind::Dispute => &self.dispute_function,
+        }
+    }
 }
 
 /// Analytics Flow routes Enums
@@ -981,6 +1015,7 @@ pub enum AnalyticsFlow {
     GeneratePaymentReport,
     GenerateDisputeReport,
     GenerateRefundReport,
+    DownloadReport,
     GetApiEventMetrics,
     GetApiEventFilters,
     GetConnectorEvents,
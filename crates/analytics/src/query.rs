@@ -335,6 +335,20 @@ pub struct TopN {
     pub order: Order,
 }
 
+// Limit the number of rows returned by a query
+// ---
+// Description -
+// limit: Maximum number of rows returned
+// ---
+// Usage -
+// Use via set_limit_by_clause fn of query_builder, along with an order by clause so that the
+// returned rows are stable. Subsequent pages are fetched with add_keyset_filter_clause rather
+// than an offset, so that the rows skipped are not scanned again for every page
+#[derive(Debug)]
+pub struct LimitByClause {
+    pub limit: u64,
+}
+
 #[derive(Debug)]
 pub struct QueryBuilder<T>
 where
@@ -347,6 +361,9 @@ where
     having: Option<Vec<(String, FilterTypes, String)>>,
     outer_select: Vec<String>,
     top_n: Option<TopN>,
+    order_by: Vec<String>,
+    limit_by: Option<LimitByClause>,
+    keyset: Option<String>,
     table: AnalyticsCollection,
     distinct: bool,
     db_type: PhantomData<T>,
@@ -486,6 +503,9 @@ where
             having: Default::default(),
             outer_select: Default::default(),
             top_n: Default::default(),
+            order_by: Default::default(),
+            limit_by: Default::default(),
+            keyset: Default::default(),
             table,
             distinct: Default::default(),
             db_type: Default::default(),
@@ -550,6 +570,65 @@ where
         self.distinct = true
     }
 
+    pub fn add_order_by_clause(&mut self, column: impl ToSql<T>, order: Order) -> QueryResult<()> {
+        let column = column
+            .to_sql(&self.table_engine)
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing order by field")?;
+        self.order_by.push(format!("{column} {order}"));
+        Ok(())
+    }
+
+    pub fn set_limit_by_clause(&mut self, limit: u64) {
+        self.limit_by = Some(LimitByClause { limit });
+    }
+
+    /// Filters the rows following the row with the specified values of the columns, in the
+    /// ascending order of the columns. The query is expected to be ordered by the same columns,
+    /// so that pages of rows can be fetched one after the other.
+    pub fn add_keyset_filter_clause(
+        &mut self,
+        after: &[(impl ToSql<T>, String)],
+    ) -> QueryResult<()> {
+        let after = after
+            .iter()
+            .map(|(column, value)| {
+                column
+                    .to_sql(&self.table_engine)
+                    .map(|column| (column, format!("'{}'", value.replace('\'', "''"))))
+            })
+            .collect::<error_stack::Result<Vec<_>, ParsingError>>()
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing keyset filter field")?;
+
+        // (c1 > v1) OR (c1 = v1 AND c2 > v2) OR ...
+        let condition = (0..after.len())
+            .map(|position| {
+                after
+                    .iter()
+                    .take(position)
+                    .map(|(column, value)| format!("{column} = {value}"))
+                    .chain(
+                        after
+                            .get(position)
+                            .map(|(column, value)| format!("{column} > {value}")),
+                    )
+                    .collect::<Vec<_>>()
+                    .join(" AND ")
+            })
+            .map(|condition| format!("({condition})"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+
+        if condition.is_empty() {
+            Err(QueryBuildingError::InvalidQuery(
+                "No keyset filter fields provided",
+            ))?;
+        }
+        self.keyset = Some(format!("({condition})"));
+        Ok(())
+    }
+
     pub fn add_filter_clause(
         &mut self,
         key: impl ToSql<T>,
@@ -642,6 +721,7 @@ where
         self.filters
             .iter()
             .map(|(l, op, r)| filter_type_to_sql(l, op, r))
+            .chain(self.keyset.clone())
             .collect::<Vec<String>>()
             .join(" AND ")
     }
@@ -731,7 +811,7 @@ where
                 .attach_printable("Error serializing table value")?,
         );
 
-        if !self.filters.is_empty() {
+        if !self.filters.is_empty() || self.keyset.is_some() {
             query.push_str(" WHERE ");
             query.push_str(&self.get_filter_clause());
         }
@@ -771,6 +851,15 @@ where
             query.push_str(format!(") _ WHERE top_n <= {}", top_n.count).as_str());
         }
 
+        if !self.order_by.is_empty() {
+            query.push_str(" ORDER BY ");
+            query.push_str(&self.order_by.join(", "));
+        }
+
+        if let Some(limit_by) = &self.limit_by {
+            query.push_str(format!(" LIMIT {}", limit_by.limit).as_str());
+        }
+
         logger::debug!(%query);
 
         Ok(query)
@@ -836,3 +925,64 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::sqlx::SqlxClient;
+
+    fn get_query_builder() -> QueryBuilder<SqlxClient> {
+        let mut query_builder = QueryBuilder::new(AnalyticsCollection::Payment);
+        query_builder.add_select_column("attempt_id").unwrap();
+        query_builder
+            .add_filter_clause("merchant_id", "merchant")
+            .unwrap();
+        query_builder
+    }
+
+    #[test]
+    fn test_order_by_and_limit_by_clauses() {
+        let mut query_builder = get_query_builder();
+        query_builder
+            .add_order_by_clause("created_at", Order::Ascending)
+            .unwrap();
+        query_builder
+            .add_order_by_clause("attempt_id", Order::Descending)
+            .unwrap();
+        query_builder.set_limit_by_clause(10);
+
+        assert_eq!(
+            query_builder.build_query().unwrap(),
+            "SELECT attempt_id FROM payment_attempt WHERE merchant_id = 'merchant' \
+             ORDER BY created_at asc, attempt_id desc LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_keyset_filter_clause() {
+        let mut query_builder = get_query_builder();
+        query_builder
+            .add_keyset_filter_clause(&[
+                ("created_at", String::from("2024-01-01 00:00:00")),
+                ("attempt_id", String::from("pay_'1")),
+            ])
+            .unwrap();
+        query_builder
+            .add_order_by_clause("created_at", Order::Ascending)
+            .unwrap();
+        query_builder.set_limit_by_clause(10);
+
+        assert_eq!(
+            query_builder.build_query().unwrap(),
+            "SELECT attempt_id FROM payment_attempt WHERE merchant_id = 'merchant' AND \
+             ((created_at > '2024-01-01 00:00:00') OR \
+             (created_at = '2024-01-01 00:00:00' AND attempt_id > 'pay_''1')) \
+             ORDER BY created_at asc LIMIT 10"
+        );
+
+        let mut query_builder = get_query_builder();
+        let after: &[(&str, String)] = &[];
+        assert!(query_builder.add_keyset_filter_clause(after).is_err());
+    }
+}
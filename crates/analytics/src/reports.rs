@@ -0,0 +1,345 @@
+pub mod writer;
+
+use std::collections::HashMap;
+
+use api_models::analytics::{GenerateReportRequest, Granularity, ReportRequest};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::{report, ResultExt};
+use time::PrimitiveDateTime;
+
+pub use self::writer::ReportWriter;
+pub use crate::types::{ReportsError, ReportsResult};
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, Order, QueryBuilder, QueryFilter, QueryResult, ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, LoadRow},
+};
+
+/// Data sources the reports can be generated from
+pub trait ReportAnalytics: LoadRow<ReportRow>
+where
+    AnalyticsCollection: ToSql<Self>,
+{
+    /// Adds the columns of the report to the query, so that each row loaded holds the values of
+    /// the columns as text
+    fn add_report_columns(
+        builder: &mut QueryBuilder<Self>,
+        columns: &[&'static str],
+    ) -> QueryResult<()>;
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportKind {
+    Payment,
+    Refund,
+    Dispute,
+}
+
+impl ReportKind {
+    fn get_collection(&self) -> AnalyticsCollection {
+        match self {
+            Self::Payment => AnalyticsCollection::Payment,
+            Self::Refund => AnalyticsCollection::Refund,
+            Self::Dispute => AnalyticsCollection::Dispute,
+        }
+    }
+
+    /// Columns of the report, in the order they are written in
+    pub fn get_columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::Payment => &[
+                "payment_id",
+                "attempt_id",
+                "merchant_id",
+                "profile_id",
+                "status",
+                "amount",
+                "currency",
+                "connector",
+                "payment_method",
+                "payment_method_type",
+                "authentication_type",
+                "capture_method",
+                "connector_transaction_id",
+                "error_code",
+                "error_message",
+                "created_at",
+                "modified_at",
+            ],
+            Self::Refund => &[
+                "refund_id",
+                "payment_id",
+                "attempt_id",
+                "merchant_id",
+                "profile_id",
+                "refund_status",
+                "refund_type",
+                "total_amount",
+                "refund_amount",
+                "currency",
+                "connector",
+                "connector_refund_id",
+                "refund_reason",
+                "refund_error_code",
+                "refund_error_message",
+                "created_at",
+                "modified_at",
+            ],
+            Self::Dispute => &[
+                "dispute_id",
+                "payment_id",
+                "attempt_id",
+                "merchant_id",
+                "profile_id",
+                "dispute_stage",
+                "dispute_status",
+                "dispute_amount",
+                "currency",
+                "connector",
+                "connector_dispute_id",
+                "connector_status",
+                "connector_reason",
+                "challenge_required_by",
+                "created_at",
+                "modified_at",
+            ],
+        }
+    }
+
+    /// Columns the rows are ordered by, the creation time followed by the column uniquely
+    /// identifying a row
+    fn get_order_columns(&self) -> [&'static str; 2] {
+        match self {
+            Self::Payment => ["created_at", "attempt_id"],
+            Self::Refund => ["created_at", "refund_id"],
+            Self::Dispute => ["created_at", "dispute_id"],
+        }
+    }
+
+    /// Values of the order columns in the row, which the next page of rows follows
+    fn get_row_key(&self, row: &ReportRow) -> ReportsResult<[(&'static str, String); 2]> {
+        let [created_at, id] = self.get_order_columns().map(|column| {
+            row.get(column)
+                .map(|value| (column, value.to_owned()))
+                .ok_or(report!(ReportsError::QueryExecutionFailure))
+                .attach_printable_lazy(|| format!("Column {column} is missing in report row"))
+        });
+        Ok([created_at?, id?])
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl ReportFormat {
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    pub fn get_content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// Where the reports are generated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportBackend {
+    /// By the configured AWS Lambda functions
+    #[default]
+    Lambda,
+    /// By the scheduler, from the analytics data source
+    InProcess,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct InProcessReportConfig {
+    pub format: ReportFormat,
+    /// Number of rows loaded from the data source at a time
+    pub page_size: u64,
+    /// Number of days after which the generated reports are deleted from the file storage
+    pub retention_days: u16,
+}
+
+impl Default for InProcessReportConfig {
+    fn default() -> Self {
+        Self {
+            format: ReportFormat::default(),
+            page_size: 10000,
+            retention_days: 7,
+        }
+    }
+}
+
+/// Tracking data of the scheduler task generating a report
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ReportGenerationTrackingData {
+    pub kind: ReportKind,
+    pub format: ReportFormat,
+    pub request: GenerateReportRequest,
+    pub user_id: String,
+}
+
+impl ReportGenerationTrackingData {
+    /// Key of the generated report in the file storage
+    pub fn get_file_key(&self, report_id: &str) -> String {
+        format!("reports/{report_id}.{}", self.format.get_extension())
+    }
+}
+
+/// Destination of the encoded output of a report, written a part at a time as the rows are
+/// loaded
+#[async_trait::async_trait]
+pub trait ReportSink: Send {
+    async fn write_part(&mut self, part: Vec<u8>) -> ReportsResult<()>;
+}
+
+/// A row of a report, holding the values of the non null columns as text
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReportRow(pub HashMap<String, String>);
+
+impl ReportRow {
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.0.get(column).map(String::as_str)
+    }
+}
+
+/// Generates the report of the kind, applying the same time range and access filters as the
+/// analytics queries. The rows are loaded a page at a time, each page following the last row of
+/// the previous one, and written to the sink as they are encoded.
+pub async fn generate_report<T>(
+    pool: &T,
+    kind: ReportKind,
+    request: &ReportRequest,
+    auth: &AuthInfo,
+    config: &InProcessReportConfig,
+    format: ReportFormat,
+    sink: &mut dyn ReportSink,
+) -> ReportsResult<()>
+where
+    T: AnalyticsDataSource + ReportAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    let columns = kind.get_columns();
+    let page_size = config.page_size.max(1);
+    let page_length =
+        usize::try_from(page_size).change_context(ReportsError::QueryBuildingError)?;
+
+    let mut writer = ReportWriter::new(format, columns)?;
+    let mut last_row_key = None;
+    loop {
+        let rows = get_report_page(pool, kind, request, auth, page_size, last_row_key).await?;
+        writer.write_page(columns, &rows)?;
+
+        let output = writer.take_output()?;
+        if !output.is_empty() {
+            sink.write_part(output).await?;
+        }
+
+        if rows.len() < page_length {
+            break;
+        }
+        last_row_key = rows.last().map(|row| kind.get_row_key(row)).transpose()?;
+    }
+
+    sink.write_part(writer.finish()?).await
+}
+
+async fn get_report_page<T>(
+    pool: &T,
+    kind: ReportKind,
+    request: &ReportRequest,
+    auth: &AuthInfo,
+    limit: u64,
+    after: Option<[(&'static str, String); 2]>,
+) -> ReportsResult<Vec<ReportRow>>
+where
+    T: AnalyticsDataSource + ReportAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    let mut query_builder: QueryBuilder<T> = QueryBuilder::new(kind.get_collection());
+
+    T::add_report_columns(&mut query_builder, kind.get_columns()).switch()?;
+
+    request
+        .time_range
+        .set_filter_clause(&mut query_builder)
+        .attach_printable("Error filtering time range")
+        .switch()?;
+
+    auth.set_filter_clause(&mut query_builder).switch()?;
+
+    if let Some(after) = after {
+        query_builder.add_keyset_filter_clause(&after).switch()?;
+    }
+
+    for column in kind.get_order_columns() {
+        query_builder
+            .add_order_by_clause(column, Order::Ascending)
+            .switch()?;
+    }
+    query_builder.set_limit_by_clause(limit);
+
+    query_builder
+        .execute_query::<ReportRow, _>(pool)
+        .await
+        .change_context(ReportsError::QueryBuildingError)?
+        .change_context(ReportsError::QueryExecutionFailure)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_row_key_holds_the_order_columns() {
+        let row = ReportRow(HashMap::from([
+            (String::from("refund_id"), String::from("ref_1")),
+            (String::from("payment_id"), String::from("pay_1")),
+            (
+                String::from("created_at"),
+                String::from("2024-01-01 00:00:00"),
+            ),
+        ]));
+
+        assert_eq!(
+            ReportKind::Refund.get_row_key(&row).unwrap(),
+            [
+                ("created_at", String::from("2024-01-01 00:00:00")),
+                ("refund_id", String::from("ref_1")),
+            ]
+        );
+        // The rows of the report cannot be paged through without their order columns
+        assert!(ReportKind::Dispute.get_row_key(&row).is_err());
+    }
+}
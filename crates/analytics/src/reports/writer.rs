@@ -0,0 +1,287 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use error_stack::{report, ResultExt};
+use parquet::{
+    basic::{ConvertedType, Repetition, Type as PhysicalType},
+    data_type::{ByteArray, ByteArrayType},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::types::Type,
+};
+
+use super::{ReportFormat, ReportRow};
+use crate::types::{ReportsError, ReportsResult};
+
+/// Buffer the encoded report is written to, which is drained as the report is uploaded
+#[derive(Clone, Debug, Default)]
+pub struct ReportBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl ReportBuffer {
+    fn take(&self) -> ReportsResult<Vec<u8>> {
+        self.0
+            .lock()
+            .map(|mut buffer| std::mem::take(&mut *buffer))
+            .map_err(|_| report!(ReportsError::EncodingFailure))
+            .attach_printable("Report buffer lock is poisoned")
+    }
+}
+
+impl Write for ReportBuffer {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| std::io::Error::other("Report buffer lock is poisoned"))?
+            .extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Encodes the rows of a report a page at a time. Each page of a Parquet report is written as a
+/// row group, with all the columns stored as optional UTF-8 strings. The encoded output is taken
+/// after every page, so that the report does not have to be held in memory.
+pub struct ReportWriter {
+    encoder: ReportEncoder,
+    buffer: ReportBuffer,
+}
+
+enum ReportEncoder {
+    Csv(csv::Writer<ReportBuffer>),
+    Parquet(SerializedFileWriter<ReportBuffer>),
+}
+
+impl ReportWriter {
+    pub fn new(format: ReportFormat, columns: &[&'static str]) -> ReportsResult<Self> {
+        let buffer = ReportBuffer::default();
+        let encoder = match format {
+            ReportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(buffer.clone());
+                writer
+                    .write_record(columns)
+                    .change_context(ReportsError::EncodingFailure)
+                    .attach_printable("Error writing report header")?;
+                ReportEncoder::Csv(writer)
+            }
+            ReportFormat::Parquet => {
+                let fields = columns
+                    .iter()
+                    .map(|column| {
+                        Type::primitive_type_builder(column, PhysicalType::BYTE_ARRAY)
+                            .with_repetition(Repetition::OPTIONAL)
+                            .with_converted_type(ConvertedType::UTF8)
+                            .build()
+                            .map(Arc::new)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .change_context(ReportsError::EncodingFailure)
+                    .attach_printable("Error building report schema")?;
+
+                let schema = Type::group_type_builder("report")
+                    .with_fields(fields)
+                    .build()
+                    .change_context(ReportsError::EncodingFailure)
+                    .attach_printable("Error building report schema")?;
+
+                SerializedFileWriter::new(
+                    buffer.clone(),
+                    Arc::new(schema),
+                    Arc::new(WriterProperties::builder().build()),
+                )
+                .map(ReportEncoder::Parquet)
+                .change_context(ReportsError::EncodingFailure)?
+            }
+        };
+
+        Ok(Self { encoder, buffer })
+    }
+
+    pub fn write_page(
+        &mut self,
+        columns: &[&'static str],
+        rows: &[ReportRow],
+    ) -> ReportsResult<()> {
+        match &mut self.encoder {
+            ReportEncoder::Csv(writer) => rows.iter().try_for_each(|row| {
+                writer
+                    .write_record(
+                        columns
+                            .iter()
+                            .map(|column| row.get(column).unwrap_or_default()),
+                    )
+                    .change_context(ReportsError::EncodingFailure)
+                    .attach_printable("Error writing report row")
+            }),
+            ReportEncoder::Parquet(writer) => {
+                if rows.is_empty() {
+                    return Ok(());
+                }
+
+                let mut row_group_writer = writer
+                    .next_row_group()
+                    .change_context(ReportsError::EncodingFailure)?;
+
+                for column in columns {
+                    let mut column_writer = row_group_writer
+                        .next_column()
+                        .change_context(ReportsError::EncodingFailure)?
+                        .ok_or(report!(ReportsError::EncodingFailure))
+                        .attach_printable_lazy(|| {
+                            format!("Column {column} is missing in the report schema")
+                        })?;
+
+                    // Null values are only recorded through their definition level
+                    let mut values = Vec::with_capacity(rows.len());
+                    let mut definition_levels = Vec::with_capacity(rows.len());
+                    for row in rows {
+                        match row.get(column) {
+                            Some(value) => {
+                                values.push(ByteArray::from(value));
+                                definition_levels.push(1);
+                            }
+                            None => definition_levels.push(0),
+                        }
+                    }
+
+                    column_writer
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, Some(definition_levels.as_slice()), None)
+                        .change_context(ReportsError::EncodingFailure)
+                        .attach_printable_lazy(|| {
+                            format!("Error writing report column {column}")
+                        })?;
+                    column_writer
+                        .close()
+                        .change_context(ReportsError::EncodingFailure)?;
+                }
+
+                row_group_writer
+                    .close()
+                    .map(|_| ())
+                    .change_context(ReportsError::EncodingFailure)
+            }
+        }
+    }
+
+    /// Takes the output encoded since the last call, to be uploaded as the next part of the
+    /// report
+    pub fn take_output(&mut self) -> ReportsResult<Vec<u8>> {
+        if let ReportEncoder::Csv(writer) = &mut self.encoder {
+            writer
+                .flush()
+                .change_context(ReportsError::EncodingFailure)
+                .attach_printable("Error flushing report rows")?;
+        }
+        self.buffer.take()
+    }
+
+    /// Completes the report, returning the remaining output
+    pub fn finish(self) -> ReportsResult<Vec<u8>> {
+        match self.encoder {
+            ReportEncoder::Csv(writer) => writer.into_inner().map(|_| ()).map_err(|error| {
+                report!(ReportsError::EncodingFailure).attach_printable(error.to_string())
+            })?,
+            ReportEncoder::Parquet(writer) => writer
+                .into_inner()
+                .map(|_| ())
+                .change_context(ReportsError::EncodingFailure)?,
+        }
+        self.buffer.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use parquet::{
+        file::{reader::FileReader, serialized_reader::SerializedFileReader},
+        record::Field,
+    };
+
+    use super::*;
+
+    const COLUMNS: &[&str] = &["payment_id", "amount"];
+
+    fn get_rows() -> Vec<ReportRow> {
+        vec![
+            ReportRow(
+                [("payment_id", "pay_1"), ("amount", "100")]
+                    .into_iter()
+                    .map(|(column, value)| (column.to_owned(), value.to_owned()))
+                    .collect(),
+            ),
+            ReportRow(
+                [("payment_id", "pay_2,\"quoted\"")]
+                    .into_iter()
+                    .map(|(column, value)| (column.to_owned(), value.to_owned()))
+                    .collect(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_csv_report_is_written_a_page_at_a_time() {
+        let rows = get_rows();
+        let mut writer = ReportWriter::new(ReportFormat::Csv, COLUMNS).unwrap();
+
+        let (first_page, second_page) = rows.split_at(1);
+        writer.write_page(COLUMNS, first_page).unwrap();
+        assert_eq!(
+            writer.take_output().unwrap(),
+            b"payment_id,amount\npay_1,100\n"
+        );
+        assert!(writer.take_output().unwrap().is_empty());
+
+        writer.write_page(COLUMNS, second_page).unwrap();
+        let remaining = writer.finish().unwrap();
+        assert_eq!(remaining, b"\"pay_2,\"\"quoted\"\"\",\n");
+    }
+
+    #[test]
+    fn test_parquet_report_is_written_a_page_at_a_time() {
+        let rows = get_rows();
+        let mut writer = ReportWriter::new(ReportFormat::Parquet, COLUMNS).unwrap();
+
+        let mut report = writer.take_output().unwrap();
+        for page in rows.chunks(1) {
+            writer.write_page(COLUMNS, page).unwrap();
+            report.extend(writer.take_output().unwrap());
+        }
+        report.extend(writer.finish().unwrap());
+
+        let path = std::env::temp_dir().join(format!(
+            "{}.parquet",
+            common_utils::generate_id(10, "report")
+        ));
+        std::fs::write(&path, report).unwrap();
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 2);
+
+        let values = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .map(|(_, field)| match field {
+                        Field::Str(value) => Some(value.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                vec![Some(String::from("pay_1")), Some(String::from("100"))],
+                vec![Some(String::from("pay_2,\"quoted\"")), None],
+            ]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -15,7 +15,7 @@ use diesel_models::enums::{
 use error_stack::ResultExt;
 use sqlx::{
     postgres::{PgArgumentBuffer, PgPoolOptions, PgRow, PgTypeInfo, PgValueRef},
-    Decode, Encode,
+    Column, Decode, Encode,
     Error::ColumnNotFound,
     FromRow, Pool, Postgres, Row,
 };
@@ -24,7 +24,7 @@ use time::PrimitiveDateTime;
 
 use super::{
     health_check::HealthCheck,
-    query::{Aggregate, ToSql, Window},
+    query::{Aggregate, QueryBuilder, QueryResult, ToSql, Window},
     types::{
         AnalyticsCollection, AnalyticsDataSource, DBEnumWrapper, LoadRow, QueryExecutionError,
         TableEngine,
@@ -159,6 +159,17 @@ impl super::disputes::metrics::DisputeMetricAnalytics for SqlxClient {}
 impl super::frm::metrics::FrmMetricAnalytics for SqlxClient {}
 impl super::frm::filters::FrmFilterAnalytics for SqlxClient {}
 
+impl super::reports::ReportAnalytics for SqlxClient {
+    fn add_report_columns(
+        builder: &mut QueryBuilder<Self>,
+        columns: &[&'static str],
+    ) -> QueryResult<()> {
+        columns.iter().try_for_each(|column| {
+            builder.add_select_column(format!("CAST({column} AS TEXT) AS {column}"))
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl AnalyticsDataSource for SqlxClient {
     type Row = PgRow;
@@ -571,6 +582,20 @@ impl<'a> FromRow<'a, PgRow> for super::payment_intents::filters::PaymentIntentFi
     }
 }
 
+impl<'a> FromRow<'a, PgRow> for super::reports::ReportRow {
+    fn from_row(row: &'a PgRow) -> sqlx::Result<Self> {
+        row.columns()
+            .iter()
+            .filter_map(|column| {
+                row.try_get::<Option<String>, _>(column.ordinal())
+                    .map(|value| value.map(|value| (column.name().to_owned(), value)))
+                    .transpose()
+            })
+            .collect::<sqlx::Result<_>>()
+            .map(Self)
+    }
+}
+
 impl<'a> FromRow<'a, PgRow> for super::refunds::filters::RefundFilterRow {
     fn from_row(row: &'a PgRow) -> sqlx::Result<Self> {
         let currency: Option<DBEnumWrapper<Currency>> =
@@ -150,4 +150,30 @@ impl ErrorSwitch<AnalyticsError> for FiltersError {
     }
 }
 
+pub type ReportsResult<T> = CustomResult<T, ReportsError>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReportsError {
+    #[error("Error building query")]
+    QueryBuildingError,
+    #[error("Error running Query")]
+    QueryExecutionFailure,
+    #[error("Error encoding report")]
+    EncodingFailure,
+    #[error("Error uploading report")]
+    UploadFailure,
+}
+
+impl ErrorSwitch<ReportsError> for QueryBuildingError {
+    fn switch(&self) -> ReportsError {
+        ReportsError::QueryBuildingError
+    }
+}
+
+impl ErrorSwitch<AnalyticsError> for ReportsError {
+    fn switch(&self) -> AnalyticsError {
+        AnalyticsError::UnknownError
+    }
+}
+
 impl_api_event_type!(Miscellaneous, (AnalyticsDomain));
@@ -1968,6 +1968,12 @@ pub struct BusinessProfileCreate {
     /// If set to `true` tax_connector_id will be checked.
     #[serde(default)]
     pub is_tax_connector_enabled: bool,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[nutype::nutype(
@@ -2071,6 +2077,12 @@ pub struct BusinessProfileCreate {
     /// If set to `true` tax_connector_id will be checked.
     #[serde(default)]
     pub is_tax_connector_enabled: bool,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -2190,6 +2202,12 @@ pub struct BusinessProfileResponse {
     /// Indicates if tax_calculator connector is enabled or not.
     /// If set to `true` tax_connector_id will be checked.
     pub is_tax_connector_enabled: bool,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -2297,6 +2315,12 @@ pub struct BusinessProfileResponse {
     /// Indicates if tax_calculator connector is enabled or not.
     /// If set to `true` tax_connector_id will be checked.
     pub is_tax_connector_enabled: bool,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -2408,6 +2432,12 @@ pub struct BusinessProfileUpdate {
     /// Indicates if tax_calculator connector is enabled or not.
     /// If set to `true` tax_connector_id will be checked.
     pub is_tax_connector_enabled: Option<bool>,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -2507,6 +2537,12 @@ pub struct BusinessProfileUpdate {
     /// Indicates if tax_calculator connector is enabled or not.
     /// If set to `true` tax_connector_id will be checked.
     pub is_tax_connector_enabled: Option<bool>,
+
+    /// Timezone of the merchant, in which the time of day and day of week routing rules are
+    /// evaluated. Either `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name
+    /// like `Asia/Kolkata`, defaults to `UTC`.
+    #[schema(max_length = 64, example = "+05:30")]
+    pub timezone: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -0,0 +1,57 @@
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CacheStatisticsResponse {
+    /// The statistics of the in-memory caches of the instance of the application that served the
+    /// request, since the instance was started
+    pub caches: Vec<CacheStatistics>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CacheStatistics {
+    /// The name of the cache, such as `CONFIG_CACHE`
+    pub name: String,
+    /// The approximate number of entries in the cache
+    pub entry_count: u64,
+    pub hit_count: u64,
+    pub miss_count: u64,
+    /// The number of entries removed on expiry, or to keep the cache within its capacity
+    pub eviction_count: u64,
+    /// The number of entries removed on invalidation
+    pub invalidation_count: u64,
+    /// The estimated size of the entries in bytes
+    pub estimated_size: u64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheKeysListConstraints {
+    /// The prefix of the keys to be listed, all the keys are listed if not specified
+    pub prefix: Option<String>,
+    /// The maximum number of keys to be listed
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CacheKeysListResponse {
+    /// The name of the cache
+    pub name: String,
+    /// The keys of the cache, in sorted order
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheInvalidateRequest {
+    /// The name of the cache the keys are to be invalidated in, the keys are invalidated in all
+    /// the caches if not specified
+    pub cache: Option<String>,
+    /// The glob pattern of the keys to be invalidated, where `*` matches any sequence of
+    /// characters and `?` matches any single character. All the keys are invalidated if not
+    /// specified.
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CacheInvalidateResponse {
+    /// The number of instances of the application the invalidation was published to
+    pub receivers: usize,
+}
@@ -7,6 +7,15 @@ pub struct CurrencyConversionParams {
     pub amount: MinorUnit,
     pub to_currency: String,
     pub from_currency: String,
+    /// Rounding applied to the minor unit of `to_currency`, defaults to banker's rounding
+    pub rounding_strategy: Option<common_enums::RoundingStrategy>,
+    /// Unix timestamp (in seconds) at which the exchange rates to be used were valid,
+    /// the latest rates are used if not provided
+    pub rates_timestamp: Option<i64>,
+    /// Payment whose exchange rates are to be used, which are the rates valid when the payment
+    /// was created, like for refunding a cross currency payment. Cannot be provided along with
+    /// `rates_timestamp`
+    pub payment_id: Option<common_utils::id_type::PaymentId>,
 }
 
 /// Response to be send for convert currency route
@@ -14,6 +23,7 @@ pub struct CurrencyConversionParams {
 #[serde(rename_all = "snake_case")]
 pub struct CurrencyConversionResponse {
     pub converted_amount: String,
+    pub converted_minor_amount: MinorUnit,
     pub currency: String,
 }
 
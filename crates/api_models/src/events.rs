@@ -28,13 +28,16 @@ use crate::{
         outgoing_webhook_event::OutgoingWebhookLogsRequest, sdk_events::*, search::*, *,
     },
     api_keys::*,
+    cache::*,
     cards_info::*,
     disputes::*,
     files::*,
+    kv_consistency::*,
     mandates::*,
     organization::{OrganizationId, OrganizationRequest, OrganizationResponse},
     payment_methods::*,
     payments::*,
+    process_tracker::*,
     user::{UserKeyTransferRequest, UserTransferKeyResponse},
     verifications::*,
 };
@@ -121,6 +124,7 @@ impl_api_event_type!(
         GetApiEventMetricRequest,
         SdkEventsRequest,
         ReportRequest,
+        GenerateReportResponse,
         ConnectorEventsRequest,
         OutgoingWebhookLogsRequest,
         GetGlobalSearchRequest,
@@ -133,7 +137,20 @@ impl_api_event_type!(
         OrganizationResponse,
         OrganizationRequest,
         OrganizationId,
-        CustomerListRequest
+        CustomerListRequest,
+        ProcessTrackerListConstraints,
+        ProcessTrackerListResponse,
+        ProcessTrackerResponse,
+        ProcessTrackerCancelRequest,
+        KvConsistencyCheckRequest,
+        KvConsistencyCheckResponse,
+        StorageSchemeMigrationRequest,
+        StorageSchemeMigrationStatus,
+        CacheStatisticsResponse,
+        CacheKeysListConstraints,
+        CacheKeysListResponse,
+        CacheInvalidateRequest,
+        CacheInvalidateResponse
     )
 );
 
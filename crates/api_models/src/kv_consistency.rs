@@ -0,0 +1,143 @@
+use time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KvConsistencyCheckRequest {
+    /// The merchant whose entries in the Redis KV store are to be checked
+    pub merchant_id: common_utils::id_type::MerchantId,
+    /// Time greater than or equals to the created time of the entries to be checked
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_gte: Option<PrimitiveDateTime>,
+    /// Time less than or equals to the created time of the entries to be checked
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_lte: Option<PrimitiveDateTime>,
+    /// Whether the Redis entries that are older than the corresponding Postgres rows are to be
+    /// overwritten with the Postgres rows
+    #[serde(default)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KvConsistencyCheckResponse {
+    /// The number of Redis hashes (partition keys) that were walked
+    pub partition_keys: usize,
+    /// The number of Redis hash fields that were compared with Postgres
+    pub checked_entries: usize,
+    /// The entries whose Redis and Postgres values differ
+    pub divergences: Vec<KvDivergence>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KvDivergence {
+    /// The Redis hash the entry belongs to
+    pub partition_key: String,
+    /// The field of the Redis hash holding the entry
+    pub field: String,
+    /// The kind of entity stored in the entry
+    pub entity: KvEntity,
+    /// How the Redis and Postgres values differ
+    pub kind: KvDivergenceKind,
+    /// The top level fields whose values differ, empty if the Postgres row is missing
+    pub mismatched_fields: Vec<String>,
+    /// What the divergence is attributed to, or how it was resolved
+    pub resolution: KvDivergenceResolution,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KvEntity {
+    PaymentIntent,
+    PaymentAttempt,
+    Refund,
+    Address,
+    Customer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KvDivergenceKind {
+    /// The entry is present in Redis, but not in Postgres
+    MissingInDatabase,
+    /// The entry is present in both, with different values
+    Mismatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KvDivergenceResolution {
+    /// Redis is ahead of Postgres, the drainer has not applied the entry yet, or failed to
+    PendingDrain,
+    /// Redis is behind Postgres, and can be repaired by overwriting it with the Postgres row
+    StaleInRedis,
+    /// Redis was behind Postgres, and has been overwritten with the Postgres row
+    Repaired,
+    /// Redis was behind Postgres, but was updated while being repaired, and was left unchanged
+    UpdatedConcurrently,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageSchemeMigrationRequest {
+    #[serde(skip_deserializing)]
+    pub merchant_id: common_utils::id_type::MerchantId,
+    /// The storage scheme the merchant is to be migrated to
+    pub storage_scheme: common_enums::MerchantStorageScheme,
+    /// Time greater than or equals to the created time of the Postgres rows to be loaded into the
+    /// Redis KV store when migrating to `redis_kv`, defaults to the time-to-live of the Redis KV
+    /// hashes before now
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub warm_created_gte: Option<PrimitiveDateTime>,
+    /// Maximum time in seconds to wait for the drainer to apply the Redis KV entries of the
+    /// merchant to Postgres when migrating to `postgres_only`
+    pub drain_timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct StorageSchemeMigrationStatus {
+    /// The ID of the migration, which is also the ID of the process tracker task running it
+    #[serde(default)]
+    pub migration_id: String,
+    /// The merchant being migrated
+    pub merchant_id: common_utils::id_type::MerchantId,
+    /// The storage scheme the merchant is being migrated to
+    pub storage_scheme: common_enums::MerchantStorageScheme,
+    /// The stage the migration is in
+    pub stage: StorageSchemeMigrationStage,
+    /// The number of Redis KV entries the drainer is yet to apply to Postgres
+    pub pending_entries: usize,
+    /// The number of Redis KV entries the drainer holds behind entries it moved to its dead
+    /// letter stream, which have to be replayed or discarded for the migration to complete
+    #[serde(default)]
+    pub dead_lettered_entries: usize,
+    /// The number of Postgres rows loaded into the Redis KV store
+    pub warmed_entries: usize,
+    /// The number of Redis KV entries overwritten with newer Postgres rows
+    pub repaired_entries: usize,
+    /// The reason why the migration failed
+    pub error: Option<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub started_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageSchemeMigrationStage {
+    /// Waiting for the drainer to apply the Redis KV entries of the merchant to Postgres, after
+    /// switching the merchant to `postgres_only`
+    Draining,
+    /// Loading the recent Postgres rows of the merchant into the Redis KV store, after switching
+    /// the merchant to `redis_kv`
+    Warming,
+    /// Overwriting the Redis KV entries that were updated in Postgres while warming
+    Verifying,
+    Completed,
+    Failed,
+}
+
+impl StorageSchemeMigrationStage {
+    pub fn is_in_progress(&self) -> bool {
+        matches!(self, Self::Draining | Self::Warming | Self::Verifying)
+    }
+}
@@ -3,6 +3,7 @@ pub mod analytics;
 pub mod api_keys;
 pub mod apple_pay_certificates_migration;
 pub mod blocklist;
+pub mod cache;
 pub mod cards_info;
 pub mod conditional_configs;
 pub mod connector_onboarding;
@@ -18,6 +19,7 @@ pub mod events;
 pub mod files;
 pub mod gsm;
 pub mod health_check;
+pub mod kv_consistency;
 pub mod locker_migration;
 pub mod mandates;
 pub mod organization;
@@ -27,6 +29,7 @@ pub mod payments;
 pub mod payouts;
 pub mod pm_auth;
 pub mod poll;
+pub mod process_tracker;
 #[cfg(feature = "recon")]
 pub mod recon;
 pub mod refunds;
@@ -0,0 +1,82 @@
+use time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerListConstraints {
+    /// The runner of the tasks, such as `PAYMENTS_SYNC_WORKFLOW`
+    pub runner: String,
+    /// The status of the tasks, such as `pending`
+    pub status: Option<String>,
+    /// Time greater than or equals to the task created time
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_gte: PrimitiveDateTime,
+    /// Time less than or equals to the task created time, defaults to the current time
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_lte: Option<PrimitiveDateTime>,
+    /// The maximum number of tasks to be listed, at most 100
+    pub limit: Option<i64>,
+    /// The number of tasks to be skipped
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessTrackerListResponse {
+    /// The number of tasks included in the list
+    pub size: usize,
+    /// The list of tasks
+    pub data: Vec<ProcessTrackerResponse>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessTrackerResponse {
+    /// The identifier of the task
+    pub id: String,
+    /// The name of the task
+    pub name: Option<String>,
+    /// The tags associated with the task
+    pub tag: Vec<String>,
+    /// The runner of the task
+    pub runner: Option<String>,
+    /// The status of the task
+    pub status: String,
+    /// The business status of the task
+    pub business_status: String,
+    /// The number of times the task has been retried
+    pub retry_count: i32,
+    /// The time at which the task is scheduled to be picked up next
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+    /// The data required by the task for its execution
+    pub tracking_data: serde_json::Value,
+    /// The retries of the task, in the order they were made
+    pub retry_history: Vec<ProcessTrackerRetryResponse>,
+    /// The other events recorded against the task
+    pub event: Vec<String>,
+    /// The time at which the task was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    /// The time at which the task was last updated
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessTrackerRetryResponse {
+    /// The retry count of the task after the retry
+    pub retry_count: i32,
+    /// The status of the task when it was retried
+    pub previous_status: String,
+    /// The time at which the task was retried
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub retried_at: PrimitiveDateTime,
+    /// The time at which the task was scheduled to be picked up again
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerCancelRequest {
+    /// The business status with which the task is to be finished
+    pub business_status: String,
+}
@@ -131,33 +131,91 @@ impl EuclidDirFilter for ConnectorSelection {
         DirKeyKind::CardRedirectType,
         DirKeyKind::BankTransferType,
         DirKeyKind::RealTimePaymentType,
+        DirKeyKind::CardIssuer,
+        DirKeyKind::CardIssuerCountry,
+        DirKeyKind::TimeOfDay,
+        DirKeyKind::DayOfWeek,
     ];
 }
 
+fn get_connector_dir_values_for_analysis(
+    connectors: Vec<RoutableConnectorChoice>,
+    rule_name: String,
+) -> Vec<(euclid::frontend::dir::DirValue, euclid::types::Metadata)> {
+    connectors
+        .into_iter()
+        .map(|connector_choice| {
+            let connector_name = connector_choice.connector.to_string();
+            let mca_id = connector_choice.merchant_connector_id.clone();
+
+            (
+                euclid::frontend::dir::DirValue::Connector(Box::new(connector_choice.into())),
+                std::collections::HashMap::from_iter([(
+                    "CONNECTOR_SELECTION".to_string(),
+                    serde_json::json!({
+                        "rule_name": rule_name,
+                        "connector_name": connector_name,
+                        "mca_id": mca_id,
+                    }),
+                )]),
+            )
+        })
+        .collect()
+}
+
 impl EuclidAnalysable for ConnectorSelection {
+    /// Only the connectors of a priority selection are analysed as part of the rule's conditions.
+    /// The connectors of a volume split are not, so a rule splitting payments to a connector that
+    /// is ineligible for them no longer fails the analysis of the program, like the one run by
+    /// `analyzeProgram` of the routing dashboard. It is reported as an ineligible output warning
+    /// instead.
     fn get_dir_value_for_analysis(
         &self,
         rule_name: String,
     ) -> Vec<(euclid::frontend::dir::DirValue, euclid::types::Metadata)> {
-        self.get_connector_list()
-            .into_iter()
-            .map(|connector_choice| {
-                let connector_name = connector_choice.connector.to_string();
-                let mca_id = connector_choice.merchant_connector_id.clone();
-
-                (
-                    euclid::frontend::dir::DirValue::Connector(Box::new(connector_choice.into())),
-                    std::collections::HashMap::from_iter([(
-                        "CONNECTOR_SELECTION".to_string(),
-                        serde_json::json!({
-                            "rule_name": rule_name,
-                            "connector_name": connector_name,
-                            "mca_id": mca_id,
-                        }),
-                    )]),
-                )
-            })
-            .collect()
+        match self {
+            Self::Priority(_) => {
+                get_connector_dir_values_for_analysis(self.get_connector_list(), rule_name)
+            }
+            Self::VolumeSplit(_) => Vec::new(),
+        }
+    }
+
+    /// Connectors of a volume split are checked for eligibility on their own, since a split to an
+    /// ineligible connector does not make the rule invalid
+    fn get_split_dir_values_for_analysis(
+        &self,
+        rule_name: String,
+    ) -> Vec<(euclid::frontend::dir::DirValue, euclid::types::Metadata)> {
+        match self {
+            Self::Priority(_) => Vec::new(),
+            Self::VolumeSplit(_) => {
+                get_connector_dir_values_for_analysis(self.get_connector_list(), rule_name)
+            }
+        }
+    }
+}
+
+/// The connector selection in the form of the DSL, for rendering it with the DSL printer
+impl From<ConnectorSelection> for ast::ConnectorSelection {
+    fn from(value: ConnectorSelection) -> Self {
+        match value {
+            ConnectorSelection::Priority(choices) => Self::Priority(
+                choices
+                    .into_iter()
+                    .map(ast::RoutableConnectorChoice::from)
+                    .collect(),
+            ),
+            ConnectorSelection::VolumeSplit(splits) => Self::VolumeSplit(
+                splits
+                    .into_iter()
+                    .map(|split| ast::ConnectorVolumeSplit {
+                        connector: split.connector.into(),
+                        split: split.split,
+                    })
+                    .collect(),
+            ),
+        }
     }
 }
 
@@ -202,6 +260,19 @@ impl std::fmt::Display for RoutableConnectorChoice {
     }
 }
 
+impl From<RoutableConnectorChoice> for ast::RoutableConnectorChoice {
+    fn from(value: RoutableConnectorChoice) -> Self {
+        Self {
+            choice_kind: match value.choice_kind {
+                RoutableChoiceKind::OnlyConnector => ast::RoutableChoiceKind::OnlyConnector,
+                RoutableChoiceKind::FullStruct => ast::RoutableChoiceKind::FullStruct,
+            },
+            connector: value.connector,
+            merchant_connector_id: value.merchant_connector_id,
+        }
+    }
+}
+
 impl From<RoutableConnectorChoice> for ast::ConnectorChoice {
     fn from(value: RoutableConnectorChoice) -> Self {
         Self {
@@ -260,6 +331,7 @@ pub enum RoutingAlgorithmKind {
     Priority,
     VolumeSplit,
     Advanced,
+    SuccessRateBased,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -282,6 +354,7 @@ pub enum RoutingAlgorithm {
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     #[schema(value_type=ProgramConnectorSelection)]
     Advanced(ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedRoutingConfig),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -291,6 +364,7 @@ pub enum RoutingAlgorithmSerde {
     Priority(Vec<RoutableConnectorChoice>),
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     Advanced(ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedRoutingConfig),
 }
 
 impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
@@ -308,6 +382,7 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
                     "Connectors list can't be empty for Volume split Algorithm",
                 ))?
             }
+            RoutingAlgorithmSerde::SuccessRateBased(config) => config.validate()?,
             _ => {}
         };
         Ok(match value {
@@ -315,6 +390,7 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
             RoutingAlgorithmSerde::Priority(i) => Self::Priority(i),
             RoutingAlgorithmSerde::VolumeSplit(i) => Self::VolumeSplit(i),
             RoutingAlgorithmSerde::Advanced(i) => Self::Advanced(i),
+            RoutingAlgorithmSerde::SuccessRateBased(i) => Self::SuccessRateBased(i),
         })
     }
 }
@@ -417,10 +493,80 @@ impl RoutingAlgorithm {
             Self::Priority(_) => RoutingAlgorithmKind::Priority,
             Self::VolumeSplit(_) => RoutingAlgorithmKind::VolumeSplit,
             Self::Advanced(_) => RoutingAlgorithmKind::Advanced,
+            Self::SuccessRateBased(_) => RoutingAlgorithmKind::SuccessRateBased,
+        }
+    }
+}
+
+/// Configuration of the success rate based routing algorithm.
+///
+/// The eligible connectors are ranked by their rolling success rate, tracked per profile,
+/// connector and payment method over a sliding window of `bucket_count` buckets spanning
+/// `window_duration_in_secs`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct SuccessRateBasedRoutingConfig {
+    /// The connectors that are to be ranked, in the order used to break ties
+    pub connectors: Vec<RoutableConnectorChoice>,
+    #[serde(default)]
+    pub params: SuccessRateWindowParams,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(default)]
+pub struct SuccessRateWindowParams {
+    /// Total duration of the sliding window over which the success rate is computed
+    pub window_duration_in_secs: u32,
+    /// Number of buckets the window is split into
+    pub bucket_count: u8,
+    /// Minimum number of attempts in the window before the computed success rate is trusted
+    pub min_aggregates_size: u32,
+    /// Success rate, between 0 and 1, assumed for connectors without enough attempts in the window
+    pub default_success_rate: f64,
+}
+
+impl Default for SuccessRateWindowParams {
+    fn default() -> Self {
+        Self {
+            window_duration_in_secs: 3600,
+            bucket_count: 12,
+            min_aggregates_size: 10,
+            default_success_rate: 1.0,
         }
     }
 }
 
+impl SuccessRateWindowParams {
+    pub fn get_bucket_size_in_secs(&self) -> i64 {
+        i64::from(self.window_duration_in_secs / u32::from(self.bucket_count.max(1))).max(1)
+    }
+}
+
+impl SuccessRateBasedRoutingConfig {
+    fn validate(&self) -> Result<(), error_stack::Report<ParsingError>> {
+        if self.connectors.is_empty() {
+            Err(ParsingError::StructParseFailure(
+                "Connectors list can't be empty for Success Rate Based Algorithm",
+            ))?
+        }
+        if self.params.bucket_count == 0 {
+            Err(ParsingError::StructParseFailure(
+                "Bucket count must be greater than zero for Success Rate Based Algorithm",
+            ))?
+        }
+        if self.params.window_duration_in_secs < u32::from(self.params.bucket_count) {
+            Err(ParsingError::StructParseFailure(
+                "Window duration can't be shorter than the bucket count for Success Rate Based Algorithm",
+            ))?
+        }
+        if !(0.0..=1.0).contains(&self.params.default_success_rate) {
+            Err(ParsingError::StructParseFailure(
+                "Default success rate must lie between 0 and 1 for Success Rate Based Algorithm",
+            ))?
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RoutingAlgorithmRef {
     pub algorithm_id: Option<common_utils::id_type::RoutingId>,
@@ -467,6 +613,53 @@ pub struct RoutingDictionaryRecord {
     pub created_at: i64,
     pub modified_at: i64,
     pub algorithm_for: Option<TransactionType>,
+    /// Warnings about the rules of the algorithm, returned when the algorithm is created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<RoutingAnalysisWarning>>,
+}
+
+/// A rule of a routing algorithm that is valid, but does not behave the way it was likely meant to
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingAnalysisWarning {
+    pub warning_type: RoutingAnalysisWarningType,
+    /// Name of the rule the warning is about
+    pub rule_name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingAnalysisWarningType {
+    /// The rule can never match, since every payment it matches is matched by an earlier rule
+    ShadowedRule,
+    /// A branch of the rule can never match, since it holds contradicting conditions
+    UnreachableBranch,
+    /// A connector the rule splits payments across is ineligible for every payment matched by
+    /// the rule
+    IneligibleOutput,
+}
+
+impl From<euclid::dssa::types::AnalysisWarning> for RoutingAnalysisWarning {
+    fn from(warning: euclid::dssa::types::AnalysisWarning) -> Self {
+        let warning_type = match warning {
+            euclid::dssa::types::AnalysisWarning::ShadowedRule { .. } => {
+                RoutingAnalysisWarningType::ShadowedRule
+            }
+            euclid::dssa::types::AnalysisWarning::UnreachableBranch { .. }
+            | euclid::dssa::types::AnalysisWarning::UnreachableAlternative { .. } => {
+                RoutingAnalysisWarningType::UnreachableBranch
+            }
+            euclid::dssa::types::AnalysisWarning::IneligibleOutput { .. } => {
+                RoutingAnalysisWarningType::IneligibleOutput
+            }
+        };
+
+        Self {
+            warning_type,
+            rule_name: warning.get_rule_name().to_string(),
+            message: warning.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -495,3 +688,83 @@ pub struct RoutingLinkWrapper {
     pub profile_id: common_utils::id_type::ProfileId,
     pub algorithm_id: RoutingAlgorithmId,
 }
+
+/// Request to replay the payments of a profile, made in a time range, against a draft routing
+/// algorithm
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingSimulationRequest {
+    /// The profile whose payments are replayed
+    #[schema(value_type = String)]
+    pub profile_id: common_utils::id_type::ProfileId,
+    /// The draft routing algorithm the payments are replayed against
+    #[schema(value_type = ProgramConnectorSelection)]
+    pub algorithm: ast::Program<ConnectorSelection>,
+    /// The time range in which the replayed payments were created
+    pub time_range: crate::payments::TimeRange,
+    /// Only replay the payments made in this currency
+    #[schema(value_type = Option<Currency>)]
+    pub currency: Option<common_enums::Currency>,
+    /// Maximum number of payments replayed
+    pub limit: Option<u32>,
+}
+
+/// Outcome of replaying historical payments against a draft routing algorithm
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingSimulationResponse {
+    /// Number of payments replayed
+    pub payment_count: u64,
+    /// Total amount of the payments replayed, in each currency
+    pub total_amounts: Vec<CurrencyAmount>,
+    /// Number of payments the draft algorithm routes to a different connector
+    pub rerouted_payment_count: u64,
+    /// Total amount of the payments the draft algorithm routes to a different connector, in each
+    /// currency
+    pub rerouted_amounts: Vec<CurrencyAmount>,
+    /// Number of payments routed by the default selection of the draft algorithm
+    pub default_selection_count: u64,
+    /// Number of payments which could not be replayed
+    pub skipped_payment_count: u64,
+    /// Volume and amount routed to each connector historically and by the draft algorithm
+    pub connectors: Vec<ConnectorSimulationSummary>,
+    /// Payments grouped by their historical connector and the connector chosen by the draft
+    /// algorithm, for the payments routed differently
+    pub route_changes: Vec<RouteChange>,
+    /// Number of payments matched by each rule of the draft algorithm
+    pub rule_matches: Vec<RuleMatchCount>,
+    /// Rules of the draft algorithm which none of the payments matched
+    pub unmatched_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ConnectorSimulationSummary {
+    pub connector: String,
+    pub historical_payment_count: u64,
+    pub historical_amounts: Vec<CurrencyAmount>,
+    pub simulated_payment_count: u64,
+    pub simulated_amounts: Vec<CurrencyAmount>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RouteChange {
+    /// The connector the payments were routed to, absent for payments which were never routed
+    pub historical_connector: Option<String>,
+    /// The connector the draft algorithm routes the payments to
+    pub simulated_connector: String,
+    pub payment_count: u64,
+    pub total_amounts: Vec<CurrencyAmount>,
+}
+
+/// Total amount of the payments made in a currency
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct CurrencyAmount {
+    #[schema(value_type = Currency)]
+    pub currency: common_enums::Currency,
+    #[schema(value_type = i64)]
+    pub amount: common_utils::types::MinorUnit,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RuleMatchCount {
+    pub rule_name: String,
+    pub payment_count: u64,
+}
@@ -1,3 +1,4 @@
+pub mod networks;
 pub mod validate;
 use std::ops::Deref;
 
@@ -0,0 +1,263 @@
+//!
+//! Offline detection of the card networks from the issuer identification number (IIN) ranges,
+//! along with the card number and card security code length rules of each network
+//!
+
+use common_enums::CardNetwork::{
+    self, AmericanExpress, CartesBancaires, DinersClub, Discover, Maestro, Mastercard, RuPay,
+    UnionPay, Visa, JCB,
+};
+
+///
+/// A range of IINs, where the bounds are prefixes of the same number of digits. A range holding
+/// more than one network is a co-badged range, the first network being the primary one.
+///
+#[derive(Debug)]
+pub struct IinRange {
+    pub start: &'static str,
+    pub end: &'static str,
+    pub networks: &'static [CardNetwork],
+}
+
+impl IinRange {
+    const fn new(start: &'static str, end: &'static str, networks: &'static [CardNetwork]) -> Self {
+        Self {
+            start,
+            end,
+            networks,
+        }
+    }
+
+    const fn prefix(prefix: &'static str, networks: &'static [CardNetwork]) -> Self {
+        Self::new(prefix, prefix, networks)
+    }
+
+    fn get_prefix_length(&self) -> usize {
+        self.start.len()
+    }
+
+    fn contains(&self, card_number: &str) -> bool {
+        card_number
+            .get(..self.get_prefix_length())
+            .is_some_and(|prefix| self.start <= prefix && prefix <= self.end)
+    }
+}
+
+///
+/// Card number and card security code length rules of a network
+///
+#[derive(Debug)]
+pub struct NetworkRules {
+    pub network: CardNetwork,
+    pub card_number_lengths: &'static [usize],
+    pub cvc_length: usize,
+}
+
+impl NetworkRules {
+    pub fn is_valid_card_number_length(&self, length: usize) -> bool {
+        self.card_number_lengths.contains(&length)
+    }
+
+    pub fn is_valid_cvc(&self, cvc: &str) -> bool {
+        cvc.len() == self.cvc_length && cvc.chars().all(|character| character.is_ascii_digit())
+    }
+}
+
+///
+/// Built-in IIN ranges, the most specific range matching a card number determines its networks.
+///
+/// Co-badging is only recorded for the BINs issued under a network partnership, the ranges of
+/// the networks themselves are not co-badged. Cartes Bancaires cards are issued within the Visa
+/// and Mastercard ranges on BINs that are not allocated contiguously, so only known Cartes
+/// Bancaires BINs are listed.
+///
+pub const IIN_RANGES: &[IinRange] = &[
+    IinRange::prefix("4", &[Visa]),
+    IinRange::new("51", "55", &[Mastercard]),
+    IinRange::new("2221", "2720", &[Mastercard]),
+    IinRange::prefix("34", &[AmericanExpress]),
+    IinRange::prefix("37", &[AmericanExpress]),
+    IinRange::new("300", "305", &[DinersClub]),
+    IinRange::prefix("3095", &[DinersClub]),
+    IinRange::prefix("36", &[DinersClub]),
+    IinRange::new("38", "39", &[DinersClub]),
+    IinRange::new("3528", "3589", &[JCB]),
+    // RuPay JCB Global cards
+    IinRange::prefix("353500", &[JCB, RuPay]),
+    IinRange::prefix("356038", &[JCB, RuPay]),
+    IinRange::prefix("6011", &[Discover]),
+    IinRange::new("644", "649", &[Discover]),
+    IinRange::prefix("65", &[Discover]),
+    IinRange::new("6521", "6522", &[RuPay, Discover]),
+    IinRange::prefix("60", &[RuPay]),
+    IinRange::prefix("508", &[RuPay]),
+    IinRange::new("81", "82", &[RuPay]),
+    IinRange::prefix("62", &[UnionPay]),
+    IinRange::new("622126", "622925", &[UnionPay, Discover]),
+    IinRange::prefix("5018", &[Maestro]),
+    IinRange::prefix("5020", &[Maestro]),
+    IinRange::prefix("5038", &[Maestro]),
+    IinRange::prefix("5893", &[Maestro]),
+    IinRange::prefix("6304", &[Maestro]),
+    IinRange::prefix("6759", &[Maestro]),
+    IinRange::new("6761", "6763", &[Maestro]),
+    IinRange::prefix("40000025", &[CartesBancaires, Visa]),
+    IinRange::prefix("403550", &[CartesBancaires, Visa]),
+    IinRange::prefix("55555525", &[CartesBancaires, Mastercard]),
+];
+
+///
+/// Length rules of the networks
+///
+pub const NETWORK_RULES: &[NetworkRules] = &[
+    NetworkRules {
+        network: Visa,
+        card_number_lengths: &[13, 16, 19],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: Mastercard,
+        card_number_lengths: &[16],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: AmericanExpress,
+        card_number_lengths: &[15],
+        cvc_length: 4,
+    },
+    NetworkRules {
+        network: JCB,
+        card_number_lengths: &[16, 17, 18, 19],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: DinersClub,
+        card_number_lengths: &[14, 15, 16, 17, 18, 19],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: Discover,
+        card_number_lengths: &[16, 17, 18, 19],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: CartesBancaires,
+        card_number_lengths: &[16],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: UnionPay,
+        card_number_lengths: &[16, 17, 18, 19],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: RuPay,
+        card_number_lengths: &[16],
+        cvc_length: 3,
+    },
+    NetworkRules {
+        network: Maestro,
+        card_number_lengths: &[12, 13, 14, 15, 16, 17, 18, 19],
+        cvc_length: 3,
+    },
+];
+
+///
+/// Returns the networks of the card number, the primary network first. More than one network is
+/// returned for co-badged cards, and none if the card number is in none of the known ranges.
+///
+pub fn get_card_networks(card_number: &str) -> &'static [CardNetwork] {
+    IIN_RANGES
+        .iter()
+        .filter(|range| range.contains(card_number))
+        .max_by_key(|range| range.get_prefix_length())
+        .map(|range| range.networks)
+        .unwrap_or_default()
+}
+
+pub fn get_network_rules(network: &CardNetwork) -> Option<&'static NetworkRules> {
+    NETWORK_RULES.iter().find(|rules| rules.network == *network)
+}
+
+///
+/// Checks that the length of the card number is valid for at least one of its networks. Card
+/// numbers of unknown networks are only subject to the ISO length limits.
+///
+pub fn is_valid_card_number_length_for_networks(card_number: &str) -> bool {
+    let mut rules = get_card_networks(card_number)
+        .iter()
+        .filter_map(get_network_rules)
+        .peekable();
+
+    rules.peek().is_none()
+        || rules.any(|rules| rules.is_valid_card_number_length(card_number.len()))
+}
+
+///
+/// Checks that the card security code has the length required by the network, the card security
+/// codes of networks without rules are accepted
+///
+pub fn is_valid_cvc_for_network(network: &CardNetwork, cvc: &str) -> bool {
+    get_network_rules(network).map_or(true, |rules| rules.is_valid_cvc(cvc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_networks_are_detected_from_the_most_specific_range() {
+        assert_eq!(get_card_networks("4242424242424242"), &[Visa]);
+        assert_eq!(get_card_networks("2223003122003222"), &[Mastercard]);
+        assert_eq!(get_card_networks("378282246310005"), &[AmericanExpress]);
+        assert_eq!(get_card_networks("6011111111111117"), &[Discover]);
+        assert_eq!(get_card_networks("6069985555555555"), &[RuPay]);
+        assert_eq!(get_card_networks("6759649826438453"), &[Maestro]);
+        assert_eq!(get_card_networks("30569309025904"), &[DinersClub]);
+        assert!(get_card_networks("9000100111111111").is_empty());
+    }
+
+    #[test]
+    fn test_co_badged_card_networks_are_detected() {
+        assert_eq!(get_card_networks("6221261111111111"), &[UnionPay, Discover]);
+        assert_eq!(get_card_networks("6200000000000005"), &[UnionPay]);
+        assert_eq!(get_card_networks("3530111333300000"), &[JCB]);
+        assert_eq!(get_card_networks("3566002020360505"), &[JCB]);
+        assert_eq!(get_card_networks("3589000000000000"), &[JCB]);
+        assert_eq!(get_card_networks("3535001111111111"), &[JCB, RuPay]);
+        assert_eq!(get_card_networks("6521000000000000"), &[RuPay, Discover]);
+    }
+
+    #[test]
+    fn test_cartes_bancaires_cards_are_detected() {
+        assert_eq!(
+            get_card_networks("4000002500001001"),
+            &[CartesBancaires, Visa]
+        );
+        assert_eq!(
+            get_card_networks("5555552500001001"),
+            &[CartesBancaires, Mastercard]
+        );
+        assert_eq!(get_card_networks("4000002800001001"), &[Visa]);
+    }
+
+    #[test]
+    fn test_card_number_length_is_validated_for_the_networks() {
+        assert!(is_valid_card_number_length_for_networks("378282246310005"));
+        assert!(!is_valid_card_number_length_for_networks(
+            "3782822463100051"
+        ));
+        assert!(is_valid_card_number_length_for_networks("4222222222222"));
+        assert!(!is_valid_card_number_length_for_networks("42222222222222"));
+        assert!(is_valid_card_number_length_for_networks("900010011111"));
+    }
+
+    #[test]
+    fn test_cvc_is_validated_for_the_network() {
+        assert!(is_valid_cvc_for_network(&AmericanExpress, "1234"));
+        assert!(!is_valid_cvc_for_network(&AmericanExpress, "123"));
+        assert!(is_valid_cvc_for_network(&Visa, "012"));
+        assert!(!is_valid_cvc_for_network(&Visa, "12a"));
+        assert!(is_valid_cvc_for_network(&CardNetwork::Interac, "1234"));
+    }
+}
@@ -6,6 +6,8 @@ use router_env::{logger, which as router_env_which, Env};
 use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
+use crate::networks;
+
 ///
 /// Minimum limit of a card number will not be less than 8 by ISO standards
 ///
@@ -46,6 +48,26 @@ impl CardNumber {
             .rev()
             .collect::<String>()
     }
+
+    /// Networks of the card detected from its IIN, more than one for co-badged cards
+    pub fn get_card_networks(&self) -> &'static [common_enums::CardNetwork] {
+        networks::get_card_networks(self.0.peek())
+    }
+
+    /// Primary network of the card detected from its IIN
+    pub fn get_card_network(&self) -> Option<common_enums::CardNetwork> {
+        self.get_card_networks().first().cloned()
+    }
+
+    pub fn is_co_badged(&self) -> bool {
+        self.get_card_networks().len() > 1
+    }
+
+    /// Checks the card number length against the rules of its networks, on top of the ISO
+    /// length limits checked when the card number is parsed
+    pub fn is_valid_length_for_networks(&self) -> bool {
+        networks::is_valid_card_number_length_for_networks(self.0.peek())
+    }
 }
 
 impl FromStr for CardNumber {
@@ -225,6 +247,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn card_number_length_is_only_checked_for_the_networks_on_request() {
+        let card_number = CardNumber::from_str("42222222222226").unwrap();
+        assert!(!card_number.is_valid_length_for_networks());
+
+        let card_number = CardNumber::from_str("4222222222222").unwrap();
+        assert!(card_number.is_valid_length_for_networks());
+    }
+
     #[test]
     fn card_number_no_whitespace() {
         let s = "3714    4963  5398 431";
@@ -3136,3 +3136,29 @@ pub enum OrderFulfillmentTimeOrigin {
     Create,
     Confirm,
 }
+
+/// The strategy used to round a converted amount to the minor unit of its currency
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RoundingStrategy {
+    /// Rounds half-way values to the nearest even number
+    #[default]
+    Bankers,
+    /// Rounds half-way values away from zero
+    HalfUp,
+    /// Rounds towards negative infinity
+    Floor,
+}
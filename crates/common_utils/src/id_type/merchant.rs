@@ -199,6 +199,11 @@ impl MerchantId {
         }
     }
 
+    /// get_fx_spread_key
+    pub fn get_fx_spread_key(&self) -> String {
+        format!("fx_spread_{}", self.get_string_repr())
+    }
+
     /// Get should call gsm key for payment
     pub fn get_should_call_gsm_key(&self) -> String {
         format!("should_call_gsm_{}", self.get_string_repr())
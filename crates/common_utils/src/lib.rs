@@ -46,8 +46,9 @@ pub mod date_time {
             well_known::iso8601::{Config, EncodedConfig, Iso8601, TimePrecision},
             BorrowedFormatItem,
         },
-        OffsetDateTime, PrimitiveDateTime,
+        OffsetDateTime, PrimitiveDateTime, UtcOffset,
     };
+    use time_tz::{Offset, OffsetResult, PrimitiveDateTimeExt, TimeZone};
 
     /// Enum to represent date formats
     #[derive(Debug)]
@@ -76,6 +77,76 @@ pub mod date_time {
         OffsetDateTime::now_utc().unix_timestamp()
     }
 
+    /// A timezone, which is either a fixed offset from UTC or a zone of the IANA time zone
+    /// database
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Timezone {
+        /// A fixed offset from UTC like `+05:30`
+        Offset(UtcOffset),
+        /// A zone like `Europe/London`, whose offset from UTC changes with daylight saving time
+        Named(&'static time_tz::Tz),
+    }
+
+    impl Timezone {
+        /// The offset from UTC of the timezone at the given instant
+        pub fn offset_at(self, date_time: OffsetDateTime) -> UtcOffset {
+            match self {
+                Self::Offset(offset) => offset,
+                Self::Named(timezone) => timezone.get_offset_utc(&date_time).to_utc(),
+            }
+        }
+
+        /// Converts the given date and time in UTC to the date and time in the timezone
+        pub fn local_date_time(self, date_time: PrimitiveDateTime) -> OffsetDateTime {
+            let date_time = date_time.assume_utc();
+            date_time.to_offset(self.offset_at(date_time))
+        }
+
+        /// The instants at which the clocks in the timezone show the given local date and time,
+        /// in increasing order. There are two such instants when the clocks are set back, and
+        /// none when the clocks are set forward past the local date and time.
+        pub fn instants_at(self, local_date_time: PrimitiveDateTime) -> Vec<OffsetDateTime> {
+            match self {
+                Self::Offset(offset) => vec![local_date_time.assume_offset(offset)],
+                Self::Named(timezone) => match local_date_time.assume_timezone(timezone) {
+                    OffsetResult::Some(date_time) => vec![date_time],
+                    OffsetResult::Ambiguous(first, second) => {
+                        vec![first.min(second), first.max(second)]
+                    }
+                    OffsetResult::None => Vec::new(),
+                },
+            }
+        }
+    }
+
+    /// Parses a timezone which is either `UTC`, a fixed offset from UTC like `+05:30` or `-08:00`,
+    /// or an IANA time zone name like `Europe/London`
+    pub fn parse_timezone(timezone: Option<&str>) -> Result<Timezone, String> {
+        let Some(timezone) = timezone.filter(|timezone| !timezone.eq_ignore_ascii_case("UTC"))
+        else {
+            return Ok(Timezone::Offset(UtcOffset::UTC));
+        };
+
+        let invalid_timezone = || format!("invalid timezone '{timezone}'");
+        let (sign, offset) = if let Some(offset) = timezone.strip_prefix('+') {
+            (1, offset)
+        } else if let Some(offset) = timezone.strip_prefix('-') {
+            (-1, offset)
+        } else {
+            return time_tz::timezones::get_by_name(timezone)
+                .map(Timezone::Named)
+                .ok_or_else(invalid_timezone);
+        };
+
+        let (hours, minutes) = offset.split_once(':').ok_or_else(invalid_timezone)?;
+        let hours = hours.parse::<i8>().map_err(|_| invalid_timezone())?;
+        let minutes = minutes.parse::<i8>().map_err(|_| invalid_timezone())?;
+
+        UtcOffset::from_hms(sign * hours, sign * minutes, 0)
+            .map(Timezone::Offset)
+            .map_err(|_| invalid_timezone())
+    }
+
     /// Calculate execution time for a async block in milliseconds
     #[cfg(feature = "async_ext")]
     pub async fn time_it<T, Fut: futures::Future<Output = T>, F: FnOnce() -> Fut>(
@@ -1,5 +1,5 @@
-use common_enums::Currency;
-use rust_decimal::Decimal;
+use common_enums::{Currency, RoundingStrategy};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use rusty_money::Money;
 
 use crate::{
@@ -7,6 +7,23 @@ use crate::{
     types::{currency_match, ExchangeRates},
 };
 
+/// Options applied on top of the plain conversion of an amount
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConversionOptions {
+    /// Rounding applied to the minor unit of the target currency
+    pub rounding_strategy: RoundingStrategy,
+    /// Markup over the exchange rate as a fraction, `0.02` adds 2% to the converted amount
+    pub spread: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvertedAmount {
+    /// The converted amount in the major unit of the target currency
+    pub amount: Decimal,
+    /// The converted amount in the minor unit of the target currency
+    pub minor_amount: i64,
+}
+
 pub fn convert(
     ex_rates: &ExchangeRates,
     from_currency: Currency,
@@ -26,6 +43,40 @@ pub fn convert(
     }
 }
 
+/// Converts an amount in the minor unit of `from_currency`, applies the spread and rounds the
+/// result to the minor unit of `to_currency`
+pub fn convert_with_options(
+    ex_rates: &ExchangeRates,
+    from_currency: Currency,
+    to_currency: Currency,
+    amount: i64,
+    options: ConversionOptions,
+) -> Result<ConvertedAmount, CurrencyConversionError> {
+    let converted_amount = convert(ex_rates, from_currency, to_currency, amount)?
+        .checked_mul(Decimal::ONE + options.spread)
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    let exponent = currency_match(to_currency).exponent;
+    let amount = converted_amount.round_dp_with_strategy(
+        exponent,
+        match options.rounding_strategy {
+            RoundingStrategy::Bankers => rust_decimal::RoundingStrategy::MidpointNearestEven,
+            RoundingStrategy::HalfUp => rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            RoundingStrategy::Floor => rust_decimal::RoundingStrategy::ToNegativeInfinity,
+        },
+    );
+    let minor_amount = 10_i64
+        .checked_pow(exponent)
+        .and_then(|minor_units| Decimal::from(minor_units).checked_mul(amount))
+        .and_then(|minor_amount| minor_amount.to_i64())
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    Ok(ConvertedAmount {
+        amount,
+        minor_amount,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::print_stdout)]
@@ -98,4 +149,80 @@ mod tests {
             amount, convert_from, convert_to, res
         );
     }
+
+    #[test]
+    fn conversion_with_rounding_strategies() {
+        use super::*;
+        let mut conversion: HashMap<Currency, CurrencyFactors> = HashMap::new();
+        conversion.insert(
+            Currency::USD,
+            CurrencyFactors::new(Decimal::new(1, 0), Decimal::new(1, 0)),
+        );
+        conversion.insert(
+            Currency::EUR,
+            CurrencyFactors::new(Decimal::new(5, 1), Decimal::new(2, 0)),
+        );
+        conversion.insert(
+            Currency::JPY,
+            CurrencyFactors::new(
+                Decimal::new(150, 0),
+                Decimal::new(1, 0) / Decimal::new(150, 0),
+            ),
+        );
+        let sample_rate = ExchangeRates::new(Currency::USD, conversion);
+
+        let convert_usd = |to_currency, amount, rounding_strategy| {
+            convert_with_options(
+                &sample_rate,
+                Currency::USD,
+                to_currency,
+                amount,
+                ConversionOptions {
+                    rounding_strategy,
+                    spread: Decimal::ZERO,
+                },
+            )
+            .expect("converted_currency")
+            .minor_amount
+        };
+
+        // 0.25 USD -> 0.125 EUR
+        assert_eq!(
+            convert_usd(Currency::EUR, 25, RoundingStrategy::Bankers),
+            12
+        );
+        assert_eq!(convert_usd(Currency::EUR, 25, RoundingStrategy::HalfUp), 13);
+        assert_eq!(convert_usd(Currency::EUR, 27, RoundingStrategy::Floor), 13);
+        // JPY has no minor unit, 0.01 USD -> 1.5 JPY
+        assert_eq!(convert_usd(Currency::JPY, 1, RoundingStrategy::Bankers), 2);
+        assert_eq!(convert_usd(Currency::JPY, 1, RoundingStrategy::Floor), 1);
+    }
+
+    #[test]
+    fn conversion_with_spread() {
+        use super::*;
+        let mut conversion: HashMap<Currency, CurrencyFactors> = HashMap::new();
+        conversion.insert(
+            Currency::USD,
+            CurrencyFactors::new(Decimal::new(1, 0), Decimal::new(1, 0)),
+        );
+        conversion.insert(
+            Currency::INR,
+            CurrencyFactors::new(Decimal::new(80, 0), Decimal::new(125, 4)),
+        );
+        let sample_rate = ExchangeRates::new(Currency::USD, conversion);
+        let converted_amount = convert_with_options(
+            &sample_rate,
+            Currency::USD,
+            Currency::INR,
+            1000,
+            ConversionOptions {
+                rounding_strategy: RoundingStrategy::HalfUp,
+                spread: Decimal::new(2, 2),
+            },
+        )
+        .expect("converted_currency");
+        assert_eq!(converted_amount.amount, Decimal::new(816, 0));
+        assert_eq!(converted_amount.minor_amount, 81600);
+    }
 }
@@ -5,4 +5,10 @@ pub enum CurrencyConversionError {
     DecimalMultiplicationFailed,
     #[error("Currency not supported: '{0}'")]
     ConversionNotSupported(String),
+    #[error("Exchange rates source unavailable: {0}")]
+    RatesSourceUnavailable(String),
+    #[error("Invalid exchange rates data: {0}")]
+    InvalidRatesData(String),
+    #[error("No provider returned usable exchange rates: {0}")]
+    ExchangeRatesUnavailable(String),
 }
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+
+use crate::types::ExchangeRates;
+
+/// Snapshots of exchange rates keyed by the unix timestamp (in seconds) from which they are valid
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeRatesHistory {
+    snapshots: BTreeMap<i64, ExchangeRates>,
+}
+
+impl ExchangeRatesHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, timestamp: i64, rates: ExchangeRates) {
+        self.snapshots.insert(timestamp, rates);
+    }
+
+    /// The rates valid at `timestamp`, which are the latest rates stored at or before it
+    pub fn rates_at(&self, timestamp: i64) -> Option<&ExchangeRates> {
+        self.snapshots
+            .range(..=timestamp)
+            .next_back()
+            .map(|(_, rates)| rates)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+impl FromIterator<(i64, ExchangeRates)> for ExchangeRatesHistory {
+    fn from_iter<T: IntoIterator<Item = (i64, ExchangeRates)>>(iter: T) -> Self {
+        Self {
+            snapshots: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common_enums::Currency;
+
+    use super::*;
+
+    #[test]
+    fn rates_valid_at_timestamp() {
+        let history = [Currency::USD, Currency::EUR, Currency::GBP]
+            .into_iter()
+            .zip([100, 200, 300])
+            .map(|(base_currency, timestamp)| {
+                (timestamp, ExchangeRates::new(base_currency, HashMap::new()))
+            })
+            .collect::<ExchangeRatesHistory>();
+
+        assert!(history.rates_at(99).is_none());
+        let base_currency_at =
+            |timestamp| history.rates_at(timestamp).map(|rates| rates.base_currency);
+        assert_eq!(base_currency_at(100), Some(Currency::USD));
+        assert_eq!(base_currency_at(250), Some(Currency::EUR));
+        assert_eq!(base_currency_at(1000), Some(Currency::GBP));
+    }
+}
@@ -1,3 +1,5 @@
 pub mod conversion;
 pub mod error;
+pub mod history;
+pub mod providers;
 pub mod types;
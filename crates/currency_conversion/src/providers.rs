@@ -0,0 +1,345 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr, time::SystemTime};
+
+use common_enums::Currency;
+use rust_decimal::Decimal;
+
+use crate::{
+    error::CurrencyConversionError,
+    types::{CurrencyFactors, ExchangeRates},
+};
+
+/// Exchange rates along with the unix timestamp (in seconds) at which they were published
+#[derive(Debug, Clone)]
+pub struct ExchangeRatesSnapshot {
+    pub rates: ExchangeRates,
+    pub timestamp: i64,
+}
+
+/// A source of exchange rates, like a forex API, a local rates file or a table of rates
+#[async_trait::async_trait]
+pub trait ExchangeRatesProvider: Send + Sync {
+    /// Name of the provider, used for logging
+    fn name(&self) -> &'static str;
+
+    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot, CurrencyConversionError>;
+}
+
+/// Decides whether rates returned by a provider are too old to be used
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StalenessPolicy {
+    /// Maximum age of the rates in seconds, rates never go stale if not set
+    pub max_age: Option<i64>,
+    /// Whether the freshest stale rates may be used when no provider returns fresh rates
+    pub serve_stale: bool,
+}
+
+impl StalenessPolicy {
+    pub fn is_stale(&self, snapshot: &ExchangeRatesSnapshot, now: i64) -> bool {
+        self.max_age
+            .is_some_and(|max_age| snapshot.timestamp.saturating_add(max_age) < now)
+    }
+}
+
+/// Queries the providers in order and returns the rates of the first provider that returns
+/// fresh rates, as decided by the staleness policy
+pub struct FallbackChain<'a> {
+    providers: Vec<Box<dyn ExchangeRatesProvider + 'a>>,
+    staleness_policy: StalenessPolicy,
+}
+
+impl<'a> FallbackChain<'a> {
+    pub fn new(staleness_policy: StalenessPolicy) -> Self {
+        Self {
+            providers: Vec::new(),
+            staleness_policy,
+        }
+    }
+
+    pub fn with_provider(mut self, provider: impl ExchangeRatesProvider + 'a) -> Self {
+        self.providers.push(Box::new(provider));
+        self
+    }
+
+    pub async fn fetch_exchange_rates(
+        &self,
+        now: i64,
+    ) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+        let mut freshest_stale_snapshot: Option<ExchangeRatesSnapshot> = None;
+        let mut failed_providers = Vec::new();
+
+        for provider in &self.providers {
+            match provider.fetch_exchange_rates().await {
+                Ok(snapshot) if !self.staleness_policy.is_stale(&snapshot, now) => {
+                    return Ok(snapshot)
+                }
+                Ok(snapshot) => {
+                    failed_providers.push(format!("{}: stale rates", provider.name()));
+                    if freshest_stale_snapshot
+                        .as_ref()
+                        .map_or(true, |stale| stale.timestamp < snapshot.timestamp)
+                    {
+                        freshest_stale_snapshot = Some(snapshot);
+                    }
+                }
+                Err(error) => failed_providers.push(format!("{}: {error}", provider.name())),
+            }
+        }
+
+        freshest_stale_snapshot
+            .filter(|_| self.staleness_policy.serve_stale)
+            .ok_or_else(|| {
+                CurrencyConversionError::ExchangeRatesUnavailable(failed_providers.join(", "))
+            })
+    }
+}
+
+/// Serves a fixed table of rates, like the one embedded in the application configuration
+#[derive(Debug, Clone)]
+pub struct StaticRatesProvider {
+    snapshot: ExchangeRatesSnapshot,
+}
+
+impl StaticRatesProvider {
+    pub fn new(rates: ExchangeRates, timestamp: i64) -> Self {
+        Self {
+            snapshot: ExchangeRatesSnapshot { rates, timestamp },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesProvider for StaticRatesProvider {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+        Ok(self.snapshot.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatesFileFormat {
+    /// `{"base_currency": "USD", "timestamp": 1700000000, "rates": {"EUR": "0.92"}}`,
+    /// the timestamp is optional
+    Json,
+    /// One `currency,rate` pair per line, with an optional `currency,rate` header
+    Csv,
+}
+
+/// Reads rates from a local file. The rates are the amount of the currency one unit of the base
+/// currency buys. The modification time of the file is used when the file carries no timestamp.
+#[derive(Debug, Clone)]
+pub struct FileRatesProvider {
+    pub path: PathBuf,
+    pub format: RatesFileFormat,
+    /// Base currency of the rates in a CSV file
+    pub base_currency: Currency,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct JsonRatesFile {
+    base_currency: Currency,
+    timestamp: Option<i64>,
+    rates: HashMap<Currency, Decimal>,
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesProvider for FileRatesProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+        let read_error = |error: std::io::Error| {
+            CurrencyConversionError::RatesSourceUnavailable(error.to_string())
+        };
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(read_error)?;
+        let modified_at = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map_err(read_error)?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+            .unwrap_or_default();
+
+        match self.format {
+            RatesFileFormat::Json => {
+                let (rates, timestamp) = parse_json_rates(&contents)?;
+                Ok(ExchangeRatesSnapshot {
+                    rates,
+                    timestamp: timestamp.unwrap_or(modified_at),
+                })
+            }
+            RatesFileFormat::Csv => Ok(ExchangeRatesSnapshot {
+                rates: parse_csv_rates(&contents, self.base_currency)?,
+                timestamp: modified_at,
+            }),
+        }
+    }
+}
+
+pub fn parse_json_rates(
+    contents: &str,
+) -> Result<(ExchangeRates, Option<i64>), CurrencyConversionError> {
+    let rates_file: JsonRatesFile = serde_json::from_str(contents)
+        .map_err(|error| CurrencyConversionError::InvalidRatesData(error.to_string()))?;
+
+    Ok((
+        exchange_rates_from_rates(rates_file.base_currency, rates_file.rates)?,
+        rates_file.timestamp,
+    ))
+}
+
+pub fn parse_csv_rates(
+    contents: &str,
+    base_currency: Currency,
+) -> Result<ExchangeRates, CurrencyConversionError> {
+    let mut rates = HashMap::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (line_number == 0 && line.eq_ignore_ascii_case("currency,rate")) {
+            continue;
+        }
+
+        let invalid_line = || {
+            CurrencyConversionError::InvalidRatesData(format!(
+                "invalid entry on line {}: '{line}'",
+                line_number + 1
+            ))
+        };
+        let (currency, rate) = line.split_once(',').ok_or_else(invalid_line)?;
+        let currency = Currency::from_str(currency.trim()).map_err(|_| invalid_line())?;
+        let rate = Decimal::from_str(rate.trim()).map_err(|_| invalid_line())?;
+        rates.insert(currency, rate);
+    }
+
+    exchange_rates_from_rates(base_currency, rates)
+}
+
+fn exchange_rates_from_rates(
+    base_currency: Currency,
+    rates: HashMap<Currency, Decimal>,
+) -> Result<ExchangeRates, CurrencyConversionError> {
+    let mut conversion = rates
+        .into_iter()
+        .map(|(currency, rate)| {
+            let from_factor = Decimal::ONE
+                .checked_div(rate)
+                .filter(|_| rate.is_sign_positive())
+                .ok_or_else(|| {
+                    CurrencyConversionError::InvalidRatesData(format!(
+                        "invalid rate {rate} for {currency}"
+                    ))
+                })?;
+            Ok((currency, CurrencyFactors::new(rate, from_factor)))
+        })
+        .collect::<Result<HashMap<_, _>, CurrencyConversionError>>()?;
+    conversion
+        .entry(base_currency)
+        .or_insert_with(|| CurrencyFactors::new(Decimal::ONE, Decimal::ONE));
+
+    Ok(ExchangeRates::new(base_currency, conversion))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use super::*;
+
+    struct FailingProvider;
+
+    #[async_trait::async_trait]
+    impl ExchangeRatesProvider for FailingProvider {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn fetch_exchange_rates(
+            &self,
+        ) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+            Err(CurrencyConversionError::RatesSourceUnavailable(
+                "unreachable".to_string(),
+            ))
+        }
+    }
+
+    fn sample_rates() -> ExchangeRates {
+        parse_csv_rates("currency,rate\nEUR,0.5\nINR,80\n", Currency::USD).expect("valid csv")
+    }
+
+    #[test]
+    fn csv_rates_are_parsed_with_base_currency() {
+        let rates = sample_rates();
+        assert_eq!(rates.base_currency, Currency::USD);
+        let eur = rates.conversion.get(&Currency::EUR).unwrap();
+        assert_eq!(eur.to_factor, Decimal::new(5, 1));
+        assert_eq!(eur.from_factor, Decimal::new(2, 0));
+        assert_eq!(
+            rates.conversion.get(&Currency::USD).unwrap().to_factor,
+            Decimal::ONE
+        );
+    }
+
+    #[test]
+    fn invalid_csv_rates_are_rejected() {
+        assert!(parse_csv_rates("EUR;0.5", Currency::USD).is_err());
+        assert!(parse_csv_rates("EUR,0", Currency::USD).is_err());
+        assert!(parse_csv_rates("XYZ,1.2", Currency::USD).is_err());
+    }
+
+    #[test]
+    fn json_rates_are_parsed() {
+        let (rates, timestamp) = parse_json_rates(
+            r#"{"base_currency": "EUR", "timestamp": 1700000000, "rates": {"USD": "2", "INR": 90}}"#,
+        )
+        .expect("valid json");
+        assert_eq!(timestamp, Some(1700000000));
+        assert_eq!(rates.base_currency, Currency::EUR);
+        assert_eq!(
+            rates.conversion.get(&Currency::USD).unwrap().from_factor,
+            Decimal::new(5, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_chain_skips_failing_and_stale_providers() {
+        let policy = StalenessPolicy {
+            max_age: Some(100),
+            serve_stale: false,
+        };
+        let snapshot = FallbackChain::new(policy)
+            .with_provider(FailingProvider)
+            .with_provider(StaticRatesProvider::new(sample_rates(), 0))
+            .with_provider(StaticRatesProvider::new(sample_rates(), 950))
+            .fetch_exchange_rates(1000)
+            .await
+            .expect("fresh rates");
+        assert_eq!(snapshot.timestamp, 950);
+    }
+
+    #[tokio::test]
+    async fn fallback_chain_serves_stale_rates_only_when_allowed() {
+        let chain = |serve_stale| {
+            FallbackChain::new(StalenessPolicy {
+                max_age: Some(100),
+                serve_stale,
+            })
+            .with_provider(StaticRatesProvider::new(sample_rates(), 10))
+            .with_provider(StaticRatesProvider::new(sample_rates(), 20))
+            .with_provider(FailingProvider)
+        };
+
+        assert!(chain(false).fetch_exchange_rates(1000).await.is_err());
+        let snapshot = chain(true)
+            .fetch_exchange_rates(1000)
+            .await
+            .expect("stale rates");
+        assert_eq!(snapshot.timestamp, 20);
+    }
+}
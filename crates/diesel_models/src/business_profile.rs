@@ -59,6 +59,7 @@ pub struct BusinessProfile {
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: Option<bool>,
     pub version: common_enums::ApiVersion,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -102,6 +103,7 @@ pub struct BusinessProfileNew {
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: Option<bool>,
     pub version: common_enums::ApiVersion,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -141,6 +143,7 @@ pub struct BusinessProfileUpdateInternal {
     pub always_collect_shipping_details_from_wallet_connector: Option<bool>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: Option<bool>,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -179,6 +182,7 @@ impl BusinessProfileUpdateInternal {
             always_collect_shipping_details_from_wallet_connector,
             tax_connector_id,
             is_tax_connector_enabled,
+            timezone,
         } = self;
         BusinessProfile {
             profile_id: source.profile_id,
@@ -232,6 +236,7 @@ impl BusinessProfileUpdateInternal {
             tax_connector_id: tax_connector_id.or(source.tax_connector_id),
             is_tax_connector_enabled: is_tax_connector_enabled.or(source.is_tax_connector_enabled),
             version: source.version,
+            timezone: timezone.or(source.timezone),
         }
     }
 }
@@ -281,6 +286,7 @@ pub struct BusinessProfile {
     pub default_fallback_routing: Option<pii::SecretSerdeValue>,
     pub id: common_utils::id_type::ProfileId,
     pub version: common_enums::ApiVersion,
+    pub timezone: Option<String>,
 }
 
 impl BusinessProfile {
@@ -338,6 +344,7 @@ pub struct BusinessProfileNew {
     pub default_fallback_routing: Option<pii::SecretSerdeValue>,
     pub id: common_utils::id_type::ProfileId,
     pub version: common_enums::ApiVersion,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -376,6 +383,7 @@ pub struct BusinessProfileUpdateInternal {
     pub frm_routing_algorithm_id: Option<String>,
     pub payout_routing_algorithm_id: Option<common_utils::id_type::RoutingId>,
     pub default_fallback_routing: Option<pii::SecretSerdeValue>,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -413,6 +421,7 @@ impl BusinessProfileUpdateInternal {
             frm_routing_algorithm_id,
             payout_routing_algorithm_id,
             default_fallback_routing,
+            timezone,
         } = self;
         BusinessProfile {
             id: source.id,
@@ -470,6 +479,7 @@ impl BusinessProfileUpdateInternal {
                 .or(source.payout_routing_algorithm_id),
             default_fallback_routing: default_fallback_routing.or(source.default_fallback_routing),
             version: source.version,
+            timezone: timezone.or(source.timezone),
         }
     }
 }
@@ -520,6 +530,7 @@ impl From<BusinessProfileNew> for BusinessProfile {
             payout_routing_algorithm_id: new.payout_routing_algorithm_id,
             default_fallback_routing: new.default_fallback_routing,
             version: new.version,
+            timezone: new.timezone,
         }
     }
 }
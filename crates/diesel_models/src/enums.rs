@@ -50,6 +50,7 @@ pub enum RoutingAlgorithmKind {
     Priority,
     VolumeSplit,
     Advanced,
+    SuccessRateBased,
 }
 
 #[derive(
@@ -99,6 +100,8 @@ pub enum ProcessTrackerStatus {
     ProcessStarted,
     // Finished by consumer
     Finish,
+    // Exhausted the retry policy of its runner
+    DeadLetter,
 }
 
 // Refund
@@ -99,6 +99,67 @@ impl ProcessTrackerNew {
     }
 }
 
+/// An entry of the retry history of a process, recorded in its `event` column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessTrackerRetryEvent {
+    /// The retry count of the process after the retry
+    pub retry_count: i32,
+    /// The status of the process when it was retried
+    pub previous_status: storage_enums::ProcessTrackerStatus,
+    /// The time at which the process was retried
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub retried_at: PrimitiveDateTime,
+    /// The time at which the process was scheduled to be picked up again
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+}
+
+impl ProcessTracker {
+    /// Returns the update scheduling the process to be retried at the schedule time, which
+    /// records the retry in the retry history of the process
+    pub fn get_retry_update(
+        &self,
+        schedule_time: PrimitiveDateTime,
+    ) -> StorageResult<ProcessTrackerUpdate> {
+        let retry_count = self.retry_count + 1;
+        let retry_event = ProcessTrackerRetryEvent {
+            retry_count,
+            previous_status: self.status,
+            retried_at: common_utils::date_time::now(),
+            schedule_time,
+        }
+        .encode_to_string_of_json()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Failed to serialize process tracker retry event")?;
+
+        let mut event = self.event.clone();
+        event.push(retry_event);
+
+        Ok(ProcessTrackerUpdate::StatusRetryUpdate {
+            status: storage_enums::ProcessTrackerStatus::Pending,
+            retry_count,
+            schedule_time,
+            event: Some(event),
+        })
+    }
+
+    /// Returns the update moving the process to the dead letter status, which appends the
+    /// errors the process failed with to the events of the process
+    pub fn get_dead_letter_update(
+        &self,
+        business_status: &str,
+        error_chain: Vec<String>,
+    ) -> ProcessTrackerUpdate {
+        let mut event = self.event.clone();
+        event.extend(error_chain);
+
+        ProcessTrackerUpdate::DeadLetterUpdate {
+            business_status: String::from(business_status),
+            event,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ProcessTrackerUpdate {
     Update {
@@ -118,6 +179,11 @@ pub enum ProcessTrackerUpdate {
         status: storage_enums::ProcessTrackerStatus,
         retry_count: i32,
         schedule_time: PrimitiveDateTime,
+        event: Option<Vec<String>>,
+    },
+    DeadLetterUpdate {
+        business_status: String,
+        event: Vec<String>,
     },
 }
 
@@ -130,6 +196,7 @@ pub struct ProcessTrackerUpdateInternal {
     tracking_data: Option<serde_json::Value>,
     business_status: Option<String>,
     status: Option<storage_enums::ProcessTrackerStatus>,
+    event: Option<Vec<String>>,
     updated_at: Option<PrimitiveDateTime>,
 }
 
@@ -142,6 +209,7 @@ impl Default for ProcessTrackerUpdateInternal {
             tracking_data: Option::default(),
             business_status: Option::default(),
             status: Option::default(),
+            event: Option::default(),
             updated_at: Some(common_utils::date_time::now()),
         }
     }
@@ -165,6 +233,7 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 tracking_data,
                 business_status,
                 status,
+                event: None,
                 updated_at,
             },
             ProcessTrackerUpdate::StatusUpdate {
@@ -179,10 +248,21 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 status,
                 retry_count,
                 schedule_time,
+                event,
             } => Self {
                 status: Some(status),
                 retry_count: Some(retry_count),
                 schedule_time: Some(schedule_time),
+                event,
+                ..Default::default()
+            },
+            ProcessTrackerUpdate::DeadLetterUpdate {
+                business_status,
+                event,
+            } => Self {
+                status: Some(storage_enums::ProcessTrackerStatus::DeadLetter),
+                business_status: Some(business_status),
+                event: Some(event),
                 ..Default::default()
             },
         }
@@ -197,6 +277,7 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
     Debug,
     PartialEq,
     Eq,
+    Hash,
     strum::EnumString,
     strum::Display,
 )]
@@ -210,6 +291,9 @@ pub enum ProcessTrackerRunner {
     OutgoingWebhookRetryWorkflow,
     AttachPayoutAccountWorkflow,
     PaymentMethodStatusUpdateWorkflow,
+    ReportGenerationWorkflow,
+    QueuedPaymentWebhookWorkflow,
+    StorageSchemeMigrationWorkflow,
 }
 
 #[cfg(test)]
@@ -263,6 +347,16 @@ pub mod business_status {
     /// A task that reaches this status should not be retried (rescheduled for execution) later.
     pub const RESOURCE_STATUS_MISMATCH: &str = "RESOURCE_STATUS_MISMATCH";
 
+    /// The task kept failing until it was older than the maximum age allowed by the retry policy
+    /// of its runner.
+    /// A task that reaches this status should not be retried (rescheduled for execution) later.
+    pub const MAX_AGE_EXCEEDED: &str = "MAX_AGE_EXCEEDED";
+
     /// Business status set for newly created tasks.
     pub const PENDING: &str = "Pending";
+
+    /// A run of a recurring task was skipped since a previous run of the same task was still in
+    /// progress.
+    /// A task that reaches this status should not be retried (rescheduled for execution) later.
+    pub const RECURRING_RUN_SKIPPED: &str = "RECURRING_RUN_SKIPPED";
 }
@@ -41,6 +41,24 @@ impl ProcessTracker {
         }
     }
 
+    /// Updates the process only if its status is still the expected one, returns `None` if the
+    /// status of the process has changed in the meantime
+    #[instrument(skip(conn))]
+    pub async fn update_if_status(
+        self,
+        conn: &PgPooledConn,
+        expected_status: enums::ProcessTrackerStatus,
+        process: ProcessTrackerUpdate,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.eq(self.id).and(dsl::status.eq(expected_status)),
+            ProcessTrackerUpdateInternal::from(process),
+        )
+        .await
+        .map(|processes| processes.into_iter().next())
+    }
+
     #[instrument(skip(conn))]
     pub async fn update_process_status_by_ids(
         conn: &PgPooledConn,
@@ -89,6 +107,79 @@ impl ProcessTracker {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_latest_process_by_name(
+        conn: &PgPooledConn,
+        name: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::name.eq(name.to_owned()),
+            Some(1),
+            None,
+            Some(dsl::schedule_time.desc()),
+        )
+        .await
+        .map(|processes| processes.into_iter().next())
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_earliest_pending_process_by_name(
+        conn: &PgPooledConn,
+        name: &str,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::name.eq(name.to_owned()).and(dsl::status.ne_all(vec![
+                enums::ProcessTrackerStatus::Finish,
+                enums::ProcessTrackerStatus::DeadLetter,
+            ])),
+            Some(1),
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+        .map(|processes| processes.into_iter().next())
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_processes_by_runner_status_time(
+        conn: &PgPooledConn,
+        runner: &str,
+        status: Option<enums::ProcessTrackerStatus>,
+        time_lower_limit: PrimitiveDateTime,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        let predicate = dsl::runner
+            .eq(runner.to_owned())
+            .and(dsl::created_at.between(time_lower_limit, time_upper_limit));
+
+        match status {
+            Some(status) => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    predicate.and(dsl::status.eq(status)),
+                    limit,
+                    offset,
+                    Some(dsl::created_at.desc()),
+                )
+                .await
+            }
+            None => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    predicate,
+                    limit,
+                    offset,
+                    Some(dsl::created_at.desc()),
+                )
+                .await
+            }
+        }
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_processes_to_clean(
         conn: &PgPooledConn,
@@ -209,6 +209,8 @@ diesel::table! {
         tax_connector_id -> Nullable<Varchar>,
         is_tax_connector_enabled -> Nullable<Bool>,
         version -> ApiVersion,
+        #[max_length = 64]
+        timezone -> Nullable<Varchar>,
     }
 }
 
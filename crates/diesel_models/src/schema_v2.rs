@@ -216,6 +216,8 @@ diesel::table! {
         #[max_length = 64]
         id -> Varchar,
         version -> ApiVersion,
+        #[max_length = 64]
+        timezone -> Nullable<Varchar>,
     }
 }
 
@@ -0,0 +1,580 @@
+//! Entries of the drainer stream that cannot be drained are moved to a dead letter stream of the
+//! same shard, so that they do not block the entries appended after them. These entries can be
+//! inspected, edited and re-enqueued to the drainer stream, or discarded, with the `dead-letter`
+//! subcommand of the drainer binary.
+//!
+//! Since the entries of a global ID have to be drained in order, the entries of a global ID that
+//! has entries in the dead letter stream are held, by moving them to the dead letter stream as
+//! well, until those entries are replayed or discarded.
+
+use std::{collections::HashMap, sync::Arc};
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel_models::errors::DatabaseError;
+use error_stack::{report, Report, ResultExt};
+use redis_interface::RedisEntryId;
+
+use crate::{errors, metrics, services::Store, stream::StreamEntries, StreamData};
+
+const ERROR_FIELD: &str = "dead_letter_error";
+const TABLE_FIELD: &str = "dead_letter_table";
+const OPERATION_FIELD: &str = "dead_letter_operation";
+const SOURCE_ID_FIELD: &str = "dead_letter_source_id";
+const FAILED_AT_FIELD: &str = "dead_letter_failed_at";
+/// Set on the entries appended back to the drainer stream, so that they are not held behind the
+/// dead letter entries of their global ID
+const REPLAYED_FIELD: &str = "dead_letter_replayed";
+const GLOBAL_ID_FIELD: &str = "global_id";
+
+/// Error recorded for the entries held behind the dead letter entries of their global ID
+const HELD_ERROR: &str = "Held behind a dead letter entry of the same global ID";
+
+/// Used as the table and operation of entries that could not be deserialized
+pub const UNKNOWN: &str = "unknown";
+
+/// Reason why an entry could not be drained
+#[derive(Debug)]
+pub struct Failure<'a> {
+    pub error: String,
+    pub table: &'a str,
+    pub operation: &'a str,
+}
+
+#[inline(always)]
+fn dead_letter_stream(stream_name: &str) -> String {
+    // Example: {shard_5}_drainer_stream_dead_letter
+    format!("{stream_name}_dead_letter")
+}
+
+/// Set of the IDs (in the drainer stream) of the dead letter entries of the global ID
+#[inline(always)]
+fn held_entries_key(stream_name: &str, global_id: &str) -> String {
+    // Example: {shard_5}_drainer_stream_dead_letter_held_<global_id>
+    format!("{}_held_{global_id}", dead_letter_stream(stream_name))
+}
+
+/// Whether the query failed for a reason that does not change when the query is executed again,
+/// like the row to be updated not existing or a constraint of the table being violated. Other
+/// failures, like connection and unknown errors, are retried in the next session instead.
+pub fn is_dead_letter_failure(error: &Report<DatabaseError>) -> bool {
+    match error.current_context() {
+        DatabaseError::NotFound
+        | DatabaseError::NoFieldsToUpdate
+        | DatabaseError::QueryGenerationFailed => true,
+        DatabaseError::DatabaseConnectionError | DatabaseError::UniqueViolation => false,
+        // Foreign key, not null and check constraint violations are reported as unknown errors,
+        // the diesel error they are caused by is looked up to tell them apart
+        DatabaseError::Others => error
+            .downcast_ref::<DieselError>()
+            .is_some_and(is_constraint_violation),
+    }
+}
+
+fn is_constraint_violation(error: &DieselError) -> bool {
+    matches!(
+        error,
+        DieselError::DatabaseError(
+            DatabaseErrorKind::ForeignKeyViolation
+                | DatabaseErrorKind::NotNullViolation
+                | DatabaseErrorKind::CheckViolation,
+            _
+        )
+    )
+}
+
+/// Returns the failure recorded for an entry held behind the dead letter entries of its global ID
+pub fn get_held_failure<'a>(table: &'a str, operation: &'a str) -> Failure<'a> {
+    Failure {
+        error: HELD_ERROR.to_string(),
+        table,
+        operation,
+    }
+}
+
+pub fn is_replayed(entry: &HashMap<String, String>) -> bool {
+    entry.contains_key(REPLAYED_FIELD)
+}
+
+/// Parses a stream entry ID of the form `<milliseconds>-<sequence>` into a comparable tuple
+fn parse_stream_entry_id(entry_id: &str) -> Option<(u64, u64)> {
+    let (milliseconds, sequence) = entry_id.split_once('-')?;
+    Some((milliseconds.parse().ok()?, sequence.parse().ok()?))
+}
+
+/// Returns a held entry that was appended to the drainer stream before the specified entry
+fn find_older_held_entry<'a>(held_entries: &'a [String], source_id: &str) -> Option<&'a String> {
+    let source_id = parse_stream_entry_id(source_id)?;
+    held_entries.iter().find(|held_entry| {
+        parse_stream_entry_id(held_entry).is_some_and(|held_entry| held_entry < source_id)
+    })
+}
+
+/// Returns the fields of the dead letter entry to be appended back to the drainer stream
+fn get_replay_fields(
+    mut entry: HashMap<String, String>,
+    replaced_fields: &[(String, String)],
+) -> HashMap<String, String> {
+    for field in [
+        ERROR_FIELD,
+        TABLE_FIELD,
+        OPERATION_FIELD,
+        SOURCE_ID_FIELD,
+        FAILED_AT_FIELD,
+        REPLAYED_FIELD,
+    ] {
+        entry.remove(field);
+    }
+    entry.extend(replaced_fields.iter().cloned());
+    entry.insert(REPLAYED_FIELD.to_string(), true.to_string());
+    entry
+}
+
+impl Store {
+    #[inline(always)]
+    pub(crate) fn get_dead_letter_stream_name(&self, stream_index: u8) -> String {
+        dead_letter_stream(&self.get_drainer_stream_name(stream_index))
+    }
+
+    /// Appends the entry read from the drainer stream to its dead letter stream, along with the
+    /// reason why it could not be drained, and holds the subsequent entries of its global ID
+    #[router_env::instrument(skip_all)]
+    pub async fn move_to_dead_letter(
+        &self,
+        stream_name: &str,
+        entry_id: &str,
+        entry: HashMap<String, String>,
+        failure: Failure<'_>,
+    ) -> errors::DrainerResult<()> {
+        let global_id = entry.get(GLOBAL_ID_FIELD).cloned();
+        let mut fields = entry.into_iter().collect::<Vec<_>>();
+        fields.extend([
+            (ERROR_FIELD.to_string(), failure.error),
+            (TABLE_FIELD.to_string(), failure.table.to_string()),
+            (OPERATION_FIELD.to_string(), failure.operation.to_string()),
+            (SOURCE_ID_FIELD.to_string(), entry_id.to_string()),
+            (
+                FAILED_AT_FIELD.to_string(),
+                common_utils::date_time::now_unix_timestamp().to_string(),
+            ),
+        ]);
+
+        self.redis_conn
+            .stream_append_entry(
+                &dead_letter_stream(stream_name),
+                &RedisEntryId::AutoGeneratedID,
+                fields,
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        if let Some(global_id) = global_id {
+            self.redis_conn
+                .sadd(&held_entries_key(stream_name, &global_id), entry_id)
+                .await
+                .map_err(errors::DrainerError::from)?;
+        }
+
+        metrics::ENTRIES_DEAD_LETTERED.add(
+            &metrics::CONTEXT,
+            1,
+            &[
+                metrics::KeyValue::new("operation", failure.operation.to_owned()),
+                metrics::KeyValue::new("table", failure.table.to_owned()),
+            ],
+        );
+
+        Ok(())
+    }
+
+    pub async fn has_dead_letters(&self, stream_name: &str) -> errors::DrainerResult<bool> {
+        Ok(self
+            .redis_conn
+            .stream_get_length(&dead_letter_stream(stream_name))
+            .await
+            .map_err(errors::DrainerError::from)?
+            > 0)
+    }
+
+    /// Whether the entries of the global ID are held behind its dead letter entries
+    pub async fn is_held(&self, stream_name: &str, global_id: &str) -> errors::DrainerResult<bool> {
+        Ok(self
+            .redis_conn
+            .exists::<Vec<u8>>(&held_entries_key(stream_name, global_id))
+            .await
+            .map_err(errors::DrainerError::from)?)
+    }
+
+    /// Releases the hold of the dead letter entry on the subsequent entries of its global ID
+    async fn release_held_entry(
+        &self,
+        stream_index: u8,
+        entry: &HashMap<String, String>,
+    ) -> errors::DrainerResult<()> {
+        if let (Some(global_id), Some(source_id)) =
+            (entry.get(GLOBAL_ID_FIELD), entry.get(SOURCE_ID_FIELD))
+        {
+            self.redis_conn
+                .srem(
+                    &held_entries_key(&self.get_drainer_stream_name(stream_index), global_id),
+                    source_id.as_str(),
+                )
+                .await
+                .map_err(errors::DrainerError::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the entries of the dead letter stream of the shard, oldest first
+    pub async fn read_dead_letters(
+        &self,
+        stream_index: u8,
+        count: Option<u64>,
+    ) -> errors::DrainerResult<StreamEntries> {
+        Ok(self
+            .redis_conn
+            .stream_read_range(
+                &self.get_dead_letter_stream_name(stream_index),
+                "-",
+                "+",
+                count,
+            )
+            .await
+            .map_err(errors::DrainerError::from)?)
+    }
+
+    async fn get_dead_letter(
+        &self,
+        stream_index: u8,
+        entry_id: &str,
+    ) -> errors::DrainerResult<HashMap<String, String>> {
+        self.redis_conn
+            .stream_read_range(
+                &self.get_dead_letter_stream_name(stream_index),
+                entry_id,
+                entry_id,
+                Some(1),
+            )
+            .await
+            .map_err(errors::DrainerError::from)?
+            .pop()
+            .map(|(_, entry)| entry)
+            .ok_or_else(|| {
+                report!(errors::DrainerError::UnexpectedError(format!(
+                    "Dead letter entry {entry_id} was not found"
+                )))
+            })
+    }
+
+    /// Appends the entry, with the specified fields replaced, back to the drainer stream of the
+    /// shard and removes it from the dead letter stream. The dead letter entries of a global ID
+    /// have to be replayed in the order they were appended to the drainer stream.
+    pub async fn replay_dead_letter(
+        &self,
+        stream_index: u8,
+        entry_id: &str,
+        replaced_fields: &[(String, String)],
+    ) -> errors::DrainerResult<()> {
+        let entry = self.get_dead_letter(stream_index, entry_id).await?;
+
+        if let (Some(global_id), Some(source_id)) =
+            (entry.get(GLOBAL_ID_FIELD), entry.get(SOURCE_ID_FIELD))
+        {
+            let held_entries = self
+                .redis_conn
+                .smembers(&held_entries_key(
+                    &self.get_drainer_stream_name(stream_index),
+                    global_id,
+                ))
+                .await
+                .map_err(errors::DrainerError::from)?;
+
+            if let Some(older_entry) = find_older_held_entry(&held_entries, source_id) {
+                return Err(report!(errors::DrainerError::UnexpectedError(format!(
+                    "Dead letter entry {entry_id} is held behind the entry {older_entry} of the \
+                     drainer stream, which has to be replayed or discarded first"
+                ))));
+            }
+        }
+
+        let replay_fields = get_replay_fields(entry.clone(), replaced_fields);
+
+        // Entries that still cannot be deserialized would only end up in the dead letter stream
+        // again
+        StreamData::from_hashmap(replay_fields.clone())
+            .map_err(errors::DrainerError::ParsingError)
+            .attach_printable_lazy(|| format!("Dead letter entry {entry_id} is not valid"))?;
+
+        self.redis_conn
+            .stream_append_entry(
+                &self.get_drainer_stream_name(stream_index),
+                &RedisEntryId::AutoGeneratedID,
+                replay_fields.into_iter().collect::<Vec<_>>(),
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        self.remove_dead_letter(stream_index, entry_id, &entry)
+            .await
+    }
+
+    pub async fn discard_dead_letter(
+        &self,
+        stream_index: u8,
+        entry_id: &str,
+    ) -> errors::DrainerResult<()> {
+        let entry = self.get_dead_letter(stream_index, entry_id).await?;
+        self.remove_dead_letter(stream_index, entry_id, &entry)
+            .await
+    }
+
+    async fn remove_dead_letter(
+        &self,
+        stream_index: u8,
+        entry_id: &str,
+        entry: &HashMap<String, String>,
+    ) -> errors::DrainerResult<()> {
+        self.redis_conn
+            .stream_delete_entries(&self.get_dead_letter_stream_name(stream_index), entry_id)
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        self.release_held_entry(stream_index, entry).await
+    }
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum DeadLetterCommand {
+    /// List the entries in the dead letter stream of a shard
+    List {
+        #[command(flatten)]
+        shard: ShardArgs,
+        /// Maximum number of entries to list
+        #[arg(long, default_value_t = 100)]
+        count: u64,
+    },
+    /// Append entries back to the drainer stream of the shard, optionally replacing their fields
+    Replay {
+        #[command(flatten)]
+        shard: ShardArgs,
+        #[command(flatten)]
+        entries: EntryArgs,
+        /// Field of the entries to replace, the value is read from a file if it starts with `@`
+        #[arg(long = "set", value_name = "FIELD=VALUE", value_parser = parse_field)]
+        replaced_fields: Vec<(String, String)>,
+    },
+    /// Remove entries from the dead letter stream of the shard without draining them
+    Discard {
+        #[command(flatten)]
+        shard: ShardArgs,
+        #[command(flatten)]
+        entries: EntryArgs,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ShardArgs {
+    /// Tenant whose drainer stream is used
+    #[arg(long)]
+    tenant: String,
+    /// Index of the shard of the drainer stream
+    #[arg(long)]
+    shard: u8,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct EntryArgs {
+    /// ID of an entry in the dead letter stream
+    #[arg(long = "id", required_unless_present = "all")]
+    ids: Vec<String>,
+    /// Select all the entries in the dead letter stream
+    #[arg(long, conflicts_with = "ids")]
+    all: bool,
+}
+
+fn parse_field(field: &str) -> Result<(String, String), String> {
+    let (name, value) = field
+        .split_once('=')
+        .ok_or_else(|| format!("expected FIELD=VALUE, got {field}"))?;
+
+    let value = match value.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {path}: {error}"))?,
+        None => value.to_string(),
+    };
+
+    Ok((name.to_string(), value))
+}
+
+impl EntryArgs {
+    async fn get_entry_ids(
+        self,
+        store: &Store,
+        stream_index: u8,
+    ) -> errors::DrainerResult<Vec<String>> {
+        if self.all {
+            Ok(store
+                .read_dead_letters(stream_index, None)
+                .await?
+                .into_iter()
+                .map(|(entry_id, _)| entry_id)
+                .collect())
+        } else {
+            Ok(self.ids)
+        }
+    }
+}
+
+fn get_store(
+    stores: &HashMap<String, Arc<Store>>,
+    shard: &ShardArgs,
+) -> errors::DrainerResult<Arc<Store>> {
+    let store = stores.get(&shard.tenant).cloned().ok_or_else(|| {
+        report!(errors::DrainerError::UnexpectedError(format!(
+            "Tenant {} is not configured",
+            shard.tenant
+        )))
+    })?;
+
+    common_utils::fp_utils::when(shard.shard >= store.config.drainer_num_partitions, || {
+        Err(report!(errors::DrainerError::UnexpectedError(format!(
+            "Shard must be less than {}",
+            store.config.drainer_num_partitions
+        ))))
+    })?;
+
+    Ok(store)
+}
+
+/// Runs the `dead-letter` subcommand, printing its output to the standard output
+#[allow(clippy::print_stdout)]
+pub async fn run_command(
+    stores: &HashMap<String, Arc<Store>>,
+    command: DeadLetterCommand,
+) -> errors::DrainerResult<()> {
+    match command {
+        DeadLetterCommand::List { shard, count } => {
+            let store = get_store(stores, &shard)?;
+            for (entry_id, entry) in store.read_dead_letters(shard.shard, Some(count)).await? {
+                let entry = serde_json::json!({ "id": entry_id, "fields": entry });
+                println!("{entry}");
+            }
+        }
+        DeadLetterCommand::Replay {
+            shard,
+            entries,
+            replaced_fields,
+        } => {
+            let store = get_store(stores, &shard)?;
+            for entry_id in entries.get_entry_ids(&store, shard.shard).await? {
+                match store
+                    .replay_dead_letter(shard.shard, &entry_id, &replaced_fields)
+                    .await
+                {
+                    Ok(()) => println!("Replayed {entry_id}"),
+                    Err(error) => println!("Failed to replay {entry_id}: {error:?}"),
+                }
+            }
+        }
+        DeadLetterCommand::Discard { shard, entries } => {
+            let store = get_store(stores, &shard)?;
+            for entry_id in entries.get_entry_ids(&store, shard.shard).await? {
+                store.discard_dead_letter(shard.shard, &entry_id).await?;
+                println!("Discarded {entry_id}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn database_error(kind: DatabaseErrorKind) -> Report<DatabaseError> {
+        report!(DieselError::DatabaseError(
+            kind,
+            Box::new("violates a constraint".to_string())
+        ))
+        .change_context(DatabaseError::Others)
+    }
+
+    #[test]
+    fn test_dead_letter_failures() {
+        assert!(is_dead_letter_failure(&report!(DatabaseError::NotFound)));
+        assert!(is_dead_letter_failure(&report!(
+            DatabaseError::QueryGenerationFailed
+        )));
+        assert!(!is_dead_letter_failure(&report!(DatabaseError::Others)));
+        assert!(!is_dead_letter_failure(&report!(
+            DatabaseError::DatabaseConnectionError
+        )));
+    }
+
+    #[test]
+    fn test_constraint_violations_are_dead_letter_failures() {
+        assert!(is_dead_letter_failure(&database_error(
+            DatabaseErrorKind::ForeignKeyViolation
+        )));
+        assert!(is_dead_letter_failure(&database_error(
+            DatabaseErrorKind::NotNullViolation
+        )));
+        assert!(is_dead_letter_failure(&database_error(
+            DatabaseErrorKind::CheckViolation
+        )));
+        assert!(!is_dead_letter_failure(&database_error(
+            DatabaseErrorKind::SerializationFailure
+        )));
+        assert!(!is_dead_letter_failure(&database_error(
+            DatabaseErrorKind::ClosedConnection
+        )));
+    }
+
+    #[test]
+    fn test_older_held_entries_are_found_in_stream_order() {
+        let held_entries = vec!["999-5".to_string(), "1700000000000-1".to_string()];
+
+        assert_eq!(
+            find_older_held_entry(&held_entries, "1700000000000-0"),
+            Some(&"999-5".to_string())
+        );
+        assert_eq!(find_older_held_entry(&held_entries, "999-5"), None);
+        assert_eq!(find_older_held_entry(&held_entries, "invalid"), None);
+    }
+
+    #[test]
+    fn test_replay_fields() {
+        let entry = fields(&[
+            ("global_id", "payment_1"),
+            ("typed_sql", "{}"),
+            (ERROR_FIELD, "error"),
+            (TABLE_FIELD, "payment_attempt"),
+            (OPERATION_FIELD, "update"),
+            (SOURCE_ID_FIELD, "1700000000000-0"),
+            (FAILED_AT_FIELD, "1700000000"),
+        ]);
+        assert!(!is_replayed(&entry));
+
+        let replay_fields = get_replay_fields(
+            entry,
+            &[("typed_sql".to_string(), "{\"fixed\":true}".to_string())],
+        );
+        assert!(is_replayed(&replay_fields));
+        assert_eq!(
+            replay_fields,
+            fields(&[
+                ("global_id", "payment_1"),
+                ("typed_sql", "{\"fixed\":true}"),
+                (REPLAYED_FIELD, "true"),
+            ])
+        );
+    }
+}
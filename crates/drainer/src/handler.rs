@@ -10,8 +10,8 @@ use tokio::{
 };
 
 use crate::{
-    errors, instrument, logger, metrics, query::ExecuteQuery, tracing, utils, DrainerSettings,
-    Store, StreamData,
+    dead_letter, errors, instrument, logger, metrics, query::ExecuteQuery, tracing, utils,
+    DrainerSettings, Store, StreamData,
 };
 
 /// Handler handles the spawning and closing of drainer
@@ -244,8 +244,11 @@ async fn drainer(
 
     let mut last_processed_id = String::new();
 
+    // The entries of a global ID are held only while the dead letter stream has entries
+    let has_dead_letters = store.has_dead_letters(stream_name).await?;
+
     for (entry_id, entry) in entries.clone() {
-        let data = match StreamData::from_hashmap(entry) {
+        let data = match StreamData::from_hashmap(entry.clone()) {
             Ok(data) => data,
             Err(err) => {
                 logger::error!(operation = "deserialization", err=?err);
@@ -258,11 +261,44 @@ async fn drainer(
                     }],
                 );
 
-                // break from the loop in case of a deser error
+                let failure = dead_letter::Failure {
+                    error: format!("{err:?}"),
+                    table: dead_letter::UNKNOWN,
+                    operation: dead_letter::UNKNOWN,
+                };
+                // break from the loop if the entry could not be moved out of the way
+                if move_to_dead_letter(&store, stream_name, &entry_id, entry, failure).await {
+                    last_processed_id = entry_id;
+                    continue;
+                }
                 break;
             }
         };
 
+        let table = data.typed_sql.table();
+        let operation = data.typed_sql.operation();
+
+        // Entries appended after a dead letter entry of the same global ID are not drained until
+        // that entry is replayed or discarded, so that updates are not applied out of order
+        if has_dead_letters && !dead_letter::is_replayed(&entry) {
+            match store.is_held(stream_name, &data.global_id).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    let failure = dead_letter::get_held_failure(table, operation);
+                    // break from the loop if the entry could not be moved out of the way
+                    if !move_to_dead_letter(&store, stream_name, &entry_id, entry, failure).await {
+                        break;
+                    }
+                    last_processed_id = entry_id;
+                    continue;
+                }
+                Err(err) => {
+                    logger::error!(%entry_id, ?err, "Failed to check whether the entry is held");
+                    break;
+                }
+            }
+        }
+
         tracing::Span::current().record("request_id", data.request_id);
         tracing::Span::current().record("global_id", data.global_id);
         tracing::Span::current().record("session_id", &session_id);
@@ -277,8 +313,21 @@ async fn drainer(
                 diesel_models::errors::DatabaseError::UniqueViolation => {
                     last_processed_id = entry_id;
                 }
-                // break from the loop in case of an error in query
-                _ => break,
+                // The entry is retried in the next session, unless the query failed for a reason
+                // that would not change on retrying it
+                _ if !dead_letter::is_dead_letter_failure(&err) => break,
+                _ => {
+                    let failure = dead_letter::Failure {
+                        error: format!("{err:?}"),
+                        table,
+                        operation,
+                    };
+                    // break from the loop if the entry could not be moved out of the way
+                    if !move_to_dead_letter(&store, stream_name, &entry_id, entry, failure).await {
+                        break;
+                    }
+                    last_processed_id = entry_id;
+                }
             },
         }
     }
@@ -301,3 +350,26 @@ async fn drainer(
 
     Ok(())
 }
+
+/// Moves an entry that could not be drained to the dead letter stream, so that the entries after
+/// it can be drained. Returns whether the entry was moved.
+async fn move_to_dead_letter(
+    store: &Store,
+    stream_name: &str,
+    entry_id: &str,
+    entry: HashMap<String, String>,
+    failure: dead_letter::Failure<'_>,
+) -> bool {
+    logger::warn!(
+        %entry_id,
+        table = failure.table,
+        operation = failure.operation,
+        "Moving entry to the dead letter stream"
+    );
+
+    store
+        .move_to_dead_letter(stream_name, entry_id, entry, failure)
+        .await
+        .inspect_err(|err| logger::error!(operation = "dead_letter", err=?err))
+        .is_ok()
+}
@@ -1,4 +1,5 @@
 mod connection;
+pub mod dead_letter;
 pub mod errors;
 mod handler;
 mod health_check;
@@ -26,9 +27,7 @@ use tokio::sync::mpsc;
 
 pub(crate) type Settings = settings::Settings<RawSecret>;
 
-use crate::{
-    connection::pg_connection, services::Store, settings::DrainerSettings, types::StreamData,
-};
+use crate::{services::Store, settings::DrainerSettings, types::StreamData};
 
 pub async fn start_drainer(
     stores: HashMap<String, Arc<Store>>,
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
 use drainer::{
-    errors::DrainerResult, logger::logger, services, settings, start_drainer, start_web_server,
+    dead_letter, errors::DrainerResult, logger::logger, services, settings, start_drainer,
+    start_web_server,
 };
 use router_env::tracing::Instrument;
 
@@ -25,6 +26,10 @@ async fn main() -> DrainerResult<()> {
         stores.insert(tenant_name.clone(), store);
     }
 
+    if let Some(settings::Command::DeadLetter(command)) = cmd_line.command {
+        return dead_letter::run_command(&stores, command).await;
+    }
+
     #[allow(clippy::print_stdout)] // The logger has not yet been initialized
     #[cfg(feature = "vergen")]
     {
@@ -47,11 +47,15 @@ fn get_program_data() -> (ast::Program<DummyOutput>, inputs::BackendInput) {
             billing_country: Some(enums::Country::France),
             business_label: None,
             setup_future_usage: None,
+            time_of_day: None,
+            day_of_week: None,
         },
         payment_method: inputs::PaymentMethodInput {
             payment_method: Some(enums::PaymentMethod::PayLater),
             payment_method_type: Some(enums::PaymentMethodType::Sofort),
             card_network: None,
+            card_issuer: None,
+            card_issuer_country: None,
         },
         mandate: inputs::MandateData {
             mandate_acceptance_type: None,
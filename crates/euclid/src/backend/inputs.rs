@@ -15,6 +15,8 @@ pub struct PaymentMethodInput {
     pub payment_method: Option<enums::PaymentMethod>,
     pub payment_method_type: Option<enums::PaymentMethodType>,
     pub card_network: Option<enums::CardNetwork>,
+    pub card_issuer: Option<String>,
+    pub card_issuer_country: Option<enums::Country>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +30,9 @@ pub struct PaymentInput {
     pub billing_country: Option<enums::Country>,
     pub business_label: Option<String>,
     pub setup_future_usage: Option<enums::SetupFutureUsage>,
+    /// Time of the payment in the timezone of the merchant, as a 24 hour HHMM number
+    pub time_of_day: Option<i64>,
+    pub day_of_week: Option<enums::DayOfWeek>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
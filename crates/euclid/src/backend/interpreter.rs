@@ -80,12 +80,38 @@ where
         }
     }
 
+    fn eval_condition(
+        condition: &ast::Condition,
+        ctx: &types::Context,
+    ) -> Result<bool, types::InterpreterError> {
+        match condition {
+            ast::Condition::Comparison(comparison) => Self::eval_comparison(comparison, ctx),
+            ast::Condition::Group(ast::ConditionGroup::AllOf(conditions)) => {
+                Self::eval_if_condition(conditions, ctx)
+            }
+            ast::Condition::Group(ast::ConditionGroup::AnyOf(conditions)) => {
+                for condition in conditions {
+                    let res = Self::eval_condition(condition, ctx)?;
+
+                    if res {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            ast::Condition::Group(ast::ConditionGroup::Not(condition)) => {
+                Self::eval_condition(condition, ctx).map(|res| !res)
+            }
+        }
+    }
+
     fn eval_if_condition(
         condition: &ast::IfCondition,
         ctx: &types::Context,
     ) -> Result<bool, types::InterpreterError> {
-        for comparison in condition {
-            let res = Self::eval_comparison(comparison, ctx)?;
+        for condition in condition {
+            let res = Self::eval_condition(condition, ctx)?;
 
             if !res {
                 return Ok(false);
@@ -34,13 +34,26 @@ where
         }
     }
 
+    fn eval_condition(cond: &vir::ValuedCondition, ctx: &types::Context) -> bool {
+        match cond {
+            vir::ValuedCondition::Comparison(comp) => Self::eval_comparison(comp, ctx),
+            vir::ValuedCondition::AllOf(conds) => {
+                conds.iter().all(|cond| Self::eval_condition(cond, ctx))
+            }
+            vir::ValuedCondition::AnyOf(conds) => {
+                conds.iter().any(|cond| Self::eval_condition(cond, ctx))
+            }
+            vir::ValuedCondition::Not(cond) => !Self::eval_condition(cond, ctx),
+        }
+    }
+
     #[inline]
-    fn eval_condition(cond: &vir::ValuedIfCondition, ctx: &types::Context) -> bool {
-        cond.iter().all(|comp| Self::eval_comparison(comp, ctx))
+    fn eval_if_condition(cond: &vir::ValuedIfCondition, ctx: &types::Context) -> bool {
+        cond.iter().all(|cond| Self::eval_condition(cond, ctx))
     }
 
     fn eval_statement(stmt: &vir::ValuedIfStatement, ctx: &types::Context) -> bool {
-        Self::eval_condition(&stmt.condition, ctx)
+        Self::eval_if_condition(&stmt.condition, ctx)
             .then(|| {
                 stmt.nested.as_ref().map_or(true, |nested_stmts| {
                     nested_stmts.iter().any(|s| Self::eval_statement(s, ctx))
@@ -140,11 +153,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -180,11 +197,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -221,11 +242,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -262,11 +287,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -303,11 +332,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: Some(enums::MandateAcceptanceType::Online),
@@ -344,11 +377,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -385,11 +422,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -426,11 +467,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -467,11 +512,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: Some(enums::SetupFutureUsage::OffSession),
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -510,11 +559,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -551,11 +604,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -601,11 +658,15 @@ mod test {
                 billing_country: Some(enums::Country::France),
                 business_label: None,
                 setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
             },
             payment_method: inputs::PaymentMethodInput {
                 payment_method: Some(enums::PaymentMethod::PayLater),
                 payment_method_type: Some(enums::PaymentMethodType::Affirm),
                 card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
             },
             mandate: inputs::MandateData {
                 mandate_acceptance_type: None,
@@ -627,4 +688,145 @@ mod test {
             "rule_1"
         );
     }
+
+    #[test]
+    fn test_condition_groups() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"]
+        {
+           payment_method = pay_later & (amount > 1000 | currency = EUR) & !(capture_method = manual)
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let inp = inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(120),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                capture_method: Some(enums::CaptureMethod::Automatic),
+                business_country: Some(enums::Country::UnitedStatesOfAmerica),
+                billing_country: Some(enums::Country::France),
+                business_label: None,
+                setup_future_usage: None,
+                time_of_day: None,
+                day_of_week: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::PayLater),
+                payment_method_type: Some(enums::PaymentMethodType::Affirm),
+                card_network: None,
+                card_issuer: None,
+                card_issuer_country: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+        };
+        let mut inp_large_amount = inp.clone();
+        inp_large_amount.payment.amount = MinorUnit::new(1500);
+        let mut inp_eur = inp.clone();
+        inp_eur.payment.currency = enums::Currency::EUR;
+        let mut inp_eur_manual = inp_eur.clone();
+        inp_eur_manual.payment.capture_method = Some(enums::CaptureMethod::Manual);
+
+        let vir_backend =
+            VirInterpreterBackend::<DummyOutput>::with_program(program.clone()).expect("Program");
+        let ast_backend =
+            backend::interpreter::InterpreterBackend::<DummyOutput>::with_program(program)
+                .expect("Program");
+
+        for (input, expected_rule_name) in [
+            (inp, None),
+            (inp_large_amount, Some("rule_1")),
+            (inp_eur, Some("rule_1")),
+            (inp_eur_manual, None),
+        ] {
+            let vir_result = vir_backend.execute(input.clone()).expect("Execution");
+            let ast_result = ast_backend.execute(input).expect("Execution");
+            assert_eq!(vir_result.rule_name.as_deref(), expected_rule_name);
+            assert_eq!(ast_result.rule_name.as_deref(), expected_rule_name);
+        }
+    }
+
+    #[test]
+    fn test_time_and_issuer_keys() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"]
+        {
+           day_of_week = (saturday, sunday) & card_issuer_country = India
+        }
+
+        rule_2: ["adyen"]
+        {
+           time_of_day >= 1800 & card_issuer = "HDFC Bank"
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let inp = inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(120),
+                card_bin: None,
+                currency: enums::Currency::INR,
+                authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                capture_method: Some(enums::CaptureMethod::Automatic),
+                business_country: Some(enums::Country::India),
+                billing_country: Some(enums::Country::India),
+                business_label: None,
+                setup_future_usage: None,
+                time_of_day: Some(1200),
+                day_of_week: Some(enums::DayOfWeek::Monday),
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::Card),
+                payment_method_type: Some(enums::PaymentMethodType::Credit),
+                card_network: None,
+                card_issuer: Some("HDFC Bank".to_string()),
+                card_issuer_country: Some(enums::Country::India),
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+        };
+        let mut inp_weekend = inp.clone();
+        inp_weekend.payment.day_of_week = Some(enums::DayOfWeek::Saturday);
+        let mut inp_evening = inp.clone();
+        inp_evening.payment.time_of_day = Some(1930);
+        let mut inp_evening_other_issuer = inp_evening.clone();
+        inp_evening_other_issuer.payment_method.card_issuer = Some("Axis Bank".to_string());
+
+        let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+        for (input, expected_rule_name) in [
+            (inp, None),
+            (inp_weekend, Some("rule_1")),
+            (inp_evening, Some("rule_2")),
+            (inp_evening_other_issuer, None),
+        ] {
+            let result = backend.execute(input).expect("Execution");
+            assert_eq!(result.rule_name.as_deref(), expected_rule_name);
+        }
+
+        let invalid_program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"]
+        {
+           time_of_day < 2460
+        }
+        "#;
+        let (_, invalid_program) =
+            ast::parser::program::<DummyOutput>(invalid_program_str).expect("Program");
+        assert!(VirInterpreterBackend::<DummyOutput>::with_program(invalid_program).is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use common_utils::types::MinorUnit;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
@@ -78,6 +79,14 @@ impl Context {
             enum_values.insert(EuclidValue::CardNetwork(card_network));
         }
 
+        if let Some(card_issuer) = payment_method.card_issuer {
+            enum_values.insert(EuclidValue::CardIssuer(StrValue { value: card_issuer }));
+        }
+
+        if let Some(country) = payment_method.card_issuer_country {
+            enum_values.insert(EuclidValue::CardIssuerCountry(country));
+        }
+
         if let Some(at) = payment.authentication_type {
             enum_values.insert(EuclidValue::AuthenticationType(at));
         }
@@ -104,6 +113,9 @@ impl Context {
         if let Some(setup_future_usage) = payment.setup_future_usage {
             enum_values.insert(EuclidValue::SetupFutureUsage(setup_future_usage));
         }
+        if let Some(day_of_week) = payment.day_of_week {
+            enum_values.insert(EuclidValue::DayOfWeek(day_of_week));
+        }
         if let Some(payment_type) = payment_mandate.payment_type {
             enum_values.insert(EuclidValue::PaymentType(payment_type));
         }
@@ -114,7 +126,7 @@ impl Context {
             enum_values.insert(EuclidValue::MandateAcceptanceType(mandate_acceptance_type));
         }
 
-        let numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
+        let mut numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
             EuclidKey::PaymentAmount,
             EuclidValue::PaymentAmount(types::NumValue {
                 number: payment.amount,
@@ -122,6 +134,16 @@ impl Context {
             }),
         )]);
 
+        if let Some(time_of_day) = payment.time_of_day {
+            numeric_values.insert(
+                EuclidKey::TimeOfDay,
+                EuclidValue::TimeOfDay(types::NumValue {
+                    number: MinorUnit::new(time_of_day),
+                    refinement: None,
+                }),
+            );
+        }
+
         Self {
             atomic_values: enum_values,
             numeric_values,
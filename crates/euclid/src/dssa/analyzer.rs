@@ -18,7 +18,7 @@ use crate::{
         dir::{self, EuclidDirFilter},
         vir,
     },
-    types::{DataType, Metadata},
+    types::{DataType, Metadata, NumValue, NumValueRefinement},
 };
 
 /// Analyses conflicting assertions on the same key in a conjunctive context.
@@ -220,22 +220,712 @@ fn perform_context_analyses(
     Ok(())
 }
 
+/// The range of numbers satisfying the numeric conditions on a key, both bounds being inclusive
+#[derive(Debug, Clone, Default)]
+struct NumRange {
+    lower: Option<i64>,
+    upper: Option<i64>,
+    excluded: Vec<i64>,
+}
+
+impl NumRange {
+    fn from_num_value(value: &NumValue) -> Self {
+        let mut range = Self::default();
+        range.refine(value);
+        range
+    }
+
+    fn refine(&mut self, value: &NumValue) {
+        let number = value.number.get_amount_as_i64();
+        match value.refinement {
+            None => {
+                self.raise_lower(number);
+                self.reduce_upper(number);
+            }
+            Some(NumValueRefinement::GreaterThan) => self.raise_lower(number.saturating_add(1)),
+            Some(NumValueRefinement::GreaterThanEqual) => self.raise_lower(number),
+            Some(NumValueRefinement::LessThan) => self.reduce_upper(number.saturating_sub(1)),
+            Some(NumValueRefinement::LessThanEqual) => self.reduce_upper(number),
+            Some(NumValueRefinement::NotEqual) => self.excluded.push(number),
+        }
+    }
+
+    fn raise_lower(&mut self, bound: i64) {
+        self.lower = Some(self.lower.map_or(bound, |lower| lower.max(bound)));
+    }
+
+    fn reduce_upper(&mut self, bound: i64) {
+        self.upper = Some(self.upper.map_or(bound, |upper| upper.min(bound)));
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        let mut intersection = self.clone();
+        if let Some(lower) = other.lower {
+            intersection.raise_lower(lower);
+        }
+        if let Some(upper) = other.upper {
+            intersection.reduce_upper(upper);
+        }
+        intersection.excluded.extend_from_slice(&other.excluded);
+        intersection
+    }
+
+    fn contains_number(&self, number: i64) -> bool {
+        self.lower.map_or(true, |lower| lower <= number)
+            && self.upper.map_or(true, |upper| number <= upper)
+            && !self.excluded.contains(&number)
+    }
+
+    fn is_empty(&self) -> bool {
+        match (self.lower, self.upper) {
+            (Some(lower), Some(upper)) => {
+                lower > upper
+                    || (upper.saturating_sub(lower)
+                        < i64::try_from(self.excluded.len()).unwrap_or(i64::MAX)
+                        && (lower..=upper).all(|number| self.excluded.contains(&number)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks that every number in the other range is in this range
+    fn contains_range(&self, other: &Self) -> bool {
+        self.lower.map_or(true, |lower| {
+            other.lower.is_some_and(|other_lower| other_lower >= lower)
+        }) && self.upper.map_or(true, |upper| {
+            other.upper.is_some_and(|other_upper| other_upper <= upper)
+        }) && self
+            .excluded
+            .iter()
+            .all(|number| !other.contains_number(*number))
+    }
+}
+
+/// The conditions a conjunctive context holds on each key, used to check whether the conditions
+/// of another context hold whenever this context holds
+#[derive(Debug, Default)]
+struct ContextFacts<'a> {
+    assertions: FxHashMap<dir::DirKey, FxHashSet<&'a dir::DirValue>>,
+    negations: FxHashMap<dir::DirKey, FxHashSet<&'a dir::DirValue>>,
+    ranges: FxHashMap<dir::DirKey, NumRange>,
+}
+
+impl<'a> ContextFacts<'a> {
+    /// Negated numeric conditions are left out, so a context is only considered to imply the
+    /// conditions it provably implies
+    fn new(context: &[types::ContextValue<'a>]) -> Self {
+        let mut facts = Self::default();
+
+        for ctx_val in context {
+            match ctx_val.value {
+                types::CtxValueKind::Assertion(value) => match value.get_num_value() {
+                    Some(num_value) => facts
+                        .ranges
+                        .entry(value.get_key())
+                        .or_default()
+                        .refine(&num_value),
+                    None => {
+                        facts
+                            .assertions
+                            .entry(value.get_key())
+                            .or_default()
+                            .insert(value);
+                    }
+                },
+
+                types::CtxValueKind::Negation(values) => {
+                    for value in values.iter().filter(|val| val.get_num_value().is_none()) {
+                        facts
+                            .negations
+                            .entry(value.get_key())
+                            .or_default()
+                            .insert(value);
+                    }
+                }
+            }
+        }
+
+        facts
+    }
+
+    /// Returns a key on which the context holds contradicting conditions, in which case the
+    /// context can never hold
+    fn get_contradicting_key(&self) -> Option<&dir::DirKey> {
+        self.ranges
+            .iter()
+            .find(|(_, range)| range.is_empty())
+            .map(|(key, _)| key)
+            .or_else(|| {
+                self.assertions
+                    .iter()
+                    .find(|(key, values)| {
+                        values.len() > 1
+                            || self
+                                .negations
+                                .get(*key)
+                                .is_some_and(|negations| !negations.is_disjoint(values))
+                    })
+                    .map(|(key, _)| key)
+            })
+    }
+
+    fn implies(&self, value: &types::CtxValueKind<'_>) -> bool {
+        match value {
+            types::CtxValueKind::Assertion(value) => self.implies_assertion(value),
+            types::CtxValueKind::Negation(values) => {
+                values.iter().all(|value| self.implies_negation(value))
+            }
+        }
+    }
+
+    fn implies_assertion(&self, value: &dir::DirValue) -> bool {
+        let key = value.get_key();
+
+        match value.get_num_value() {
+            Some(num_value) => self
+                .ranges
+                .get(&key)
+                .is_some_and(|range| NumRange::from_num_value(&num_value).contains_range(range)),
+            None => {
+                self.assertions
+                    .get(&key)
+                    .is_some_and(|values| values.contains(value))
+                    || key
+                        .kind
+                        .get_value_set()
+                        .zip(self.negations.get(&key))
+                        .is_some_and(|(value_set, negations)| {
+                            value_set
+                                .iter()
+                                .all(|other| other == value || negations.contains(other))
+                        })
+            }
+        }
+    }
+
+    fn implies_negation(&self, value: &dir::DirValue) -> bool {
+        let key = value.get_key();
+
+        match value.get_num_value() {
+            Some(num_value) => self.ranges.get(&key).is_some_and(|range| {
+                range
+                    .intersect(&NumRange::from_num_value(&num_value))
+                    .is_empty()
+            }),
+            None => {
+                self.negations
+                    .get(&key)
+                    .is_some_and(|negations| negations.contains(value))
+                    || self
+                        .assertions
+                        .get(&key)
+                        .is_some_and(|values| values.iter().any(|asserted| *asserted != value))
+            }
+        }
+    }
+}
+
+/// Returns the conjunctive contexts of the rule that can hold, warning about the branches of the
+/// rule holding contradicting conditions that the condition analyses do not catch.
+///
+/// For example,
+/// ```notrust
+/// amount > 500 {
+///     amount < 100
+/// }
+/// ```notrust
+fn get_reachable_contexts<'a, O>(
+    rule: &'a dir::DirRule<O>,
+    warnings: &mut Vec<types::AnalysisWarning>,
+) -> Result<Vec<types::ConjunctiveContext<'a>>, types::AnalysisError> {
+    let mut contexts = Vec::new();
+    if rule.statements.is_empty() {
+        return Ok(contexts);
+    }
+
+    let mut unreachable_keys: FxHashSet<dir::DirKey> = FxHashSet::default();
+    let mut ctx_manager =
+        state_machine::RuleContextManager::new(rule, &[]).map_err(|err| types::AnalysisError {
+            metadata: Default::default(),
+            error_type: types::AnalysisErrorType::StateMachine(err),
+        })?;
+    while let Some(ctx) = ctx_manager.advance().map_err(|err| types::AnalysisError {
+        metadata: Default::default(),
+        error_type: types::AnalysisErrorType::StateMachine(err),
+    })? {
+        match ContextFacts::new(ctx).get_contradicting_key() {
+            Some(key) => {
+                if unreachable_keys.insert(key.clone()) {
+                    warnings.push(types::AnalysisWarning::UnreachableBranch {
+                        rule_name: rule.name.clone(),
+                        key: key.clone(),
+                        metadata: ctx
+                            .iter()
+                            .filter(|ctx_val| ctx_val.value.get_key().as_ref() == Some(key))
+                            .map(|ctx_val| ctx_val.metadata.clone())
+                            .collect(),
+                    });
+                }
+            }
+
+            None => contexts.push(ctx.clone()),
+        }
+    }
+
+    Ok(contexts)
+}
+
+/// The values of a single key for which an earlier context matches the payments matched by a
+/// later context
+enum KeyCoverage {
+    /// The numbers in the range
+    Range(NumRange),
+    /// The enum variants in the set, or every variant if `None`
+    Variants(Option<FxHashSet<dir::DirValue>>),
+}
+
+/// Returns the values of the key for which the earlier context matches the payments the facts
+/// hold for. Returns `None` if the facts do not imply the conditions of the earlier context on
+/// the other keys, or if its conditions on the key cannot be represented.
+fn get_key_coverage(
+    facts: &ContextFacts<'_>,
+    earlier_context: &types::ConjunctiveContext<'_>,
+    key: &dir::DirKey,
+) -> Option<KeyCoverage> {
+    let mut range = NumRange::default();
+    let mut assertions: FxHashSet<&dir::DirValue> = FxHashSet::default();
+    let mut negations: FxHashSet<&dir::DirValue> = FxHashSet::default();
+
+    for ctx_val in earlier_context {
+        match ctx_val.value {
+            types::CtxValueKind::Assertion(value) if value.get_key() == *key => {
+                match value.get_num_value() {
+                    Some(num_value) => range.refine(&num_value),
+                    None => {
+                        assertions.insert(value);
+                    }
+                }
+            }
+            types::CtxValueKind::Assertion(value) => {
+                if !facts.implies_assertion(value) {
+                    return None;
+                }
+            }
+            types::CtxValueKind::Negation(values) => {
+                for value in values {
+                    if value.get_key() != *key {
+                        if !facts.implies_negation(value) {
+                            return None;
+                        }
+                    } else if value.get_num_value().is_some() {
+                        return None;
+                    } else {
+                        negations.insert(value);
+                    }
+                }
+            }
+        }
+    }
+
+    match key.kind.get_type() {
+        DataType::Number => range
+            .excluded
+            .is_empty()
+            .then_some(KeyCoverage::Range(range)),
+        DataType::EnumVariant => {
+            let variants = if assertions.is_empty() {
+                match key.kind.get_value_set() {
+                    Some(value_set) => Some(value_set),
+                    None if negations.is_empty() => None,
+                    None => return None,
+                }
+            } else {
+                Some(assertions.into_iter().cloned().collect())
+            };
+
+            Some(KeyCoverage::Variants(variants.map(|variants| {
+                variants
+                    .into_iter()
+                    .filter(|variant| !negations.contains(&variant))
+                    .collect()
+            })))
+        }
+        DataType::MetadataValue | DataType::StrValue => None,
+    }
+}
+
+/// Returns the earlier rules whose ranges together cover every number in the target range
+fn get_covering_ranges<'r>(
+    target: &NumRange,
+    ranges: &[(&'r str, NumRange)],
+) -> Option<Vec<&'r str>> {
+    let lower_bound = |range: &NumRange| range.lower.unwrap_or(i64::MIN);
+    let upper_bound = |range: &NumRange| range.upper.unwrap_or(i64::MAX);
+
+    let mut covering_rules = Vec::new();
+    let mut next = lower_bound(target);
+    while next <= upper_bound(target) {
+        let covered_upto = if target.excluded.contains(&next) {
+            next
+        } else {
+            // The range reaching the furthest from the smallest number yet to be covered
+            let (rule_name, range) = ranges
+                .iter()
+                .filter(|(_, range)| range.contains_number(next))
+                .min_by_key(|(_, range)| std::cmp::Reverse(upper_bound(range)))?;
+            if !covering_rules.contains(rule_name) {
+                covering_rules.push(*rule_name);
+            }
+            upper_bound(range)
+        };
+
+        match covered_upto.checked_add(1) {
+            Some(number) => next = number,
+            None => break,
+        }
+    }
+
+    Some(covering_rules)
+}
+
+/// Returns the earlier rules whose contexts together match every payment the facts hold for,
+/// splitting the payments on the values of a single numeric or enum key. Each earlier context
+/// used is to hold conditions on the other keys that are implied by the facts.
+///
+/// For example, `amount > 50` is matched by `amount < 100` and `amount >= 100` together.
+fn get_covering_rules(
+    facts: &ContextFacts<'_>,
+    earlier_rules: &[(&str, Vec<types::ConjunctiveContext<'_>>)],
+) -> Option<Vec<String>> {
+    let mut keys: Vec<dir::DirKey> = Vec::new();
+    for (_, earlier_contexts) in earlier_rules {
+        for ctx_val in earlier_contexts.iter().flatten() {
+            let values = match ctx_val.value {
+                types::CtxValueKind::Assertion(value) => std::slice::from_ref(value),
+                types::CtxValueKind::Negation(values) => values,
+            };
+            for key in values.iter().map(dir::DirValue::get_key) {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+
+    keys.iter().find_map(|key| {
+        let coverages = earlier_rules
+            .iter()
+            .flat_map(|(rule_name, earlier_contexts)| {
+                earlier_contexts.iter().filter_map(move |earlier_context| {
+                    get_key_coverage(facts, earlier_context, key)
+                        .map(|coverage| (*rule_name, coverage))
+                })
+            });
+
+        let covering_rules = match key.kind.get_type() {
+            DataType::Number => {
+                let ranges: Vec<(&str, NumRange)> = coverages
+                    .filter_map(|(rule_name, coverage)| match coverage {
+                        KeyCoverage::Range(range) => Some((rule_name, range)),
+                        KeyCoverage::Variants(_) => None,
+                    })
+                    .collect();
+                let target = facts.ranges.get(key).cloned().unwrap_or_default();
+
+                get_covering_ranges(&target, &ranges)?
+            }
+            DataType::EnumVariant => {
+                let variants: Vec<(&str, Option<FxHashSet<dir::DirValue>>)> = coverages
+                    .filter_map(|(rule_name, coverage)| match coverage {
+                        KeyCoverage::Variants(variants) => Some((rule_name, variants)),
+                        KeyCoverage::Range(_) => None,
+                    })
+                    .collect();
+                let target: Vec<dir::DirValue> = match facts.assertions.get(key) {
+                    Some(values) => values.iter().map(|value| (*value).clone()).collect(),
+                    None => key
+                        .kind
+                        .get_value_set()?
+                        .into_iter()
+                        .filter(|value| {
+                            facts
+                                .negations
+                                .get(key)
+                                .map_or(true, |negations| !negations.contains(&value))
+                        })
+                        .collect(),
+                };
+
+                let mut covering_rules = Vec::new();
+                for value in &target {
+                    let (rule_name, _) = variants.iter().find(|(_, variants)| {
+                        variants
+                            .as_ref()
+                            .map_or(true, |variants| variants.contains(value))
+                    })?;
+                    if !covering_rules.contains(rule_name) {
+                        covering_rules.push(*rule_name);
+                    }
+                }
+                covering_rules
+            }
+            DataType::MetadataValue | DataType::StrValue => return None,
+        };
+
+        Some(
+            earlier_rules
+                .iter()
+                .map(|(rule_name, _)| *rule_name)
+                .filter(|rule_name| covering_rules.contains(rule_name))
+                .map(String::from)
+                .collect(),
+        )
+    })
+}
+
+/// Returns the earlier rules shadowing a rule, if every payment matched by the rule is matched by
+/// an earlier rule. Since the rules are tried in order, a shadowed rule can never match.
+///
+/// A context of the rule is considered matched by an earlier rule when it implies every condition
+/// of a single context of the earlier rule. Otherwise, it is considered matched by the earlier
+/// rules when their contexts together cover every value of a single numeric or enum key the
+/// context allows, as found by [`get_covering_rules`].
+fn get_shadowing_rules(
+    contexts: &[types::ConjunctiveContext<'_>],
+    earlier_rules: &[(&str, Vec<types::ConjunctiveContext<'_>>)],
+) -> Option<Vec<String>> {
+    let mut shadowing_rules: Vec<String> = Vec::new();
+
+    for context in contexts {
+        let facts = ContextFacts::new(context);
+        let rule_names = match earlier_rules.iter().find(|(_, earlier_contexts)| {
+            earlier_contexts.iter().any(|earlier_context| {
+                earlier_context
+                    .iter()
+                    .all(|ctx_val| facts.implies(&ctx_val.value))
+            })
+        }) {
+            Some((rule_name, _)) => vec![rule_name.to_string()],
+            None => get_covering_rules(&facts, earlier_rules)?,
+        };
+
+        for rule_name in rule_names {
+            if !shadowing_rules.contains(&rule_name) {
+                shadowing_rules.push(rule_name);
+            }
+        }
+    }
+
+    Some(shadowing_rules)
+}
+
+/// Analyses the outputs across which the payments matching a rule are split, warning about the
+/// outputs the knowledge graph makes ineligible in every context of the rule. Payments split to
+/// such an output can never be processed by it.
+fn analyze_split_outputs(
+    rule_name: &str,
+    contexts: &[types::ConjunctiveContext<'_>],
+    split_selection_data: &[(dir::DirValue, Metadata)],
+    knowledge_graph: &ConstraintGraph<dir::DirValue>,
+    warnings: &mut Vec<types::AnalysisWarning>,
+) {
+    // Contexts that are invalid by themselves say nothing about the eligibility of the outputs
+    let valid_contexts: Vec<&types::ConjunctiveContext<'_>> = contexts
+        .iter()
+        .filter(|context| {
+            knowledge_graph
+                .perform_context_analysis(context, &mut Memoization::new(), None)
+                .is_ok()
+        })
+        .collect();
+
+    if valid_contexts.is_empty() {
+        return;
+    }
+
+    for (output, metadata) in split_selection_data {
+        let is_eligible = valid_contexts.iter().any(|context| {
+            let mut context: types::ConjunctiveContext<'_> = (*context).clone();
+            context.push(types::ContextValue::assertion(output, metadata));
+            knowledge_graph
+                .perform_context_analysis(&context, &mut Memoization::new(), None)
+                .is_ok()
+        });
+
+        if !is_eligible {
+            warnings.push(types::AnalysisWarning::IneligibleOutput {
+                rule_name: rule_name.to_string(),
+                output: output.clone(),
+                metadata: metadata.clone(),
+            });
+        }
+    }
+}
+
+/// Analyses the rules of the program for conditions that are valid, but do not behave the way
+/// they were likely meant to. These are
+/// - branches of a rule that can never match, like `amount > 500 & amount < 100`
+/// - rules that can never match, since every payment they match is matched by an earlier rule
+/// - outputs a rule splits payments across, that the knowledge graph makes ineligible for every
+///   payment matched by the rule
+pub fn analyze_warnings<O: EuclidAnalysable>(
+    program: &dir::DirProgram<O>,
+    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
+) -> Result<Vec<types::AnalysisWarning>, types::AnalysisError> {
+    let knowledge_graph = knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH);
+    let split_selection_data = state_machine::make_split_selection_data(program);
+
+    let mut warnings = Vec::new();
+    let mut earlier_rules: Vec<(&str, Vec<types::ConjunctiveContext<'_>>)> = Vec::new();
+
+    for (rule, split_selection_data) in program.rules.iter().zip(split_selection_data.iter()) {
+        let contexts = get_reachable_contexts(rule, &mut warnings)?;
+
+        if !contexts.is_empty() {
+            if let Some(shadowing_rules) = get_shadowing_rules(&contexts, &earlier_rules) {
+                warnings.push(types::AnalysisWarning::ShadowedRule {
+                    rule_name: rule.name.clone(),
+                    shadowing_rules,
+                });
+            }
+
+            analyze_split_outputs(
+                &rule.name,
+                &contexts,
+                split_selection_data,
+                knowledge_graph,
+                &mut warnings,
+            );
+        }
+
+        earlier_rules.push((rule.name.as_str(), contexts));
+    }
+
+    Ok(warnings)
+}
+
 pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
     program: ast::Program<O>,
     knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
 ) -> Result<vir::ValuedProgram<O>, types::AnalysisError> {
+    analyze_with_warnings(program, knowledge_graph).map(|(program, _)| program)
+}
+
+/// Analyses the program like [`analyze`], additionally returning the warnings of
+/// [`analyze_warnings`]
+pub fn analyze_with_warnings<O: EuclidAnalysable + EuclidDirFilter>(
+    program: ast::Program<O>,
+    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
+) -> Result<(vir::ValuedProgram<O>, Vec<types::AnalysisWarning>), types::AnalysisError> {
     let dir_program = ast::lowering::lower_program(program)?;
 
     let selection_data = state_machine::make_connector_selection_data(&dir_program);
-    let mut ctx_manager = state_machine::AnalysisContextManager::new(&dir_program, &selection_data);
-    while let Some(ctx) = ctx_manager.advance().map_err(|err| types::AnalysisError {
+    let state_machine_error = |err| types::AnalysisError {
         metadata: Default::default(),
         error_type: types::AnalysisErrorType::StateMachine(err),
-    })? {
-        perform_context_analyses(ctx, knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH))?;
+    };
+    let mut ctx_manager = state_machine::AnalysisContextManager::new(&dir_program, &selection_data)
+        .map_err(state_machine_error)?;
+
+    let mut statement_indices: FxHashMap<(usize, Vec<usize>), usize> = FxHashMap::default();
+    let mut statement_analyses: Vec<StatementAnalysis> = Vec::new();
+    while let Some(ctx) = ctx_manager.advance().map_err(state_machine_error)? {
+        let result =
+            perform_context_analyses(ctx, knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH));
+
+        // Contexts of rules without statements are analysed on their own
+        let Some(branch) = ctx_manager
+            .get_branch()
+            .filter(|branch| !branch.statements.is_empty())
+        else {
+            result?;
+            continue;
+        };
+
+        let statement_idx = *statement_indices
+            .entry((branch.rule_idx, branch.statements))
+            .or_insert_with(|| {
+                statement_analyses.push(StatementAnalysis {
+                    rule_idx: branch.rule_idx,
+                    alternatives: Vec::new(),
+                });
+                statement_analyses.len() - 1
+            });
+        if let Some(statement_analysis) = statement_analyses.get_mut(statement_idx) {
+            statement_analysis.record(branch.alternatives, result);
+        }
     }
 
-    dir::lowering::lower_program(dir_program)
+    let mut warnings = Vec::new();
+    for statement_analysis in statement_analyses {
+        let rule_name = dir_program
+            .rules
+            .get(statement_analysis.rule_idx)
+            .map(|rule| rule.name.clone())
+            .unwrap_or_default();
+        warnings.extend(statement_analysis.into_warnings(rule_name)?);
+    }
+
+    warnings.extend(analyze_warnings(&dir_program, knowledge_graph)?);
+
+    Ok((dir::lowering::lower_program(dir_program)?, warnings))
+}
+
+/// Results of the analysis of the contexts of a statement, for each alternative conjunction of
+/// its conditions along with the alternatives of the statements it is nested in. Only the first
+/// error of each alternative is kept.
+struct StatementAnalysis {
+    rule_idx: usize,
+    alternatives: Vec<(Vec<usize>, Option<types::AnalysisError>)>,
+}
+
+impl StatementAnalysis {
+    fn record(&mut self, alternative: Vec<usize>, result: Result<(), types::AnalysisError>) {
+        match self
+            .alternatives
+            .iter_mut()
+            .find(|(recorded, _)| *recorded == alternative)
+        {
+            Some((_, error)) => {
+                if error.is_none() {
+                    *error = result.err();
+                }
+            }
+            None => self.alternatives.push((alternative, result.err())),
+        }
+    }
+
+    /// A statement is only invalid if every alternative of its condition is, since a payment
+    /// matching any of the alternatives matches the statement. The invalid alternatives of a valid
+    /// statement can never match, which is warned about. Conflicting and negated assertions are
+    /// left to the unreachable branch warnings of [`analyze_warnings`].
+    fn into_warnings(
+        self,
+        rule_name: String,
+    ) -> Result<Vec<types::AnalysisWarning>, types::AnalysisError> {
+        let has_valid_alternative = self.alternatives.iter().any(|(_, error)| error.is_none());
+        let mut errors = self.alternatives.into_iter().filter_map(|(_, error)| error);
+
+        if !has_valid_alternative {
+            if let Some(error) = errors.next() {
+                return Err(error);
+            }
+        }
+
+        Ok(errors
+            .filter(|error| {
+                !matches!(
+                    error.error_type,
+                    types::AnalysisErrorType::ConflictingAssertions { .. }
+                        | types::AnalysisErrorType::NegatedAssertion { .. }
+                )
+            })
+            .map(|error| types::AnalysisWarning::UnreachableAlternative {
+                rule_name: rule_name.clone(),
+                reason: error.error_type.to_string(),
+            })
+            .collect())
+    }
 }
 
 #[cfg(all(test, feature = "ast_parser"))]
@@ -376,6 +1066,257 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_condition_group_analysis() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card & (payment_method = wallet | currency = USD)
+            }
+
+            rule_2: ["stripe"]
+            {
+                payment_method = wallet & (currency = USD | !(payment_method = wallet | amount > 500))
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let (_, warnings) = analyze_with_warnings(program, None).expect("Analysis");
+
+        let unreachable_branches: Vec<(&str, &dir::DirKeyKind)> = warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                types::AnalysisWarning::UnreachableBranch { rule_name, key, .. } => {
+                    Some((rule_name.as_str(), &key.kind))
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            unreachable_branches,
+            [
+                ("rule_1", &dir::DirKeyKind::PaymentMethod),
+                ("rule_2", &dir::DirKeyKind::PaymentMethod)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_statement_is_invalid_only_if_every_alternative_is() {
+        let error = |error_type| types::AnalysisError {
+            error_type,
+            metadata: Default::default(),
+        };
+
+        let mut statement_analysis = StatementAnalysis {
+            rule_idx: 0,
+            alternatives: Vec::new(),
+        };
+        statement_analysis.record(vec![0], Err(error(types::AnalysisErrorType::NotSupported)));
+        statement_analysis.record(vec![0], Ok(()));
+        statement_analysis.record(vec![1], Ok(()));
+
+        let warnings = statement_analysis
+            .into_warnings("rule_1".to_string())
+            .expect("Valid statement");
+        assert!(matches!(
+            warnings.as_slice(),
+            [types::AnalysisWarning::UnreachableAlternative { rule_name, .. }] if rule_name == "rule_1"
+        ));
+
+        let mut statement_analysis = StatementAnalysis {
+            rule_idx: 0,
+            alternatives: Vec::new(),
+        };
+        statement_analysis.record(vec![0], Err(error(types::AnalysisErrorType::NotSupported)));
+        statement_analysis.record(
+            vec![1],
+            Err(error(types::AnalysisErrorType::NotImplemented)),
+        );
+
+        assert!(matches!(
+            statement_analysis.into_warnings("rule_1".to_string()),
+            Err(types::AnalysisError {
+                error_type: types::AnalysisErrorType::NotSupported,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_contradictory_condition_group_analysis() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = wallet & (!(payment_method = wallet | amount > 500) | payment_method = card)
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze(program, None);
+
+        if let Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::NegatedAssertion { value, .. },
+            ..
+        }) = analysis_result
+        {
+            assert_eq!(
+                value,
+                dirval!(PaymentMethod = Wallet),
+                "Expected to catch payment_method = wallet negated within the group"
+            );
+        } else {
+            panic!("Expected negated assertion error");
+        }
+    }
+
+    #[test]
+    fn test_shadowed_rule_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card & amount > 500
+            }
+
+            rule_2: ["stripe"]
+            {
+                payment_method = wallet
+            }
+
+            rule_3: ["checkout"]
+            {
+                payment_method = card & amount >= 1000 & currency = USD
+                payment_method = wallet & capture_method = manual
+            }
+
+            rule_4: ["paypal"]
+            {
+                payment_method = card & amount > 100
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let (_, warnings) = analyze_with_warnings(program, None).expect("Analysis");
+
+        if let [types::AnalysisWarning::ShadowedRule {
+            rule_name,
+            shadowing_rules,
+        }] = warnings.as_slice()
+        {
+            assert_eq!(rule_name, "rule_3");
+            assert_eq!(shadowing_rules, &["rule_1", "rule_2"]);
+        } else {
+            panic!("Expected only rule_3 to be shadowed");
+        }
+    }
+
+    #[test]
+    fn test_rule_shadowed_by_several_rules_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                amount < 100
+            }
+
+            rule_2: ["stripe"]
+            {
+                amount >= 100 & amount <= 1000
+            }
+
+            rule_3: ["checkout"]
+            {
+                amount > 50 & amount < 500
+            }
+
+            rule_4: ["paypal"]
+            {
+                currency = USD & capture_method = manual
+            }
+
+            rule_5: ["adyen"]
+            {
+                currency /= USD
+            }
+
+            rule_6: ["stripe"]
+            {
+                capture_method = manual & payment_method = card
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let (_, warnings) = analyze_with_warnings(program, None).expect("Analysis");
+
+        let shadowed_rules: Vec<(&str, &[String])> = warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                types::AnalysisWarning::ShadowedRule {
+                    rule_name,
+                    shadowing_rules,
+                } => Some((rule_name.as_str(), shadowing_rules.as_slice())),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            shadowed_rules,
+            [
+                ("rule_3", ["rule_1", "rule_2"].map(String::from).as_slice()),
+                ("rule_6", ["rule_4", "rule_5"].map(String::from).as_slice()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_branch_detection() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                amount > 500 {
+                    amount < 100
+                    currency = USD
+                }
+            }
+
+            rule_2: ["stripe"]
+            {
+                card_bin = "424242" & card_bin = "555555"
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let (_, warnings) = analyze_with_warnings(program, None).expect("Analysis");
+
+        let unreachable_keys: Vec<(&str, &dir::DirKeyKind)> = warnings
+            .iter()
+            .map(|warning| match warning {
+                types::AnalysisWarning::UnreachableBranch { rule_name, key, .. } => {
+                    (rule_name.as_str(), &key.kind)
+                }
+                _ => panic!("Expected only unreachable branch warnings"),
+            })
+            .collect();
+
+        assert_eq!(
+            unreachable_keys,
+            [
+                ("rule_1", &dir::DirKeyKind::PaymentAmount),
+                ("rule_2", &dir::DirKeyKind::CardBin)
+            ]
+        );
+    }
+
     #[test]
     fn test_negation_graph_analysis() {
         let graph = knowledge! {
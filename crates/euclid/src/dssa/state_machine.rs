@@ -6,8 +6,15 @@ use crate::{dssa::types, frontend::dir, types::Metadata};
 pub enum StateMachineError {
     #[error("Index out of bounds: {0}")]
     IndexOutOfBounds(&'static str),
+    #[error("The condition of a statement has more than {0} alternatives")]
+    TooManyAlternatives(usize),
 }
 
+/// Maximum number of alternative conjunctions the condition of a statement may expand into. Each
+/// condition group multiplies the alternatives of the conditions it is combined with, so the
+/// expansion is bounded to keep the analysis of a program tractable.
+pub const MAX_CONDITION_ALTERNATIVES: usize = 256;
+
 #[derive(Debug)]
 struct ComparisonStateMachine<'a> {
     values: &'a [dir::DirValue],
@@ -77,6 +84,144 @@ impl<'a> ComparisonStateMachine<'a> {
     }
 }
 
+/// A comparison of a condition in disjunctive normal form. A negated comparison holds when the
+/// comparison does not hold.
+#[derive(Debug, Clone, Copy)]
+struct ComparisonLiteral<'a> {
+    comparison: &'a dir::DirComparison,
+    negated: bool,
+}
+
+impl<'a> ComparisonLiteral<'a> {
+    fn get_logic(&self) -> &'a dir::DirComparisonLogic {
+        match (self.negated, &self.comparison.logic) {
+            (false, logic) => logic,
+            (true, dir::DirComparisonLogic::PositiveDisjunction) => {
+                &dir::DirComparisonLogic::NegativeConjunction
+            }
+            (true, dir::DirComparisonLogic::NegativeConjunction) => {
+                &dir::DirComparisonLogic::PositiveDisjunction
+            }
+        }
+    }
+}
+
+type Conjunction<'a> = Vec<ComparisonLiteral<'a>>;
+
+fn check_alternative_count(count: usize) -> Result<(), StateMachineError> {
+    if count > MAX_CONDITION_ALTERNATIVES {
+        Err(StateMachineError::TooManyAlternatives(
+            MAX_CONDITION_ALTERNATIVES,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns every way of picking one conjunction from each of the alternatives, each pick being
+/// merged into a single conjunction
+fn get_conjunction_product<'a>(
+    alternatives: impl Iterator<Item = Result<Vec<Conjunction<'a>>, StateMachineError>>,
+) -> Result<Vec<Conjunction<'a>>, StateMachineError> {
+    alternatives.fold(Ok(vec![Vec::new()]), |product, alternatives| {
+        let (product, alternatives) = (product?, alternatives?);
+        check_alternative_count(product.len().saturating_mul(alternatives.len()))?;
+
+        Ok(product
+            .iter()
+            .flat_map(|conjunction| {
+                alternatives.iter().map(move |alternative| {
+                    conjunction.iter().chain(alternative).copied().collect()
+                })
+            })
+            .collect())
+    })
+}
+
+/// Expands the condition into its disjunctive normal form, the alternative conjunctions of
+/// comparisons any of which satisfies the condition. Negations are pushed down to the comparisons.
+fn get_disjunctive_normal_form(
+    condition: &dir::DirCondition,
+    negated: bool,
+) -> Result<Vec<Conjunction<'_>>, StateMachineError> {
+    match (condition, negated) {
+        (dir::DirCondition::Comparison(comparison), negated) => Ok(vec![vec![ComparisonLiteral {
+            comparison,
+            negated,
+        }]]),
+        (dir::DirCondition::AllOf(conditions), false)
+        | (dir::DirCondition::AnyOf(conditions), true) => get_conjunction_product(
+            conditions
+                .iter()
+                .map(|condition| get_disjunctive_normal_form(condition, negated)),
+        ),
+        (dir::DirCondition::AnyOf(conditions), false)
+        | (dir::DirCondition::AllOf(conditions), true) => {
+            let mut alternatives = Vec::new();
+            for condition in conditions {
+                alternatives.extend(get_disjunctive_normal_form(condition, negated)?);
+                check_alternative_count(alternatives.len())?;
+            }
+            Ok(alternatives)
+        }
+        (dir::DirCondition::Not(condition), negated) => {
+            get_disjunctive_normal_form(condition, !negated)
+        }
+    }
+}
+
+/// An IF statement along with one of the alternative conjunctions of its condition. A statement
+/// whose condition holds groups is expanded into one statement per alternative conjunction.
+#[derive(Debug, Clone)]
+struct ConjunctiveStatement<'a> {
+    condition: Conjunction<'a>,
+    statement: &'a dir::DirIfStatement,
+    /// Index of the statement among the statements it is nested with
+    statement_idx: usize,
+    /// Index of the alternative conjunction among the alternatives of the statement
+    alternative_idx: usize,
+}
+
+fn make_conjunctive_statements(
+    statements: &[dir::DirIfStatement],
+) -> Result<Vec<ConjunctiveStatement<'_>>, StateMachineError> {
+    let mut conjunctive_statements = Vec::with_capacity(statements.len());
+
+    for (statement_idx, statement) in statements.iter().enumerate() {
+        let alternatives = get_conjunction_product(
+            statement
+                .condition
+                .iter()
+                .map(|condition| get_disjunctive_normal_form(condition, false)),
+        )?;
+
+        conjunctive_statements.extend(alternatives.into_iter().enumerate().map(
+            |(alternative_idx, condition)| ConjunctiveStatement {
+                condition,
+                statement,
+                statement_idx,
+                alternative_idx,
+            },
+        ));
+    }
+
+    Ok(conjunctive_statements)
+}
+
+/// The statements a context of a rule is made of, and the alternative conjunction of the
+/// condition of each of them. Contexts of the same statements differing in their alternatives
+/// hold for different payments, a statement can only never match if all of its alternatives can
+/// never match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContextBranch {
+    pub rule_idx: usize,
+    /// Index of the statement at each level of nesting, among the statements nested with it
+    pub statements: Vec<usize>,
+    /// Index of the alternative conjunction of the condition of the statement at each level of
+    /// nesting
+    pub alternatives: Vec<usize>,
+}
+
 #[derive(Debug)]
 struct ConditionStateMachine<'a> {
     state_machines: Vec<ComparisonStateMachine<'a>>,
@@ -84,15 +229,15 @@ struct ConditionStateMachine<'a> {
 }
 
 impl<'a> ConditionStateMachine<'a> {
-    fn new(condition: &'a [dir::DirComparison], start_idx: usize) -> Self {
+    fn new(condition: &[ComparisonLiteral<'a>], start_idx: usize) -> Self {
         let mut machines = Vec::<ComparisonStateMachine<'a>>::with_capacity(condition.len());
 
         let mut machine_idx = start_idx;
-        for cond in condition {
+        for literal in condition {
             let machine = ComparisonStateMachine {
-                values: &cond.values,
-                logic: &cond.logic,
-                metadata: &cond.metadata,
+                values: &literal.comparison.values,
+                logic: literal.get_logic(),
+                metadata: &literal.comparison.metadata,
                 count: 0,
                 ctx_idx: machine_idx,
             };
@@ -152,23 +297,30 @@ impl<'a> ConditionStateMachine<'a> {
 #[derive(Debug)]
 struct IfStmtStateMachine<'a> {
     condition_machine: ConditionStateMachine<'a>,
-    nested: Vec<&'a dir::DirIfStatement>,
+    nested: Vec<ConjunctiveStatement<'a>>,
     nested_idx: usize,
+    statement_idx: usize,
+    alternative_idx: usize,
 }
 
 impl<'a> IfStmtStateMachine<'a> {
-    fn new(stmt: &'a dir::DirIfStatement, ctx_start_idx: usize) -> Self {
+    fn new(
+        stmt: &ConjunctiveStatement<'a>,
+        ctx_start_idx: usize,
+    ) -> Result<Self, StateMachineError> {
         let condition_machine = ConditionStateMachine::new(&stmt.condition, ctx_start_idx);
-        let nested: Vec<&'a dir::DirIfStatement> = match &stmt.nested {
+        let nested: Vec<ConjunctiveStatement<'a>> = match &stmt.statement.nested {
             None => Vec::new(),
-            Some(nested_stmts) => nested_stmts.iter().collect(),
+            Some(nested_stmts) => make_conjunctive_statements(nested_stmts)?,
         };
 
-        Self {
+        Ok(Self {
             condition_machine,
             nested,
             nested_idx: 0,
-        }
+            statement_idx: stmt.statement_idx,
+            alternative_idx: stmt.alternative_idx,
+        })
     }
 
     fn init(
@@ -176,10 +328,10 @@ impl<'a> IfStmtStateMachine<'a> {
         context: &mut types::ConjunctiveContext<'a>,
     ) -> Result<Option<Self>, StateMachineError> {
         self.condition_machine.init(context)?;
-        Ok(self
-            .nested
+        self.nested
             .first()
-            .map(|nested| Self::new(nested, self.condition_machine.get_next_ctx_idx())))
+            .map(|nested| Self::new(nested, self.condition_machine.get_next_ctx_idx()))
+            .transpose()
     }
 
     #[inline]
@@ -211,14 +363,15 @@ impl<'a> IfStmtStateMachine<'a> {
             Ok(None)
         } else {
             self.nested_idx = (self.nested_idx + 1) % self.nested.len();
-            Ok(Some(Self::new(
+            Self::new(
                 self.nested
                     .get(self.nested_idx)
                     .ok_or(StateMachineError::IndexOutOfBounds(
                         "in IfStmtStateMachine while advancing",
                     ))?,
                 self.condition_machine.get_next_ctx_idx(),
-            )))
+            )
+            .map(Some)
         }
     }
 }
@@ -235,22 +388,37 @@ impl<'a> RuleStateMachine<'a> {
     fn new<O>(
         rule: &'a dir::DirRule<O>,
         connector_selection_data: &'a [(dir::DirValue, Metadata)],
-    ) -> Self {
+    ) -> Result<Self, StateMachineError> {
+        let statements = make_conjunctive_statements(&rule.statements)?;
         let mut if_stmt_machines: Vec<IfStmtStateMachine<'a>> =
-            Vec::with_capacity(rule.statements.len());
+            Vec::with_capacity(statements.len());
 
-        for stmt in rule.statements.iter().rev() {
+        for stmt in statements.iter().rev() {
             if_stmt_machines.push(IfStmtStateMachine::new(
                 stmt,
                 connector_selection_data.len(),
-            ));
+            )?);
         }
 
-        Self {
+        Ok(Self {
             connector_selection_data,
             connectors_added: false,
             if_stmt_machines,
             running_stack: Vec::new(),
+        })
+    }
+
+    fn get_branch(&self, rule_idx: usize) -> ContextBranch {
+        let (statements, alternatives) = self
+            .running_stack
+            .iter()
+            .map(|machine| (machine.statement_idx, machine.alternative_idx))
+            .unzip();
+
+        ContextBranch {
+            rule_idx,
+            statements,
+            alternatives,
         }
     }
 
@@ -345,12 +513,12 @@ impl<'a> RuleContextManager<'a> {
     pub fn new<O>(
         rule: &'a dir::DirRule<O>,
         connector_selection_data: &'a [(dir::DirValue, Metadata)],
-    ) -> Self {
-        Self {
+    ) -> Result<Self, StateMachineError> {
+        Ok(Self {
             context: Vec::new(),
-            machine: RuleStateMachine::new(rule, connector_selection_data),
+            machine: RuleStateMachine::new(rule, connector_selection_data)?,
             init: false,
-        }
+        })
     }
 
     pub fn advance(&mut self) -> Result<Option<&types::ConjunctiveContext<'a>>, StateMachineError> {
@@ -396,6 +564,7 @@ impl<'a> RuleContextManager<'a> {
 pub struct ProgramStateMachine<'a> {
     rule_machines: Vec<RuleStateMachine<'a>>,
     current_rule_machine: Option<RuleStateMachine<'a>>,
+    current_rule_idx: usize,
     is_init: bool,
 }
 
@@ -403,7 +572,7 @@ impl<'a> ProgramStateMachine<'a> {
     pub fn new<O>(
         program: &'a dir::DirProgram<O>,
         connector_selection_data: &'a [Vec<(dir::DirValue, Metadata)>],
-    ) -> Self {
+    ) -> Result<Self, StateMachineError> {
         let mut rule_machines: Vec<RuleStateMachine<'a>> = program
             .rules
             .iter()
@@ -412,13 +581,21 @@ impl<'a> ProgramStateMachine<'a> {
             .map(|(rule, connector_selection_data)| {
                 RuleStateMachine::new(rule, connector_selection_data)
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self {
+        Ok(Self {
             current_rule_machine: rule_machines.pop(),
             rule_machines,
+            current_rule_idx: 0,
             is_init: false,
-        }
+        })
+    }
+
+    /// Returns the statements the current context is made of
+    pub fn get_branch(&self) -> Option<ContextBranch> {
+        self.current_rule_machine
+            .as_ref()
+            .map(|rsm| rsm.get_branch(self.current_rule_idx))
     }
 
     pub fn is_finished(&self) -> bool {
@@ -452,6 +629,7 @@ impl<'a> ProgramStateMachine<'a> {
             .map_or(true, |rsm| rsm.is_finished())
         {
             self.current_rule_machine = self.rule_machines.pop();
+            self.current_rule_idx += 1;
             context.clear();
             if let Some(rsm) = self.current_rule_machine.as_mut() {
                 rsm.init_next(context)?;
@@ -474,15 +652,20 @@ impl<'a> AnalysisContextManager<'a> {
     pub fn new<O>(
         program: &'a dir::DirProgram<O>,
         connector_selection_data: &'a [Vec<(dir::DirValue, Metadata)>],
-    ) -> Self {
-        let machine = ProgramStateMachine::new(program, connector_selection_data);
+    ) -> Result<Self, StateMachineError> {
+        let machine = ProgramStateMachine::new(program, connector_selection_data)?;
         let context: types::ConjunctiveContext<'a> = Vec::new();
 
-        Self {
+        Ok(Self {
             context,
             machine,
             init: false,
-        }
+        })
+    }
+
+    /// Returns the statements the context last returned by [`Self::advance`] is made of
+    pub fn get_branch(&self) -> Option<ContextBranch> {
+        self.machine.get_branch()
     }
 
     pub fn advance(&mut self) -> Result<Option<&types::ConjunctiveContext<'a>>, StateMachineError> {
@@ -517,6 +700,19 @@ pub fn make_connector_selection_data<O: EuclidAnalysable>(
         .collect()
 }
 
+pub fn make_split_selection_data<O: EuclidAnalysable>(
+    program: &dir::DirProgram<O>,
+) -> Vec<Vec<(dir::DirValue, Metadata)>> {
+    program
+        .rules
+        .iter()
+        .map(|rule| {
+            rule.connector_selection
+                .get_split_dir_values_for_analysis(rule.name.clone())
+        })
+        .collect()
+}
+
 #[cfg(all(test, feature = "ast_parser"))]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -554,7 +750,8 @@ mod tests {
         let lowered = ast::lowering::lower_program(program).expect("Lowering");
 
         let selection_data = make_connector_selection_data(&lowered);
-        let mut state_machine = ProgramStateMachine::new(&lowered, &selection_data);
+        let mut state_machine =
+            ProgramStateMachine::new(&lowered, &selection_data).expect("State machine");
         let mut ctx: types::ConjunctiveContext<'_> = Vec::new();
         state_machine.init(&mut ctx).expect("State machine init");
 
@@ -692,7 +889,8 @@ mod tests {
 
         assert_eq!(expected_idx, 14);
 
-        let mut ctx_manager = AnalysisContextManager::new(&lowered, &selection_data);
+        let mut ctx_manager =
+            AnalysisContextManager::new(&lowered, &selection_data).expect("Context manager");
         expected_idx = 0;
         while let Some(ctx) = ctx_manager.advance().expect("Context Manager Context") {
             let values = ctx
@@ -715,4 +913,95 @@ mod tests {
 
         assert_eq!(expected_idx, 14);
     }
+
+    #[test]
+    fn test_condition_group_contexts() {
+        let program_str = r#"
+            default: ["stripe"]
+
+            stripe_first: ["stripe"]
+            {
+                payment_method = wallet & (currency = USD | !(capture_method = manual & currency = GBP)) {
+                    capture_method = automatic
+                }
+            }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let lowered = ast::lowering::lower_program(program).expect("Lowering");
+        let selection_data = make_connector_selection_data(&lowered);
+
+        let expected_contexts: Vec<Vec<(bool, dir::DirValue)>> = vec![
+            vec![
+                (true, dirval!(PaymentMethod = Wallet)),
+                (true, dirval!(PaymentCurrency = USD)),
+                (true, dirval!(CaptureMethod = Automatic)),
+            ],
+            vec![
+                (true, dirval!(PaymentMethod = Wallet)),
+                (false, dirval!(CaptureMethod = Manual)),
+                (true, dirval!(CaptureMethod = Automatic)),
+            ],
+            vec![
+                (true, dirval!(PaymentMethod = Wallet)),
+                (false, dirval!(PaymentCurrency = GBP)),
+                (true, dirval!(CaptureMethod = Automatic)),
+            ],
+        ];
+
+        let mut ctx_manager =
+            AnalysisContextManager::new(&lowered, &selection_data).expect("Context manager");
+        let mut contexts: Vec<Vec<(bool, dir::DirValue)>> = Vec::new();
+        let mut branches: Vec<ContextBranch> = Vec::new();
+        while let Some(ctx) = ctx_manager.advance().expect("Context Manager Context") {
+            contexts.push(
+                ctx.iter()
+                    .skip(1)
+                    .flat_map(|c| match c.value {
+                        types::CtxValueKind::Assertion(val) => vec![(true, val.clone())],
+                        types::CtxValueKind::Negation(vals) => {
+                            vals.iter().map(|val| (false, val.clone())).collect()
+                        }
+                    })
+                    .collect(),
+            );
+            branches.push(ctx_manager.get_branch().expect("Context branch"));
+        }
+
+        assert_eq!(contexts, expected_contexts);
+        assert_eq!(
+            branches,
+            (0..3)
+                .map(|alternative_idx| ContextBranch {
+                    rule_idx: 0,
+                    statements: vec![0, 0],
+                    alternatives: vec![alternative_idx, 0],
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_condition_alternatives_are_bounded() {
+        let groups = vec!["(currency = USD | amount > 100)"; 9].join(" & ");
+        let program_str = format!(
+            r#"
+            default: ["stripe"]
+
+            stripe_first: ["stripe"]
+            {{
+                payment_method = wallet & {groups}
+            }}
+        "#
+        );
+        let (_, program) = ast::parser::program::<DummyOutput>(&program_str).expect("Program");
+        let lowered = ast::lowering::lower_program(program).expect("Lowering");
+        let selection_data = make_connector_selection_data(&lowered);
+
+        assert!(matches!(
+            AnalysisContextManager::new(&lowered, &selection_data),
+            Err(StateMachineError::TooManyAlternatives(
+                MAX_CONDITION_ALTERNATIVES
+            ))
+        ));
+    }
 }
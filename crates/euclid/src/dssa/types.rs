@@ -10,6 +10,16 @@ use crate::{
 
 pub trait EuclidAnalysable: Sized {
     fn get_dir_value_for_analysis(&self, rule_name: String) -> Vec<(dir::DirValue, Metadata)>;
+
+    /// Returns the outputs across which the payments matching the rule are split. Unlike the
+    /// values returned by [`Self::get_dir_value_for_analysis`], these are not analysed as part of
+    /// the rule's conditions, each of them is checked for eligibility on its own.
+    fn get_split_dir_values_for_analysis(
+        &self,
+        _rule_name: String,
+    ) -> Vec<(dir::DirValue, Metadata)> {
+        Vec::new()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -154,6 +164,78 @@ pub enum AnalysisErrorType {
     NotSupported,
 }
 
+/// Conditions of a program that are valid, but do not behave the way they were likely meant to
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "snake_case")]
+pub enum AnalysisWarning {
+    ShadowedRule {
+        rule_name: String,
+        shadowing_rules: Vec<String>,
+    },
+    UnreachableBranch {
+        rule_name: String,
+        key: dir::DirKey,
+        metadata: Vec<Metadata>,
+    },
+    IneligibleOutput {
+        rule_name: String,
+        output: dir::DirValue,
+        metadata: Metadata,
+    },
+    UnreachableAlternative {
+        rule_name: String,
+        reason: String,
+    },
+}
+
+impl AnalysisWarning {
+    pub fn get_rule_name(&self) -> &str {
+        match self {
+            Self::ShadowedRule { rule_name, .. }
+            | Self::UnreachableBranch { rule_name, .. }
+            | Self::IneligibleOutput { rule_name, .. }
+            | Self::UnreachableAlternative { rule_name, .. } => rule_name,
+        }
+    }
+}
+
+impl fmt::Display for AnalysisWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShadowedRule {
+                rule_name,
+                shadowing_rules,
+            } => write!(
+                f,
+                "Rule '{rule_name}' can never match, every payment it matches is matched by the earlier rules '{}'",
+                shadowing_rules.join("', '")
+            ),
+            Self::UnreachableBranch { rule_name, key, .. } => write!(
+                f,
+                "A branch of rule '{rule_name}' can never match, it holds contradicting conditions on '{}'",
+                key.kind
+            ),
+            Self::IneligibleOutput {
+                rule_name, output, ..
+            } => match output {
+                dir::DirValue::Connector(choice) => write!(
+                    f,
+                    "Connector '{}' of rule '{rule_name}' is ineligible for every payment matched by the rule",
+                    choice.connector
+                ),
+                output => write!(
+                    f,
+                    "Output '{output}' of rule '{rule_name}' is ineligible for every payment matched by the rule"
+                ),
+            },
+            Self::UnreachableAlternative { rule_name, reason } => write!(
+                f,
+                "An alternative of a condition group of rule '{rule_name}' can never match: {reason}"
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ValueType {
     EnumVariants(Vec<EuclidValue>),
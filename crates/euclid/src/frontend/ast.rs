@@ -1,6 +1,7 @@
 pub mod lowering;
 #[cfg(feature = "ast_parser")]
 pub mod parser;
+pub mod printer;
 
 use common_enums::RoutableConnectors;
 use common_utils::types::MinorUnit;
@@ -80,7 +81,7 @@ pub enum ComparisonType {
 }
 
 /// Represents a single comparison condition.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Comparison {
     /// The left hand side which will always be a domain input identifier like "payment.method.cardtype"
@@ -96,13 +97,43 @@ pub struct Comparison {
     pub metadata: Metadata,
 }
 
-/// Represents all the conditions of an IF statement
+/// Represents a single condition of an IF statement, either a comparison or a group of
+/// conditions
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Condition {
+    Comparison(Comparison),
+    Group(ConditionGroup),
+}
+
+/// Represents a group of conditions. Groups can be nested to any depth, the conditions of
+/// an all-of group are separated by `&`, those of an any-of group by `|`, and a negated
+/// condition is prefixed with `!`
+///
+/// ```text
+/// (payment.method.network = visa & payment.amount > 1000)
+/// (payment.currency = USD | payment.currency = EUR)
+/// !(payment.method = card)
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ConditionGroup {
+    /// Holds when all of the conditions hold
+    AllOf(Vec<Condition>),
+    /// Holds when any of the conditions holds
+    AnyOf(Vec<Condition>),
+    /// Holds when the condition does not hold
+    Not(Box<Condition>),
+}
+
+/// Represents all the conditions of an IF statement, all of which have to hold
 /// eg:
 ///
 /// ```text
 /// payment.method = card & payment.method.cardtype = debit & payment.method.network = diners
+/// payment.method = card & (payment.currency = USD | !(payment.amount < 1000))
 /// ```
-pub type IfCondition = Vec<Comparison>;
+pub type IfCondition = Vec<Condition>;
 
 /// Represents an IF statement with conditions and optional nested IF statements
 ///
@@ -113,10 +144,10 @@ pub type IfCondition = Vec<Comparison>;
 ///     }
 /// }
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IfStatement {
-    #[schema(value_type=Vec<Comparison>)]
+    #[schema(value_type=Vec<Condition>)]
     pub condition: IfCondition,
     pub nested: Option<Vec<IfStatement>>,
 }
@@ -136,7 +167,7 @@ pub struct IfStatement {
 /// }
 /// ```
 
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(RuleConnectorSelection = Rule<ConnectorSelection>)]
 pub struct Rule<O> {
@@ -148,7 +179,7 @@ pub struct Rule<O> {
 
 /// The program, having a default connector selection and
 /// a bunch of rules. Also can hold arbitrary metadata.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(ProgramConnectorSelection = Program<ConnectorSelection>)]
 pub struct Program<O> {
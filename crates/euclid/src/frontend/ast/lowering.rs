@@ -5,6 +5,8 @@
 
 use std::str::FromStr;
 
+use common_utils::types::MinorUnit;
+
 use crate::{
     dssa::types::{AnalysisError, AnalysisErrorType},
     enums::CollectVariants,
@@ -68,20 +70,24 @@ macro_rules! lower_enum {
 /// payment_amount = 17052001
 /// ```notrust
 /// This is for the cases in which there are numerical values involved and they are lowered
-/// accordingly on basis of the supplied key, currently payment_amount is the only key having this
-/// use case
+/// accordingly on basis of the supplied key, an optional validation closure can be supplied to
+/// restrict the accepted numbers
 
 macro_rules! lower_number {
-    ($key:ident, $value:ident, $comp:ident) => {
+    ($key:ident, $value:ident, $comp:ident $(, $validation_closure:expr)?) => {
         match $value {
-            ast::ValueType::Number(num) => Ok(vec![dir::DirValue::$key(types::NumValue {
-                number: num,
-                refinement: $comp.into(),
-            })]),
+            ast::ValueType::Number(num) => {
+                $($validation_closure(&num)?;)?
+                Ok(vec![dir::DirValue::$key(types::NumValue {
+                    number: num,
+                    refinement: $comp.into(),
+                })])
+            }
 
             ast::ValueType::NumberArray(na) => na
                 .into_iter()
                 .map(|num| {
+                    $($validation_closure(&num)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: num,
                         refinement: $comp.clone().into(),
@@ -92,6 +98,7 @@ macro_rules! lower_number {
             ast::ValueType::NumberComparisonArray(nca) => nca
                 .into_iter()
                 .map(|nc| {
+                    $($validation_closure(&nc.number)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: nc.number,
                         refinement: nc.comparison_type.into(),
@@ -297,6 +304,28 @@ fn lower_comparison_inner<O: EuclidDirFilter>(
 
         dir::DirKeyKind::PaymentAmount => lower_number!(PaymentAmount, value, comparison),
 
+        dir::DirKeyKind::TimeOfDay => {
+            let validation_closure = |num: &MinorUnit| -> Result<(), AnalysisErrorType> {
+                let time = num.get_amount_as_i64();
+                if (0..2400).contains(&time) && time % 100 < 60 {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::TimeOfDay,
+                        value: time.to_string(),
+                        message: Some("Expected a 24 hour time in the HHMM format".to_string()),
+                    })
+                }
+            };
+            lower_number!(TimeOfDay, value, comparison, validation_closure)
+        }
+
+        dir::DirKeyKind::DayOfWeek => lower_enum!(DayOfWeek, value),
+
+        dir::DirKeyKind::CardIssuerCountry => lower_enum!(CardIssuerCountry, value),
+
+        dir::DirKeyKind::CardIssuer => lower_str!(CardIssuer, value),
+
         dir::DirKeyKind::Connector => Err(AnalysisErrorType::InvalidKey(
             dir::DirKeyKind::Connector.to_string(),
         )),
@@ -329,6 +358,32 @@ fn lower_comparison<O: EuclidDirFilter>(
     })
 }
 
+/// lowers a condition, lowering the conditions of condition groups recursively
+fn lower_condition<O: EuclidDirFilter>(
+    condition: ast::Condition,
+) -> Result<dir::DirCondition, AnalysisError> {
+    Ok(match condition {
+        ast::Condition::Comparison(comp) => {
+            dir::DirCondition::Comparison(lower_comparison::<O>(comp)?)
+        }
+        ast::Condition::Group(ast::ConditionGroup::AllOf(conditions)) => dir::DirCondition::AllOf(
+            conditions
+                .into_iter()
+                .map(lower_condition::<O>)
+                .collect::<Result<_, _>>()?,
+        ),
+        ast::Condition::Group(ast::ConditionGroup::AnyOf(conditions)) => dir::DirCondition::AnyOf(
+            conditions
+                .into_iter()
+                .map(lower_condition::<O>)
+                .collect::<Result<_, _>>()?,
+        ),
+        ast::Condition::Group(ast::ConditionGroup::Not(condition)) => {
+            dir::DirCondition::Not(Box::new(lower_condition::<O>(*condition)?))
+        }
+    })
+}
+
 /// lowers the if statement accordingly with a condition and following nested if statements (if
 /// present)
 fn lower_if_statement<O: EuclidDirFilter>(
@@ -338,7 +393,7 @@ fn lower_if_statement<O: EuclidDirFilter>(
         condition: stmt
             .condition
             .into_iter()
-            .map(lower_comparison::<O>)
+            .map(lower_condition::<O>)
             .collect::<Result<_, _>>()?,
         nested: stmt
             .nested
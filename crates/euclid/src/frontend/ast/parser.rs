@@ -21,14 +21,14 @@ impl EuclidParsable for DummyOutput {
     fn parse_output(input: &str) -> ParseResult<&str, Self> {
         let string_w = sequence::delimited(
             skip_ws(complete::tag("\"")),
-            complete::take_while(|c| c != '"'),
+            escaped_string,
             skip_ws(complete::tag("\"")),
         );
         let full_sequence = multi::many0(sequence::preceded(
             skip_ws(complete::tag(",")),
             sequence::delimited(
                 skip_ws(complete::tag("\"")),
-                complete::take_while(|c| c != '"'),
+                escaped_string,
                 skip_ws(complete::tag("\"")),
             ),
         ));
@@ -41,16 +41,88 @@ impl EuclidParsable for DummyOutput {
                     sequence,
                     skip_ws(complete::tag("]")),
                 ),
-                |out: (&str, Vec<&str>)| {
-                    let mut first = out.1;
-                    first.insert(0, out.0);
-                    let v = first.iter().map(|s| s.to_string()).collect();
-                    Self { outputs: v }
+                |out: (String, Vec<String>)| {
+                    let mut outputs = out.1;
+                    outputs.insert(0, out.0);
+                    Self { outputs }
                 },
             ),
         )(input)
     }
 }
+impl EuclidParsable for ast::ConnectorSelection {
+    fn parse_output(input: &str) -> ParseResult<&str, Self> {
+        let volume_split = combinator::map(
+            sequence::separated_pair(
+                connector_choice,
+                skip_ws(complete::tag(":")),
+                skip_ws(percentage),
+            ),
+            |(connector, split)| ast::ConnectorVolumeSplit { connector, split },
+        );
+
+        error::context(
+            "connector_selection",
+            sequence::delimited(
+                skip_ws(complete::tag("[")),
+                branch::alt((
+                    combinator::map(
+                        multi::separated_list1(skip_ws(complete::tag(",")), volume_split),
+                        Self::VolumeSplit,
+                    ),
+                    combinator::map(
+                        multi::separated_list1(skip_ws(complete::tag(",")), connector_choice),
+                        Self::Priority,
+                    ),
+                )),
+                skip_ws(complete::tag("]")),
+            ),
+        )(input)
+    }
+}
+
+/// Parses a connector, optionally followed by its merchant connector account ID in parentheses
+///
+/// ```text
+/// stripe(mca_cDHNbAUiRLfbE0Kj9g5R)
+/// ```
+pub fn connector_choice(input: &str) -> ParseResult<&str, ast::RoutableConnectorChoice> {
+    let connector = combinator::map_res(identifier, |name| {
+        name.parse::<common_enums::RoutableConnectors>()
+            .map_err(|_| EuclidError::InvalidConnector(name))
+    });
+
+    let merchant_connector_id = combinator::map_res(
+        sequence::delimited(
+            skip_ws(complete::tag("(")),
+            skip_ws(branch::alt((
+                combinator::map(
+                    complete::take_while1(super::printer::is_id_character),
+                    str::to_string,
+                ),
+                string_str,
+            ))),
+            skip_ws(complete::tag(")")),
+        ),
+        |id: String| {
+            common_utils::id_type::MerchantConnectorAccountId::wrap(id.clone())
+                .map_err(|_| EuclidError::InvalidConnector(id))
+        },
+    );
+
+    error::context(
+        "connector_choice",
+        combinator::map(
+            sequence::pair(skip_ws(connector), combinator::opt(merchant_connector_id)),
+            |(connector, merchant_connector_id)| ast::RoutableConnectorChoice {
+                choice_kind: ast::RoutableChoiceKind::FullStruct,
+                connector,
+                merchant_connector_id,
+            },
+        ),
+    )(input)
+}
+
 pub fn skip_ws<'a, F, O>(inner: F) -> impl FnMut(&'a str) -> ParseResult<&str, O>
 where
     F: FnMut(&'a str) -> ParseResult<&str, O> + 'a,
@@ -62,7 +134,10 @@ pub fn num_i64(input: &str) -> ParseResult<&str, i64> {
     error::context(
         "num_i32",
         combinator::map_res(
-            complete::take_while1(|c: char| c.is_ascii_digit()),
+            combinator::recognize(sequence::pair(
+                combinator::opt(complete::tag("-")),
+                complete::take_while1(|c: char| c.is_ascii_digit()),
+            )),
             |o: &str| {
                 o.parse::<i64>()
                     .map_err(|_| EuclidError::InvalidNumber(o.to_string()))
@@ -74,17 +149,30 @@ pub fn num_i64(input: &str) -> ParseResult<&str, i64> {
 pub fn string_str(input: &str) -> ParseResult<&str, String> {
     error::context(
         "String",
-        combinator::map(
-            sequence::delimited(
-                complete::tag("\""),
-                complete::take_while1(|c: char| c != '"'),
-                complete::tag("\""),
-            ),
-            |val: &str| val.to_string(),
+        sequence::delimited(
+            complete::tag("\""),
+            combinator::verify(escaped_string, |val: &String| !val.is_empty()),
+            complete::tag("\""),
         ),
     )(input)
 }
 
+/// Parses the contents of a string literal, up to its closing double quote. Double quotes and
+/// backslashes are escaped with a backslash.
+pub fn escaped_string(input: &str) -> ParseResult<&str, String> {
+    combinator::map(
+        combinator::opt(complete::escaped_transform(
+            complete::is_not("\\\""),
+            '\\',
+            branch::alt((
+                combinator::value("\\", complete::tag("\\")),
+                combinator::value("\"", complete::tag("\"")),
+            )),
+        )),
+        Option::unwrap_or_default,
+    )(input)
+}
+
 pub fn identifier(input: &str) -> ParseResult<&str, String> {
     error::context(
         "identifier",
@@ -102,7 +190,7 @@ pub fn percentage(input: &str) -> ParseResult<&str, u8> {
         "volume_split_percentage",
         combinator::map_res(
             sequence::terminated(
-                complete::take_while_m_n(1, 2, |c: char| c.is_ascii_digit()),
+                complete::take_while_m_n(1, 3, |c: char| c.is_ascii_digit()),
                 complete::tag("%"),
             ),
             |o: &str| {
@@ -199,14 +287,18 @@ pub fn enum_variant_array_value(input: &str) -> ParseResult<&str, ast::ValueType
 pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison> {
     let operator = combinator::map_res(
         branch::alt((
+            complete::tag("/="),
             complete::tag(">="),
             complete::tag("<="),
+            complete::tag("="),
             complete::tag(">"),
             complete::tag("<"),
         )),
         |s: &str| match s {
+            "/=" => Ok(ast::ComparisonType::NotEqual),
             ">=" => Ok(ast::ComparisonType::GreaterThanEqual),
             "<=" => Ok(ast::ComparisonType::LessThanEqual),
+            "=" => Ok(ast::ComparisonType::Equal),
             ">" => Ok(ast::ComparisonType::GreaterThan),
             "<" => Ok(ast::ComparisonType::LessThan),
             _ => Err(EuclidError::InvalidOperator(s.to_string())),
@@ -216,7 +308,7 @@ pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison
     error::context(
         "number_comparison",
         combinator::map(
-            sequence::pair(operator, num_i64),
+            sequence::pair(operator, skip_ws(num_i64)),
             |tup: (ast::ComparisonType, i64)| ast::NumberComparison {
                 comparison_type: tup.0,
                 number: MinorUnit::new(tup.1),
@@ -332,22 +424,87 @@ pub fn arbitrary_comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
     )(input)
 }
 
-pub fn comparison_array(input: &str) -> ParseResult<&str, Vec<ast::Comparison>> {
+pub fn negated_condition(input: &str) -> ParseResult<&str, ast::Condition> {
+    error::context(
+        "negated_condition",
+        combinator::map(
+            sequence::preceded(skip_ws(complete::tag("!")), condition),
+            |negated: ast::Condition| {
+                ast::Condition::Group(ast::ConditionGroup::Not(Box::new(negated)))
+            },
+        ),
+    )(input)
+}
+
+/// Parses a parenthesized group of conditions. The conditions of a group are either all
+/// separated by `&` or all separated by `|`, mixing both requires nesting groups. A single
+/// parenthesized condition is the condition itself.
+pub fn condition_group(input: &str) -> ParseResult<&str, ast::Condition> {
+    let separated_conditions = |separator: &'static str| {
+        sequence::pair(
+            skip_ws(complete::tag(separator)),
+            multi::separated_list1(skip_ws(complete::tag(separator)), condition),
+        )
+    };
+
+    let full_sequence = sequence::pair(
+        condition,
+        combinator::opt(branch::alt((
+            separated_conditions("&"),
+            separated_conditions("|"),
+        ))),
+    );
+
+    error::context(
+        "condition_group",
+        combinator::map_res(
+            sequence::delimited(
+                skip_ws(complete::tag("(")),
+                full_sequence,
+                skip_ws(complete::tag(")")),
+            ),
+            |tup: (ast::Condition, Option<(&str, Vec<ast::Condition>)>)| match tup.1 {
+                None => Ok(tup.0),
+                Some((separator, mut rest)) => {
+                    rest.insert(0, tup.0);
+                    match separator {
+                        "&" => Ok(ast::Condition::Group(ast::ConditionGroup::AllOf(rest))),
+                        "|" => Ok(ast::Condition::Group(ast::ConditionGroup::AnyOf(rest))),
+                        _ => Err(EuclidError::InvalidOperator(separator.to_string())),
+                    }
+                }
+            },
+        ),
+    )(input)
+}
+
+pub fn condition(input: &str) -> ParseResult<&str, ast::Condition> {
+    error::context(
+        "condition",
+        branch::alt((
+            negated_condition,
+            condition_group,
+            combinator::map(
+                branch::alt((comparison, arbitrary_comparison)),
+                ast::Condition::Comparison,
+            ),
+        )),
+    )(input)
+}
+
+pub fn condition_array(input: &str) -> ParseResult<&str, ast::IfCondition> {
     let many_with_ampersand = error::context(
         "many_with_amp",
-        multi::many0(sequence::preceded(skip_ws(complete::tag("&")), comparison)),
+        multi::many0(sequence::preceded(skip_ws(complete::tag("&")), condition)),
     );
 
-    let full_sequence = sequence::pair(
-        skip_ws(branch::alt((comparison, arbitrary_comparison))),
-        many_with_ampersand,
-    );
+    let full_sequence = sequence::pair(condition, many_with_ampersand);
 
     error::context(
-        "comparison_array",
+        "condition_array",
         combinator::map(
             full_sequence,
-            |tup: (ast::Comparison, Vec<ast::Comparison>)| {
+            |tup: (ast::Condition, Vec<ast::Condition>)| {
                 let mut rest = tup.1;
                 rest.insert(0, tup.0);
                 rest
@@ -366,7 +523,7 @@ pub fn if_statement(input: &str) -> ParseResult<&str, ast::IfStatement> {
     error::context(
         "if_statement",
         combinator::map(
-            sequence::pair(comparison_array, combinator::opt(nested_block)),
+            sequence::pair(condition_array, combinator::opt(nested_block)),
             |tup: (ast::IfCondition, Option<Vec<ast::IfStatement>>)| ast::IfStatement {
                 condition: tup.0,
                 nested: tup.1,
@@ -434,7 +591,7 @@ pub fn program<O: EuclidParsable + 'static>(input: &str) -> ParseResult<&str, as
     error::context(
         "program",
         combinator::map(
-            sequence::pair(default_output, multi::many1(skip_ws(rule::<O>))),
+            sequence::pair(default_output, multi::many0(skip_ws(rule::<O>))),
             |tup: (O, Vec<ast::Rule<O>>)| ast::Program {
                 default_selection: tup.0,
                 rules: tup.1,
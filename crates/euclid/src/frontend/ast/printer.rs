@@ -0,0 +1,606 @@
+//! Renders programs back into the textual DSL accepted by the parser.
+//!
+//! The output is canonical: each statement is on its own line, nested blocks are indented by
+//! four spaces, and a blank line separates the rules. Double quotes and backslashes in strings are
+//! escaped with a backslash. Printing a program and parsing the output yields the same program,
+//! as long as the program can be expressed in the DSL: string values and metadata keys cannot be
+//! empty, conditions, arrays and connector lists cannot be empty, condition groups need at least
+//! two conditions, rules need at least one statement, and the free form metadata of the program
+//! and its comparisons is not part of the DSL.
+
+use common_enums::RoutableConnectors;
+use common_utils::id_type::MerchantConnectorAccountId;
+
+use crate::{frontend::ast, types::DummyOutput};
+
+const INDENT: &str = "    ";
+
+/// Outputs of a program that can be rendered in the DSL
+pub trait EuclidPrintable {
+    fn print_output(&self) -> String;
+}
+
+impl EuclidPrintable for DummyOutput {
+    fn print_output(&self) -> String {
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| string(output))
+            .collect::<Vec<_>>();
+
+        format!("[{}]", outputs.join(", "))
+    }
+}
+
+impl EuclidPrintable for ast::ConnectorSelection {
+    fn print_output(&self) -> String {
+        match self {
+            Self::Priority(choices) => priority(
+                choices
+                    .iter()
+                    .map(|choice| (&choice.connector, choice.merchant_connector_id.as_ref())),
+            ),
+            Self::VolumeSplit(splits) => volume_split(splits.iter().map(|split| {
+                (
+                    &split.connector.connector,
+                    split.connector.merchant_connector_id.as_ref(),
+                    split.split,
+                )
+            })),
+        }
+    }
+}
+
+/// Renders a string literal, escaping the double quotes and backslashes it holds
+///
+/// ```text
+/// "say \"hi\""
+/// ```
+pub fn string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for character in value.chars() {
+        if matches!(character, '"' | '\\') {
+            out.push('\\');
+        }
+        out.push(character);
+    }
+    out.push('"');
+    out
+}
+
+/// Renders a connector, along with its merchant connector account ID if present. IDs holding
+/// characters other than the ones of generated IDs are rendered as string literals.
+///
+/// ```text
+/// stripe(mca_cDHNbAUiRLfbE0Kj9g5R)
+/// ```
+pub fn connector_choice(
+    connector: &RoutableConnectors,
+    merchant_connector_id: Option<&MerchantConnectorAccountId>,
+) -> String {
+    match merchant_connector_id.map(MerchantConnectorAccountId::get_string_repr) {
+        Some(id) if !id.is_empty() && id.chars().all(is_id_character) => {
+            format!("{connector}({id})")
+        }
+        Some(id) => format!("{connector}({})", string(id)),
+        None => connector.to_string(),
+    }
+}
+
+/// Characters of the merchant connector account IDs which can be rendered without quotes
+pub fn is_id_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '_' || character == '-'
+}
+
+/// Renders a priority list of connectors
+///
+/// ```text
+/// [stripe, adyen]
+/// ```
+pub fn priority<'a>(
+    choices: impl Iterator<
+        Item = (
+            &'a RoutableConnectors,
+            Option<&'a MerchantConnectorAccountId>,
+        ),
+    >,
+) -> String {
+    let choices = choices
+        .map(|(connector, merchant_connector_id)| {
+            connector_choice(connector, merchant_connector_id)
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", choices.join(", "))
+}
+
+/// Renders a volume split between connectors
+///
+/// ```text
+/// [stripe: 70%, adyen: 30%]
+/// ```
+pub fn volume_split<'a>(
+    splits: impl Iterator<
+        Item = (
+            &'a RoutableConnectors,
+            Option<&'a MerchantConnectorAccountId>,
+            u8,
+        ),
+    >,
+) -> String {
+    let splits = splits
+        .map(|(connector, merchant_connector_id, split)| {
+            format!(
+                "{}: {split}%",
+                connector_choice(connector, merchant_connector_id)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", splits.join(", "))
+}
+
+pub fn comparison_type(comparison_type: &ast::ComparisonType) -> &'static str {
+    match comparison_type {
+        ast::ComparisonType::Equal => "=",
+        ast::ComparisonType::NotEqual => "/=",
+        ast::ComparisonType::LessThan => "<",
+        ast::ComparisonType::LessThanEqual => "<=",
+        ast::ComparisonType::GreaterThan => ">",
+        ast::ComparisonType::GreaterThanEqual => ">=",
+    }
+}
+
+pub fn value_type(value: &ast::ValueType) -> String {
+    match value {
+        ast::ValueType::Number(number) => number.get_amount_as_i64().to_string(),
+        ast::ValueType::EnumVariant(variant) => variant.clone(),
+        ast::ValueType::MetadataVariant(metadata) => {
+            format!("{} = {}", string(&metadata.key), string(&metadata.value))
+        }
+        ast::ValueType::StrValue(value) => string(value),
+        ast::ValueType::NumberArray(numbers) => {
+            let numbers = numbers
+                .iter()
+                .map(|number| number.get_amount_as_i64().to_string())
+                .collect::<Vec<_>>();
+            format!("({})", numbers.join(", "))
+        }
+        ast::ValueType::EnumVariantArray(variants) => format!("({})", variants.join(", ")),
+        ast::ValueType::NumberComparisonArray(comparisons) => {
+            let comparisons = comparisons
+                .iter()
+                .map(|comparison| {
+                    format!(
+                        "{} {}",
+                        comparison_type(&comparison.comparison_type),
+                        comparison.number.get_amount_as_i64()
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("({})", comparisons.join(", "))
+        }
+    }
+}
+
+pub fn comparison(comparison: &ast::Comparison) -> String {
+    match &comparison.value {
+        // Metadata comparisons are written as `"key" = "value"`, the key taking the place of
+        // the left hand side
+        ast::ValueType::MetadataVariant(metadata) => format!(
+            "{} {} {}",
+            string(&metadata.key),
+            comparison_type(&comparison.comparison),
+            string(&metadata.value)
+        ),
+        value => format!(
+            "{} {} {}",
+            comparison.lhs,
+            comparison_type(&comparison.comparison),
+            value_type(value)
+        ),
+    }
+}
+
+/// Renders a group of conditions, nested groups being parenthesized
+///
+/// ```text
+/// (payment.currency = USD | !(payment.amount < 1000))
+/// ```
+pub fn condition_group(group: &ast::ConditionGroup) -> String {
+    let join = |conditions: &[ast::Condition], separator: &str| {
+        conditions
+            .iter()
+            .map(condition)
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+
+    match group {
+        ast::ConditionGroup::AllOf(conditions) => format!("({})", join(conditions, " & ")),
+        ast::ConditionGroup::AnyOf(conditions) => format!("({})", join(conditions, " | ")),
+        ast::ConditionGroup::Not(negated) => match negated.as_ref() {
+            ast::Condition::Comparison(negated_comparison) => {
+                format!("!({})", comparison(negated_comparison))
+            }
+            ast::Condition::Group(negated_group) => format!("!{}", condition_group(negated_group)),
+        },
+    }
+}
+
+pub fn condition(condition: &ast::Condition) -> String {
+    match condition {
+        ast::Condition::Comparison(condition_comparison) => comparison(condition_comparison),
+        ast::Condition::Group(group) => condition_group(group),
+    }
+}
+
+pub fn if_condition(conditions: &ast::IfCondition) -> String {
+    conditions
+        .iter()
+        .map(condition)
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+fn write_if_statement(out: &mut String, statement: &ast::IfStatement, depth: usize) {
+    let indent = INDENT.repeat(depth);
+    out.push_str(&indent);
+    out.push_str(&if_condition(&statement.condition));
+
+    if let Some(nested) = &statement.nested {
+        out.push_str(" {\n");
+        for nested_statement in nested {
+            write_if_statement(out, nested_statement, depth + 1);
+        }
+        out.push_str(&indent);
+        out.push('}');
+    }
+    out.push('\n');
+}
+
+pub fn if_statement(statement: &ast::IfStatement) -> String {
+    let mut out = String::new();
+    write_if_statement(&mut out, statement, 0);
+    out
+}
+
+pub fn rule<O: EuclidPrintable>(rule: &ast::Rule<O>) -> String {
+    let mut out = format!(
+        "{}: {}\n{{\n",
+        rule.name,
+        rule.connector_selection.print_output()
+    );
+    for statement in &rule.statements {
+        write_if_statement(&mut out, statement, 1);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders the program in the canonical form of the DSL
+pub fn program<O: EuclidPrintable>(program: &ast::Program<O>) -> String {
+    let mut out = format!("default: {}\n", program.default_selection.print_output());
+
+    for program_rule in &program.rules {
+        out.push('\n');
+        out.push_str(&rule(program_rule));
+    }
+    out
+}
+
+#[cfg(all(test, feature = "ast_parser"))]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use common_utils::types::MinorUnit;
+    use proptest::{collection, option, prelude::*};
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::frontend::ast::parser::{self, EuclidParsable};
+
+    fn arb_identifier() -> impl Strategy<Value = String> {
+        "[a-z_][a-z0-9_]{0,12}"
+    }
+
+    fn arb_string() -> impl Strategy<Value = String> {
+        "([^\"\\\\]|\"|\\\\){1,16}"
+    }
+
+    fn arb_number() -> impl Strategy<Value = MinorUnit> {
+        any::<i64>().prop_map(MinorUnit::new)
+    }
+
+    fn arb_comparison_type() -> impl Strategy<Value = ast::ComparisonType> {
+        prop_oneof![
+            Just(ast::ComparisonType::Equal),
+            Just(ast::ComparisonType::NotEqual),
+            Just(ast::ComparisonType::LessThan),
+            Just(ast::ComparisonType::LessThanEqual),
+            Just(ast::ComparisonType::GreaterThan),
+            Just(ast::ComparisonType::GreaterThanEqual),
+        ]
+    }
+
+    fn arb_comparison() -> impl Strategy<Value = ast::Comparison> {
+        let value = prop_oneof![
+            arb_number().prop_map(ast::ValueType::Number),
+            arb_identifier().prop_map(ast::ValueType::EnumVariant),
+            arb_string().prop_map(ast::ValueType::StrValue),
+            collection::vec(arb_number(), 1..4).prop_map(ast::ValueType::NumberArray),
+            collection::vec(arb_identifier(), 1..4).prop_map(ast::ValueType::EnumVariantArray),
+            collection::vec(
+                (arb_comparison_type(), arb_number()).prop_map(|(comparison_type, number)| {
+                    ast::NumberComparison {
+                        comparison_type,
+                        number,
+                    }
+                }),
+                1..4
+            )
+            .prop_map(ast::ValueType::NumberComparisonArray),
+        ];
+
+        let lhs_comparison = (
+            "[a-z_]{1,8}(\\.[a-z_]{1,8}){0,2}",
+            arb_comparison_type(),
+            value,
+        )
+            .prop_map(|(lhs, comparison, value)| ast::Comparison {
+                lhs,
+                comparison,
+                value,
+                metadata: Default::default(),
+            });
+
+        let metadata_comparison = (arb_string(), arb_comparison_type(), arb_string()).prop_map(
+            |(key, comparison, value)| ast::Comparison {
+                lhs: "metadata".to_string(),
+                comparison,
+                value: ast::ValueType::MetadataVariant(ast::MetadataValue { key, value }),
+                metadata: Default::default(),
+            },
+        );
+
+        prop_oneof![3 => lhs_comparison, 1 => metadata_comparison]
+    }
+
+    fn arb_condition() -> impl Strategy<Value = ast::Condition> {
+        let leaf = arb_comparison().prop_map(ast::Condition::Comparison);
+
+        leaf.prop_recursive(3, 12, 3, |inner| {
+            prop_oneof![
+                collection::vec(inner.clone(), 2..4).prop_map(|conditions| ast::Condition::Group(
+                    ast::ConditionGroup::AllOf(conditions)
+                )),
+                collection::vec(inner.clone(), 2..4).prop_map(|conditions| ast::Condition::Group(
+                    ast::ConditionGroup::AnyOf(conditions)
+                )),
+                inner.prop_map(|condition| ast::Condition::Group(ast::ConditionGroup::Not(
+                    Box::new(condition)
+                ))),
+            ]
+        })
+    }
+
+    fn arb_if_statement() -> impl Strategy<Value = ast::IfStatement> {
+        let leaf = collection::vec(arb_condition(), 1..4).prop_map(|condition| ast::IfStatement {
+            condition,
+            nested: None,
+        });
+
+        leaf.prop_recursive(3, 16, 3, |inner| {
+            (
+                collection::vec(arb_condition(), 1..4),
+                option::of(collection::vec(inner, 0..3)),
+            )
+                .prop_map(|(condition, nested)| ast::IfStatement { condition, nested })
+        })
+    }
+
+    fn arb_connector_choice() -> impl Strategy<Value = ast::RoutableConnectorChoice> {
+        let connectors = RoutableConnectors::iter().collect::<Vec<_>>();
+        (
+            proptest::sample::select(connectors),
+            option::of("mca_[a-zA-Z0-9_-]{8,20}"),
+            prop_oneof![
+                Just(ast::RoutableChoiceKind::OnlyConnector),
+                Just(ast::RoutableChoiceKind::FullStruct),
+            ],
+        )
+            .prop_map(|(connector, merchant_connector_id, choice_kind)| {
+                ast::RoutableConnectorChoice {
+                    choice_kind,
+                    connector,
+                    merchant_connector_id: merchant_connector_id
+                        .map(|id| MerchantConnectorAccountId::wrap(id).unwrap()),
+                }
+            })
+    }
+
+    fn arb_connector_selection() -> impl Strategy<Value = ast::ConnectorSelection> {
+        prop_oneof![
+            collection::vec(arb_connector_choice(), 1..4)
+                .prop_map(ast::ConnectorSelection::Priority),
+            collection::vec(
+                (arb_connector_choice(), 0..=100u8)
+                    .prop_map(|(connector, split)| ast::ConnectorVolumeSplit { connector, split }),
+                1..4
+            )
+            .prop_map(ast::ConnectorSelection::VolumeSplit),
+        ]
+    }
+
+    fn arb_dummy_output() -> impl Strategy<Value = DummyOutput> {
+        collection::vec("([^\"\\\\]|\"|\\\\){0,8}", 1..4)
+            .prop_map(|outputs| DummyOutput { outputs })
+    }
+
+    fn arb_program<O: std::fmt::Debug>(
+        output: impl Strategy<Value = O> + Clone,
+    ) -> impl Strategy<Value = ast::Program<O>> {
+        let rule = (
+            arb_identifier(),
+            output.clone(),
+            collection::vec(arb_if_statement(), 1..4),
+        )
+            .prop_map(|(name, connector_selection, statements)| ast::Rule {
+                name,
+                connector_selection,
+                statements,
+            });
+
+        (output, collection::vec(rule, 0..4)).prop_map(|(default_selection, rules)| ast::Program {
+            default_selection,
+            rules,
+            metadata: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_program_is_printed_in_canonical_form() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"] {
+            payment.method = card & "merchant" = "mid" {
+                payment.method.cardtype = (credit, debit)
+                payment.amount = (>= 100, < 1000)
+            }
+            payment.currency /= USD
+        }
+        "#;
+
+        let (_, parsed_program) = parser::program::<DummyOutput>(program_str).expect("Program");
+
+        assert_eq!(
+            program(&parsed_program),
+            concat!(
+                "default: [\"stripe\", \"adyen\"]\n",
+                "\n",
+                "rule_1: [\"stripe\"]\n",
+                "{\n",
+                "    payment.method = card & \"merchant\" = \"mid\" {\n",
+                "        payment.method.cardtype = (credit, debit)\n",
+                "        payment.amount = (>= 100, < 1000)\n",
+                "    }\n",
+                "    payment.currency /= USD\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_condition_groups_are_printed() {
+        let program_str = r#"
+        default: ["stripe"]
+
+        rule_1: ["adyen"] {
+            payment.method = card & ( payment.currency = USD | payment.amount > 1000 )
+                & ! ( payment.method.network = visa & "merchant" = "mid" )
+                & !payment.method.cardtype = debit
+        }
+        "#;
+
+        let (_, parsed_program) = parser::program::<DummyOutput>(program_str).expect("Program");
+
+        assert_eq!(
+            program(&parsed_program),
+            concat!(
+                "default: [\"stripe\"]\n",
+                "\n",
+                "rule_1: [\"adyen\"]\n",
+                "{\n",
+                "    payment.method = card & (payment.currency = USD | payment.amount > 1000)",
+                " & !(payment.method.network = visa & \"merchant\" = \"mid\")",
+                " & !(payment.method.cardtype = debit)\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_connector_selection_is_printed() {
+        let (_, selection) =
+            ast::ConnectorSelection::parse_output("[stripe(mca_1234abcd): 70%, adyen: 30%]")
+                .expect("Connector selection");
+
+        assert_eq!(
+            selection.print_output(),
+            "[stripe(mca_1234abcd): 70%, adyen: 30%]"
+        );
+    }
+
+    #[test]
+    fn test_strings_are_escaped() {
+        let program_str = r#"
+        default: ["say \"hi\"", "back\\slash"]
+
+        rule_1: ["stripe"] {
+            payment.method = card & "quoted \"key\"" = "value \\ \"x\""
+            billing.name = "\"name\""
+        }
+        "#;
+
+        let (_, parsed_program) = parser::program::<DummyOutput>(program_str).expect("Program");
+
+        assert_eq!(
+            parsed_program.default_selection.outputs,
+            vec!["say \"hi\"".to_string(), "back\\slash".to_string()]
+        );
+        assert_eq!(
+            program(&parsed_program),
+            concat!(
+                "default: [\"say \\\"hi\\\"\", \"back\\\\slash\"]\n",
+                "\n",
+                "rule_1: [\"stripe\"]\n",
+                "{\n",
+                "    payment.method = card & \"quoted \\\"key\\\"\" = \"value \\\\ \\\"x\\\"\"\n",
+                "    billing.name = \"\\\"name\\\"\"\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_quoted_merchant_connector_ids_are_parsed() {
+        let (_, selection) =
+            ast::ConnectorSelection::parse_output("[stripe(\"mca_1234abcd\"), adyen]")
+                .expect("Connector selection");
+
+        assert_eq!(selection.print_output(), "[stripe(mca_1234abcd), adyen]");
+        assert!(ast::ConnectorSelection::parse_output("[stripe(\"mca \\\"1\\\"\")]").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_print_parse_roundtrip_dummy_output(generated_program in arb_program(arb_dummy_output())) {
+            let printed = program(&generated_program);
+            let (rest, parsed) = parser::program::<DummyOutput>(&printed).unwrap();
+
+            prop_assert!(rest.trim().is_empty());
+            prop_assert_eq!(
+                serde_json::to_value(&parsed).unwrap(),
+                serde_json::to_value(&generated_program).unwrap()
+            );
+        }
+
+        #[test]
+        fn proptest_print_parse_roundtrip_connector_selection(
+            generated_program in arb_program(arb_connector_selection())
+        ) {
+            let printed = program(&generated_program);
+            let (rest, parsed) = parser::program::<ast::ConnectorSelection>(&printed).unwrap();
+
+            // The parser cannot tell how the connectors were chosen, so the programs are compared
+            // in their serialized form, which leaves out the choice kind
+            prop_assert!(rest.trim().is_empty());
+            prop_assert_eq!(
+                serde_json::to_value(&parsed).unwrap(),
+                serde_json::to_value(&generated_program).unwrap()
+            );
+        }
+    }
+}
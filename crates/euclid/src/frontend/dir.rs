@@ -278,6 +278,34 @@ pub enum DirKeyKind {
         props(Category = "Payment Method Types")
     )]
     OpenBankingType,
+    #[strum(
+        serialize = "card_issuer",
+        detailed_message = "Name of the bank that issued the payment card",
+        props(Category = "Payment Methods")
+    )]
+    #[serde(rename = "card_issuer")]
+    CardIssuer,
+    #[strum(
+        serialize = "card_issuer_country",
+        detailed_message = "Country of the bank that issued the payment card",
+        props(Category = "Payment Methods")
+    )]
+    #[serde(rename = "card_issuer_country")]
+    CardIssuerCountry,
+    #[strum(
+        serialize = "time_of_day",
+        detailed_message = "Time of the payment in the timezone of the merchant, as a 24 hour HHMM number - eg. 1730",
+        props(Category = "Payments")
+    )]
+    #[serde(rename = "time_of_day")]
+    TimeOfDay,
+    #[strum(
+        serialize = "day_of_week",
+        detailed_message = "Day of the week of the payment in the timezone of the merchant",
+        props(Category = "Payments")
+    )]
+    #[serde(rename = "day_of_week")]
+    DayOfWeek,
 }
 
 pub trait EuclidDirFilter: Sized
@@ -327,6 +355,10 @@ impl DirKeyKind {
             Self::CardRedirectType => types::DataType::EnumVariant,
             Self::RealTimePaymentType => types::DataType::EnumVariant,
             Self::OpenBankingType => types::DataType::EnumVariant,
+            Self::CardIssuer => types::DataType::StrValue,
+            Self::CardIssuerCountry => types::DataType::EnumVariant,
+            Self::TimeOfDay => types::DataType::Number,
+            Self::DayOfWeek => types::DataType::EnumVariant,
         }
     }
     pub fn get_value_set(&self) -> Option<Vec<DirValue>> {
@@ -459,6 +491,14 @@ impl DirKeyKind {
                     .map(DirValue::OpenBankingType)
                     .collect(),
             ),
+            Self::CardIssuer => None,
+            Self::CardIssuerCountry => Some(
+                enums::Country::iter()
+                    .map(DirValue::CardIssuerCountry)
+                    .collect(),
+            ),
+            Self::TimeOfDay => None,
+            Self::DayOfWeek => Some(enums::DayOfWeek::iter().map(DirValue::DayOfWeek).collect()),
         }
     }
 }
@@ -528,6 +568,14 @@ pub enum DirValue {
     RealTimePaymentType(enums::RealTimePaymentType),
     #[serde(rename = "open_banking")]
     OpenBankingType(enums::OpenBankingType),
+    #[serde(rename = "card_issuer")]
+    CardIssuer(types::StrValue),
+    #[serde(rename = "card_issuer_country")]
+    CardIssuerCountry(enums::Country),
+    #[serde(rename = "time_of_day")]
+    TimeOfDay(types::NumValue),
+    #[serde(rename = "day_of_week")]
+    DayOfWeek(enums::DayOfWeek),
 }
 
 impl DirValue {
@@ -563,6 +611,10 @@ impl DirValue {
             Self::GiftCardType(_) => (DirKeyKind::GiftCardType, None),
             Self::RealTimePaymentType(_) => (DirKeyKind::RealTimePaymentType, None),
             Self::OpenBankingType(_) => (DirKeyKind::OpenBankingType, None),
+            Self::CardIssuer(_) => (DirKeyKind::CardIssuer, None),
+            Self::CardIssuerCountry(_) => (DirKeyKind::CardIssuerCountry, None),
+            Self::TimeOfDay(_) => (DirKeyKind::TimeOfDay, None),
+            Self::DayOfWeek(_) => (DirKeyKind::DayOfWeek, None),
         };
 
         DirKey::new(kind, data)
@@ -599,12 +651,17 @@ impl DirValue {
             Self::CardRedirectType(_) => None,
             Self::RealTimePaymentType(_) => None,
             Self::OpenBankingType(_) => None,
+            Self::CardIssuer(_) => None,
+            Self::CardIssuerCountry(_) => None,
+            Self::TimeOfDay(_) => None,
+            Self::DayOfWeek(_) => None,
         }
     }
 
     pub fn get_str_val(&self) -> Option<types::StrValue> {
         match self {
             Self::CardBin(val) => Some(val.clone()),
+            Self::CardIssuer(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -612,6 +669,7 @@ impl DirValue {
     pub fn get_num_value(&self) -> Option<types::NumValue> {
         match self {
             Self::PaymentAmount(val) => Some(val.clone()),
+            Self::TimeOfDay(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -645,6 +703,9 @@ impl DirValue {
             (Self::UpiType(ut1), Self::UpiType(ut2)) => ut1 == ut2,
             (Self::VoucherType(vt1), Self::VoucherType(vt2)) => vt1 == vt2,
             (Self::CardRedirectType(crt1), Self::CardRedirectType(crt2)) => crt1 == crt2,
+            (Self::CardIssuer(ci1), Self::CardIssuer(ci2)) => ci1 == ci2,
+            (Self::CardIssuerCountry(c1), Self::CardIssuerCountry(c2)) => c1 == c2,
+            (Self::DayOfWeek(dow1), Self::DayOfWeek(dow2)) => dow1 == dow2,
             _ => false,
         }
     }
@@ -758,7 +819,15 @@ pub struct DirComparison {
     pub metadata: types::Metadata,
 }
 
-pub type DirIfCondition = Vec<DirComparison>;
+#[derive(Debug, Clone)]
+pub enum DirCondition {
+    Comparison(DirComparison),
+    AllOf(Vec<DirCondition>),
+    AnyOf(Vec<DirCondition>),
+    Not(Box<DirCondition>),
+}
+
+pub type DirIfCondition = Vec<DirCondition>;
 
 #[derive(Debug, Clone)]
 pub struct DirIfStatement {
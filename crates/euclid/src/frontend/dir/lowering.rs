@@ -245,6 +245,10 @@ fn lower_value(dir_value: dir::DirValue) -> Result<EuclidValue, AnalysisErrorTyp
         dir::DirValue::BankDebitType(bdt) => EuclidValue::PaymentMethodType(bdt.into()),
         dir::DirValue::RewardType(rt) => EuclidValue::PaymentMethodType(rt.into()),
         dir::DirValue::BusinessLabel(bl) => EuclidValue::BusinessLabel(bl),
+        dir::DirValue::CardIssuer(ci) => EuclidValue::CardIssuer(ci),
+        dir::DirValue::CardIssuerCountry(cic) => EuclidValue::CardIssuerCountry(cic),
+        dir::DirValue::TimeOfDay(tod) => EuclidValue::TimeOfDay(tod),
+        dir::DirValue::DayOfWeek(dow) => EuclidValue::DayOfWeek(dow),
         dir::DirValue::SetupFutureUsage(sfu) => EuclidValue::SetupFutureUsage(sfu),
         dir::DirValue::OpenBankingType(ob) => EuclidValue::PaymentMethodType(ob.into()),
     })
@@ -271,6 +275,31 @@ fn lower_comparison(
     })
 }
 
+fn lower_condition(
+    dir_condition: dir::DirCondition,
+) -> Result<vir::ValuedCondition, AnalysisErrorType> {
+    Ok(match dir_condition {
+        dir::DirCondition::Comparison(comparison) => {
+            vir::ValuedCondition::Comparison(lower_comparison(comparison)?)
+        }
+        dir::DirCondition::AllOf(conditions) => vir::ValuedCondition::AllOf(
+            conditions
+                .into_iter()
+                .map(lower_condition)
+                .collect::<Result<_, _>>()?,
+        ),
+        dir::DirCondition::AnyOf(conditions) => vir::ValuedCondition::AnyOf(
+            conditions
+                .into_iter()
+                .map(lower_condition)
+                .collect::<Result<_, _>>()?,
+        ),
+        dir::DirCondition::Not(condition) => {
+            vir::ValuedCondition::Not(Box::new(lower_condition(*condition)?))
+        }
+    })
+}
+
 fn lower_if_statement(
     dir_if_statement: dir::DirIfStatement,
 ) -> Result<vir::ValuedIfStatement, AnalysisErrorType> {
@@ -278,7 +307,7 @@ fn lower_if_statement(
         condition: dir_if_statement
             .condition
             .into_iter()
-            .map(lower_comparison)
+            .map(lower_condition)
             .collect::<Result<_, _>>()?,
         nested: dir_if_statement
             .nested
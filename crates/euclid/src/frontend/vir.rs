@@ -16,7 +16,15 @@ pub struct ValuedComparison {
     pub metadata: Metadata,
 }
 
-pub type ValuedIfCondition = Vec<ValuedComparison>;
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ValuedCondition {
+    Comparison(ValuedComparison),
+    AllOf(Vec<ValuedCondition>),
+    AnyOf(Vec<ValuedCondition>),
+    Not(Box<ValuedCondition>),
+}
+
+pub type ValuedIfCondition = Vec<ValuedCondition>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ValuedIfStatement {
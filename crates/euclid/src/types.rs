@@ -60,6 +60,14 @@ pub enum EuclidKey {
     BusinessLabel,
     #[strum(serialize = "setup_future_usage")]
     SetupFutureUsage,
+    #[strum(serialize = "card_issuer")]
+    CardIssuer,
+    #[strum(serialize = "card_issuer_country")]
+    CardIssuerCountry,
+    #[strum(serialize = "time_of_day")]
+    TimeOfDay,
+    #[strum(serialize = "day_of_week")]
+    DayOfWeek,
 }
 impl EuclidDirFilter for DummyOutput {
     const ALLOWED: &'static [DirKeyKind] = &[
@@ -77,6 +85,10 @@ impl EuclidDirFilter for DummyOutput {
         DirKeyKind::MandateType,
         DirKeyKind::PaymentType,
         DirKeyKind::SetupFutureUsage,
+        DirKeyKind::CardIssuer,
+        DirKeyKind::CardIssuerCountry,
+        DirKeyKind::TimeOfDay,
+        DirKeyKind::DayOfWeek,
     ];
 }
 impl EuclidAnalysable for DummyOutput {
@@ -104,7 +116,7 @@ impl EuclidAnalysable for DummyOutput {
             .collect()
     }
 }
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct DummyOutput {
     pub outputs: Vec<String>,
 }
@@ -138,6 +150,10 @@ impl EuclidKey {
             Self::PaymentType => DataType::EnumVariant,
             Self::BusinessLabel => DataType::StrValue,
             Self::SetupFutureUsage => DataType::EnumVariant,
+            Self::CardIssuer => DataType::StrValue,
+            Self::CardIssuerCountry => DataType::EnumVariant,
+            Self::TimeOfDay => DataType::Number,
+            Self::DayOfWeek => DataType::EnumVariant,
         }
     }
 }
@@ -253,12 +269,17 @@ pub enum EuclidValue {
     BillingCountry(enums::Country),
     BusinessLabel(StrValue),
     SetupFutureUsage(enums::SetupFutureUsage),
+    CardIssuer(StrValue),
+    CardIssuerCountry(enums::Country),
+    TimeOfDay(NumValue),
+    DayOfWeek(enums::DayOfWeek),
 }
 
 impl EuclidValue {
     pub fn get_num_value(&self) -> Option<NumValue> {
         match self {
             Self::PaymentAmount(val) => Some(val.clone()),
+            Self::TimeOfDay(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -281,6 +302,10 @@ impl EuclidValue {
             Self::BillingCountry(_) => EuclidKey::BillingCountry,
             Self::BusinessLabel(_) => EuclidKey::BusinessLabel,
             Self::SetupFutureUsage(_) => EuclidKey::SetupFutureUsage,
+            Self::CardIssuer(_) => EuclidKey::CardIssuer,
+            Self::CardIssuerCountry(_) => EuclidKey::CardIssuerCountry,
+            Self::TimeOfDay(_) => EuclidKey::TimeOfDay,
+            Self::DayOfWeek(_) => EuclidKey::DayOfWeek,
         }
     }
 }
@@ -127,7 +127,7 @@ pub fn get_valid_connectors_for_rule(rule: JsValue) -> JsResult {
         .collect();
     let mut invalid_connectors: HashSet<ast::ConnectorChoice> = HashSet::new();
 
-    let mut ctx_manager = state_machine::RuleContextManager::new(&dir_rule, &[]);
+    let mut ctx_manager = state_machine::RuleContextManager::new(&dir_rule, &[]).err_to_js()?;
 
     let dummy_meta = HashMap::new();
 
@@ -173,11 +173,37 @@ pub fn get_valid_connectors_for_rule(rule: JsValue) -> JsResult {
     Ok(serde_wasm_bindgen::to_value(&valid_connectors)?)
 }
 
+/// Analyses the program, returning the warnings about rules that do not behave the way they were
+/// likely meant to
 #[wasm_bindgen(js_name = analyzeProgram)]
 pub fn analyze_program(js_program: JsValue) -> JsResult {
     let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(js_program)?;
-    analyzer::analyze(program, SEED_DATA.get().map(|sd| &sd.cgraph)).err_to_js()?;
-    Ok(JsValue::NULL)
+    let (_, warnings) =
+        analyzer::analyze_with_warnings(program, SEED_DATA.get().map(|sd| &sd.cgraph))
+            .err_to_js()?;
+    Ok(serde_wasm_bindgen::to_value(&warnings)?)
+}
+
+/// Renders the program in the routing DSL, the rendered program parses back into the same program
+#[wasm_bindgen(js_name = printProgram)]
+pub fn print_program(js_program: JsValue) -> JsResult {
+    let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(js_program)?;
+    let program = ast::Program {
+        default_selection: ast::ConnectorSelection::from(program.default_selection),
+        rules: program
+            .rules
+            .into_iter()
+            .map(|rule| ast::Rule {
+                name: rule.name,
+                connector_selection: rule.connector_selection.into(),
+                statements: rule.statements,
+            })
+            .collect(),
+        metadata: program.metadata,
+    };
+    Ok(serde_wasm_bindgen::to_value(&ast::printer::program(
+        &program,
+    ))?)
 }
 
 #[wasm_bindgen(js_name = runProgram)]
@@ -262,10 +288,14 @@ pub fn get_variant_values(key: &str) -> Result<JsValue, JsValue> {
         dir::DirKeyKind::BankDebitType => dir_enums::BankDebitType::VARIANTS,
         dir::DirKeyKind::RealTimePaymentType => dir_enums::RealTimePaymentType::VARIANTS,
         dir::DirKeyKind::OpenBankingType => dir_enums::OpenBankingType::VARIANTS,
+        dir::DirKeyKind::CardIssuerCountry => dir_enums::Country::VARIANTS,
+        dir::DirKeyKind::DayOfWeek => dir_enums::DayOfWeek::VARIANTS,
 
         dir::DirKeyKind::PaymentAmount
         | dir::DirKeyKind::Connector
         | dir::DirKeyKind::CardBin
+        | dir::DirKeyKind::CardIssuer
+        | dir::DirKeyKind::TimeOfDay
         | dir::DirKeyKind::BusinessLabel
         | dir::DirKeyKind::MetaData => Err("Key does not have variants".to_string())?,
     };
@@ -29,6 +29,17 @@ impl EncryptionManagementInterface for AwsKmsClient {
 
 #[async_trait::async_trait]
 impl SecretManagementInterface for AwsKmsClient {
+    async fn store_secret(
+        &self,
+        _name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.encrypt(input.peek())
+            .await
+            .change_context(SecretsManagementError::StoreSecretFailed)
+            .map(Into::into)
+    }
+
     async fn get_secret(
         &self,
         input: Secret<String>,
@@ -38,4 +49,15 @@ impl SecretManagementInterface for AwsKmsClient {
             .change_context(SecretsManagementError::FetchSecretFailed)
             .map(Into::into)
     }
+
+    async fn rotate_secret(
+        &self,
+        _reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.encrypt(new_value.peek())
+            .await
+            .change_context(SecretsManagementError::RotateSecretFailed)
+            .map(Into::into)
+    }
 }
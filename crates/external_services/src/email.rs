@@ -1,4 +1,4 @@
-//! Interactions with the AWS SES SDK
+//! Interactions with email service providers
 
 use aws_sdk_sesv2::types::Body;
 use common_utils::{errors::CustomResult, pii};
@@ -7,6 +7,12 @@ use serde::Deserialize;
 /// Implementation of aws ses client
 pub mod ses;
 
+/// Implementation of SMTP client
+pub mod smtp;
+
+/// Implementation of a client that writes emails to files instead of sending them
+pub mod file_sink;
+
 /// Custom Result type alias for Email operations.
 pub type EmailResult<T> = CustomResult<T, EmailError>;
 
@@ -118,6 +124,13 @@ pub enum AvailableEmailClients {
     #[default]
     /// AWS ses email client
     SES,
+
+    /// SMTP email client
+    SMTP,
+
+    /// Email client that writes emails to files in a directory
+    #[serde(rename = "FILE_SINK")]
+    FileSink,
 }
 
 /// Struct that contains the settings required to construct an EmailClient.
@@ -135,10 +148,37 @@ pub struct EmailSettings {
     /// Configs related to AWS Simple Email Service
     pub aws_ses: Option<ses::SESConfig>,
 
+    /// Configs related to the SMTP server
+    pub smtp: Option<smtp::SmtpServerConfig>,
+
+    /// Configs related to the file sink
+    pub file_sink: Option<file_sink::FileSinkConfig>,
+
     /// The active email client to use
     pub active_email_client: AvailableEmailClients,
 }
 
+impl EmailSettings {
+    /// Validates the configuration of the active email client
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self.active_email_client {
+            AvailableEmailClients::SES => Ok(()),
+            AvailableEmailClients::SMTP => self
+                .smtp
+                .as_ref()
+                .ok_or("SMTP email client is selected, but SMTP configuration is missing")?
+                .validate(),
+            AvailableEmailClients::FileSink => self
+                .file_sink
+                .as_ref()
+                .ok_or(
+                    "File sink email client is selected, but file sink configuration is missing",
+                )?
+                .validate(),
+        }
+    }
+}
+
 /// Errors that could occur from EmailClient.
 #[derive(Debug, thiserror::Error)]
 pub enum EmailError {
@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use common_utils::{consts, date_time, errors::CustomResult, pii};
+use error_stack::ResultExt;
+use masking::PeekInterface;
+
+use crate::email::{EmailClient, EmailError, EmailResult, EmailSettings, IntermediateString};
+
+/// Client that writes emails to files in a directory instead of sending them, meant for local
+/// development and tests
+#[derive(Debug, Clone)]
+pub struct FileSinkEmailClient {
+    sender: String,
+    directory: PathBuf,
+}
+
+/// Struct that contains the configs required to construct a file sink email client
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FileSinkConfig {
+    /// Directory the emails are written to, created if it does not exist
+    pub directory: PathBuf,
+}
+
+impl FileSinkConfig {
+    /// Validates the file sink configuration
+    pub fn validate(&self) -> Result<(), &'static str> {
+        common_utils::fp_utils::when(self.directory.as_os_str().is_empty(), || {
+            Err("file sink directory must not be empty")
+        })
+    }
+}
+
+/// An email as written to the file sink
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SinkedEmail {
+    /// Sender of the email
+    pub sender: String,
+
+    /// Recipient of the email
+    pub recipient: String,
+
+    /// Subject of the email
+    pub subject: String,
+
+    /// Rendered HTML body of the email
+    pub body: String,
+}
+
+impl FileSinkEmailClient {
+    /// Constructs a new file sink client
+    pub fn create(conf: &EmailSettings) -> CustomResult<Self, EmailError> {
+        let file_sink_config = conf
+            .file_sink
+            .as_ref()
+            .ok_or(EmailError::ClientBuildingFailure)
+            .attach_printable(
+                "The selected email client is file sink, but configuration is missing",
+            )?;
+
+        Ok(Self {
+            sender: conf.sender_email.clone(),
+            directory: file_sink_config.directory.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailClient for FileSinkEmailClient {
+    type RichText = String;
+
+    fn convert_to_rich_text(
+        &self,
+        intermediate_string: IntermediateString,
+    ) -> CustomResult<Self::RichText, EmailError> {
+        Ok(intermediate_string.into_inner())
+    }
+
+    async fn send_email(
+        &self,
+        recipient: pii::Email,
+        subject: String,
+        body: Self::RichText,
+        _proxy_url: Option<&String>,
+    ) -> EmailResult<()> {
+        let email = SinkedEmail {
+            sender: self.sender.clone(),
+            recipient: recipient.peek().clone(),
+            subject,
+            body,
+        };
+        let contents = serde_json::to_vec_pretty(&email)
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to serialize email")?;
+
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to create the file sink directory")?;
+
+        // Prefixing the file name with the timestamp keeps the emails sorted in the order they
+        // were sent
+        let file_name = format!(
+            "{}.json",
+            common_utils::generate_id(
+                consts::ID_LENGTH,
+                &date_time::now_unix_timestamp().to_string()
+            )
+        );
+        tokio::fs::write(self.directory.join(file_name), contents)
+            .await
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to write email to the file sink directory")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_sink_writes_email() {
+        let directory = std::env::temp_dir().join(common_utils::generate_id(10, "file_sink"));
+        let client = FileSinkEmailClient {
+            sender: "no-reply@example.com".to_string(),
+            directory: directory.clone(),
+        };
+
+        client
+            .send_email(
+                pii::Email::try_from("user@example.com".to_string()).unwrap(),
+                "Welcome".to_string(),
+                "<p>Hello</p>".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let mut entries = std::fs::read_dir(&directory).unwrap();
+        let file = entries.next().unwrap().unwrap();
+        assert!(entries.next().is_none());
+
+        let email: SinkedEmail =
+            serde_json::from_slice(&std::fs::read(file.path()).unwrap()).unwrap();
+        assert_eq!(email.recipient, "user@example.com");
+        assert_eq!(email.subject, "Welcome");
+        assert_eq!(email.body, "<p>Hello</p>");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use common_utils::{errors::CustomResult, pii};
+use error_stack::{report, ResultExt};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::{authentication::Credentials, PoolConfig},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use masking::{ExposeInterface, PeekInterface, Secret};
+
+use crate::email::{EmailClient, EmailError, EmailResult, EmailSettings, IntermediateString};
+
+/// Client for sending emails through an SMTP server
+#[derive(Debug, Clone)]
+pub struct SmtpServer {
+    sender: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+/// Struct that contains the SMTP server specific configs required to construct an SMTP email
+/// client
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct SmtpServerConfig {
+    /// Hostname of the SMTP server, also used to validate its TLS certificate
+    pub host: String,
+
+    /// Port of the SMTP server, defaults to the standard port of the connection type
+    pub port: Option<u16>,
+
+    /// How the connection to the SMTP server is secured
+    #[serde(default)]
+    pub connection: SmtpConnection,
+
+    /// Username to authenticate with, authentication is skipped if not set
+    pub username: Option<Secret<String>>,
+
+    /// Password to authenticate with
+    pub password: Option<Secret<String>>,
+
+    /// Timeout in seconds for each command sent to the SMTP server
+    #[serde(default = "SmtpServerConfig::default_timeout")]
+    pub timeout: u64,
+
+    /// Maximum number of connections kept open to the SMTP server
+    #[serde(default = "SmtpServerConfig::default_max_connections")]
+    pub max_connections: u32,
+
+    /// Time in seconds after which an idle connection to the SMTP server is closed
+    #[serde(default = "SmtpServerConfig::default_idle_timeout")]
+    pub idle_timeout: u64,
+}
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpConnection {
+    /// Connect without encryption and upgrade the connection with STARTTLS, on port 587 by
+    /// default. Sending fails if the server does not support STARTTLS.
+    #[default]
+    StartTls,
+
+    /// Connect with implicit TLS, on port 465 by default
+    Tls,
+
+    /// Connect without encryption, on port 25 by default. Only meant for local SMTP servers.
+    Plaintext,
+}
+
+impl SmtpServerConfig {
+    fn default_timeout() -> u64 {
+        60
+    }
+
+    fn default_max_connections() -> u32 {
+        10
+    }
+
+    fn default_idle_timeout() -> u64 {
+        60
+    }
+
+    /// Validates the SMTP server configuration
+    pub fn validate(&self) -> Result<(), &'static str> {
+        use common_utils::fp_utils::when;
+
+        when(self.host.is_empty(), || Err("SMTP host must not be empty"))?;
+
+        when(self.username.is_some() != self.password.is_some(), || {
+            Err("SMTP username and password must be set together")
+        })?;
+
+        when(self.max_connections == 0, || {
+            Err("SMTP max connections must be greater than zero")
+        })
+    }
+}
+
+impl SmtpServer {
+    /// Constructs a new SMTP client, connections to the server are opened when emails are sent
+    pub fn create(conf: &EmailSettings) -> CustomResult<Self, EmailError> {
+        let smtp_config = conf
+            .smtp
+            .as_ref()
+            .ok_or(EmailError::ClientBuildingFailure)
+            .attach_printable("The selected email client is SMTP, but configuration is missing")?;
+
+        let sender = conf
+            .sender_email
+            .parse::<Mailbox>()
+            .change_context(EmailError::ClientBuildingFailure)
+            .attach_printable("Invalid sender email")?;
+
+        let mut builder = match smtp_config.connection {
+            SmtpConnection::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp_config.host)
+                    .change_context(EmailError::ClientBuildingFailure)?
+            }
+            SmtpConnection::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp_config.host)
+                .change_context(EmailError::ClientBuildingFailure)?,
+            SmtpConnection::Plaintext => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp_config.host)
+            }
+        }
+        .timeout(Some(Duration::from_secs(smtp_config.timeout)))
+        .pool_config(
+            PoolConfig::new()
+                .max_size(smtp_config.max_connections)
+                .idle_timeout(Duration::from_secs(smtp_config.idle_timeout)),
+        );
+
+        if let Some(port) = smtp_config.port {
+            builder = builder.port(port);
+        }
+
+        match (&smtp_config.username, &smtp_config.password) {
+            (Some(username), Some(password)) => {
+                builder = builder.credentials(Credentials::new(
+                    username.clone().expose(),
+                    password.clone().expose(),
+                ));
+            }
+            (None, None) => {}
+            _ => Err(report!(EmailError::ClientBuildingFailure)
+                .attach_printable("SMTP username and password must be set together"))?,
+        }
+
+        Ok(Self {
+            sender,
+            transport: builder.build(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailClient for SmtpServer {
+    type RichText = String;
+
+    fn convert_to_rich_text(
+        &self,
+        intermediate_string: IntermediateString,
+    ) -> CustomResult<Self::RichText, EmailError> {
+        Ok(intermediate_string.into_inner())
+    }
+
+    async fn send_email(
+        &self,
+        recipient: pii::Email,
+        subject: String,
+        body: Self::RichText,
+        _proxy_url: Option<&String>,
+    ) -> EmailResult<()> {
+        let recipient = recipient
+            .peek()
+            .parse::<Mailbox>()
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Invalid recipient email")?;
+
+        let message = Message::builder()
+            .from(self.sender.clone())
+            .to(recipient)
+            .subject(subject)
+            .header(ContentType::TEXT_HTML)
+            .body(body)
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to build email message")?;
+
+        self.transport
+            .send(message)
+            .await
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to send email through the SMTP server")?;
+
+        Ok(())
+    }
+}
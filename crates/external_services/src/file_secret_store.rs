@@ -0,0 +1,5 @@
+//! Secrets stored in an encrypted local bundle file or in environment variables
+
+pub mod core;
+
+pub mod implementers;
@@ -0,0 +1,336 @@
+//! Secrets stored in an encrypted local bundle file or in environment variables
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use common_utils::errors::CustomResult;
+use error_stack::{report, ResultExt};
+
+use crate::local_key::{
+    self,
+    core::{LocalKeyClient, LocalKeyConfig},
+};
+
+/// Prefix of the references to secrets read from environment variables
+const ENV_PREFIX: &str = "env:";
+
+/// Prefix of the references to secrets read from the bundle file
+const BUNDLE_PREFIX: &str = "bundle:";
+
+/// Configuration parameters required for constructing a [`FileSecretStore`].
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct FileSecretStoreConfig {
+    /// Path of the JSON file mapping the names of the secrets to their encrypted values, created
+    /// when a secret is first stored
+    pub bundle_file: PathBuf,
+
+    /// Path of the master key file the secrets in the bundle are encrypted with
+    pub key_file: PathBuf,
+}
+
+impl FileSecretStoreConfig {
+    /// Verifies that the [`FileSecretStore`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        use common_utils::fp_utils::when;
+
+        when(self.bundle_file.as_os_str().is_empty(), || {
+            Err("Secret bundle file path must not be empty")
+        })?;
+
+        when(self.key_file.as_os_str().is_empty(), || {
+            Err("Secret bundle master key file path must not be empty")
+        })
+    }
+}
+
+/// A reference to a secret, as specified in the configuration in place of the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretReference<'a> {
+    /// `env:<name>`, the secret is read from the environment variable with the name
+    Env(&'a str),
+
+    /// `bundle:<name>`, the secret is read from the entry of the bundle file with the name
+    Bundle(&'a str),
+}
+
+impl<'a> SecretReference<'a> {
+    /// Parses a reference to a secret of the form `env:<name>` or `bundle:<name>`
+    pub fn parse(reference: &'a str) -> CustomResult<Self, FileSecretStoreError> {
+        if let Some(name) = reference.strip_prefix(ENV_PREFIX) {
+            Ok(Self::Env(name))
+        } else if let Some(name) = reference.strip_prefix(BUNDLE_PREFIX) {
+            Ok(Self::Bundle(name))
+        } else {
+            Err(report!(FileSecretStoreError::InvalidReference)).attach_printable(
+                "Secret reference must be of the form `env:<name>` or `bundle:<name>`",
+            )
+        }
+    }
+
+    /// Returns the name of the environment variable or bundle entry
+    pub fn get_name(&self) -> &'a str {
+        match self {
+            Self::Env(name) | Self::Bundle(name) => name,
+        }
+    }
+}
+
+/// Client for reading secrets from environment variables, and reading and writing secrets in a
+/// bundle file encrypted with the master keys in a local key file.
+#[derive(Debug)]
+pub struct FileSecretStore {
+    bundle_file: PathBuf,
+    client: LocalKeyClient,
+    bundle: Mutex<BTreeMap<String, String>>,
+}
+
+impl FileSecretStore {
+    /// Constructs a new file secret store, reading the bundle file if it exists.
+    pub fn new(config: &FileSecretStoreConfig) -> CustomResult<Self, FileSecretStoreError> {
+        let client = LocalKeyClient::new(&LocalKeyConfig {
+            key_file: config.key_file.clone(),
+        })
+        .change_context(FileSecretStoreError::ClientCreationFailed)?;
+
+        Ok(Self {
+            bundle_file: config.bundle_file.clone(),
+            client,
+            bundle: Mutex::new(Self::read_bundle(&config.bundle_file)?),
+        })
+    }
+
+    /// Retrieves the secret the reference refers to
+    pub fn get(&self, reference: &str) -> CustomResult<String, FileSecretStoreError> {
+        match SecretReference::parse(reference)? {
+            SecretReference::Env(name) => std::env::var(name)
+                .change_context(FileSecretStoreError::SecretNotFound)
+                .attach_printable_lazy(|| format!("Environment variable {name} is not set")),
+            SecretReference::Bundle(name) => {
+                let encrypted = self
+                    .lock_bundle()?
+                    .get(name)
+                    .cloned()
+                    .ok_or(report!(FileSecretStoreError::SecretNotFound))
+                    .attach_printable_lazy(|| format!("Secret {name} is missing in the bundle"))?;
+
+                let decrypted = self
+                    .client
+                    .decrypt(encrypted)
+                    .change_context(FileSecretStoreError::DecryptionFailed)
+                    .attach_printable_lazy(|| format!("Failed to decrypt secret {name}"))?;
+
+                String::from_utf8(decrypted)
+                    .change_context(FileSecretStoreError::Utf8DecodingFailed)
+            }
+        }
+    }
+
+    /// Encrypts the secret and stores it in the bundle file under the specified name, replacing
+    /// the existing secret with the name. Returns the reference to the stored secret.
+    pub fn store(&self, name: &str, value: &str) -> CustomResult<String, FileSecretStoreError> {
+        common_utils::fp_utils::when(name.is_empty(), || {
+            Err(report!(FileSecretStoreError::InvalidReference))
+                .attach_printable("Secret name must not be empty")
+        })?;
+
+        let encrypted = self
+            .client
+            .encrypt(value)
+            .change_context(FileSecretStoreError::EncryptionFailed)?;
+
+        let mut bundle = self.lock_bundle()?;
+        let mut updated_bundle = bundle.clone();
+        updated_bundle.insert(name.to_owned(), encrypted);
+
+        // The bundle is written before being updated in memory, so that the secrets served by
+        // the store are always persisted
+        Self::write_bundle(&self.bundle_file, &updated_bundle)?;
+        *bundle = updated_bundle;
+
+        Ok(format!("{BUNDLE_PREFIX}{name}"))
+    }
+
+    /// Replaces the secret the reference refers to with the new value, and returns the reference
+    /// to the new secret. Secrets read from environment variables are moved to the bundle file,
+    /// under the name of the environment variable.
+    pub fn rotate(
+        &self,
+        reference: &str,
+        value: &str,
+    ) -> CustomResult<String, FileSecretStoreError> {
+        self.store(SecretReference::parse(reference)?.get_name(), value)
+    }
+
+    /// Re-encrypts the secrets in the bundle file with the active key, to be used for migrating
+    /// the secrets encrypted with older keys after the master keys are rotated. Returns the
+    /// number of re-encrypted secrets.
+    pub fn re_encrypt(&self) -> CustomResult<usize, FileSecretStoreError> {
+        let mut bundle = self.lock_bundle()?;
+        let updated_bundle = bundle
+            .iter()
+            .map(|(name, encrypted)| {
+                self.client
+                    .re_encrypt(encrypted)
+                    .change_context(FileSecretStoreError::EncryptionFailed)
+                    .attach_printable_lazy(|| format!("Failed to re-encrypt secret {name}"))
+                    .map(|re_encrypted| (name.clone(), re_encrypted))
+            })
+            .collect::<CustomResult<BTreeMap<_, _>, _>>()?;
+
+        Self::write_bundle(&self.bundle_file, &updated_bundle)?;
+        let re_encrypted_secrets = updated_bundle.len();
+        *bundle = updated_bundle;
+
+        Ok(re_encrypted_secrets)
+    }
+
+    fn lock_bundle(
+        &self,
+    ) -> CustomResult<std::sync::MutexGuard<'_, BTreeMap<String, String>>, FileSecretStoreError>
+    {
+        self.bundle
+            .lock()
+            .map_err(|_| report!(FileSecretStoreError::BundleLockPoisoned))
+    }
+
+    fn read_bundle(path: &Path) -> CustomResult<BTreeMap<String, String>, FileSecretStoreError> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(BTreeMap::new())
+            }
+            Err(error) => {
+                return Err(report!(error)
+                    .change_context(FileSecretStoreError::BundleReadFailed)
+                    .attach_printable(format!("Failed to read bundle file {}", path.display())))
+            }
+        };
+
+        serde_json::from_slice(&contents)
+            .change_context(FileSecretStoreError::BundleParsingFailed)
+            .attach_printable_lazy(|| format!("Failed to parse bundle file {}", path.display()))
+    }
+
+    fn write_bundle(
+        path: &Path,
+        bundle: &BTreeMap<String, String>,
+    ) -> CustomResult<(), FileSecretStoreError> {
+        let contents = serde_json::to_vec_pretty(bundle)
+            .change_context(FileSecretStoreError::BundleWriteFailed)?;
+
+        local_key::core::write_private_file(path, &contents)
+            .change_context(FileSecretStoreError::BundleWriteFailed)
+            .attach_printable_lazy(|| format!("Failed to write bundle file {}", path.display()))
+    }
+}
+
+/// Errors that could occur during file secret store operations.
+#[derive(Debug, thiserror::Error)]
+pub enum FileSecretStoreError {
+    /// An error occurred when creating the client for the master keys.
+    #[error("Failed to create the master key client")]
+    ClientCreationFailed,
+
+    /// An error occurred when reading the bundle file.
+    #[error("Failed to read the secret bundle file")]
+    BundleReadFailed,
+
+    /// An error occurred when parsing the bundle file.
+    #[error("Failed to parse the secret bundle file")]
+    BundleParsingFailed,
+
+    /// An error occurred when writing the bundle file.
+    #[error("Failed to write the secret bundle file")]
+    BundleWriteFailed,
+
+    /// A thread panicked while holding the lock on the bundle.
+    #[error("The lock on the secret bundle is poisoned")]
+    BundleLockPoisoned,
+
+    /// The reference is not of the form `env:<name>` or `bundle:<name>`.
+    #[error("Invalid secret reference")]
+    InvalidReference,
+
+    /// The secret the reference refers to does not exist.
+    #[error("Secret not found")]
+    SecretNotFound,
+
+    /// An error occurred when encrypting the secret.
+    #[error("Failed to encrypt the secret")]
+    EncryptionFailed,
+
+    /// An error occurred when decrypting the secret.
+    #[error("Failed to decrypt the secret")]
+    DecryptionFailed,
+
+    /// An error occurred UTF-8 decoding the decrypted secret.
+    #[error("Failed to UTF-8 decode the decrypted secret")]
+    Utf8DecodingFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::local_key::core::LocalKeyFile;
+
+    #[test]
+    fn test_secrets_are_stored_and_rotated() {
+        let directory =
+            std::env::temp_dir().join(common_utils::generate_id(10, "file_secret_store"));
+        std::fs::create_dir_all(&directory).unwrap();
+        let config = FileSecretStoreConfig {
+            bundle_file: directory.join("secrets.json"),
+            key_file: directory.join("master_keys.json"),
+        };
+        LocalKeyFile::generate().write(&config.key_file).unwrap();
+
+        let store = FileSecretStore::new(&config).unwrap();
+        let reference = store.store("jwt_secret", "secret").unwrap();
+        assert_eq!(reference, "bundle:jwt_secret");
+        assert_eq!(store.get(&reference).unwrap(), "secret");
+
+        assert_eq!(store.rotate(&reference, "rotated").unwrap(), reference);
+        // The rotated secret is persisted in the bundle file
+        let store = FileSecretStore::new(&config).unwrap();
+        assert_eq!(store.get(&reference).unwrap(), "rotated");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&config.bundle_file)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let env_var = common_utils::generate_id(10, "FILE_SECRET_STORE");
+        std::env::set_var(&env_var, "from_env");
+        let env_reference = format!("env:{env_var}");
+        assert_eq!(store.get(&env_reference).unwrap(), "from_env");
+        assert_eq!(
+            store.rotate(&env_reference, "moved").unwrap(),
+            format!("bundle:{env_var}")
+        );
+
+        assert!(store.get("bundle:missing").is_err());
+        assert!(store.get("jwt_secret").is_err());
+
+        let mut key_file = LocalKeyFile::read(&config.key_file).unwrap();
+        assert_eq!(key_file.rotate(), 2);
+        key_file.write(&config.key_file).unwrap();
+        let store = FileSecretStore::new(&config).unwrap();
+        assert_eq!(store.re_encrypt().unwrap(), 2);
+        for encrypted in store.lock_bundle().unwrap().values() {
+            assert_eq!(LocalKeyClient::get_key_version(encrypted).unwrap(), 2);
+        }
+        assert_eq!(store.get(&reference).unwrap(), "rotated");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+//! Trait implementations for file secret store client
+
+use common_utils::errors::CustomResult;
+use error_stack::ResultExt;
+use hyperswitch_interfaces::secrets_interface::{
+    SecretManagementInterface, SecretsManagementError,
+};
+use masking::{PeekInterface, Secret};
+
+use crate::file_secret_store::core::FileSecretStore;
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for FileSecretStore {
+    async fn store_secret(
+        &self,
+        name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.store(name, input.peek())
+            .change_context(SecretsManagementError::StoreSecretFailed)
+            .map(Into::into)
+    }
+
+    async fn get_secret(
+        &self,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.get(input.peek())
+            .change_context(SecretsManagementError::FetchSecretFailed)
+            .map(Into::into)
+    }
+
+    async fn rotate_secret(
+        &self,
+        reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.rotate(reference.peek(), new_value.peek())
+            .change_context(SecretsManagementError::RotateSecretFailed)
+            .map(Into::into)
+    }
+}
@@ -9,10 +9,12 @@ use std::{
 
 use common_utils::errors::CustomResult;
 
-/// Includes functionality for AWS S3 storage operations.
+/// Includes functionality for AWS S3 and S3-compatible storage operations.
 #[cfg(feature = "aws_s3")]
 mod aws_s3;
 
+mod content_addressed;
+
 mod file_system;
 
 /// Enum representing different file storage configurations, allowing for multiple storage schemes.
@@ -26,9 +28,21 @@ pub enum FileStorageConfig {
         /// Configuration for AWS S3 file storage.
         aws_s3: aws_s3::AwsFileStorageConfig,
     },
+    /// S3-compatible endpoint storage configuration.
+    #[cfg(feature = "aws_s3")]
+    S3Compatible {
+        /// Configuration for S3-compatible file storage.
+        s3_compatible: aws_s3::S3CompatibleFileStorageConfig,
+    },
     /// Local file system storage configuration.
     #[default]
     FileSystem,
+    /// Content-addressed local file system storage configuration.
+    ContentAddressed {
+        /// Configuration for content-addressed file storage.
+        #[serde(default)]
+        content_addressed: content_addressed::ContentAddressedStorageConfig,
+    },
 }
 
 impl FileStorageConfig {
@@ -37,7 +51,10 @@ impl FileStorageConfig {
         match self {
             #[cfg(feature = "aws_s3")]
             Self::AwsS3 { aws_s3 } => aws_s3.validate(),
+            #[cfg(feature = "aws_s3")]
+            Self::S3Compatible { s3_compatible } => s3_compatible.validate(),
             Self::FileSystem => Ok(()),
+            Self::ContentAddressed { content_addressed } => content_addressed.validate(),
         }
     }
 
@@ -46,7 +63,14 @@ impl FileStorageConfig {
         match self {
             #[cfg(feature = "aws_s3")]
             Self::AwsS3 { aws_s3 } => Arc::new(aws_s3::AwsFileStorageClient::new(aws_s3).await),
+            #[cfg(feature = "aws_s3")]
+            Self::S3Compatible { s3_compatible } => Arc::new(
+                aws_s3::AwsFileStorageClient::new_s3_compatible(s3_compatible),
+            ),
             Self::FileSystem => Arc::new(file_system::FileSystem),
+            Self::ContentAddressed { content_addressed } => Arc::new(
+                content_addressed::ContentAddressedStorage::new(content_addressed),
+            ),
         }
     }
 }
@@ -66,10 +90,30 @@ pub trait FileStorageInterface: dyn_clone::DynClone + Sync + Send {
 
     /// Retrieves a file from the selected storage scheme.
     async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError>;
+
+    /// Starts uploading a file in parts, for files too large to be held in memory at once. The
+    /// file is stored under the file key once the upload is completed.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<Box<dyn MultipartUpload>, FileStorageError>;
 }
 
 dyn_clone::clone_trait_object!(FileStorageInterface);
 
+/// A file being uploaded in parts, started with [`FileStorageInterface::start_multipart_upload`]
+#[async_trait::async_trait]
+pub trait MultipartUpload: Send {
+    /// Uploads the next part of the file.
+    async fn upload_part(&mut self, part: Vec<u8>) -> CustomResult<(), FileStorageError>;
+
+    /// Completes the upload, storing the file made of the uploaded parts.
+    async fn complete(self: Box<Self>) -> CustomResult<(), FileStorageError>;
+
+    /// Aborts the upload, discarding the uploaded parts.
+    async fn abort(self: Box<Self>) -> CustomResult<(), FileStorageError>;
+}
+
 /// Error thrown when the file storage config is invalid
 #[derive(Debug, Clone)]
 pub struct InvalidFileStorageConfig(&'static str);
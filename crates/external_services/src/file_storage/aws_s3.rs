@@ -1,16 +1,22 @@
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_s3::{
+    config::Credentials,
     operation::{
         delete_object::DeleteObjectError, get_object::GetObjectError, put_object::PutObjectError,
     },
+    types::{CompletedMultipartUpload, CompletedPart},
     Client,
 };
 use aws_sdk_sts::config::Region;
 use common_utils::{errors::CustomResult, ext_traits::ConfigExt};
-use error_stack::ResultExt;
+use error_stack::{report, ResultExt};
+use masking::{PeekInterface, Secret};
 
 use super::InvalidFileStorageConfig;
-use crate::file_storage::{FileStorageError, FileStorageInterface};
+use crate::file_storage::{FileStorageError, FileStorageInterface, MultipartUpload};
+
+/// Minimum size of the parts of a multipart upload, other than the last part
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
 
 /// Configuration for AWS S3 file storage.
 #[derive(Debug, serde::Deserialize, Clone, Default)]
@@ -39,6 +45,73 @@ impl AwsFileStorageConfig {
     }
 }
 
+/// Configuration for file storage on any S3-compatible endpoint, such as MinIO.
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct S3CompatibleFileStorageConfig {
+    /// The URL of the S3-compatible endpoint
+    endpoint_url: String,
+    /// The region to sign requests for, most S3-compatible endpoints accept any region
+    region: String,
+    /// The bucket to send file uploads
+    bucket_name: String,
+    /// The access key ID to authenticate requests with
+    access_key_id: Secret<String>,
+    /// The secret access key to authenticate requests with
+    secret_access_key: Secret<String>,
+    /// Whether the bucket is addressed in the path of the URL instead of its host name, which
+    /// is required by most S3-compatible endpoints
+    force_path_style: bool,
+}
+
+impl Default for S3CompatibleFileStorageConfig {
+    fn default() -> Self {
+        Self {
+            endpoint_url: String::new(),
+            region: String::from("us-east-1"),
+            bucket_name: String::new(),
+            access_key_id: Secret::default(),
+            secret_access_key: Secret::default(),
+            force_path_style: true,
+        }
+    }
+}
+
+impl S3CompatibleFileStorageConfig {
+    /// Validates the S3-compatible file storage configuration.
+    pub(super) fn validate(&self) -> Result<(), InvalidFileStorageConfig> {
+        use common_utils::fp_utils::when;
+
+        when(self.endpoint_url.is_default_or_empty(), || {
+            Err(InvalidFileStorageConfig(
+                "s3 compatible endpoint url must not be empty",
+            ))
+        })?;
+
+        when(self.region.is_default_or_empty(), || {
+            Err(InvalidFileStorageConfig(
+                "s3 compatible region must not be empty",
+            ))
+        })?;
+
+        when(self.bucket_name.is_default_or_empty(), || {
+            Err(InvalidFileStorageConfig(
+                "s3 compatible bucket name must not be empty",
+            ))
+        })?;
+
+        when(
+            self.access_key_id.is_default_or_empty()
+                || self.secret_access_key.is_default_or_empty(),
+            || {
+                Err(InvalidFileStorageConfig(
+                    "s3 compatible access key id and secret access key must not be empty",
+                ))
+            },
+        )
+    }
+}
+
 /// AWS S3 file storage client.
 #[derive(Debug, Clone)]
 pub(super) struct AwsFileStorageClient {
@@ -59,6 +132,29 @@ impl AwsFileStorageClient {
         }
     }
 
+    /// Creates a new file storage client for an S3-compatible endpoint. The credentials and
+    /// region are taken from the configuration alone, without consulting the AWS configuration
+    /// chain.
+    pub(super) fn new_s3_compatible(config: &S3CompatibleFileStorageConfig) -> Self {
+        let s3_config = aws_sdk_s3::Config::builder()
+            .endpoint_url(&config.endpoint_url)
+            .region(Region::new(config.region.clone()))
+            .credentials_provider(Credentials::new(
+                config.access_key_id.peek(),
+                config.secret_access_key.peek(),
+                None,
+                None,
+                "s3_compatible_file_storage",
+            ))
+            .force_path_style(config.force_path_style)
+            .build();
+
+        Self {
+            inner_client: Client::from_conf(s3_config),
+            bucket_name: config.bucket_name.clone(),
+        }
+    }
+
     /// Uploads a file to AWS S3.
     async fn upload_file(
         &self,
@@ -104,6 +200,161 @@ impl AwsFileStorageClient {
             .map_err(AwsS3StorageError::UnknownError)?
             .to_vec())
     }
+
+    /// Starts uploading a file to AWS S3 in parts.
+    fn start_multipart_upload(&self, file_key: &str) -> AwsMultipartUpload {
+        AwsMultipartUpload {
+            inner_client: self.inner_client.clone(),
+            bucket_name: self.bucket_name.clone(),
+            file_key: file_key.to_owned(),
+            upload_id: None,
+            buffer: Vec::new(),
+            completed_parts: Vec::new(),
+        }
+    }
+}
+
+/// A file being uploaded to AWS S3 in parts. The parts are buffered until they reach the minimum
+/// part size, and files smaller than the minimum part size are uploaded in a single request.
+#[derive(Debug)]
+struct AwsMultipartUpload {
+    inner_client: Client,
+    bucket_name: String,
+    file_key: String,
+    /// The ID of the multipart upload, created once the first part is uploaded
+    upload_id: Option<String>,
+    buffer: Vec<u8>,
+    completed_parts: Vec<CompletedPart>,
+}
+
+impl AwsMultipartUpload {
+    async fn upload_part(&mut self, part: Vec<u8>) -> CustomResult<(), AwsS3StorageError> {
+        self.buffer.extend(part);
+        if self.buffer.len() >= MIN_PART_SIZE {
+            self.upload_buffer().await?;
+        }
+        Ok(())
+    }
+
+    /// Uploads the buffered data as the next part, creating the multipart upload if needed
+    async fn upload_buffer(&mut self) -> CustomResult<(), AwsS3StorageError> {
+        let upload_id = match &self.upload_id {
+            Some(upload_id) => upload_id.clone(),
+            None => {
+                let upload_id = self
+                    .inner_client
+                    .create_multipart_upload()
+                    .bucket(&self.bucket_name)
+                    .key(&self.file_key)
+                    .send()
+                    .await
+                    .change_context(AwsS3StorageError::MultipartUploadFailure)?
+                    .upload_id()
+                    .map(ToOwned::to_owned)
+                    .ok_or(report!(AwsS3StorageError::MultipartUploadFailure))
+                    .attach_printable("Missing upload ID in multipart upload response")?;
+                self.upload_id = Some(upload_id.clone());
+                upload_id
+            }
+        };
+
+        let part_number = i32::try_from(self.completed_parts.len() + 1)
+            .change_context(AwsS3StorageError::MultipartUploadFailure)?;
+        let e_tag = self
+            .inner_client
+            .upload_part()
+            .bucket(&self.bucket_name)
+            .key(&self.file_key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(std::mem::take(&mut self.buffer).into())
+            .send()
+            .await
+            .change_context(AwsS3StorageError::MultipartUploadFailure)
+            .attach_printable_lazy(|| format!("Failed to upload part {part_number}"))?
+            .e_tag()
+            .map(ToOwned::to_owned);
+
+        self.completed_parts.push(
+            CompletedPart::builder()
+                .set_e_tag(e_tag)
+                .part_number(part_number)
+                .build(),
+        );
+        Ok(())
+    }
+
+    async fn complete(mut self) -> CustomResult<(), AwsS3StorageError> {
+        if self.upload_id.is_none() {
+            self.inner_client
+                .put_object()
+                .bucket(&self.bucket_name)
+                .key(&self.file_key)
+                .body(self.buffer.into())
+                .send()
+                .await
+                .map_err(AwsS3StorageError::UploadFailure)?;
+            return Ok(());
+        }
+
+        if !self.buffer.is_empty() {
+            self.upload_buffer().await?;
+        }
+        self.inner_client
+            .complete_multipart_upload()
+            .bucket(&self.bucket_name)
+            .key(&self.file_key)
+            .set_upload_id(self.upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(self.completed_parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .change_context(AwsS3StorageError::MultipartUploadFailure)?;
+        Ok(())
+    }
+
+    async fn abort(self) -> CustomResult<(), AwsS3StorageError> {
+        if self.upload_id.is_some() {
+            self.inner_client
+                .abort_multipart_upload()
+                .bucket(&self.bucket_name)
+                .key(&self.file_key)
+                .set_upload_id(self.upload_id)
+                .send()
+                .await
+                .change_context(AwsS3StorageError::MultipartUploadFailure)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl MultipartUpload for AwsMultipartUpload {
+    /// Uploads the next part of the file to AWS S3.
+    async fn upload_part(&mut self, part: Vec<u8>) -> CustomResult<(), FileStorageError> {
+        self.upload_part(part)
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Completes the upload of the file to AWS S3.
+    async fn complete(self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        (*self)
+            .complete()
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Aborts the upload of the file to AWS S3.
+    async fn abort(self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        (*self)
+            .abort()
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
 }
 
 #[async_trait::async_trait]
@@ -135,6 +386,14 @@ impl FileStorageInterface for AwsFileStorageClient {
             .await
             .change_context(FileStorageError::RetrieveFailed)?)
     }
+
+    /// Starts uploading a file to AWS S3 in parts.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<Box<dyn MultipartUpload>, FileStorageError> {
+        Ok(Box::new(self.start_multipart_upload(file_key)))
+    }
 }
 
 /// Enum representing errors that can occur during AWS S3 file storage operations.
@@ -152,7 +411,86 @@ enum AwsS3StorageError {
     #[error("File delete from S3 failed: {0:?}")]
     DeleteFailure(aws_smithy_client::SdkError<DeleteObjectError>),
 
+    /// Error indicating that a multipart file upload to S3 failed.
+    #[error("Multipart file upload to S3 failed")]
+    MultipartUploadFailure,
+
     /// Unknown error occurred.
     #[error("Unknown error occurred: {0:?}")]
     UnknownError(aws_sdk_s3::primitives::ByteStreamError),
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+
+    fn get_s3_compatible_config() -> S3CompatibleFileStorageConfig {
+        S3CompatibleFileStorageConfig {
+            endpoint_url: String::from("http://localhost:9000"),
+            bucket_name: String::from("hyperswitch"),
+            access_key_id: Secret::new(String::from("minioadmin")),
+            secret_access_key: Secret::new(String::from("minioadmin")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_s3_compatible_config_validation() {
+        assert!(get_s3_compatible_config().validate().is_ok());
+        assert!(S3CompatibleFileStorageConfig::default().validate().is_err());
+        assert!(S3CompatibleFileStorageConfig {
+            region: String::new(),
+            ..get_s3_compatible_config()
+        }
+        .validate()
+        .is_err());
+        assert!(S3CompatibleFileStorageConfig {
+            secret_access_key: Secret::default(),
+            ..get_s3_compatible_config()
+        }
+        .validate()
+        .is_err());
+    }
+
+    /// Requires an S3-compatible endpoint with an existing bucket, such as MinIO started with
+    /// `docker run -p 9000:9000 minio/minio server /data` and a bucket created in it. The
+    /// endpoint and bucket are read from `S3_COMPATIBLE_ENDPOINT_URL` and
+    /// `S3_COMPATIBLE_BUCKET_NAME`, and the credentials from `S3_COMPATIBLE_ACCESS_KEY_ID` and
+    /// `S3_COMPATIBLE_SECRET_ACCESS_KEY`.
+    #[tokio::test]
+    #[ignore]
+    async fn test_s3_compatible_file_round_trip() {
+        let get_env = |name: &str| std::env::var(name).expect("S3-compatible test variable");
+        let config = S3CompatibleFileStorageConfig {
+            endpoint_url: get_env("S3_COMPATIBLE_ENDPOINT_URL"),
+            bucket_name: get_env("S3_COMPATIBLE_BUCKET_NAME"),
+            access_key_id: Secret::new(get_env("S3_COMPATIBLE_ACCESS_KEY_ID")),
+            secret_access_key: Secret::new(get_env("S3_COMPATIBLE_SECRET_ACCESS_KEY")),
+            ..Default::default()
+        };
+        config.validate().unwrap();
+
+        let client = AwsFileStorageClient::new_s3_compatible(&config);
+        let file_key = common_utils::generate_id(10, "s3_compatible");
+        client
+            .upload_file(&file_key, b"evidence".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(client.retrieve_file(&file_key).await.unwrap(), b"evidence");
+
+        client.delete_file(&file_key).await.unwrap();
+        assert!(client.retrieve_file(&file_key).await.is_err());
+
+        // The first part is large enough to be uploaded as a part of a multipart upload
+        let mut upload = client.start_multipart_upload(&file_key);
+        upload.upload_part(vec![b'a'; MIN_PART_SIZE]).await.unwrap();
+        upload.upload_part(b"evidence".to_vec()).await.unwrap();
+        upload.complete().await.unwrap();
+        let file = client.retrieve_file(&file_key).await.unwrap();
+        assert_eq!(file.len(), MIN_PART_SIZE + 8);
+        assert!(file.ends_with(b"evidence"));
+
+        client.delete_file(&file_key).await.unwrap();
+    }
+}
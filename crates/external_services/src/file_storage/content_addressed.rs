@@ -0,0 +1,971 @@
+//!
+//! Module for content-addressed local file system storage operations
+//!
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use common_utils::{
+    crypto::{GenerateDigest, Sha256},
+    date_time,
+    errors::CustomResult,
+};
+use error_stack::{report, ResultExt};
+use ring::digest;
+use router_env::logger;
+use tokio::io::AsyncWriteExt;
+
+use super::InvalidFileStorageConfig;
+use crate::file_storage::{FileStorageError, FileStorageInterface, MultipartUpload};
+
+/// Directory holding the file contents, named by their checksums
+const OBJECTS_DIR: &str = "objects";
+
+/// Directory holding the entries mapping the file keys to the checksums of their contents
+const KEYS_DIR: &str = "keys";
+
+/// Directory holding the files being uploaded in parts, until their checksum is known
+const STAGING_DIR: &str = "staging";
+
+/// Extension of the directories holding a marker file for each file key referencing an object
+const REFERENCES_EXTENSION: &str = "refs";
+
+/// Extension of the files being written, which are renamed once completely written
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+/// Extension of the objects being deleted, which are moved aside before their references are
+/// checked one last time
+const TRASH_EXTENSION: &str = "trash";
+
+/// Maximum number of attempts at writing a reference, as its directory may be deleted
+/// concurrently by a sweep
+const MAX_WRITE_ATTEMPTS: u8 = 3;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Maximum number of directory levels the files can be sharded into
+const MAX_SHARD_DEPTH: u8 = 8;
+
+/// Configuration for content-addressed local file system storage.
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct ContentAddressedStorageConfig {
+    /// The directory the files are stored in
+    root_dir: PathBuf,
+    /// The number of directory levels the files are sharded into, each level named by the next
+    /// two hex characters of the checksum
+    shard_depth: u8,
+    /// The number of days after which files are deleted
+    retention_days: Option<u32>,
+    /// The maximum total size in bytes of the stored files, files are counted once however many
+    /// file keys they are uploaded with. The size of the files stored by other instances sharing
+    /// the root directory is only accounted for once a sweep has run, so the quota may be
+    /// exceeded by the files stored in between.
+    max_size_in_bytes: Option<u64>,
+    /// The interval in seconds between the sweeps deleting the files past their retention and
+    /// the files no longer referenced, and recomputing the total size of the stored files
+    sweep_interval_in_secs: u64,
+}
+
+impl Default for ContentAddressedStorageConfig {
+    fn default() -> Self {
+        Self {
+            root_dir: PathBuf::from("files"),
+            shard_depth: 2,
+            retention_days: None,
+            max_size_in_bytes: None,
+            sweep_interval_in_secs: 3600,
+        }
+    }
+}
+
+impl ContentAddressedStorageConfig {
+    /// Validates the content-addressed file storage configuration.
+    pub(super) fn validate(&self) -> Result<(), InvalidFileStorageConfig> {
+        use common_utils::fp_utils::when;
+
+        when(self.root_dir.as_os_str().is_empty(), || {
+            Err(InvalidFileStorageConfig(
+                "content addressed root directory must not be empty",
+            ))
+        })?;
+
+        when(self.shard_depth > MAX_SHARD_DEPTH, || {
+            Err(InvalidFileStorageConfig(
+                "content addressed shard depth must not be greater than 8",
+            ))
+        })?;
+
+        when(self.retention_days == Some(0), || {
+            Err(InvalidFileStorageConfig(
+                "content addressed retention days must be greater than zero",
+            ))
+        })?;
+
+        when(self.max_size_in_bytes == Some(0), || {
+            Err(InvalidFileStorageConfig(
+                "content addressed max size must be greater than zero",
+            ))
+        })?;
+
+        when(self.sweep_interval_in_secs == 0, || {
+            Err(InvalidFileStorageConfig(
+                "content addressed sweep interval must be greater than zero",
+            ))
+        })
+    }
+}
+
+/// The entry mapping a file key to the checksum of its contents
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct FileEntry {
+    file_key: String,
+    /// Hex encoded SHA-256 checksum of the file contents
+    checksum: String,
+    size: u64,
+    /// UNIX timestamp of the time the file was uploaded
+    created_at: i64,
+}
+
+/// Content-addressed local file system storage. Files are stored once per distinct contents,
+/// named by the SHA-256 checksum of their contents, which is verified when they are retrieved.
+///
+/// The root directory may be shared by several instances. Every file key referencing an object
+/// holds a marker file in the directory of references of the object, and objects are only
+/// deleted by the periodic sweep once they have no references, so the operations on file keys
+/// need no coordination between instances.
+#[derive(Debug, Clone)]
+pub(super) struct ContentAddressedStorage {
+    config: Arc<ContentAddressedStorageConfig>,
+    /// The total size of the stored objects, recomputed from the root directory by every sweep
+    /// so that the objects stored by other instances are accounted for, and incremented by the
+    /// objects this instance stores in between
+    total_size: Arc<AtomicU64>,
+}
+
+impl ContentAddressedStorage {
+    /// Creates a new content-addressed file storage client, and spawns the task periodically
+    /// sweeping the root directory, which deletes the files past their retention and the objects
+    /// no longer referenced, and recomputes the total size of the stored objects.
+    pub(super) fn new(config: &ContentAddressedStorageConfig) -> Self {
+        let storage = Self::from_config(config);
+
+        let sweeper = storage.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(sweeper.config.sweep_interval_in_secs));
+            loop {
+                interval.tick().await;
+                let storage = sweeper.clone();
+                match tokio::task::spawn_blocking(move || storage.sweep()).await {
+                    Ok(Ok(summary)) => logger::debug!(?summary, "Swept the stored files"),
+                    Ok(Err(error)) => logger::error!(?error, "Failed to sweep the stored files"),
+                    Err(error) => logger::error!(?error, "File storage sweep task panicked"),
+                }
+            }
+        });
+
+        storage
+    }
+
+    fn from_config(config: &ContentAddressedStorageConfig) -> Self {
+        Self {
+            config: Arc::new(config.clone()),
+            total_size: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Stores the provided file data under the specified file key, replacing the existing file.
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+    ) -> CustomResult<(), ContentAddressedStorageError> {
+        let checksum = get_checksum(&file)?;
+        let size =
+            u64::try_from(file.len()).change_context(ContentAddressedStorageError::WriteFailure)?;
+        self.store_file(file_key, checksum, size, ObjectContents::Data(&file))
+            .await
+    }
+
+    /// Starts writing a file in parts to a staging file, which is stored under the file key once
+    /// the upload is completed.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<ContentAddressedMultipartUpload, ContentAddressedStorageError> {
+        let staging_dir = self.config.root_dir.join(STAGING_DIR);
+        tokio::fs::create_dir_all(&staging_dir)
+            .await
+            .change_context(ContentAddressedStorageError::CreateDirFailed)?;
+
+        let staged_path = staging_dir.join(format!(
+            "{}.{TEMPORARY_EXTENSION}",
+            common_utils::generate_id(10, "upload")
+        ));
+        let file = tokio::fs::File::create(&staged_path)
+            .await
+            .change_context(ContentAddressedStorageError::WriteFailure)?;
+
+        Ok(ContentAddressedMultipartUpload {
+            storage: self.clone(),
+            file_key: file_key.to_owned(),
+            staged_path,
+            file,
+            digest: digest::Context::new(&digest::SHA256),
+            size: 0,
+        })
+    }
+
+    /// Stores the contents with the checksum under the specified file key, replacing the
+    /// existing file.
+    async fn store_file(
+        &self,
+        file_key: &str,
+        checksum: String,
+        size: u64,
+        contents: ObjectContents<'_>,
+    ) -> CustomResult<(), ContentAddressedStorageError> {
+        let key_hash = get_checksum(file_key.as_bytes())?;
+        let existing_entry = self.read_entry(&key_hash).await?;
+
+        // The reference is written before checking whether the object exists, so that a sweep
+        // deleting the object concurrently either sees the reference and keeps the object, or
+        // deletes it before the check and the object is written again
+        let object_path = self.get_object_path(&checksum);
+        write_reference(&object_path, &key_hash).await?;
+
+        if !path_exists(&object_path).await? {
+            if let Err(error) = self.reserve_size(size) {
+                remove_reference(&object_path, &key_hash).await?;
+                return Err(error);
+            }
+            if let Err(error) = contents.write_to(&object_path).await {
+                self.total_size.fetch_sub(size, Ordering::SeqCst);
+                return Err(error);
+            }
+        }
+
+        let entry = FileEntry {
+            file_key: file_key.to_owned(),
+            checksum,
+            size,
+            created_at: date_time::now_unix_timestamp(),
+        };
+        write_file(
+            &self.get_entry_path(&key_hash),
+            &serde_json::to_vec(&entry)
+                .change_context(ContentAddressedStorageError::WriteFailure)?,
+        )
+        .await?;
+
+        // The previous contents of the file key are deleted by the sweep once they are no longer
+        // referenced
+        match existing_entry {
+            Some(existing_entry) if existing_entry.checksum != entry.checksum => {
+                remove_reference(&self.get_object_path(&existing_entry.checksum), &key_hash).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Deletes the file associated with the specified file key, the file contents are deleted
+    /// by the sweep once no other file key references them.
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), ContentAddressedStorageError> {
+        let key_hash = get_checksum(file_key.as_bytes())?;
+        let entry = self
+            .read_entry(&key_hash)
+            .await?
+            .ok_or(ContentAddressedStorageError::FileNotFound)?;
+
+        tokio::fs::remove_file(self.get_entry_path(&key_hash))
+            .await
+            .map_err(|error| match error.kind() {
+                ErrorKind::NotFound => report!(ContentAddressedStorageError::FileNotFound),
+                _ => report!(error).change_context(ContentAddressedStorageError::DeleteFailure),
+            })?;
+        remove_reference(&self.get_object_path(&entry.checksum), &key_hash).await
+    }
+
+    /// Retrieves the file contents associated with the specified file key, after verifying their
+    /// checksum.
+    async fn retrieve_file(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<Vec<u8>, ContentAddressedStorageError> {
+        let key_hash = get_checksum(file_key.as_bytes())?;
+        let entry = self
+            .read_entry(&key_hash)
+            .await?
+            .filter(|entry| !self.is_expired(entry))
+            .ok_or(ContentAddressedStorageError::FileNotFound)?;
+
+        let file = tokio::fs::read(self.get_object_path(&entry.checksum))
+            .await
+            .change_context(ContentAddressedStorageError::ReadFailure)?;
+
+        if get_checksum(&file)? != entry.checksum {
+            return Err(report!(ContentAddressedStorageError::ChecksumMismatch))
+                .attach_printable_lazy(|| format!("Contents of {file_key} are corrupted"));
+        }
+
+        Ok(file)
+    }
+
+    /// Adds the size of a new object to the total size, failing if the total size would exceed
+    /// the quota
+    fn reserve_size(&self, size: u64) -> CustomResult<(), ContentAddressedStorageError> {
+        let Some(max_size) = self.config.max_size_in_bytes else {
+            self.total_size.fetch_add(size, Ordering::SeqCst);
+            return Ok(());
+        };
+
+        self.total_size
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |total_size| {
+                total_size
+                    .checked_add(size)
+                    .filter(|total_size| *total_size <= max_size)
+            })
+            .map(|_| ())
+            .map_err(|_| report!(ContentAddressedStorageError::QuotaExceeded))
+            .attach_printable_lazy(|| {
+                format!("Storing {size} bytes would exceed the quota of {max_size} bytes")
+            })
+    }
+
+    /// Deletes the files past their retention and the objects no longer referenced, and
+    /// recomputes the total size of the stored objects. This walks the whole root directory with
+    /// blocking file system calls, and is run on the blocking thread pool.
+    fn sweep(&self) -> CustomResult<SweepSummary, ContentAddressedStorageError> {
+        let mut summary = SweepSummary::default();
+
+        if self.config.retention_days.is_some() {
+            for (key_hash, entry) in self.get_entries()? {
+                if self.is_expired(&entry) {
+                    ignore_not_found(std::fs::remove_file(self.get_entry_path(&key_hash)))
+                        .and_then(|()| {
+                            ignore_not_found(std::fs::remove_file(
+                                get_references_dir(&self.get_object_path(&entry.checksum))
+                                    .join(&key_hash),
+                            ))
+                        })
+                        .change_context(ContentAddressedStorageError::DeleteFailure)?;
+                    summary.expired_files += 1;
+                }
+            }
+        }
+
+        let mut object_paths = Vec::new();
+        collect_objects(&self.config.root_dir.join(OBJECTS_DIR), &mut object_paths)
+            .change_context(ContentAddressedStorageError::ReadFailure)?;
+
+        let mut total_size = 0u64;
+        for object_path in object_paths {
+            let size = match std::fs::metadata(&object_path) {
+                Ok(metadata) => metadata.len(),
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => {
+                    return Err(
+                        report!(error).change_context(ContentAddressedStorageError::ReadFailure)
+                    )
+                }
+            };
+
+            if collect_object(&object_path)
+                .change_context(ContentAddressedStorageError::DeleteFailure)?
+            {
+                summary.collected_objects += 1;
+            } else {
+                total_size = total_size.saturating_add(size);
+            }
+        }
+
+        self.total_size.store(total_size, Ordering::SeqCst);
+        summary.total_size = total_size;
+        Ok(summary)
+    }
+
+    fn is_expired(&self, entry: &FileEntry) -> bool {
+        self.config.retention_days.is_some_and(|retention_days| {
+            date_time::now_unix_timestamp().saturating_sub(entry.created_at)
+                > i64::from(retention_days).saturating_mul(SECONDS_PER_DAY)
+        })
+    }
+
+    async fn read_entry(
+        &self,
+        key_hash: &str,
+    ) -> CustomResult<Option<FileEntry>, ContentAddressedStorageError> {
+        parse_entry(tokio::fs::read(self.get_entry_path(key_hash)).await)
+    }
+
+    /// Returns the entries of all the stored file keys, along with the hashes of the file keys
+    fn get_entries(&self) -> CustomResult<Vec<(String, FileEntry)>, ContentAddressedStorageError> {
+        let mut entry_paths = Vec::new();
+        collect_files(&self.config.root_dir.join(KEYS_DIR), &mut entry_paths)
+            .change_context(ContentAddressedStorageError::ReadFailure)?;
+
+        let mut entries = Vec::with_capacity(entry_paths.len());
+        for path in entry_paths {
+            if path
+                .extension()
+                .is_some_and(|extension| extension == TEMPORARY_EXTENSION)
+            {
+                continue;
+            }
+            let Some(key_hash) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Some(entry) = parse_entry(std::fs::read(&path))? {
+                entries.push((key_hash.to_owned(), entry));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn get_object_path(&self, checksum: &str) -> PathBuf {
+        self.get_sharded_path(OBJECTS_DIR, checksum)
+    }
+
+    fn get_entry_path(&self, key_hash: &str) -> PathBuf {
+        self.get_sharded_path(KEYS_DIR, key_hash)
+            .with_extension("json")
+    }
+
+    fn get_sharded_path(&self, dir: &str, hash: &str) -> PathBuf {
+        let mut path = self.config.root_dir.join(dir);
+        for level in 0..usize::from(self.config.shard_depth) {
+            if let Some(shard) = hash.get(level * 2..level * 2 + 2) {
+                path.push(shard);
+            }
+        }
+        path.push(hash);
+        path
+    }
+}
+
+/// The contents of an object being stored
+enum ObjectContents<'a> {
+    /// The contents held in memory
+    Data(&'a [u8]),
+    /// The contents written to a staging file by a multipart upload
+    StagedFile(&'a Path),
+}
+
+impl ObjectContents<'_> {
+    async fn write_to(&self, object_path: &Path) -> CustomResult<(), ContentAddressedStorageError> {
+        match self {
+            Self::Data(data) => write_file(object_path, data).await,
+            Self::StagedFile(staged_path) => {
+                let parent = object_path
+                    .parent()
+                    .ok_or(ContentAddressedStorageError::CreateDirFailed)
+                    .attach_printable("Failed to obtain parent directory")?;
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .change_context(ContentAddressedStorageError::CreateDirFailed)?;
+                tokio::fs::rename(staged_path, object_path)
+                    .await
+                    .change_context(ContentAddressedStorageError::WriteFailure)
+            }
+        }
+    }
+}
+
+/// A file being written in parts to a staging file, whose checksum is computed as the parts are
+/// written.
+struct ContentAddressedMultipartUpload {
+    storage: ContentAddressedStorage,
+    file_key: String,
+    staged_path: PathBuf,
+    file: tokio::fs::File,
+    digest: digest::Context,
+    size: u64,
+}
+
+impl ContentAddressedMultipartUpload {
+    async fn upload_part(
+        &mut self,
+        part: Vec<u8>,
+    ) -> CustomResult<(), ContentAddressedStorageError> {
+        self.digest.update(&part);
+        self.size = self.size.saturating_add(
+            u64::try_from(part.len()).change_context(ContentAddressedStorageError::WriteFailure)?,
+        );
+        self.file
+            .write_all(&part)
+            .await
+            .change_context(ContentAddressedStorageError::WriteFailure)
+    }
+
+    async fn complete(mut self) -> CustomResult<(), ContentAddressedStorageError> {
+        self.file
+            .flush()
+            .await
+            .change_context(ContentAddressedStorageError::WriteFailure)?;
+        self.file
+            .sync_all()
+            .await
+            .change_context(ContentAddressedStorageError::WriteFailure)?;
+
+        let checksum = hex::encode(self.digest.finish());
+        let result = self
+            .storage
+            .store_file(
+                &self.file_key,
+                checksum,
+                self.size,
+                ObjectContents::StagedFile(&self.staged_path),
+            )
+            .await;
+
+        // The staging file is only moved if the object did not exist already
+        ignore_not_found(tokio::fs::remove_file(&self.staged_path).await)
+            .change_context(ContentAddressedStorageError::DeleteFailure)?;
+        result
+    }
+
+    async fn abort(self) -> CustomResult<(), ContentAddressedStorageError> {
+        drop(self.file);
+        ignore_not_found(tokio::fs::remove_file(&self.staged_path).await)
+            .change_context(ContentAddressedStorageError::DeleteFailure)
+    }
+}
+
+#[async_trait::async_trait]
+impl MultipartUpload for ContentAddressedMultipartUpload {
+    /// Appends the part to the staging file.
+    async fn upload_part(&mut self, part: Vec<u8>) -> CustomResult<(), FileStorageError> {
+        self.upload_part(part)
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Stores the staging file under the file key.
+    async fn complete(self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        (*self)
+            .complete()
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Deletes the staging file.
+    async fn abort(self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        (*self)
+            .abort()
+            .await
+            .change_context(FileStorageError::UploadFailed)
+    }
+}
+
+/// The outcome of a sweep of the stored files
+#[derive(Debug, Default)]
+struct SweepSummary {
+    /// The number of file keys deleted as they were past their retention
+    expired_files: usize,
+    /// The number of objects deleted as no file key referenced them
+    collected_objects: usize,
+    /// The total size of the objects remaining
+    total_size: u64,
+}
+
+#[async_trait::async_trait]
+impl FileStorageInterface for ContentAddressedStorage {
+    /// Stores the provided file data under the specified file key.
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+    ) -> CustomResult<(), FileStorageError> {
+        self.upload_file(file_key, file)
+            .await
+            .change_context(FileStorageError::UploadFailed)?;
+        Ok(())
+    }
+
+    /// Deletes the file associated with the specified file key.
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), FileStorageError> {
+        self.delete_file(file_key)
+            .await
+            .change_context(FileStorageError::DeleteFailed)?;
+        Ok(())
+    }
+
+    /// Retrieves the file contents associated with the specified file key.
+    async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError> {
+        Ok(self
+            .retrieve_file(file_key)
+            .await
+            .change_context(FileStorageError::RetrieveFailed)?)
+    }
+
+    /// Starts writing a file in parts, which is stored once the upload is completed.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<Box<dyn MultipartUpload>, FileStorageError> {
+        Ok(Box::new(
+            self.start_multipart_upload(file_key)
+                .await
+                .change_context(FileStorageError::UploadFailed)?,
+        ))
+    }
+}
+
+fn get_checksum(data: &[u8]) -> CustomResult<String, ContentAddressedStorageError> {
+    Sha256
+        .generate_digest(data)
+        .map(hex::encode)
+        .change_context(ContentAddressedStorageError::ChecksumFailure)
+}
+
+fn parse_entry(
+    contents: std::io::Result<Vec<u8>>,
+) -> CustomResult<Option<FileEntry>, ContentAddressedStorageError> {
+    match contents {
+        Ok(contents) => serde_json::from_slice(&contents)
+            .map(Some)
+            .change_context(ContentAddressedStorageError::ReadFailure)
+            .attach_printable("Failed to parse the file entry"),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(report!(error).change_context(ContentAddressedStorageError::ReadFailure)),
+    }
+}
+
+fn get_references_dir(object_path: &Path) -> PathBuf {
+    object_path.with_extension(REFERENCES_EXTENSION)
+}
+
+/// Writes the marker of the file key referencing the object. A sweep deletes the directory of
+/// references once it is empty, in which case the directory is created again.
+async fn write_reference(
+    object_path: &Path,
+    key_hash: &str,
+) -> CustomResult<(), ContentAddressedStorageError> {
+    let references_dir = get_references_dir(object_path);
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = match tokio::fs::create_dir_all(&references_dir).await {
+            Ok(()) => tokio::fs::write(references_dir.join(key_hash), []).await,
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound && attempts < MAX_WRITE_ATTEMPTS => {}
+            Err(error) => {
+                return Err(report!(error))
+                    .change_context(ContentAddressedStorageError::WriteFailure)
+                    .attach_printable("Failed to write the file reference")
+            }
+        }
+    }
+}
+
+async fn remove_reference(
+    object_path: &Path,
+    key_hash: &str,
+) -> CustomResult<(), ContentAddressedStorageError> {
+    match tokio::fs::remove_file(get_references_dir(object_path).join(key_hash)).await {
+        Err(error) if error.kind() != ErrorKind::NotFound => {
+            Err(report!(error).change_context(ContentAddressedStorageError::DeleteFailure))
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn path_exists(path: &Path) -> CustomResult<bool, ContentAddressedStorageError> {
+    tokio::fs::try_exists(path)
+        .await
+        .change_context(ContentAddressedStorageError::ReadFailure)
+}
+
+/// Writes the file to a temporary path unique to the write and renames it, so that partially
+/// written files are never read and concurrent writes to the same path do not interleave
+async fn write_file(
+    path: &Path,
+    contents: &[u8],
+) -> CustomResult<(), ContentAddressedStorageError> {
+    let parent = path
+        .parent()
+        .ok_or(ContentAddressedStorageError::CreateDirFailed)
+        .attach_printable("Failed to obtain parent directory")?;
+    tokio::fs::create_dir_all(parent)
+        .await
+        .change_context(ContentAddressedStorageError::CreateDirFailed)?;
+
+    let temporary_path = path.with_extension(format!(
+        "{}.{TEMPORARY_EXTENSION}",
+        common_utils::generate_id(10, "write")
+    ));
+    if let Err(error) = tokio::fs::write(&temporary_path, contents).await {
+        let _ = tokio::fs::remove_file(&temporary_path).await;
+        return Err(report!(error).change_context(ContentAddressedStorageError::WriteFailure));
+    }
+    tokio::fs::rename(&temporary_path, path)
+        .await
+        .change_context(ContentAddressedStorageError::WriteFailure)
+}
+
+/// Deletes the object if no file key references it, returning whether it was deleted. The object
+/// is moved aside before its directory of references is deleted, which only succeeds if the
+/// directory is empty, and is moved back otherwise. An upload writes its reference before checking
+/// whether the object exists, so it either prevents the deletion or writes the object again.
+fn collect_object(object_path: &Path) -> std::io::Result<bool> {
+    let references_dir = get_references_dir(object_path);
+    match std::fs::read_dir(&references_dir) {
+        Ok(mut references) if references.next().is_some() => return Ok(false),
+        Ok(_) => {}
+        Err(error) if error.kind() == ErrorKind::NotFound => {}
+        Err(error) => return Err(error),
+    }
+
+    let trash_path = object_path.with_extension(TRASH_EXTENSION);
+    match std::fs::rename(object_path, &trash_path) {
+        Ok(()) => {}
+        // The object was collected by another instance
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(true),
+        Err(error) => return Err(error),
+    }
+
+    match ignore_not_found(std::fs::remove_dir(&references_dir)) {
+        Ok(()) => {
+            ignore_not_found(std::fs::remove_file(&trash_path))?;
+            Ok(true)
+        }
+        // The object was referenced again while being moved aside
+        Err(_) => std::fs::rename(&trash_path, object_path).map(|()| false),
+    }
+}
+
+/// Collects the paths of the objects, skipping their directories of references and the files
+/// being written or deleted
+fn collect_objects(dir: &Path, objects: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    objects.extend(files.into_iter().filter(|path| {
+        path.extension().is_none()
+            && !path
+                .parent()
+                .and_then(|parent| parent.extension())
+                .is_some_and(|extension| extension == REFERENCES_EXTENSION)
+    }));
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+fn ignore_not_found(result: std::io::Result<()>) -> std::io::Result<()> {
+    match result {
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Represents an error that can occur during content-addressed file storage operations.
+#[derive(Debug, thiserror::Error)]
+enum ContentAddressedStorageError {
+    /// Error indicating no file is stored with the file key.
+    #[error("File not found")]
+    FileNotFound,
+
+    /// Error indicating computing the checksum of the file failed.
+    #[error("Failed to compute the checksum of the file")]
+    ChecksumFailure,
+
+    /// Error indicating the file contents do not match their checksum.
+    #[error("File contents do not match their checksum")]
+    ChecksumMismatch,
+
+    /// Error indicating storing the file would exceed the size quota.
+    #[error("File storage quota exceeded")]
+    QuotaExceeded,
+
+    /// Error indicating reading a file failed.
+    #[error("Failed while reading the file")]
+    ReadFailure,
+
+    /// Error indicating writing a file failed.
+    #[error("Failed while writing into file")]
+    WriteFailure,
+
+    /// Error indicating file deletion failed.
+    #[error("Failed while deleting the file")]
+    DeleteFailure,
+
+    /// Error indicating directory creation failed
+    #[error("Failed while creating a directory")]
+    CreateDirFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_storage(max_size_in_bytes: Option<u64>) -> ContentAddressedStorage {
+        ContentAddressedStorage::from_config(&ContentAddressedStorageConfig {
+            root_dir: std::env::temp_dir().join(common_utils::generate_id(10, "content_addressed")),
+            max_size_in_bytes,
+            ..Default::default()
+        })
+    }
+
+    fn get_objects(storage: &ContentAddressedStorage) -> Vec<PathBuf> {
+        let mut objects = Vec::new();
+        collect_objects(&storage.config.root_dir.join(OBJECTS_DIR), &mut objects).unwrap();
+        objects
+    }
+
+    #[tokio::test]
+    async fn test_identical_files_are_stored_once() {
+        let storage = get_storage(None);
+
+        storage
+            .upload_file("first", b"evidence".to_vec())
+            .await
+            .unwrap();
+        storage
+            .upload_file("second", b"evidence".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(storage.total_size.load(Ordering::SeqCst), 8);
+        assert_eq!(get_objects(&storage).len(), 1);
+        assert_eq!(storage.retrieve_file("second").await.unwrap(), b"evidence");
+
+        storage.delete_file("first").await.unwrap();
+        assert!(storage.retrieve_file("first").await.is_err());
+        assert_eq!(storage.sweep().unwrap().collected_objects, 0);
+        assert_eq!(storage.retrieve_file("second").await.unwrap(), b"evidence");
+
+        storage.delete_file("second").await.unwrap();
+        assert!(storage.delete_file("second").await.is_err());
+        let summary = storage.sweep().unwrap();
+        assert_eq!(summary.collected_objects, 1);
+        assert_eq!(summary.total_size, 0);
+        assert_eq!(storage.total_size.load(Ordering::SeqCst), 0);
+        assert!(get_objects(&storage).is_empty());
+
+        std::fs::remove_dir_all(&storage.config.root_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quota_and_checksum_are_enforced() {
+        let storage = get_storage(Some(10));
+
+        storage
+            .upload_file("first", b"evidence".to_vec())
+            .await
+            .unwrap();
+        assert!(storage
+            .upload_file("second", b"receipt".to_vec())
+            .await
+            .is_err());
+        // The rejected upload leaves no reference behind
+        assert!(storage.retrieve_file("second").await.is_err());
+
+        // Replacing the contents of a file key frees the space of the previous contents once
+        // they are swept
+        storage.delete_file("first").await.unwrap();
+        storage.sweep().unwrap();
+        storage
+            .upload_file("first", b"receipt".to_vec())
+            .await
+            .unwrap();
+
+        let checksum = get_checksum(b"receipt").unwrap();
+        std::fs::write(storage.get_object_path(&checksum), b"tampered").unwrap();
+        assert!(storage.retrieve_file("first").await.is_err());
+
+        std::fs::remove_dir_all(&storage.config.root_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multipart_uploads_are_stored_by_checksum() {
+        let storage = get_storage(None);
+
+        storage
+            .upload_file("first", b"evidence".to_vec())
+            .await
+            .unwrap();
+        let mut upload = storage.start_multipart_upload("second").await.unwrap();
+        upload.upload_part(b"evi".to_vec()).await.unwrap();
+        upload.upload_part(b"dence".to_vec()).await.unwrap();
+        upload.complete().await.unwrap();
+
+        // The contents uploaded in parts are deduplicated with the identical file
+        assert_eq!(storage.retrieve_file("second").await.unwrap(), b"evidence");
+        assert_eq!(get_objects(&storage).len(), 1);
+        assert_eq!(storage.total_size.load(Ordering::SeqCst), 8);
+
+        let mut upload = storage.start_multipart_upload("third").await.unwrap();
+        upload.upload_part(b"receipt".to_vec()).await.unwrap();
+        upload.abort().await.unwrap();
+        assert!(storage.retrieve_file("third").await.is_err());
+
+        let mut staged_files = Vec::new();
+        collect_files(
+            &storage.config.root_dir.join(STAGING_DIR),
+            &mut staged_files,
+        )
+        .unwrap();
+        assert!(staged_files.is_empty());
+
+        std::fs::remove_dir_all(&storage.config.root_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_accounts_for_files_stored_by_other_instances() {
+        let storage = get_storage(Some(10));
+        let other_instance = ContentAddressedStorage::from_config(&storage.config);
+
+        other_instance
+            .upload_file("first", b"evidence".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(storage.total_size.load(Ordering::SeqCst), 0);
+
+        assert_eq!(storage.sweep().unwrap().total_size, 8);
+        assert!(storage
+            .upload_file("second", b"receipt".to_vec())
+            .await
+            .is_err());
+
+        // An object referenced again after being unreferenced is kept by the sweep
+        other_instance.delete_file("first").await.unwrap();
+        storage
+            .upload_file("second", b"evidence".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(storage.sweep().unwrap().collected_objects, 0);
+        assert_eq!(storage.retrieve_file("second").await.unwrap(), b"evidence");
+
+        std::fs::remove_dir_all(&storage.config.root_dir).unwrap();
+    }
+}
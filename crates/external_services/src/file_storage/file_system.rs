@@ -10,8 +10,9 @@ use std::{
 
 use common_utils::errors::CustomResult;
 use error_stack::ResultExt;
+use tokio::io::AsyncWriteExt;
 
-use crate::file_storage::{FileStorageError, FileStorageInterface};
+use crate::file_storage::{FileStorageError, FileStorageInterface, MultipartUpload};
 
 /// Constructs the file path for a given file key within the file system.
 /// The file path is generated based on the workspace path and the provided file key.
@@ -71,6 +72,89 @@ impl FileSystem {
             .change_context(FileSystemStorageError::ReadFailure)?;
         Ok(received_data)
     }
+
+    /// Starts writing a file in parts to a temporary file, which is renamed to the path of the
+    /// file key once the upload is completed.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<FileSystemMultipartUpload, FileSystemStorageError> {
+        let file_path = get_file_path(file_key);
+        tokio::fs::create_dir_all(
+            file_path
+                .parent()
+                .ok_or(FileSystemStorageError::CreateDirFailed)
+                .attach_printable("Failed to obtain parent directory")?,
+        )
+        .await
+        .change_context(FileSystemStorageError::CreateDirFailed)?;
+
+        let temporary_path =
+            file_path.with_extension(format!("{}.tmp", common_utils::generate_id(10, "upload")));
+        let file = tokio::fs::File::create(&temporary_path)
+            .await
+            .change_context(FileSystemStorageError::CreateFailure)?;
+
+        Ok(FileSystemMultipartUpload {
+            file_path,
+            temporary_path,
+            file,
+        })
+    }
+}
+
+/// A file being written in parts to the file system.
+#[derive(Debug)]
+struct FileSystemMultipartUpload {
+    file_path: PathBuf,
+    temporary_path: PathBuf,
+    file: tokio::fs::File,
+}
+
+#[async_trait::async_trait]
+impl MultipartUpload for FileSystemMultipartUpload {
+    /// Appends the part to the temporary file.
+    async fn upload_part(&mut self, part: Vec<u8>) -> CustomResult<(), FileStorageError> {
+        self.file
+            .write_all(&part)
+            .await
+            .change_context(FileSystemStorageError::WriteFailure)
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Renames the temporary file to the path of the file key.
+    async fn complete(mut self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        self.file
+            .flush()
+            .await
+            .change_context(FileSystemStorageError::WriteFailure)
+            .change_context(FileStorageError::UploadFailed)?;
+        self.file
+            .sync_all()
+            .await
+            .change_context(FileSystemStorageError::WriteFailure)
+            .change_context(FileStorageError::UploadFailed)?;
+
+        tokio::fs::rename(&self.temporary_path, &self.file_path)
+            .await
+            .change_context(FileSystemStorageError::WriteFailure)
+            .change_context(FileStorageError::UploadFailed)
+    }
+
+    /// Deletes the temporary file.
+    async fn abort(self: Box<Self>) -> CustomResult<(), FileStorageError> {
+        let Self {
+            temporary_path,
+            file,
+            ..
+        } = *self;
+        drop(file);
+
+        tokio::fs::remove_file(temporary_path)
+            .await
+            .change_context(FileSystemStorageError::DeleteFailure)
+            .change_context(FileStorageError::UploadFailed)
+    }
 }
 
 #[async_trait::async_trait]
@@ -102,6 +186,18 @@ impl FileStorageInterface for FileSystem {
             .await
             .change_context(FileStorageError::RetrieveFailed)?)
     }
+
+    /// Starts writing a file in parts to the file system.
+    async fn start_multipart_upload(
+        &self,
+        file_key: &str,
+    ) -> CustomResult<Box<dyn MultipartUpload>, FileStorageError> {
+        Ok(Box::new(
+            self.start_multipart_upload(file_key)
+                .await
+                .change_context(FileStorageError::UploadFailed)?,
+        ))
+    }
 }
 
 /// Represents an error that can occur during local file system storage operations.
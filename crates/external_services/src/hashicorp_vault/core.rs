@@ -70,6 +70,19 @@ pub trait Engine: Sized {
     ///
     /// A future representing the result of the read operation.
     fn read(client: &HashiCorpVault, location: String) -> Self::ReturnType<'_, String>;
+
+    /// Writes data to HashiCorp Vault at the specified location.
+    ///
+    /// # Parameters
+    ///
+    /// - `client`: A reference to the HashiCorpVault client.
+    /// - `location`: The location in HashiCorp Vault to write data to.
+    /// - `value`: The data to be written.
+    ///
+    /// # Returns
+    ///
+    /// A future representing the result of the write operation.
+    fn write(client: &HashiCorpVault, location: String, value: String) -> Self::ReturnType<'_, ()>;
 }
 
 /// An implementation of the `Engine` trait for the Key-Value version 2 (Kv2) engine.
@@ -95,6 +108,36 @@ impl Engine for Kv2 {
             Ok(output.remove(key).ok_or(HashiCorpError::ParseError)?)
         })
     }
+
+    fn write(client: &HashiCorpVault, location: String, value: String) -> Self::ReturnType<'_, ()> {
+        Box::pin(async move {
+            let mut split = location.split(':');
+            let mount = split.next().ok_or(HashiCorpError::IncompleteData)?;
+            let path = split.next().ok_or(HashiCorpError::IncompleteData)?;
+            let key = split.next().unwrap_or("value");
+
+            // Writing a secret replaces all the keys at the path, so the other keys are read
+            // first to retain them
+            let mut data =
+                match vaultrs::kv2::read::<HashMap<String, String>>(&client.client, mount, path)
+                    .await
+                {
+                    Ok(data) => data,
+                    Err(vaultrs::error::ClientError::APIError { code: 404, .. }) => HashMap::new(),
+                    Err(error) => {
+                        return Err(Report::from(error).change_context(HashiCorpError::FetchFailed))
+                    }
+                };
+            data.insert(key.to_owned(), value);
+
+            vaultrs::kv2::set(&client.client, mount, path, &data)
+                .await
+                .map_err(Into::<Report<_>>::into)
+                .change_context(HashiCorpError::StoreFailed)?;
+
+            Ok(())
+        })
+    }
 }
 
 impl HashiCorpVault {
@@ -146,6 +189,32 @@ impl HashiCorpVault {
         let output = En::read(self, data).await?;
         I::from_encoded(output).ok_or(error_stack::report!(HashiCorpError::HexDecodingFailed))
     }
+
+    /// Asynchronously stores data in HashiCorp Vault using the specified engine.
+    ///
+    /// # Parameters
+    ///
+    /// - `location`: A String representing the location of the data in HashiCorp Vault.
+    /// - `value`: The data to be stored.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `En`: The engine type that implements the `Engine` trait.
+    ///
+    pub async fn store<En>(
+        &self,
+        location: String,
+        value: String,
+    ) -> error_stack::Result<(), HashiCorpError>
+    where
+        for<'a> En: Engine<
+                ReturnType<'a, ()> = Pin<
+                    Box<dyn Future<Output = error_stack::Result<(), HashiCorpError>> + Send + 'a>,
+                >,
+            > + 'a,
+    {
+        En::write(self, location, value).await
+    }
 }
 
 /// A trait for types that can be constructed from encoded data in the form of a String.
@@ -222,6 +291,10 @@ pub enum HashiCorpError {
     #[error("Failed while fetching data from the server")]
     FetchFailed,
 
+    /// Failed while storing data in vault
+    #[error("Failed while storing data in the server")]
+    StoreFailed,
+
     /// Failed while parsing received data
     #[error("Failed while parsing the response")]
     ParseError,
@@ -11,6 +11,19 @@ use crate::hashicorp_vault::core::{HashiCorpVault, Kv2};
 
 #[async_trait::async_trait]
 impl SecretManagementInterface for HashiCorpVault {
+    /// The name is the location to store the secret at, of the form `mount:path:key`
+    async fn store_secret(
+        &self,
+        name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.store::<Kv2>(name.to_owned(), input.expose())
+            .await
+            .change_context(SecretsManagementError::StoreSecretFailed)?;
+
+        Ok(name.to_owned().into())
+    }
+
     async fn get_secret(
         &self,
         input: Secret<String>,
@@ -21,4 +34,16 @@ impl SecretManagementInterface for HashiCorpVault {
             .change_context(SecretsManagementError::FetchSecretFailed)
             .map(Into::into)
     }
+
+    async fn rotate_secret(
+        &self,
+        reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.store::<Kv2>(reference.clone().expose(), new_value.expose())
+            .await
+            .change_context(SecretsManagementError::RotateSecretFailed)?;
+
+        Ok(reference)
+    }
 }
@@ -8,10 +8,14 @@ pub mod email;
 #[cfg(feature = "aws_kms")]
 pub mod aws_kms;
 
+pub mod file_secret_store;
+
 pub mod file_storage;
 #[cfg(feature = "hashicorp-vault")]
 pub mod hashicorp_vault;
 
+pub mod local_key;
+
 pub mod no_encryption;
 
 pub mod managers;
@@ -0,0 +1,5 @@
+//! Encryption with master keys stored in a local key file
+
+pub mod core;
+
+pub mod implementers;
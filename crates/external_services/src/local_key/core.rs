@@ -0,0 +1,394 @@
+//! Encryption with AES-256-GCM master keys stored in a local key file
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use base64::Engine;
+use common_utils::{
+    crypto::{self, DecodeMessage, EncodeMessage, GcmAes256},
+    errors::CustomResult,
+};
+use error_stack::{report, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+
+/// Length in bytes of the AES-256-GCM master keys
+const KEY_LENGTH: usize = 32;
+
+/// Prefix of the key version in the encrypted values, `v<version>:<base64 ciphertext>`
+const VERSION_PREFIX: &str = "v";
+
+/// Separator between the key version and the ciphertext in the encrypted values
+const VERSION_SEPARATOR: char = ':';
+
+/// Configuration parameters required for constructing a [`LocalKeyClient`].
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LocalKeyConfig {
+    /// Path of the file holding the master keys
+    pub key_file: PathBuf,
+}
+
+impl LocalKeyConfig {
+    /// Verifies that the [`LocalKeyClient`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        common_utils::fp_utils::when(self.key_file.as_os_str().is_empty(), || {
+            Err("Local master key file path must not be empty")
+        })
+    }
+}
+
+/// Contents of the local master key file. Values are encrypted with the active key, and can be
+/// decrypted with any of the keys in the file, which allows the keys to be rotated without
+/// re-encrypting every value at once.
+///
+/// ```json
+/// {
+///   "active_version": 2,
+///   "keys": [
+///     { "version": 1, "key": "<hex encoded 32 byte key>" },
+///     { "version": 2, "key": "<hex encoded 32 byte key>" }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct LocalKeyFile {
+    /// Version of the key new values are encrypted with
+    pub active_version: u32,
+
+    /// All the keys values may be encrypted with
+    pub keys: Vec<LocalKey>,
+}
+
+/// A versioned master key
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct LocalKey {
+    /// Version of the key, stored alongside the values encrypted with it
+    pub version: u32,
+
+    /// Hex encoded 32 byte AES-256-GCM key
+    pub key: Secret<String>,
+}
+
+#[derive(serde::Serialize)]
+struct LocalKeyFileRepr<'a> {
+    active_version: u32,
+    keys: Vec<LocalKeyRepr<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct LocalKeyRepr<'a> {
+    version: u32,
+    key: &'a str,
+}
+
+impl LocalKeyFile {
+    /// Generates a key file with a single random key
+    pub fn generate() -> Self {
+        Self {
+            active_version: 1,
+            keys: vec![LocalKey::generate(1)],
+        }
+    }
+
+    /// Adds a new random key to the key file and makes it the active key, returning its version.
+    /// The previous keys are retained so that the values encrypted with them can still be
+    /// decrypted, until they are re-encrypted with [`LocalKeyClient::re_encrypt`].
+    pub fn rotate(&mut self) -> u32 {
+        let version = self
+            .keys
+            .iter()
+            .map(|key| key.version)
+            .max()
+            .unwrap_or_default()
+            .saturating_add(1);
+
+        self.keys.push(LocalKey::generate(version));
+        self.active_version = version;
+        version
+    }
+
+    /// Reads the key file at the specified path
+    pub fn read(path: &std::path::Path) -> CustomResult<Self, LocalKeyError> {
+        let contents = std::fs::read(path)
+            .change_context(LocalKeyError::KeyFileReadFailed)
+            .attach_printable_lazy(|| format!("Failed to read key file {}", path.display()))?;
+
+        serde_json::from_slice(&contents)
+            .change_context(LocalKeyError::KeyFileParsingFailed)
+            .attach_printable_lazy(|| format!("Failed to parse key file {}", path.display()))
+    }
+
+    /// Writes the key file to the specified path, replacing the existing file
+    pub fn write(&self, path: &std::path::Path) -> CustomResult<(), LocalKeyError> {
+        let repr = LocalKeyFileRepr {
+            active_version: self.active_version,
+            keys: self
+                .keys
+                .iter()
+                .map(|key| LocalKeyRepr {
+                    version: key.version,
+                    key: key.key.peek(),
+                })
+                .collect(),
+        };
+        let contents =
+            serde_json::to_vec_pretty(&repr).change_context(LocalKeyError::KeyFileWriteFailed)?;
+
+        write_private_file(path, &contents)
+            .change_context(LocalKeyError::KeyFileWriteFailed)
+            .attach_printable_lazy(|| format!("Failed to write key file {}", path.display()))
+    }
+}
+
+/// Writes the contents to a temporary file readable only by the owner, and renames it to the
+/// specified path, so that a partially written file is never read and the contents are never
+/// readable by other users
+pub(crate) fn write_private_file(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let temporary_path = path.with_extension("tmp");
+    // A leftover temporary file may have been created with other permissions, and the mode is
+    // only applied to newly created files
+    match std::fs::remove_file(&temporary_path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error),
+        _ => (),
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(&temporary_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::rename(&temporary_path, path)
+}
+
+impl LocalKey {
+    fn generate(version: u32) -> Self {
+        Self {
+            version,
+            key: hex::encode(crypto::generate_cryptographically_secure_random_bytes::<
+                KEY_LENGTH,
+            >())
+            .into(),
+        }
+    }
+}
+
+/// Client for encrypting and decrypting data with the master keys in a local key file.
+#[derive(Debug, Clone)]
+pub struct LocalKeyClient {
+    active_version: u32,
+    keys: Arc<HashMap<u32, Secret<Vec<u8>>>>,
+}
+
+impl LocalKeyClient {
+    /// Constructs a new local key client from the key file in the configuration.
+    pub fn new(config: &LocalKeyConfig) -> CustomResult<Self, LocalKeyError> {
+        Self::from_key_file(LocalKeyFile::read(&config.key_file)?)
+    }
+
+    /// Constructs a new local key client from the contents of a key file.
+    pub fn from_key_file(key_file: LocalKeyFile) -> CustomResult<Self, LocalKeyError> {
+        let mut keys = HashMap::with_capacity(key_file.keys.len());
+
+        for LocalKey { version, key } in key_file.keys {
+            let key = hex::decode(key.expose())
+                .change_context(LocalKeyError::InvalidKey)
+                .attach_printable_lazy(|| format!("Key version {version} is not hex encoded"))?;
+
+            if key.len() != KEY_LENGTH {
+                return Err(report!(LocalKeyError::InvalidKey).attach_printable(format!(
+                    "Key version {version} must be {KEY_LENGTH} bytes long"
+                )));
+            }
+            if keys.insert(version, Secret::new(key)).is_some() {
+                return Err(report!(LocalKeyError::InvalidKey).attach_printable(format!(
+                    "Key version {version} is specified more than once"
+                )));
+            }
+        }
+
+        if !keys.contains_key(&key_file.active_version) {
+            return Err(report!(LocalKeyError::InvalidKey).attach_printable(format!(
+                "Active key version {} is missing in the key file",
+                key_file.active_version
+            )));
+        }
+
+        Ok(Self {
+            active_version: key_file.active_version,
+            keys: Arc::new(keys),
+        })
+    }
+
+    /// Encrypts the provided data with the active key, and returns the key version and the
+    /// base64-encoded ciphertext in the form `v<version>:<ciphertext>`.
+    pub fn encrypt(&self, data: impl AsRef<[u8]>) -> CustomResult<String, LocalKeyError> {
+        let key = self.get_key(self.active_version)?;
+        let ciphertext = GcmAes256
+            .encode_message(key.peek(), data.as_ref())
+            .change_context(LocalKeyError::EncryptionFailed)?;
+
+        Ok(format!(
+            "{VERSION_PREFIX}{}{VERSION_SEPARATOR}{}",
+            self.active_version,
+            base64::engine::general_purpose::STANDARD.encode(ciphertext)
+        ))
+    }
+
+    /// Decrypts the provided data, which was encrypted by [`LocalKeyClient::encrypt`] with any of
+    /// the keys in the key file.
+    pub fn decrypt(&self, data: impl AsRef<[u8]>) -> CustomResult<Vec<u8>, LocalKeyError> {
+        let (version, ciphertext) = Self::parse_encrypted_value(data.as_ref())?;
+        let key = self.get_key(version)?;
+        let ciphertext = base64::engine::general_purpose::STANDARD
+            .decode(ciphertext)
+            .change_context(LocalKeyError::Base64DecodingFailed)?;
+
+        GcmAes256
+            .decode_message(key.peek(), ciphertext.into())
+            .change_context(LocalKeyError::DecryptionFailed)
+    }
+
+    /// Re-encrypts the provided data with the active key, to be used for migrating values
+    /// encrypted with older keys after the keys are rotated.
+    pub fn re_encrypt(&self, data: impl AsRef<[u8]>) -> CustomResult<String, LocalKeyError> {
+        self.encrypt(self.decrypt(data)?)
+    }
+
+    /// Returns the version of the key the provided data was encrypted with
+    pub fn get_key_version(data: impl AsRef<[u8]>) -> CustomResult<u32, LocalKeyError> {
+        Self::parse_encrypted_value(data.as_ref()).map(|(version, _)| version)
+    }
+
+    fn get_key(&self, version: u32) -> CustomResult<&Secret<Vec<u8>>, LocalKeyError> {
+        self.keys
+            .get(&version)
+            .ok_or(report!(LocalKeyError::KeyVersionNotFound))
+            .attach_printable_lazy(|| format!("Key version {version} is missing in the key file"))
+    }
+
+    fn parse_encrypted_value(data: &[u8]) -> CustomResult<(u32, &str), LocalKeyError> {
+        let (version, ciphertext) = std::str::from_utf8(data)
+            .change_context(LocalKeyError::InvalidEncryptedValue)?
+            .strip_prefix(VERSION_PREFIX)
+            .and_then(|value| value.split_once(VERSION_SEPARATOR))
+            .ok_or(LocalKeyError::InvalidEncryptedValue)
+            .attach_printable("Encrypted value is not of the form `v<version>:<ciphertext>`")?;
+
+        let version = version
+            .parse()
+            .change_context(LocalKeyError::InvalidEncryptedValue)
+            .attach_printable("Invalid key version in encrypted value")?;
+
+        Ok((version, ciphertext))
+    }
+}
+
+/// Errors that could occur during local key operations.
+#[derive(Debug, thiserror::Error)]
+pub enum LocalKeyError {
+    /// An error occurred when reading the key file.
+    #[error("Failed to read the key file")]
+    KeyFileReadFailed,
+
+    /// An error occurred when parsing the key file.
+    #[error("Failed to parse the key file")]
+    KeyFileParsingFailed,
+
+    /// An error occurred when writing the key file.
+    #[error("Failed to write the key file")]
+    KeyFileWriteFailed,
+
+    /// A key in the key file is invalid.
+    #[error("Invalid key in the key file")]
+    InvalidKey,
+
+    /// The key the data was encrypted with is not present in the key file.
+    #[error("Key version not found in the key file")]
+    KeyVersionNotFound,
+
+    /// The encrypted data is not of the form `v<version>:<ciphertext>`.
+    #[error("Invalid encrypted value")]
+    InvalidEncryptedValue,
+
+    /// An error occurred when base64 decoding input data.
+    #[error("Failed to base64 decode input data")]
+    Base64DecodingFailed,
+
+    /// An error occurred when encrypting input data.
+    #[error("Failed to encrypt input data")]
+    EncryptionFailed,
+
+    /// An error occurred when decrypting input data.
+    #[error("Failed to decrypt input data")]
+    DecryptionFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_values_remain_decryptable_after_rotation() {
+        let mut key_file = LocalKeyFile::generate();
+        let client = LocalKeyClient::from_key_file(key_file.clone()).unwrap();
+        let encrypted = client.encrypt("hello").unwrap();
+        assert_eq!(LocalKeyClient::get_key_version(&encrypted).unwrap(), 1);
+        assert_eq!(client.decrypt(&encrypted).unwrap(), b"hello");
+
+        assert_eq!(key_file.rotate(), 2);
+        let client = LocalKeyClient::from_key_file(key_file).unwrap();
+        assert_eq!(client.decrypt(&encrypted).unwrap(), b"hello");
+
+        let re_encrypted = client.re_encrypt(&encrypted).unwrap();
+        assert_eq!(LocalKeyClient::get_key_version(&re_encrypted).unwrap(), 2);
+        assert_eq!(client.decrypt(&re_encrypted).unwrap(), b"hello");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_is_written_readable_only_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!(
+            "{}.json",
+            common_utils::generate_id(10, "local_key")
+        ));
+        let key_file = LocalKeyFile::generate();
+        key_file.write(&path).unwrap();
+        key_file.write(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(!path.with_extension("tmp").exists());
+        assert_eq!(LocalKeyFile::read(&path).unwrap().active_version, 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_key_files_are_rejected() {
+        let mut key_file = LocalKeyFile::generate();
+        key_file.active_version = 2;
+        assert!(LocalKeyClient::from_key_file(key_file).is_err());
+
+        let key_file = LocalKeyFile {
+            active_version: 1,
+            keys: vec![LocalKey {
+                version: 1,
+                key: hex::encode([0u8; 16]).into(),
+            }],
+        };
+        assert!(LocalKeyClient::from_key_file(key_file).is_err());
+
+        let client = LocalKeyClient::from_key_file(LocalKeyFile::generate()).unwrap();
+        assert!(client.decrypt("v2:aGVsbG8=").is_err());
+        assert!(client.decrypt("aGVsbG8=").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! Trait implementations for local key client
+
+use common_utils::errors::CustomResult;
+use error_stack::ResultExt;
+use hyperswitch_interfaces::{
+    encryption_interface::{EncryptionError, EncryptionManagementInterface},
+    secrets_interface::{SecretManagementInterface, SecretsManagementError},
+};
+use masking::{PeekInterface, Secret};
+
+use crate::local_key::core::LocalKeyClient;
+
+#[async_trait::async_trait]
+impl EncryptionManagementInterface for LocalKeyClient {
+    async fn encrypt(&self, input: &[u8]) -> CustomResult<Vec<u8>, EncryptionError> {
+        self.encrypt(input)
+            .change_context(EncryptionError::EncryptionFailed)
+            .map(|val| val.into_bytes())
+    }
+
+    async fn decrypt(&self, input: &[u8]) -> CustomResult<Vec<u8>, EncryptionError> {
+        self.decrypt(input)
+            .change_context(EncryptionError::DecryptionFailed)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for LocalKeyClient {
+    async fn store_secret(
+        &self,
+        _name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.encrypt(input.peek())
+            .change_context(SecretsManagementError::StoreSecretFailed)
+            .map(Into::into)
+    }
+
+    async fn get_secret(
+        &self,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.decrypt(input.peek())
+            .change_context(SecretsManagementError::FetchSecretFailed)
+            .and_then(|val| {
+                String::from_utf8(val)
+                    .change_context(SecretsManagementError::FetchSecretFailed)
+                    .attach_printable("Failed to convert decrypted value to UTF-8")
+            })
+            .map(Into::into)
+    }
+
+    async fn rotate_secret(
+        &self,
+        _reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        self.encrypt(new_value.peek())
+            .change_context(SecretsManagementError::RotateSecretFailed)
+            .map(Into::into)
+    }
+}
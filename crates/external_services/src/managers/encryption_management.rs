@@ -5,13 +5,14 @@
 use std::sync::Arc;
 
 use common_utils::errors::CustomResult;
+use error_stack::ResultExt;
 use hyperswitch_interfaces::encryption_interface::{
     EncryptionError, EncryptionManagementInterface,
 };
 
 #[cfg(feature = "aws_kms")]
 use crate::aws_kms;
-use crate::no_encryption::core::NoEncryption;
+use crate::{local_key, no_encryption::core::NoEncryption};
 
 /// Enum representing configuration options for encryption management.
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -25,6 +26,12 @@ pub enum EncryptionManagementConfig {
         aws_kms: aws_kms::core::AwsKmsConfig,
     },
 
+    /// Local master key file configuration
+    LocalKey {
+        /// Local key config
+        local_key: local_key::core::LocalKeyConfig,
+    },
+
     /// Variant representing no encryption
     #[default]
     NoEncryption,
@@ -37,6 +44,8 @@ impl EncryptionManagementConfig {
             #[cfg(feature = "aws_kms")]
             Self::AwsKms { aws_kms } => aws_kms.validate(),
 
+            Self::LocalKey { local_key } => local_key.validate(),
+
             Self::NoEncryption => Ok(()),
         }
     }
@@ -49,6 +58,11 @@ impl EncryptionManagementConfig {
             #[cfg(feature = "aws_kms")]
             Self::AwsKms { aws_kms } => Arc::new(aws_kms::core::AwsKmsClient::new(aws_kms).await),
 
+            Self::LocalKey { local_key } => Arc::new(
+                local_key::core::LocalKeyClient::new(local_key)
+                    .change_context(EncryptionError::ClientCreationFailed)?,
+            ),
+
             Self::NoEncryption => Arc::new(NoEncryption),
         })
     }
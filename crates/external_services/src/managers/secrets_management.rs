@@ -3,7 +3,6 @@
 //!
 
 use common_utils::errors::CustomResult;
-#[cfg(feature = "hashicorp-vault")]
 use error_stack::ResultExt;
 use hyperswitch_interfaces::secrets_interface::{
     SecretManagementInterface, SecretsManagementError,
@@ -13,7 +12,7 @@ use hyperswitch_interfaces::secrets_interface::{
 use crate::aws_kms;
 #[cfg(feature = "hashicorp-vault")]
 use crate::hashicorp_vault;
-use crate::no_encryption::core::NoEncryption;
+use crate::{file_secret_store, local_key, no_encryption::core::NoEncryption};
 
 /// Enum representing configuration options for secrets management.
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -34,6 +33,18 @@ pub enum SecretsManagementConfig {
         hc_vault: hashicorp_vault::core::HashiCorpVaultConfig,
     },
 
+    /// Encrypted local bundle file and environment variables configuration
+    FileSecretStore {
+        /// File secret store config
+        file_secret_store: file_secret_store::core::FileSecretStoreConfig,
+    },
+
+    /// Local master key file configuration
+    LocalKey {
+        /// Local key config
+        local_key: local_key::core::LocalKeyConfig,
+    },
+
     /// Variant representing no encryption
     #[default]
     NoEncryption,
@@ -47,6 +58,8 @@ impl SecretsManagementConfig {
             Self::AwsKms { aws_kms } => aws_kms.validate(),
             #[cfg(feature = "hashicorp-vault")]
             Self::HashiCorpVault { hc_vault } => hc_vault.validate(),
+            Self::FileSecretStore { file_secret_store } => file_secret_store.validate(),
+            Self::LocalKey { local_key } => local_key.validate(),
             Self::NoEncryption => Ok(()),
         }
     }
@@ -66,6 +79,14 @@ impl SecretsManagementConfig {
                     .change_context(SecretsManagementError::ClientCreationFailed)
                     .map(|inner| -> Box<dyn SecretManagementInterface> { Box::new(inner) })
             }
+            Self::FileSecretStore { file_secret_store } => {
+                file_secret_store::core::FileSecretStore::new(file_secret_store)
+                    .change_context(SecretsManagementError::ClientCreationFailed)
+                    .map(|inner| -> Box<dyn SecretManagementInterface> { Box::new(inner) })
+            }
+            Self::LocalKey { local_key } => local_key::core::LocalKeyClient::new(local_key)
+                .change_context(SecretsManagementError::ClientCreationFailed)
+                .map(|inner| -> Box<dyn SecretManagementInterface> { Box::new(inner) }),
             Self::NoEncryption => Ok(Box::new(NoEncryption)),
         }
     }
@@ -23,6 +23,17 @@ impl EncryptionManagementInterface for NoEncryption {
 
 #[async_trait::async_trait]
 impl SecretManagementInterface for NoEncryption {
+    async fn store_secret(
+        &self,
+        _name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        String::from_utf8(self.encrypt(input.expose()))
+            .map(Into::into)
+            .change_context(SecretsManagementError::StoreSecretFailed)
+            .attach_printable("Failed to convert encrypted value to UTF-8")
+    }
+
     async fn get_secret(
         &self,
         input: Secret<String>,
@@ -32,4 +43,15 @@ impl SecretManagementInterface for NoEncryption {
             .change_context(SecretsManagementError::FetchSecretFailed)
             .attach_printable("Failed to convert decrypted value to UTF-8")
     }
+
+    async fn rotate_secret(
+        &self,
+        _reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError> {
+        String::from_utf8(self.encrypt(new_value.expose()))
+            .map(Into::into)
+            .change_context(SecretsManagementError::RotateSecretFailed)
+            .attach_printable("Failed to convert encrypted value to UTF-8")
+    }
 }
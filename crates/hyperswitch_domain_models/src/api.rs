@@ -18,6 +18,8 @@ pub enum ApplicationResponse<R> {
     FileData((Vec<u8>, mime::Mime)),
     JsonWithHeaders((R, Vec<(String, masking::Maskable<String>)>)),
     GenericLinkForm(Box<GenericLinks>),
+    /// Stored JSON response and headers of an earlier request made with the same idempotency key
+    IdempotentReplay((String, Vec<(String, masking::Maskable<String>)>)),
 }
 
 impl<T: ApiEventMetric> ApiEventMetric for ApplicationResponse<T> {
@@ -56,6 +56,7 @@ pub struct BusinessProfile {
     pub always_collect_shipping_details_from_wallet_connector: Option<bool>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: bool,
+    pub timezone: Option<String>,
     pub version: common_enums::ApiVersion,
 }
 
@@ -96,6 +97,7 @@ pub struct BusinessProfileSetter {
     pub always_collect_shipping_details_from_wallet_connector: Option<bool>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: bool,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -141,6 +143,7 @@ impl From<BusinessProfileSetter> for BusinessProfile {
                 .always_collect_shipping_details_from_wallet_connector,
             tax_connector_id: value.tax_connector_id,
             is_tax_connector_enabled: value.is_tax_connector_enabled,
+            timezone: value.timezone,
             version: consts::API_VERSION,
         }
     }
@@ -193,6 +196,7 @@ pub struct BusinessProfileGeneralUpdate {
     pub always_collect_shipping_details_from_wallet_connector: Option<bool>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: Option<bool>,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(
@@ -251,6 +255,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                     always_collect_shipping_details_from_wallet_connector,
                     tax_connector_id,
                     is_tax_connector_enabled,
+                    timezone,
                 } = *update;
 
                 Self {
@@ -284,6 +289,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                     always_collect_shipping_details_from_wallet_connector,
                     tax_connector_id,
                     is_tax_connector_enabled,
+                    timezone,
                 }
             }
             BusinessProfileUpdate::RoutingAlgorithmUpdate {
@@ -319,6 +325,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 always_collect_shipping_details_from_wallet_connector: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
             BusinessProfileUpdate::ExtendedCardInfoUpdate {
                 is_extended_card_info_enabled,
@@ -352,6 +359,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 always_collect_shipping_details_from_wallet_connector: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
             BusinessProfileUpdate::ConnectorAgnosticMitUpdate {
                 is_connector_agnostic_mit_enabled,
@@ -385,6 +393,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 always_collect_shipping_details_from_wallet_connector: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
         }
     }
@@ -439,6 +448,7 @@ impl super::behaviour::Conversion for BusinessProfile {
                 .always_collect_shipping_details_from_wallet_connector,
             tax_connector_id: self.tax_connector_id,
             is_tax_connector_enabled: Some(self.is_tax_connector_enabled),
+            timezone: self.timezone,
             version: self.version,
         })
     }
@@ -503,6 +513,7 @@ impl super::behaviour::Conversion for BusinessProfile {
                     .await?,
                 tax_connector_id: item.tax_connector_id,
                 is_tax_connector_enabled: item.is_tax_connector_enabled.unwrap_or(false),
+                timezone: item.timezone,
                 version: item.version,
             })
         }
@@ -552,6 +563,7 @@ impl super::behaviour::Conversion for BusinessProfile {
                 .always_collect_shipping_details_from_wallet_connector,
             tax_connector_id: self.tax_connector_id,
             is_tax_connector_enabled: Some(self.is_tax_connector_enabled),
+            timezone: self.timezone,
             version: self.version,
         })
     }
@@ -594,6 +606,7 @@ pub struct BusinessProfile {
     pub default_fallback_routing: Option<pii::SecretSerdeValue>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: bool,
+    pub timezone: Option<String>,
     pub version: common_enums::ApiVersion,
 }
 
@@ -633,6 +646,7 @@ pub struct BusinessProfileSetter {
     pub default_fallback_routing: Option<pii::SecretSerdeValue>,
     pub tax_connector_id: Option<String>,
     pub is_tax_connector_enabled: bool,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -677,6 +691,7 @@ impl From<BusinessProfileSetter> for BusinessProfile {
             default_fallback_routing: value.default_fallback_routing,
             tax_connector_id: value.tax_connector_id,
             is_tax_connector_enabled: value.is_tax_connector_enabled,
+            timezone: value.timezone,
             version: consts::API_VERSION,
         }
     }
@@ -722,6 +737,7 @@ pub struct BusinessProfileGeneralUpdate {
     pub always_collect_shipping_details_from_wallet_connector: Option<bool>,
     pub order_fulfillment_time: Option<i64>,
     pub order_fulfillment_time_origin: Option<common_enums::OrderFulfillmentTimeOrigin>,
+    pub timezone: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "business_profile_v2"))]
@@ -773,6 +789,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                     always_collect_shipping_details_from_wallet_connector,
                     order_fulfillment_time,
                     order_fulfillment_time_origin,
+                    timezone,
                 } = *update;
                 Self {
                     profile_name,
@@ -807,6 +824,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                     default_fallback_routing: None,
                     tax_connector_id: None,
                     is_tax_connector_enabled: None,
+                    timezone,
                 }
             }
             BusinessProfileUpdate::RoutingAlgorithmUpdate {
@@ -844,6 +862,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 default_fallback_routing: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
             BusinessProfileUpdate::ExtendedCardInfoUpdate {
                 is_extended_card_info_enabled,
@@ -879,6 +898,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 default_fallback_routing: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
             BusinessProfileUpdate::ConnectorAgnosticMitUpdate {
                 is_connector_agnostic_mit_enabled,
@@ -914,6 +934,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 default_fallback_routing: None,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
             BusinessProfileUpdate::DefaultRoutingFallbackUpdate {
                 default_fallback_routing,
@@ -949,6 +970,7 @@ impl From<BusinessProfileUpdate> for BusinessProfileUpdateInternal {
                 default_fallback_routing,
                 tax_connector_id: None,
                 is_tax_connector_enabled: None,
+                timezone: None,
             },
         }
     }
@@ -1002,6 +1024,7 @@ impl super::behaviour::Conversion for BusinessProfile {
             default_fallback_routing: self.default_fallback_routing,
             tax_connector_id: self.tax_connector_id,
             is_tax_connector_enabled: Some(self.is_tax_connector_enabled),
+            timezone: self.timezone,
             version: self.version,
         })
     }
@@ -1068,6 +1091,7 @@ impl super::behaviour::Conversion for BusinessProfile {
                 default_fallback_routing: item.default_fallback_routing,
                 tax_connector_id: item.tax_connector_id,
                 is_tax_connector_enabled: item.is_tax_connector_enabled.unwrap_or(false),
+                timezone: item.timezone,
                 version: item.version,
             })
         }
@@ -1119,6 +1143,7 @@ impl super::behaviour::Conversion for BusinessProfile {
             default_fallback_routing: self.default_fallback_routing,
             tax_connector_id: self.tax_connector_id,
             is_tax_connector_enabled: Some(self.is_tax_connector_enabled),
+            timezone: self.timezone,
             version: self.version,
         })
     }
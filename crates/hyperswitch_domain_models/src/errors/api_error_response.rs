@@ -274,6 +274,10 @@ pub enum ApiErrorResponse {
     LinkConfigurationError { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_41", message = "Payout validation failed")]
     PayoutFailed { data: Option<serde_json::Value> },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_42", message = "The idempotency key '{idempotency_key}' was already used with a different request")]
+    IdempotencyKeyReused { idempotency_key: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_43", message = "A request with the idempotency key '{idempotency_key}' is still being processed")]
+    IdempotentRequestInProgress { idempotency_key: String },
 
     #[error(error_type = ErrorType::InvalidRequestError, code = "WE_01", message = "Failed to authenticate the webhook")]
     WebhookAuthenticationFailed,
@@ -627,6 +631,12 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::PayoutFailed { data } => {
                 AER::BadRequest(ApiError::new("IR", 41, "Payout failed while processing with connector.", Some(Extra { data: data.clone(), ..Default::default()})))
             },
+            Self::IdempotencyKeyReused { idempotency_key } => {
+                AER::Conflict(ApiError::new("IR", 42, format!("The idempotency key '{idempotency_key}' was already used with a different request"), None))
+            }
+            Self::IdempotentRequestInProgress { idempotency_key } => {
+                AER::Conflict(ApiError::new("IR", 43, format!("A request with the idempotency key '{idempotency_key}' is still being processed"), None))
+            }
 
             Self::WebhookAuthenticationFailed => {
                 AER::Unauthorized(ApiError::new("WE", 1, "Webhook authentication failed", None))
@@ -26,4 +26,8 @@ pub enum EncryptionError {
     /// An error occurred when decrypting input data.
     #[error("Failed to decrypt input data")]
     DecryptionFailed,
+
+    /// An error occurred when creating the encryption management client.
+    #[error("Failed while creating an encryption management client")]
+    ClientCreationFailed,
 }
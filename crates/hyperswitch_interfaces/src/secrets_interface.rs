@@ -10,17 +10,29 @@ use masking::Secret;
 /// Trait defining the interface for managing application secrets
 #[async_trait::async_trait]
 pub trait SecretManagementInterface: Send + Sync {
-    /// Given an input, encrypt/store the secret
-    // async fn store_secret(
-    //     &self,
-    //     input: Secret<String>,
-    // ) -> CustomResult<String, SecretsManagementError>;
+    /// Given an input, encrypt/store the secret under the specified name, and return the value
+    /// referencing it that is to be set in the configuration. Backends which encrypt the secret
+    /// in place ignore the name.
+    async fn store_secret(
+        &self,
+        name: &str,
+        input: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError>;
 
     /// Given an input, decrypt/retrieve the secret
     async fn get_secret(
         &self,
         input: Secret<String>,
     ) -> CustomResult<Secret<String>, SecretsManagementError>;
+
+    /// Given the value referencing an existing secret in the configuration, replace the secret
+    /// with the new value, and return the value referencing it that is to be set in the
+    /// configuration
+    async fn rotate_secret(
+        &self,
+        reference: Secret<String>,
+        new_value: Secret<String>,
+    ) -> CustomResult<Secret<String>, SecretsManagementError>;
 }
 
 /// Errors that may occur during secret management
@@ -30,6 +42,14 @@ pub enum SecretsManagementError {
     #[error("Failed to fetch the raw data")]
     FetchSecretFailed,
 
+    /// An error occurred when storing raw data.
+    #[error("Failed to store the raw data")]
+    StoreSecretFailed,
+
+    /// An error occurred when replacing existing raw data.
+    #[error("Failed to rotate the raw data")]
+    RotateSecretFailed,
+
     /// Failed while creating kms client
     #[error("Failed while creating a secrets management client")]
     ClientCreationFailed,
@@ -693,9 +693,14 @@ mod tests {
     use std::collections::{HashMap, HashSet};
 
     use api_models::enums as api_enums;
+    use api_models::routing::{ConnectorSelection, ConnectorVolumeSplit, RoutableConnectorChoice};
     use euclid::{
         dirval,
-        dssa::graph::{AnalysisContext, CgraphExt},
+        dssa::{
+            analyzer,
+            graph::{AnalysisContext, CgraphExt},
+            types::{AnalysisError, AnalysisErrorType, AnalysisWarning},
+        },
     };
     use hyperswitch_constraint_graph::{ConstraintGraph, CycleCheck, Memoization};
 
@@ -1207,4 +1212,88 @@ mod tests {
         );
         assert!(result.is_err(), "bluesnap validation failed");
     }
+
+    fn make_wallet_program(
+        connector_selection: ConnectorSelection,
+    ) -> ast::Program<ConnectorSelection> {
+        ast::Program {
+            default_selection: ConnectorSelection::Priority(vec![connector_choice(
+                api_enums::RoutableConnectors::Adyen,
+            )]),
+            rules: vec![ast::Rule {
+                name: "wallet_rule".to_string(),
+                connector_selection,
+                statements: vec![ast::IfStatement {
+                    condition: vec![ast::Condition::Comparison(ast::Comparison {
+                        lhs: "payment_method".to_string(),
+                        comparison: ast::ComparisonType::Equal,
+                        value: ast::ValueType::EnumVariant("wallet".to_string()),
+                        metadata: HashMap::new(),
+                    })],
+                    nested: None,
+                }],
+            }],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn connector_choice(connector: api_enums::RoutableConnectors) -> RoutableConnectorChoice {
+        RoutableConnectorChoice {
+            choice_kind: api_models::routing::RoutableChoiceKind::FullStruct,
+            connector,
+            merchant_connector_id: None,
+        }
+    }
+
+    #[test]
+    fn test_priority_to_ineligible_connector_is_rejected() {
+        let graph = build_test_data();
+        let program = make_wallet_program(ConnectorSelection::Priority(vec![connector_choice(
+            api_enums::RoutableConnectors::Stripe,
+        )]));
+
+        let result = analyzer::analyze_with_warnings(program, Some(&graph));
+
+        assert!(matches!(
+            result,
+            Err(AnalysisError {
+                error_type: AnalysisErrorType::GraphAnalysis(..),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_volume_split_to_ineligible_connector_is_warned_about() {
+        let graph = build_test_data();
+        let program = make_wallet_program(ConnectorSelection::VolumeSplit(vec![
+            ConnectorVolumeSplit {
+                connector: connector_choice(api_enums::RoutableConnectors::Stripe),
+                split: 50,
+            },
+            ConnectorVolumeSplit {
+                connector: connector_choice(api_enums::RoutableConnectors::Adyen),
+                split: 50,
+            },
+        ]));
+
+        // Stripe only accepts cards, Adyen has no connector account and so no constraints
+        let (_, warnings) =
+            analyzer::analyze_with_warnings(program, Some(&graph)).expect("Analysis");
+
+        let ineligible_outputs: Vec<(&str, &dir::DirValue)> = warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                AnalysisWarning::IneligibleOutput {
+                    rule_name, output, ..
+                } => Some((rule_name.as_str(), output)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            ineligible_outputs,
+            [("wallet_rule", &dirval!(Connector = Stripe))]
+        );
+    }
 }
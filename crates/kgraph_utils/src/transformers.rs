@@ -1,4 +1,5 @@
 use api_models::enums as api_enums;
+use common_utils::types::MinorUnit;
 use euclid::{
     backend::BackendInput,
     dirval,
@@ -58,6 +59,21 @@ impl IntoContext for BackendInput {
         if let Some(card_network) = self.payment_method.card_network {
             ctx.push(dir::DirValue::CardNetwork(card_network));
         }
+        if let Some(card_issuer) = self.payment_method.card_issuer {
+            ctx.push(dir::DirValue::CardIssuer(StrValue { value: card_issuer }));
+        }
+        if let Some(card_issuer_country) = self.payment_method.card_issuer_country {
+            ctx.push(dir::DirValue::CardIssuerCountry(card_issuer_country));
+        }
+        if let Some(time_of_day) = self.payment.time_of_day {
+            ctx.push(dir::DirValue::TimeOfDay(NumValue {
+                number: MinorUnit::new(time_of_day),
+                refinement: None,
+            }));
+        }
+        if let Some(day_of_week) = self.payment.day_of_week {
+            ctx.push(dir::DirValue::DayOfWeek(day_of_week));
+        }
         if let Some(setup_future_usage) = self.payment.setup_future_usage {
             ctx.push(dir::DirValue::SetupFutureUsage(setup_future_usage));
         }
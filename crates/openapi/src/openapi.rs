@@ -158,6 +158,7 @@ Never share your secret api keys. Keep them guarded and secure.
         routes::routing::routing_retrieve_linked_config,
         routes::routing::routing_retrieve_default_config_for_profiles,
         routes::routing::routing_update_default_config_for_profile,
+        routes::routing::routing_simulate_config,
 
         // Routes for blocklist
         routes::blocklist::remove_entry_from_blocklist,
@@ -530,6 +531,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::GooglePayAssuranceDetails,
         api_models::routing::RoutingConfigRequest,
         api_models::routing::RoutingDictionaryRecord,
+        api_models::routing::RoutingAnalysisWarning,
+        api_models::routing::RoutingAnalysisWarningType,
         api_models::routing::RoutingKind,
         api_models::routing::RoutableConnectorChoice,
         api_models::routing::LinkedRoutingConfigRetrieveResponse,
@@ -541,12 +544,22 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::routing::RoutingAlgorithm,
         api_models::routing::StraightThroughAlgorithm,
         api_models::routing::ConnectorVolumeSplit,
+        api_models::routing::SuccessRateBasedRoutingConfig,
+        api_models::routing::SuccessRateWindowParams,
+        api_models::routing::RoutingSimulationRequest,
+        api_models::routing::RoutingSimulationResponse,
+        api_models::routing::ConnectorSimulationSummary,
+        api_models::routing::RouteChange,
+        api_models::routing::CurrencyAmount,
+        api_models::routing::RuleMatchCount,
         api_models::routing::ConnectorSelection,
         api_models::routing::ast::RoutableChoiceKind,
         api_models::enums::RoutableConnectors,
         api_models::routing::ast::ProgramConnectorSelection,
         api_models::routing::ast::RuleConnectorSelection,
         api_models::routing::ast::IfStatement,
+        api_models::routing::ast::Condition,
+        api_models::routing::ast::ConditionGroup,
         api_models::routing::ast::Comparison,
         api_models::routing::ast::ComparisonType,
         api_models::routing::ast::ValueType,
@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use common_utils::{
     errors::CustomResult,
@@ -15,14 +15,17 @@ use common_utils::{
 };
 use error_stack::{report, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, ListInterface, SetsInterface, StreamsInterface},
+    interfaces::{
+        ClientLike, HashesInterface, KeysInterface, ListInterface, LuaInterface, SetsInterface,
+        SortedSetsInterface, StreamsInterface,
+    },
     prelude::RedisErrorKind,
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
         MultipleValues, RedisKey, RedisMap, RedisValue, Scanner, SetOptions, XCap, XReadResponse,
     },
 };
-use futures::StreamExt;
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use tracing::instrument;
 
 use crate::{
@@ -332,6 +335,37 @@ impl super::RedisConnectionPool {
             .await
     }
 
+    /// Sets the field of the hash only if it currently holds the expected value, leaving the
+    /// expiry of the hash unchanged. Returns whether the field was set.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_hash_field_if_equals(
+        &self,
+        key: &str,
+        field: &str,
+        expected_value: &str,
+        value: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        const SCRIPT: &str = r#"
+            if redis.call("HGET", KEYS[1], ARGV[1]) == ARGV[2] then
+                redis.call("HSET", KEYS[1], ARGV[1], ARGV[3])
+                return 1
+            end
+            return 0
+        "#;
+
+        let reply: i64 = self
+            .pool
+            .eval(
+                SCRIPT,
+                vec![self.add_prefix(key)],
+                vec![field, expected_value, value],
+            )
+            .await
+            .change_context(errors::RedisError::SetHashFieldFailed)?;
+
+        Ok(reply == 1)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn serialize_and_set_hash_field_if_not_exist<V>(
         &self,
@@ -415,6 +449,49 @@ impl super::RedisConnectionPool {
             .await)
     }
 
+    /// Returns the keys matching the pattern, without the key prefix. All the primary nodes are
+    /// scanned when clustering is enabled.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn scan_keys(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.scan_keys_in_pages(pattern, count).try_concat().await
+    }
+
+    /// Scans the keys matching the pattern, yielding the keys of each page as it is scanned, so
+    /// that the keys do not have to be held in memory all at once
+    pub fn scan_keys_in_pages(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> BoxStream<'static, CustomResult<Vec<String>, errors::RedisError>> {
+        let client = self.pool.next();
+        let pattern = self.add_prefix(pattern);
+        let key_prefix = self.add_prefix("");
+
+        let pages = if client.is_clustered() {
+            client.scan_cluster(pattern, count, None).boxed()
+        } else {
+            client.scan(pattern, count, None).boxed()
+        };
+
+        pages
+            .map(move |page| {
+                let mut page = page.change_context(errors::RedisError::ScanKeysFailed)?;
+                Ok(page
+                    .take_results()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(RedisKey::as_str)
+                    .filter_map(|key| key.strip_prefix(key_prefix.as_str()))
+                    .map(String::from)
+                    .collect())
+            })
+            .boxed()
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn hscan_and_deserialize<T>(
         &self,
@@ -450,6 +527,17 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::GetHashFieldFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_all_hash_fields<V>(&self, key: &str) -> CustomResult<V, errors::RedisError>
+    where
+        V: FromRedis + Unpin + Send + 'static,
+    {
+        self.pool
+            .hgetall(self.add_prefix(key))
+            .await
+            .change_context(errors::RedisError::GetHashFieldFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn get_hash_field_and_deserialize<V>(
         &self,
@@ -487,6 +575,87 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetAddMembersFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn srem<V>(&self, key: &str, members: V) -> CustomResult<usize, errors::RedisError>
+    where
+        V: TryInto<MultipleValues> + Debug + Send,
+        V::Error: Into<fred::error::RedisError> + Send,
+    {
+        self.pool
+            .srem(self.add_prefix(key), members)
+            .await
+            .change_context(errors::RedisError::SetRemoveMembersFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn smembers(&self, key: &str) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.pool
+            .smembers(self.add_prefix(key))
+            .await
+            .change_context(errors::RedisError::SetGetMembersFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn add_member_to_sorted_set<V>(
+        &self,
+        key: &str,
+        score: f64,
+        member: V,
+    ) -> CustomResult<(), errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send,
+        V::Error: Into<fred::error::RedisError> + Send,
+    {
+        self.pool
+            .zadd::<(), _, _>(
+                self.add_prefix(key),
+                None,
+                None,
+                false,
+                false,
+                (score, member),
+            )
+            .await
+            .change_context(errors::RedisError::SortedSetAddMembersFailed)
+    }
+
+    /// Members of the sorted set with scores between `max` and `min` (both inclusive), in the
+    /// decreasing order of their scores
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_sorted_set_members_by_score_rev(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+        count: Option<i64>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.pool
+            .zrevrangebyscore(
+                self.add_prefix(key),
+                max,
+                min,
+                false,
+                count.map(|count| (0, count)),
+            )
+            .await
+            .change_context(errors::RedisError::SortedSetGetMembersFailed)
+    }
+
+    /// Removes the members of the sorted set with scores between `min` and `max` (both
+    /// inclusive), returning the number of members removed
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn remove_sorted_set_members_by_score(
+        &self,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> CustomResult<usize, errors::RedisError> {
+        self.pool
+            .zremrangebyscore(self.add_prefix(key), min, max)
+            .await
+            .change_context(errors::RedisError::SortedSetRemoveMembersFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn stream_append_entry<F>(
         &self,
@@ -559,6 +728,22 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::GetLengthFailed)
     }
 
+    /// Reads the entries of the stream with IDs between `start` and `end` (both inclusive), in
+    /// the order they were appended
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_read_range(
+        &self,
+        stream: &str,
+        start: &str,
+        end: &str,
+        count: Option<u64>,
+    ) -> CustomResult<Vec<(String, HashMap<String, String>)>, errors::RedisError> {
+        self.pool
+            .xrange_values(self.add_prefix(stream), start, end, count)
+            .await
+            .change_context(errors::RedisError::StreamReadFailed)
+    }
+
     pub fn get_keys_with_prefix<K>(&self, keys: K) -> MultipleKeys
     where
         K: Into<MultipleKeys> + Debug + Send + Sync,
@@ -52,6 +52,10 @@ pub enum RedisError {
     SetHashFieldFailed,
     #[error("Failed to add members to set in Redis")]
     SetAddMembersFailed,
+    #[error("Failed to remove members from set in Redis")]
+    SetRemoveMembersFailed,
+    #[error("Failed to get members of set in Redis")]
+    SetGetMembersFailed,
     #[error("Failed to get hash field in Redis")]
     GetHashFieldFailed,
     #[error("The requested value was not found in Redis")]
@@ -78,4 +82,12 @@ pub enum RedisError {
     PopListElementsFailed,
     #[error("Failed to increment hash field in Redis")]
     IncrementHashFieldFailed,
+    #[error("Failed to scan keys in Redis")]
+    ScanKeysFailed,
+    #[error("Failed to add members to sorted set in Redis")]
+    SortedSetAddMembersFailed,
+    #[error("Failed to get members of sorted set in Redis")]
+    SortedSetGetMembersFailed,
+    #[error("Failed to remove members from sorted set in Redis")]
+    SortedSetRemoveMembersFailed,
 }
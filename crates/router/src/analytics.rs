@@ -4,9 +4,9 @@ pub mod routes {
     use actix_web::{web, Responder, Scope};
     use analytics::{
         api_event::api_events_core, connector_events::connector_events_core, enums::AuthInfo,
-        errors::AnalyticsError, lambda_utils::invoke_lambda, opensearch::OpenSearchError,
-        outgoing_webhook_event::outgoing_webhook_events_core, sdk_events::sdk_events_core,
-        AnalyticsFlow,
+        errors::AnalyticsError, opensearch::OpenSearchError,
+        outgoing_webhook_event::outgoing_webhook_events_core, reports::ReportKind,
+        sdk_events::sdk_events_core, AnalyticsFlow,
     };
     use api_models::analytics::{
         api_event::QueryType,
@@ -24,7 +24,7 @@ pub mod routes {
 
     use crate::{
         consts::opensearch::OPENSEARCH_INDEX_PERMISSIONS,
-        core::{api_locking, errors::user::UserErrors, verification::utils},
+        core::{api_locking, errors::user::UserErrors, reports, verification::utils},
         db::user::UserInterface,
         routes::AppState,
         services::{
@@ -76,6 +76,10 @@ pub mod routes {
                             web::resource("report/payments")
                                 .route(web::post().to(generate_merchant_payment_report)),
                         )
+                        .service(
+                            web::resource("report/download/{report_id}")
+                                .route(web::get().to(download_report)),
+                        )
                         .service(
                             web::resource("metrics/sdk_events")
                                 .route(web::post().to(get_sdk_event_metrics)),
@@ -1192,6 +1196,26 @@ pub mod routes {
         .await
     }
 
+    pub async fn download_report(
+        state: web::Data<AppState>,
+        req: actix_web::HttpRequest,
+        path: web::Path<String>,
+    ) -> impl Responder {
+        let flow = AnalyticsFlow::DownloadReport;
+        Box::pin(api::server_wrap(
+            flow,
+            state.clone(),
+            &req,
+            path.into_inner(),
+            |state, (_auth, user_id): auth::AuthenticationDataWithUserId, report_id, _| async move {
+                reports::download_report(&state, &user_id, &report_id).await
+            },
+            &auth::JWTAuth(Permission::GenerateReport),
+            api_locking::LockAction::NotApplicable,
+        ))
+        .await
+    }
+
     pub async fn generate_merchant_refund_report(
         state: web::Data<AppState>,
         req: actix_web::HttpRequest,
@@ -1214,7 +1238,7 @@ pub mod routes {
 
                 let org_id = auth.merchant_account.get_org_id();
                 let merchant_id = auth.merchant_account.get_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::MerchantLevel {
@@ -1224,15 +1248,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.refund_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Refund, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1261,7 +1277,7 @@ pub mod routes {
                     .get_secret();
 
                 let org_id = auth.merchant_account.get_org_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: None,
                     auth: AuthInfo::OrgLevel {
@@ -1270,15 +1286,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.refund_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Refund, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1312,7 +1320,7 @@ pub mod routes {
                     .profile_id
                     .ok_or(report!(UserErrors::JwtProfileIdMissing))
                     .change_context(AnalyticsError::AccessForbiddenError)?;
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::ProfileLevel {
@@ -1323,15 +1331,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.refund_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Refund, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1361,7 +1361,7 @@ pub mod routes {
 
                 let org_id = auth.merchant_account.get_org_id();
                 let merchant_id = auth.merchant_account.get_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::MerchantLevel {
@@ -1371,15 +1371,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.dispute_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Dispute, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1408,7 +1400,7 @@ pub mod routes {
                     .get_secret();
 
                 let org_id = auth.merchant_account.get_org_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: None,
                     auth: AuthInfo::OrgLevel {
@@ -1417,15 +1409,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.dispute_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Dispute, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1459,7 +1443,7 @@ pub mod routes {
                     .profile_id
                     .ok_or(report!(UserErrors::JwtProfileIdMissing))
                     .change_context(AnalyticsError::AccessForbiddenError)?;
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::ProfileLevel {
@@ -1470,15 +1454,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.dispute_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Dispute, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1508,7 +1484,7 @@ pub mod routes {
 
                 let org_id = auth.merchant_account.get_org_id();
                 let merchant_id = auth.merchant_account.get_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::MerchantLevel {
@@ -1518,15 +1494,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.payment_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Payment, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1555,7 +1523,7 @@ pub mod routes {
                     .get_secret();
 
                 let org_id = auth.merchant_account.get_org_id();
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: None,
                     auth: AuthInfo::OrgLevel {
@@ -1564,15 +1532,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.payment_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Payment, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1605,7 +1565,7 @@ pub mod routes {
                     .profile_id
                     .ok_or(report!(UserErrors::JwtProfileIdMissing))
                     .change_context(AnalyticsError::AccessForbiddenError)?;
-                let lambda_req = GenerateReportRequest {
+                let report_req = GenerateReportRequest {
                     request: payload,
                     merchant_id: Some(merchant_id.clone()),
                     auth: AuthInfo::ProfileLevel {
@@ -1616,15 +1576,7 @@ pub mod routes {
                     email: user_email,
                 };
 
-                let json_bytes =
-                    serde_json::to_vec(&lambda_req).map_err(|_| AnalyticsError::UnknownError)?;
-                invoke_lambda(
-                    &state.conf.report_download_config.payment_function,
-                    &state.conf.report_download_config.region,
-                    &json_bytes,
-                )
-                .await
-                .map(ApplicationResponse::Json)
+                reports::generate_report(&state, ReportKind::Payment, report_req, user_id).await
             },
             &auth::JWTAuth(Permission::GenerateReport),
             api_locking::LockAction::NotApplicable,
@@ -1,6 +1,9 @@
 use error_stack::ResultExt;
 use router::{
-    configs::settings::{CmdLineConf, Settings},
+    configs::{
+        secrets_rotation,
+        settings::{RouterCmdLineConf, RouterCommand, Settings},
+    },
     core::errors::{ApplicationError, ApplicationResult},
     logger,
     routes::metrics,
@@ -9,15 +12,25 @@ use router::{
 #[tokio::main]
 async fn main() -> ApplicationResult<()> {
     // get commandline config before initializing config
-    let cmd_line = <CmdLineConf as clap::Parser>::parse();
+    let cmd_line = <RouterCmdLineConf as clap::Parser>::parse();
 
     #[allow(clippy::expect_used)]
-    let conf = Settings::with_config_path(cmd_line.config_path)
+    let conf = Settings::with_config_path(cmd_line.conf.config_path)
         .expect("Unable to construct application configuration");
     #[allow(clippy::expect_used)]
     conf.validate()
         .expect("Failed to validate router configuration");
 
+    if let Some(RouterCommand::RotateSecrets {
+        secrets,
+        rotate_master_key,
+        values_from_env,
+    }) = cmd_line.command
+    {
+        return secrets_rotation::run_command(&conf, &secrets, rotate_master_key, values_from_env)
+            .await;
+    }
+
     #[allow(clippy::print_stdout)] // The logger has not yet been initialized
     #[cfg(feature = "vergen")]
     {
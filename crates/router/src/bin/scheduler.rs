@@ -320,6 +320,27 @@ impl ProcessTrackerWorkflows<routes::SessionState> for WorkflowRunner {
                 storage::ProcessTrackerRunner::PaymentMethodStatusUpdateWorkflow => Ok(Box::new(
                     workflows::payment_method_status_update::PaymentMethodStatusUpdateWorkflow,
                 )),
+                storage::ProcessTrackerRunner::ReportGenerationWorkflow => {
+                    #[cfg(feature = "olap")]
+                    {
+                        Ok(Box::new(
+                            workflows::report_generation::ReportGenerationWorkflow,
+                        ))
+                    }
+                    #[cfg(not(feature = "olap"))]
+                    {
+                        Err(error_stack::report!(ProcessTrackerError::UnexpectedFlow))
+                            .attach_printable(
+                            "Cannot run report generation workflow when olap feature is disabled",
+                        )
+                    }
+                }
+                storage::ProcessTrackerRunner::QueuedPaymentWebhookWorkflow => Ok(Box::new(
+                    workflows::queued_payment_webhook::QueuedPaymentWebhookWorkflow,
+                )),
+                storage::ProcessTrackerRunner::StorageSchemeMigrationWorkflow => Ok(Box::new(
+                    workflows::storage_scheme_migration::StorageSchemeMigrationWorkflow,
+                )),
             }
         };
 
@@ -1,12 +1,12 @@
 use std::{future::Future, sync::Arc, time::Instant};
 
 use actix_web::{HttpRequest, HttpResponse, Responder};
-use common_utils::errors::{CustomResult, ErrorSwitch};
+use common_utils::errors::{CustomResult, ErrorSwitch, ReportSwitchExt};
 use router_env::{instrument, tracing, Tag};
 use serde::Serialize;
 
 use crate::{
-    core::{api_locking, errors},
+    core::{api_locking, errors, idempotency},
     events::api_logs::ApiEventMetric,
     routes::{
         app::{AppStateInfo, ReqState},
@@ -44,6 +44,20 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let idempotency_input: Result<_, error_stack::Report<E>> =
+        idempotency::IdempotencyInput::from_request(
+            &flow,
+            request.headers(),
+            request_method,
+            url_path,
+            &payload,
+        )
+        .switch();
+    let idempotency_input = match idempotency_input {
+        Ok(idempotency_input) => idempotency_input,
+        Err(error) => return api::log_and_return_error_response(error),
+    };
+
     let server_wrap_util_res = metrics::request::record_request_time_metric(
         api::server_wrap_util(
             &flow,
@@ -54,21 +68,36 @@ where
             func,
             api_authentication,
             lock_action,
+            idempotency_input,
         ),
         &flow,
     )
     .await
-    .map(|response| {
+    .map(|(response, idempotency_claim)| {
         logger::info!(api_response =? response);
-        response
+        (response, idempotency_claim)
     });
 
+    let (server_wrap_util_res, idempotency_claim) = match server_wrap_util_res {
+        Ok((response, idempotency_claim)) => (Ok(response), idempotency_claim),
+        Err(error) => (Err(error), None),
+    };
+    // The Stripe compatible response to be replayed for retries of the request with the same
+    // idempotency key
+    let mut idempotent_response = None;
+
     let res = match server_wrap_util_res {
         Ok(api::ApplicationResponse::Json(response)) => {
             let response = S::try_from(response);
             match response {
                 Ok(response) => match serde_json::to_string(&response) {
-                    Ok(res) => api::http_response_json(res),
+                    Ok(res) => {
+                        idempotent_response = Some(idempotency::IdempotentResponse {
+                            body: res.clone(),
+                            headers: Vec::new(),
+                        });
+                        api::http_response_json(res)
+                    }
                     Err(_) => api::http_response_err(
                         r#"{
                                 "error": {
@@ -90,7 +119,13 @@ where
             let response = S::try_from(response);
             match response {
                 Ok(response) => match serde_json::to_string(&response) {
-                    Ok(res) => api::http_response_json_with_headers(res, headers, None),
+                    Ok(res) => {
+                        idempotent_response = Some(idempotency::IdempotentResponse {
+                            body: res.clone(),
+                            headers: headers.clone(),
+                        });
+                        api::http_response_json_with_headers(res, headers, None)
+                    }
                     Err(_) => api::http_response_err(
                         r#"{
                                 "error": {
@@ -109,6 +144,13 @@ where
             }
         }
         Ok(api::ApplicationResponse::StatusOk) => api::http_response_ok(),
+        Ok(api::ApplicationResponse::IdempotentReplay((response, mut headers))) => {
+            headers.push((
+                crate::headers::IDEMPOTENT_REPLAYED.to_string(),
+                "true".to_string().into(),
+            ));
+            api::http_response_json_with_headers(response, headers, None)
+        }
         Ok(api::ApplicationResponse::TextPlain(text)) => api::http_response_plaintext(text),
         Ok(api::ApplicationResponse::FileData((file_data, content_type))) => {
             api::http_response_file_data(file_data, content_type)
@@ -182,6 +224,13 @@ where
         Err(error) => api::log_and_return_error_response(error),
     };
 
+    if let Some(idempotency_claim) = idempotency_claim {
+        match idempotent_response {
+            Some(response) => idempotency_claim.complete(response).await,
+            None => idempotency_claim.release().await,
+        }
+    }
+
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
     let request_duration = end_instant.saturating_duration_since(start_instant);
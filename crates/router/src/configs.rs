@@ -1,6 +1,7 @@
 use hyperswitch_interfaces::secrets_interface::secret_state::RawSecret;
 
 pub(crate) mod defaults;
+pub mod secrets_rotation;
 pub mod secrets_transformers;
 pub mod settings;
 mod validations;
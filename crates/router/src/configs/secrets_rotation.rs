@@ -0,0 +1,224 @@
+//! Rotation of the secrets the router generates itself, through the configured secrets manager
+
+use std::path::Path;
+
+use common_utils::crypto;
+use error_stack::{report, ResultExt};
+use external_services::{
+    file_secret_store::core::FileSecretStore,
+    local_key::core::{LocalKeyClient, LocalKeyFile},
+    managers::secrets_management::SecretsManagementConfig,
+};
+use hyperswitch_interfaces::secrets_interface::secret_state::SecuredSecret;
+use masking::{ExposeInterface, PeekInterface, Secret};
+
+use crate::{
+    configs::settings::{Secrets, Settings},
+    core::errors::{ApplicationError, ApplicationResult},
+};
+
+/// Length of the generated secrets
+const SECRET_LENGTH: usize = 64;
+
+/// The secrets in the `[secrets]` section of the configuration that can be rotated. The master
+/// encryption key is not rotatable, as the data encrypted with it would become unreadable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RotatableSecret {
+    /// Signs the JWTs issued to users, rotating it signs out all users
+    JwtSecret,
+    /// Authenticates admin API requests
+    AdminApiKey,
+    /// Authenticates recon admin API requests
+    ReconAdminApiKey,
+}
+
+impl RotatableSecret {
+    /// Returns the key of the secret in the `[secrets]` section of the configuration
+    pub fn get_config_key(&self) -> &'static str {
+        match self {
+            Self::JwtSecret => "jwt_secret",
+            Self::AdminApiKey => "admin_api_key",
+            Self::ReconAdminApiKey => "recon_admin_api_key",
+        }
+    }
+
+    /// Returns whether the secret is an API key that clients authenticate with, and hence needs
+    /// to be known to the operator
+    pub fn is_api_key(&self) -> bool {
+        match self {
+            Self::JwtSecret => false,
+            Self::AdminApiKey | Self::ReconAdminApiKey => true,
+        }
+    }
+
+    /// Reads the new value of the secret from the environment variable named after its
+    /// configuration key in upper case
+    fn get_value_from_env(&self) -> ApplicationResult<Secret<String>> {
+        let name = self.get_config_key().to_uppercase();
+        let value = std::env::var(&name)
+            .change_context(ApplicationError::ConfigurationError)
+            .attach_printable_lazy(|| format!("Environment variable {name} is not set"))?;
+
+        common_utils::fp_utils::when(value.is_empty(), || {
+            Err(error_stack::report!(ApplicationError::ConfigurationError))
+                .attach_printable(format!("Environment variable {name} must not be empty"))
+        })?;
+
+        Ok(value.into())
+    }
+
+    fn get_reference<'a>(&self, secrets: &'a Secrets) -> &'a Secret<String> {
+        match self {
+            Self::JwtSecret => &secrets.jwt_secret,
+            Self::AdminApiKey => &secrets.admin_api_key,
+            Self::ReconAdminApiKey => &secrets.recon_admin_api_key,
+        }
+    }
+}
+
+/// Replaces the specified secrets with the new values through the configured secrets manager,
+/// and returns the values referencing them that are to be set in the configuration. The new
+/// secrets take effect once the configuration is updated and the router is restarted.
+pub async fn rotate_secrets(
+    conf: &Settings<SecuredSecret>,
+    secrets: &[(RotatableSecret, Secret<String>)],
+) -> ApplicationResult<Vec<(RotatableSecret, Secret<String>)>> {
+    let secret_management_client = conf
+        .secrets_management
+        .get_secret_management_client()
+        .await
+        .change_context(ApplicationError::ConfigurationError)
+        .attach_printable("Failed to create secret management client")?;
+
+    let mut rotated_secrets = Vec::with_capacity(secrets.len());
+    for (secret, new_value) in secrets {
+        let reference = secret_management_client
+            .rotate_secret(
+                secret.get_reference(conf.secrets.get_inner()).clone(),
+                new_value.clone(),
+            )
+            .await
+            .change_context(ApplicationError::ConfigurationError)
+            .attach_printable_lazy(|| format!("Failed to rotate {}", secret.get_config_key()))?;
+
+        rotated_secrets.push((*secret, reference));
+    }
+
+    Ok(rotated_secrets)
+}
+
+/// Adds a new key to the master key file of the `local_key` or `file_secret_store` secrets
+/// manager and makes it the active key, then re-encrypts the stored secrets with it. Returns the
+/// re-encrypted `[secrets]` entries that are to be set in the configuration, which are only
+/// encrypted in the configuration with the `local_key` secrets manager. The secrets in the bundle
+/// file of the `file_secret_store` secrets manager are re-encrypted in place.
+pub fn rotate_master_key(
+    conf: &Settings<SecuredSecret>,
+) -> ApplicationResult<Vec<(&'static str, Secret<String>)>> {
+    match &conf.secrets_management {
+        SecretsManagementConfig::LocalKey { local_key } => {
+            let client = add_master_key(&local_key.key_file)?;
+            let secrets = conf.secrets.get_inner();
+
+            [
+                ("jwt_secret", &secrets.jwt_secret),
+                ("admin_api_key", &secrets.admin_api_key),
+                ("recon_admin_api_key", &secrets.recon_admin_api_key),
+                ("master_enc_key", &secrets.master_enc_key),
+            ]
+            .into_iter()
+            .filter(|(_, value)| !value.peek().is_empty())
+            .map(|(config_key, value)| {
+                client
+                    .re_encrypt(value.peek())
+                    .change_context(ApplicationError::ConfigurationError)
+                    .attach_printable_lazy(|| format!("Failed to re-encrypt {config_key}"))
+                    .map(|re_encrypted| (config_key, re_encrypted.into()))
+            })
+            .collect()
+        }
+        SecretsManagementConfig::FileSecretStore { file_secret_store } => {
+            add_master_key(&file_secret_store.key_file)?;
+            FileSecretStore::new(file_secret_store)
+                .and_then(|store| store.re_encrypt())
+                .change_context(ApplicationError::ConfigurationError)
+                .attach_printable("Failed to re-encrypt the secrets in the bundle file")?;
+
+            Ok(Vec::new())
+        }
+        _ => Err(report!(ApplicationError::ConfigurationError)).attach_printable(
+            "Master keys can only be rotated with the `local_key` or `file_secret_store` secrets \
+             manager",
+        ),
+    }
+}
+
+/// Adds a new key to the master key file at the path and makes it the active key, and returns the
+/// client for the updated key file
+fn add_master_key(key_file_path: &Path) -> ApplicationResult<LocalKeyClient> {
+    let mut key_file =
+        LocalKeyFile::read(key_file_path).change_context(ApplicationError::ConfigurationError)?;
+    key_file.rotate();
+    key_file
+        .write(key_file_path)
+        .change_context(ApplicationError::ConfigurationError)?;
+
+    LocalKeyClient::from_key_file(key_file).change_context(ApplicationError::ConfigurationError)
+}
+
+/// Runs the `rotate-secrets` subcommand, printing the updated `[secrets]` configuration entries
+/// to the standard output. The master key file is rotated first if `rotate_master_key` is set, so
+/// that the new secrets are encrypted with the new master key. The new values are read from the
+/// environment if `values_from_env` is set, and generated otherwise, in which case the generated
+/// API keys are printed once to the standard error, as they are required to call the APIs they
+/// authenticate.
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub async fn run_command(
+    conf: &Settings<SecuredSecret>,
+    secrets: &[RotatableSecret],
+    rotate_master_key: bool,
+    values_from_env: bool,
+) -> ApplicationResult<()> {
+    let new_values = secrets
+        .iter()
+        .map(|secret| {
+            let value = if values_from_env {
+                secret.get_value_from_env()?
+            } else {
+                crypto::generate_cryptographically_secure_random_string(SECRET_LENGTH).into()
+            };
+            Ok((*secret, value))
+        })
+        .collect::<ApplicationResult<Vec<_>>>()?;
+
+    let mut updated_entries = if rotate_master_key {
+        self::rotate_master_key(conf)?
+    } else {
+        Vec::new()
+    };
+
+    for (secret, reference) in rotate_secrets(conf, &new_values).await? {
+        let config_key = secret.get_config_key();
+        updated_entries.retain(|(key, _)| *key != config_key);
+        updated_entries.push((config_key, reference));
+    }
+
+    if !updated_entries.is_empty() {
+        println!("[secrets]");
+    }
+    for (config_key, reference) in updated_entries {
+        println!("{config_key} = {:?}", reference.expose());
+    }
+
+    if !values_from_env {
+        for (secret, value) in new_values.iter().filter(|(secret, _)| secret.is_api_key()) {
+            eprintln!(
+                "New {}, which will not be shown again: {}",
+                secret.get_config_key(),
+                value.peek()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -47,6 +47,43 @@ pub struct CmdLineConf {
     pub config_path: Option<PathBuf>,
 }
 
+/// Command line arguments of the router binary, which accepts subcommands that the other binaries
+/// sharing [`CmdLineConf`] do not
+#[derive(clap::Parser, Default)]
+#[cfg_attr(feature = "vergen", command(version = router_env::version!()))]
+pub struct RouterCmdLineConf {
+    #[command(flatten)]
+    pub conf: CmdLineConf,
+
+    #[command(subcommand)]
+    pub command: Option<RouterCommand>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum RouterCommand {
+    /// Replace secrets in the `[secrets]` section with new ones through the configured secrets
+    /// manager, and print the configuration entries referencing them
+    RotateSecrets {
+        /// Secret to rotate, can be specified multiple times
+        #[arg(
+            long = "secret",
+            value_enum,
+            required_unless_present = "rotate_master_key"
+        )]
+        secrets: Vec<super::secrets_rotation::RotatableSecret>,
+
+        /// Add a new key to the master key file of the `local_key` or `file_secret_store` secrets
+        /// manager and make it the active key, before re-encrypting the stored secrets with it
+        #[arg(long)]
+        rotate_master_key: bool,
+
+        /// Read the new values of the secrets from the environment variables named after their
+        /// configuration keys in upper case (e.g. `ADMIN_API_KEY`), instead of generating them
+        #[arg(long)]
+        values_from_env: bool,
+    },
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct Settings<S: SecretState> {
@@ -98,6 +135,7 @@ pub struct Settings<S: SecretState> {
     pub multiple_api_version_supported_connectors: MultipleApiVersionSupportedConnectors,
     pub applepay_merchant_configs: SecretStateContainer<ApplepayMerchantConfigs, S>,
     pub lock_settings: LockSettings,
+    pub idempotency: IdempotencySettings,
     pub temp_locker_enable_config: TempLockerEnableConfig,
     pub generic_link: GenericLink,
     pub payment_link: PaymentLink,
@@ -285,6 +323,34 @@ pub struct ForexApi {
     pub api_timeout: u64,
     /// in ms
     pub redis_lock_timeout: u64,
+    /// Sources of exchange rates, queried in order until one of them returns fresh rates.
+    /// The primary and fallback forex APIs are used if no sources are configured.
+    pub rates_sources: Vec<ForexRatesSource>,
+    /// Age in seconds after which the rates returned by a source are considered stale
+    pub max_rates_age: Option<i64>,
+    /// Use the freshest stale rates if none of the sources return fresh rates
+    pub serve_stale_rates: bool,
+    /// Time in seconds for which the history of exchange rates is kept after the last update
+    pub rates_history_retention: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum ForexRatesSource {
+    /// The primary forex API, authenticated with `api_key`
+    ForexApi,
+    /// The fallback forex API, authenticated with `fallback_api_key`
+    FallbackForexApi,
+    /// A local JSON or CSV file of exchange rates
+    File {
+        path: PathBuf,
+        format: currency_conversion::providers::RatesFileFormat,
+        /// Base currency of the rates in a CSV file
+        #[serde(default)]
+        base_currency: enums::Currency,
+    },
+    /// A table of exchange rates embedded in the configuration
+    Static(DefaultExchangeRates),
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -807,6 +873,7 @@ impl Settings<SecuredSecret> {
             .map_err(|err| ApplicationError::InvalidConfigurationValueError(err.to_string()))?;
 
         self.lock_settings.validate()?;
+        self.idempotency.validate()?;
         self.events.validate()?;
 
         #[cfg(feature = "olap")]
@@ -819,6 +886,12 @@ impl Settings<SecuredSecret> {
         self.secrets_management
             .validate()
             .map_err(|err| ApplicationError::InvalidConfigurationValueError(err.into()))?;
+
+        #[cfg(feature = "email")]
+        self.email
+            .validate()
+            .map_err(|err| ApplicationError::InvalidConfigurationValueError(err.into()))?;
+
         self.generic_link.payment_method_collect.validate()?;
         self.generic_link.payout_link.validate()?;
         Ok(())
@@ -872,6 +945,26 @@ impl<'de> Deserialize<'de> for LockSettings {
     }
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct IdempotencySettings {
+    /// Time for which the response of a request made with an idempotency key is retained
+    pub ttl_in_secs: u32,
+    /// Time for which an idempotency key is claimed by a request, the claim is extended as long
+    /// as the request is being processed
+    pub in_progress_ttl_in_secs: u32,
+}
+
+impl Default for IdempotencySettings {
+    fn default() -> Self {
+        Self {
+            // 24 * 60 * 60 seconds
+            ttl_in_secs: 86400,
+            in_progress_ttl_in_secs: 60,
+        }
+    }
+}
+
 #[cfg(feature = "olap")]
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorOnboarding {
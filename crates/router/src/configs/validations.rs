@@ -153,6 +153,24 @@ impl super::settings::ApiKeys {
     }
 }
 
+impl super::settings::IdempotencySettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.ttl_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "idempotency ttl_in_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.in_progress_ttl_in_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "idempotency in_progress_ttl_in_secs must not be empty or 0".into(),
+            ))
+        })
+    }
+}
+
 impl super::settings::LockSettings {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
@@ -34,6 +34,9 @@ pub const FINGERPRINT_SECRET_LENGTH: usize = 64;
 
 pub const DEFAULT_LIST_API_LIMIT: u16 = 10;
 
+/// Maximum number of process tracker tasks listed by the process tracker list API
+pub const PROCESS_TRACKER_LIST_MAX_LIMIT: u16 = 100;
+
 // String literals
 pub(crate) const UNSUPPORTED_ERROR_MESSAGE: &str = "Unsupported response type";
 pub(crate) const LOW_BALANCE_ERROR_MESSAGE: &str = "Insufficient balance in the payment method";
@@ -21,6 +21,8 @@ pub mod files;
 pub mod fraud_check;
 pub mod gsm;
 pub mod health_check;
+pub mod idempotency;
+pub mod kv_consistency;
 pub mod locker_migration;
 pub mod mandate;
 pub mod metrics;
@@ -33,8 +35,12 @@ pub mod payout_link;
 pub mod payouts;
 pub mod pm_auth;
 pub mod poll;
+pub mod process_tracker;
 pub mod refunds;
+#[cfg(feature = "olap")]
+pub mod reports;
 pub mod routing;
+pub mod storage_scheme_migration;
 pub mod surcharge_decision_config;
 #[cfg(feature = "olap")]
 pub mod user;
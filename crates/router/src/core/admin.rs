@@ -3436,6 +3436,8 @@ impl BusinessProfileCreateBridge for api::BusinessProfileCreate {
             helpers::validate_session_expiry(session_expiry.to_owned())?;
         }
 
+        helpers::validate_timezone(self.timezone.as_deref())?;
+
         if let Some(intent_fulfillment_expiry) = self.intent_fulfillment_time {
             helpers::validate_intent_fulfillment_expiry(intent_fulfillment_expiry)?;
         }
@@ -3546,6 +3548,7 @@ impl BusinessProfileCreateBridge for api::BusinessProfileCreate {
                     .map(Into::into),
                 tax_connector_id: self.tax_connector_id,
                 is_tax_connector_enabled: self.is_tax_connector_enabled,
+                timezone: self.timezone,
                 always_collect_billing_details_from_wallet_connector: self
                     .always_collect_billing_details_from_wallet_connector,
                 always_collect_shipping_details_from_wallet_connector: self
@@ -3565,6 +3568,8 @@ impl BusinessProfileCreateBridge for api::BusinessProfileCreate {
             helpers::validate_session_expiry(session_expiry.to_owned())?;
         }
 
+        helpers::validate_timezone(self.timezone.as_deref())?;
+
         // Generate a unique profile id
         // TODO: the profile_id should be generated from the profile_name
         let profile_id = common_utils::generate_profile_id_of_default_length();
@@ -3654,6 +3659,7 @@ impl BusinessProfileCreateBridge for api::BusinessProfileCreate {
                 default_fallback_routing: None,
                 tax_connector_id: self.tax_connector_id,
                 is_tax_connector_enabled: self.is_tax_connector_enabled,
+                timezone: self.timezone,
             },
         ))
     }
@@ -3813,6 +3819,8 @@ impl BusinessProfileUpdateBridge for api::BusinessProfileUpdate {
             helpers::validate_session_expiry(session_expiry.to_owned())?;
         }
 
+        helpers::validate_timezone(self.timezone.as_deref())?;
+
         if let Some(intent_fulfillment_expiry) = self.intent_fulfillment_time {
             helpers::validate_intent_fulfillment_expiry(intent_fulfillment_expiry)?;
         }
@@ -3907,6 +3915,7 @@ impl BusinessProfileUpdateBridge for api::BusinessProfileUpdate {
                     .always_collect_shipping_details_from_wallet_connector,
                 tax_connector_id: self.tax_connector_id,
                 is_tax_connector_enabled: self.is_tax_connector_enabled,
+                timezone: self.timezone,
             },
         )))
     }
@@ -3924,6 +3933,8 @@ impl BusinessProfileUpdateBridge for api::BusinessProfileUpdate {
             helpers::validate_session_expiry(session_expiry.to_owned())?;
         }
 
+        helpers::validate_timezone(self.timezone.as_deref())?;
+
         let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
 
         let payment_link_config = self
@@ -3999,6 +4010,7 @@ impl BusinessProfileUpdateBridge for api::BusinessProfileUpdate {
                     .always_collect_billing_details_from_wallet_connector,
                 always_collect_shipping_details_from_wallet_connector: self
                     .always_collect_shipping_details_from_wallet_connector,
+                timezone: self.timezone,
             },
         )))
     }
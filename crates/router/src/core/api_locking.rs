@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, future::Future};
 
 use actix_web::rt::time as actix_time;
 use error_stack::{report, ResultExt};
@@ -18,12 +18,21 @@ pub enum LockStatus {
     Busy,
 }
 
+/// The outcome of performing a work item with [`LockAction::perform_queued_action`]
+#[derive(Debug)]
+pub enum QueuedActionOutcome<R> {
+    /// The work item was performed while holding the lock
+    Performed(R),
+    /// The lock was held by another request, the work item was queued to be performed later
+    Queued,
+}
+
 #[derive(Clone, Debug)]
 pub enum LockAction {
     // Sleep until the lock is acquired
     Hold { input: LockingInput },
     // Queue it but return response as 2xx, could be used for webhooks
-    QueueWithOk,
+    QueueWithOk { input: LockingInput },
     // Return Error
     Drop,
     // Locking Not applicable
@@ -47,6 +56,94 @@ impl LockingInput {
             self.unique_locking_key
         )
     }
+
+    /// Makes a single attempt at acquiring the lock
+    async fn try_acquire_lock<A>(
+        &self,
+        state: &A,
+        merchant_id: common_utils::id_type::MerchantId,
+    ) -> RouterResult<LockStatus>
+    where
+        A: SessionStateInfo,
+    {
+        let redis_conn = state
+            .store()
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+        let redis_locking_key = self.get_redis_locking_key(merchant_id);
+        let redis_lock_result = redis_conn
+            .set_key_if_not_exists_with_expiry(
+                redis_locking_key.as_str(),
+                state.get_request_id(),
+                Some(i64::from(
+                    state.conf().lock_settings.redis_lock_expiry_seconds,
+                )),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+        match redis_lock_result {
+            redis::SetnxReply::KeySet => {
+                logger::info!("Lock acquired for locking input {:?}", self);
+                tracing::Span::current().record("redis_lock_acquired", redis_locking_key);
+                Ok(LockStatus::Acquired)
+            }
+            redis::SetnxReply::KeyNotSet => {
+                logger::info!(
+                    "Lock busy by other request when tried for locking input {:?}",
+                    self
+                );
+                Ok(LockStatus::Busy)
+            }
+        }
+    }
+
+    async fn release_lock<A>(
+        &self,
+        state: &A,
+        merchant_id: common_utils::id_type::MerchantId,
+    ) -> RouterResult<()>
+    where
+        A: SessionStateInfo,
+    {
+        let redis_conn = state
+            .store()
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+        let redis_locking_key = self.get_redis_locking_key(merchant_id);
+
+        match redis_conn
+            .get_key::<Option<String>>(&redis_locking_key)
+            .await
+        {
+            Ok(val) => {
+                if val == state.get_request_id() {
+                    match redis_conn.delete_key(redis_locking_key.as_str()).await {
+                        Ok(redis::types::DelReply::KeyDeleted) => {
+                            logger::info!("Lock freed for locking input {:?}", self);
+                            tracing::Span::current()
+                                .record("redis_lock_released", redis_locking_key);
+                            Ok(())
+                        }
+                        Ok(redis::types::DelReply::KeyNotDeleted) => {
+                            Err(errors::ApiErrorResponse::InternalServerError).attach_printable(
+                                "Status release lock called but key is not found in redis",
+                            )
+                        }
+                        Err(error) => {
+                            Err(error).change_context(errors::ApiErrorResponse::InternalServerError)
+                        }
+                    }
+                } else {
+                    Err(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("The request_id which acquired the lock is not equal to the request_id requesting for releasing the lock")
+                }
+            }
+            Err(error) => Err(error).change_context(errors::ApiErrorResponse::InternalServerError),
+        }
+    }
 }
 
 impl LockAction {
@@ -61,57 +158,37 @@ impl LockAction {
     {
         match self {
             Self::Hold { input } => {
-                let redis_conn = state
-                    .store()
-                    .get_redis_conn()
-                    .change_context(errors::ApiErrorResponse::InternalServerError)?;
-
-                let redis_locking_key = input.get_redis_locking_key(merchant_id);
                 let delay_between_retries_in_milliseconds = state
                     .conf()
                     .lock_settings
                     .delay_between_retries_in_milliseconds;
-                let redis_lock_expiry_seconds =
-                    state.conf().lock_settings.redis_lock_expiry_seconds;
                 let lock_retries = input
                     .override_lock_retries
                     .unwrap_or(state.conf().lock_settings.lock_retries);
                 for _retry in 0..lock_retries {
-                    let redis_lock_result = redis_conn
-                        .set_key_if_not_exists_with_expiry(
-                            redis_locking_key.as_str(),
-                            state.get_request_id(),
-                            Some(i64::from(redis_lock_expiry_seconds)),
-                        )
-                        .await;
-
-                    match redis_lock_result {
-                        Ok(redis::SetnxReply::KeySet) => {
-                            logger::info!("Lock acquired for locking input {:?}", input);
-                            tracing::Span::current()
-                                .record("redis_lock_acquired", redis_locking_key);
-                            return Ok(());
-                        }
-                        Ok(redis::SetnxReply::KeyNotSet) => {
-                            logger::info!(
-                                "Lock busy by other request when tried for locking input {:?}",
-                                input
-                            );
+                    match input.try_acquire_lock(state, merchant_id.clone()).await? {
+                        LockStatus::Acquired => return Ok(()),
+                        LockStatus::Busy => {
                             actix_time::sleep(tokio::time::Duration::from_millis(u64::from(
                                 delay_between_retries_in_milliseconds,
                             )))
                             .await;
                         }
-                        Err(err) => {
-                            return Err(err)
-                                .change_context(errors::ApiErrorResponse::InternalServerError)
-                        }
                     }
                 }
 
                 Err(report!(errors::ApiErrorResponse::ResourceBusy))
             }
-            Self::QueueWithOk | Self::Drop | Self::NotApplicable => Ok(()),
+            Self::QueueWithOk { input } => {
+                match input.try_acquire_lock(state, merchant_id).await? {
+                    LockStatus::Acquired => Ok(()),
+                    LockStatus::Busy => Err(report!(errors::ApiErrorResponse::ResourceBusy))
+                        .attach_printable(
+                            "Requests can only be queued through `perform_queued_action`",
+                        ),
+                }
+            }
+            Self::Drop | Self::NotApplicable => Ok(()),
         }
     }
 
@@ -125,47 +202,66 @@ impl LockAction {
         A: SessionStateInfo,
     {
         match self {
-            Self::Hold { input } => {
-                let redis_conn = state
-                    .store()
-                    .get_redis_conn()
-                    .change_context(errors::ApiErrorResponse::InternalServerError)?;
-
-                let redis_locking_key = input.get_redis_locking_key(merchant_id);
-
-                match redis_conn
-                    .get_key::<Option<String>>(&redis_locking_key)
-                    .await
-                {
-                    Ok(val) => {
-                        if val == state.get_request_id() {
-                            match redis_conn.delete_key(redis_locking_key.as_str()).await {
-                                Ok(redis::types::DelReply::KeyDeleted) => {
-                                    logger::info!("Lock freed for locking input {:?}", input);
-                                    tracing::Span::current()
-                                        .record("redis_lock_released", redis_locking_key);
-                                    Ok(())
-                                }
-                                Ok(redis::types::DelReply::KeyNotDeleted) => {
-                                    Err(errors::ApiErrorResponse::InternalServerError)
-                                        .attach_printable(
-                                        "Status release lock called but key is not found in redis",
-                                    )
-                                }
-                                Err(error) => Err(error)
-                                    .change_context(errors::ApiErrorResponse::InternalServerError),
-                            }
-                        } else {
-                            Err(errors::ApiErrorResponse::InternalServerError)
-                                .attach_printable("The request_id which acquired the lock is not equal to the request_id requesting for releasing the lock")
-                        }
-                    }
-                    Err(error) => {
-                        Err(error).change_context(errors::ApiErrorResponse::InternalServerError)
-                    }
-                }
+            Self::Hold { input } | Self::QueueWithOk { input } => {
+                input.release_lock(state, merchant_id).await
+            }
+            Self::Drop | Self::NotApplicable => Ok(()),
+        }
+    }
+
+    /// Performs the work item while holding the lock.
+    ///
+    /// For [`LockAction::QueueWithOk`], if the lock is held by another request, the work item is
+    /// handed to `queue` instead, which must store it durably to be performed later, for instance
+    /// as a process tracker task which acquires the lock itself. The caller can respond with a 2xx
+    /// only once the work item is queued, so an error from `queue` is returned as is.
+    ///
+    /// The other locking actions perform the work item right away, between
+    /// [`LockAction::perform_locking_action`] and [`LockAction::free_lock_action`].
+    #[instrument(skip_all)]
+    pub async fn perform_queued_action<A, W, R, F, Fut, Q, QFut>(
+        self,
+        state: &A,
+        merchant_id: common_utils::id_type::MerchantId,
+        work_item: W,
+        perform: F,
+        queue: Q,
+    ) -> RouterResult<QueuedActionOutcome<R>>
+    where
+        A: SessionStateInfo,
+        W: Debug,
+        F: FnOnce(W) -> Fut,
+        Fut: Future<Output = RouterResult<R>>,
+        Q: FnOnce(W) -> QFut,
+        QFut: Future<Output = RouterResult<()>>,
+    {
+        let input = match self {
+            Self::QueueWithOk { input } => input,
+            Self::Hold { .. } | Self::Drop | Self::NotApplicable => {
+                self.clone()
+                    .perform_locking_action(state, merchant_id.clone())
+                    .await?;
+                let result = perform(work_item).await;
+                self.free_lock_action(state, merchant_id).await?;
+                return result.map(QueuedActionOutcome::Performed);
+            }
+        };
+
+        match input.try_acquire_lock(state, merchant_id.clone()).await? {
+            LockStatus::Acquired => {
+                let result = perform(work_item).await;
+                input.release_lock(state, merchant_id).await?;
+                result.map(QueuedActionOutcome::Performed)
+            }
+            LockStatus::Busy => {
+                logger::info!(
+                    "Queueing work item for locking input {:?}: {:?}",
+                    input,
+                    work_item
+                );
+                queue(work_item).await?;
+                Ok(QueuedActionOutcome::Queued)
             }
-            Self::QueueWithOk | Self::Drop | Self::NotApplicable => Ok(()),
         }
     }
 }
@@ -176,3 +272,124 @@ pub trait GetLockingInput {
         F: router_env::types::FlowMetric,
         lock_utils::ApiIdentifier: From<F>;
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        routes::{
+            self,
+            app::{settings::Settings, StorageImpl},
+            SessionState,
+        },
+        services,
+    };
+
+    async fn get_session_state() -> SessionState {
+        let (tx, _) = tokio::sync::oneshot::channel();
+        let app_state = Box::pin(routes::AppState::with_storage(
+            Settings::default(),
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await;
+        Arc::new(app_state)
+            .get_session_state("public", || {})
+            .unwrap()
+    }
+
+    fn get_locking_input() -> LockingInput {
+        LockingInput {
+            unique_locking_key: common_utils::generate_id(16, "pay"),
+            api_identifier: lock_utils::ApiIdentifier::Payments,
+            override_lock_retries: None,
+        }
+    }
+
+    /// Holds the lock on behalf of another request
+    async fn hold_lock(
+        state: &SessionState,
+        merchant_id: &common_utils::id_type::MerchantId,
+        input: &LockingInput,
+    ) {
+        let reply = state
+            .store
+            .get_redis_conn()
+            .unwrap()
+            .set_key_if_not_exists_with_expiry(
+                &input.get_redis_locking_key(merchant_id.clone()),
+                "other_request",
+                Some(60),
+            )
+            .await
+            .unwrap();
+        assert_eq!(reply, redis::SetnxReply::KeySet);
+    }
+
+    #[tokio::test]
+    async fn test_work_item_queued_when_lock_is_busy() {
+        let state = get_session_state().await;
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1"))
+                .unwrap();
+        let input = get_locking_input();
+        hold_lock(&state, &merchant_id, &input).await;
+
+        let performed = Arc::new(Mutex::new(Vec::new()));
+        let queued = Arc::new(Mutex::new(Vec::new()));
+        let outcome = LockAction::QueueWithOk {
+            input: input.clone(),
+        }
+        .perform_queued_action(
+            &state,
+            merchant_id,
+            1_u32,
+            |work_item| {
+                let performed = performed.clone();
+                async move {
+                    performed.lock().unwrap().push(work_item);
+                    Ok(())
+                }
+            },
+            |work_item| {
+                let queued = queued.clone();
+                async move {
+                    queued.lock().unwrap().push(work_item);
+                    Ok(())
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(outcome, QueuedActionOutcome::Queued));
+        assert!(performed.lock().unwrap().is_empty());
+        assert_eq!(*queued.lock().unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_queueing_failure_returned_when_lock_is_busy() {
+        let state = get_session_state().await;
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1"))
+                .unwrap();
+        let input = get_locking_input();
+        hold_lock(&state, &merchant_id, &input).await;
+
+        let result = LockAction::QueueWithOk { input }
+            .perform_queued_action(
+                &state,
+                merchant_id,
+                1_u32,
+                |_work_item| async { Ok(()) },
+                |_work_item| async { Err(report!(errors::ApiErrorResponse::InternalServerError)) },
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -1,10 +1,14 @@
+use api_models::cache as cache_api_types;
 use common_utils::errors::CustomResult;
 use error_stack::{report, ResultExt};
-use storage_impl::redis::cache::{publish_into_redact_channel, CacheKind};
+use storage_impl::redis::cache::{self, publish_into_redact_channel, CacheKind};
 
-use super::errors;
+use super::errors::{self, RouterResponse};
 use crate::{routes::SessionState, services};
 
+/// Default number of keys listed from a cache
+const DEFAULT_KEYS_LIST_LIMIT: usize = 100;
+
 pub async fn invalidate(
     state: SessionState,
     key: &str,
@@ -26,3 +30,93 @@ pub async fn invalidate(
             .attach_printable("Failed to invalidate cache"))
     }
 }
+
+fn find_cache(name: &str) -> CustomResult<&'static cache::Cache, errors::ApiErrorResponse> {
+    cache::find_cache_by_name(name).ok_or_else(|| {
+        report!(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Cache {name} not found"),
+        })
+    })
+}
+
+pub async fn invalidate_by_pattern(
+    state: SessionState,
+    req: cache_api_types::CacheInvalidateRequest,
+) -> RouterResponse<cache_api_types::CacheInvalidateResponse> {
+    if let Some(name) = &req.cache {
+        find_cache(name)?;
+    }
+
+    let store = state.store.as_ref();
+    let receivers = publish_into_redact_channel(
+        store.get_cache_store().as_ref(),
+        [CacheKind::Pattern {
+            cache: req.cache.map(Into::into),
+            pattern: req.pattern.unwrap_or_else(|| "*".to_owned()).into(),
+        }],
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    if receivers > 0 {
+        Ok(services::api::ApplicationResponse::Json(
+            cache_api_types::CacheInvalidateResponse { receivers },
+        ))
+    } else {
+        Err(report!(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to invalidate cache"))
+    }
+}
+
+/// Statistics are local to the instance of the application serving the request
+pub async fn get_statistics(
+    _state: SessionState,
+) -> RouterResponse<cache_api_types::CacheStatisticsResponse> {
+    let mut caches = Vec::new();
+    for cache in cache::get_all_caches() {
+        let statistics = cache.get_statistics().await;
+        caches.push(cache_api_types::CacheStatistics {
+            name: statistics.name.to_owned(),
+            entry_count: statistics.entry_count,
+            hit_count: statistics.hit_count,
+            miss_count: statistics.miss_count,
+            eviction_count: statistics.eviction_count,
+            invalidation_count: statistics.invalidation_count,
+            estimated_size: statistics.estimated_size,
+        });
+    }
+
+    Ok(services::api::ApplicationResponse::Json(
+        cache_api_types::CacheStatisticsResponse { caches },
+    ))
+}
+
+/// Keys are local to the instance of the application serving the request
+pub async fn list_keys(
+    state: SessionState,
+    name: String,
+    constraints: cache_api_types::CacheKeysListConstraints,
+) -> RouterResponse<cache_api_types::CacheKeysListResponse> {
+    let cache = find_cache(&name)?;
+    let tenant = state
+        .store
+        .get_cache_store()
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?
+        .key_prefix
+        .clone();
+
+    let keys = cache.get_keys_by_prefix(
+        &tenant,
+        constraints.prefix.as_deref().unwrap_or_default(),
+        constraints.limit.unwrap_or(DEFAULT_KEYS_LIST_LIMIT),
+    );
+
+    Ok(services::api::ApplicationResponse::Json(
+        cache_api_types::CacheKeysListResponse {
+            name: cache.name().to_owned(),
+            keys,
+        },
+    ))
+}
@@ -2,8 +2,9 @@ use common_utils::errors::CustomResult;
 use error_stack::ResultExt;
 
 use crate::{
-    core::errors::ApiErrorResponse,
+    core::errors::{ApiErrorResponse, StorageErrorExt},
     services::ApplicationResponse,
+    types::domain,
     utils::currency::{self, convert_currency, get_forex_rates},
     SessionState,
 };
@@ -28,19 +29,48 @@ pub async fn retrieve_forex(
 
 pub async fn convert_forex(
     state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
     amount: i64,
     to_currency: String,
     from_currency: String,
+    rounding_strategy: api_models::enums::RoundingStrategy,
+    rates_timestamp: Option<i64>,
+    payment_id: Option<common_utils::id_type::PaymentId>,
 ) -> CustomResult<
     ApplicationResponse<api_models::currency::CurrencyConversionResponse>,
     ApiErrorResponse,
 > {
+    let rates_timestamp = match (payment_id, rates_timestamp) {
+        (Some(_), Some(_)) => Err(ApiErrorResponse::InvalidRequestData {
+            message: "Only one of `payment_id` and `rates_timestamp` can be provided".to_string(),
+        })?,
+        (Some(payment_id), None) => {
+            let payment_intent = state
+                .store
+                .find_payment_intent_by_payment_id_merchant_id(
+                    &(&state).into(),
+                    &payment_id,
+                    merchant_account.get_id(),
+                    &key_store,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .to_not_found_response(ApiErrorResponse::PaymentNotFound)?;
+            Some(payment_intent.created_at.assume_utc().unix_timestamp())
+        }
+        (None, rates_timestamp) => rates_timestamp,
+    };
+
     Ok(ApplicationResponse::Json(
         Box::pin(convert_currency(
             state.clone(),
+            merchant_account.get_id(),
             amount,
             to_currency,
             from_currency,
+            rounding_strategy,
+            rates_timestamp,
         ))
         .await
         .change_context(ApiErrorResponse::InternalServerError)?,
@@ -308,6 +308,12 @@ pub enum RoutingError {
     VolumeSplitFailed,
     #[error("Unable to parse metadata")]
     MetadataParsingError,
+    #[error("Unable to fetch the success rate window from redis")]
+    SuccessRateWindowFetchFailed,
+    #[error("Unable to update the success rate window in redis")]
+    SuccessRateWindowUpdateFailed,
+    #[error("Unable to parse '{field_name}' of the stored payment")]
+    StoredPaymentParsingError { field_name: &'static str },
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -0,0 +1,386 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use actix_http::header::HeaderMap;
+use common_utils::{
+    crypto::{GenerateDigest, Sha256},
+    ext_traits::Encode,
+};
+use error_stack::{report, ResultExt};
+use masking::{Maskable, Secret};
+use redis_interface as redis;
+use router_env::{instrument, logger, tracing, Flow};
+
+use super::errors::{self, RouterResult};
+use crate::{
+    configs::settings::IdempotencySettings,
+    headers,
+    routes::{app::SessionStateInfo, SessionState},
+};
+
+pub const IDEMPOTENCY_PREFIX: &str = "IDEMPOTENCY";
+
+/// Number of attempts at claiming an idempotency key whose record expires between the attempt at
+/// setting it and fetching the existing record
+const MAX_CLAIM_ATTEMPTS: usize = 3;
+
+/// Flows for which requests carrying an `Idempotency-Key` header are deduplicated
+const IDEMPOTENT_FLOWS: [Flow; 10] = [
+    Flow::PaymentsCapture,
+    Flow::RefundsCreate,
+    Flow::RefundsUpdate,
+    Flow::PayoutsCreate,
+    Flow::PayoutsUpdate,
+    Flow::PayoutsConfirm,
+    Flow::PayoutsCancel,
+    Flow::PayoutsFulfill,
+    Flow::CustomersCreate,
+    Flow::CustomersUpdate,
+];
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum IdempotencyRecord {
+    // the first request with the key is still being processed
+    InProgress {
+        fingerprint: String,
+    },
+    // the first request with the key completed, its response is replayed for retries
+    Completed {
+        fingerprint: String,
+        response: String,
+        #[serde(default)]
+        headers: Vec<IdempotentHeader>,
+    },
+}
+
+impl IdempotencyRecord {
+    fn get_fingerprint(&self) -> &str {
+        match self {
+            Self::InProgress { fingerprint } | Self::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct IdempotentHeader {
+    name: String,
+    value: String,
+    is_masked: bool,
+}
+
+impl From<&(String, Maskable<String>)> for IdempotentHeader {
+    fn from((name, value): &(String, Maskable<String>)) -> Self {
+        Self {
+            name: name.clone(),
+            is_masked: value.is_masked(),
+            value: value.clone().into_inner(),
+        }
+    }
+}
+
+impl From<IdempotentHeader> for (String, Maskable<String>) {
+    fn from(header: IdempotentHeader) -> Self {
+        let value = if header.is_masked {
+            Maskable::new_masked(Secret::new(header.value))
+        } else {
+            Maskable::new_normal(header.value)
+        };
+        (header.name, value)
+    }
+}
+
+/// The response of an earlier request made with the same idempotency key
+#[derive(Debug)]
+pub struct IdempotentResponse {
+    pub body: String,
+    pub headers: Vec<(String, Maskable<String>)>,
+}
+
+/// The outcome of claiming the idempotency key for a request
+pub enum ClaimOutcome {
+    /// The key is claimed by the request, which is to be processed
+    Claimed(IdempotencyClaim),
+    /// A request with the same key has completed, its response is to be replayed
+    Replay(IdempotentResponse),
+}
+
+#[derive(Clone, Debug)]
+pub struct IdempotencyInput {
+    pub idempotency_key: String,
+    fingerprint: String,
+}
+
+impl IdempotencyInput {
+    /// Constructs the idempotency input for the request, if the request carries an
+    /// `Idempotency-Key` header and is made to a flow that supports idempotency.
+    ///
+    /// The fingerprint of the request covers the method, the path and the body of the request,
+    /// so that reusing a key for a different request can be detected.
+    pub fn from_request<T: serde::Serialize>(
+        flow: &impl router_env::types::FlowMetric,
+        headers: &HeaderMap,
+        method: &str,
+        path: &str,
+        payload: &T,
+    ) -> RouterResult<Option<Self>> {
+        let flow = flow.to_string();
+        if !IDEMPOTENT_FLOWS
+            .iter()
+            .any(|idempotent_flow| idempotent_flow.to_string() == flow)
+        {
+            return Ok(None);
+        }
+
+        let Some(idempotency_key) = headers
+            .get(headers::IDEMPOTENCY_KEY)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let mut message = format!("{method}:{path}:").into_bytes();
+        message.extend(
+            payload
+                .encode_to_vec()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to serialize the request for fingerprinting")?,
+        );
+        let fingerprint = Sha256
+            .generate_digest(&message)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to generate the request fingerprint")?;
+
+        Ok(Some(Self {
+            idempotency_key: idempotency_key.to_owned(),
+            fingerprint: hex::encode(fingerprint),
+        }))
+    }
+
+    fn get_redis_key(&self, merchant_id: &common_utils::id_type::MerchantId) -> String {
+        format!(
+            "{}_{}_{}",
+            IDEMPOTENCY_PREFIX,
+            merchant_id.get_string_repr(),
+            self.idempotency_key
+        )
+    }
+
+    /// Claims the idempotency key for the current request.
+    ///
+    /// The key is claimed only for a short while, which the claim extends as long as the request
+    /// is being processed, so that the key is freed soon if the request is abandoned midway.
+    /// Returns the stored response instead if a request with the same key and fingerprint has
+    /// already completed, in which case the stored response is to be replayed instead of
+    /// processing the request again.
+    #[instrument(skip_all)]
+    pub async fn claim(
+        self,
+        state: &SessionState,
+        merchant_id: &common_utils::id_type::MerchantId,
+    ) -> RouterResult<ClaimOutcome> {
+        let redis_conn = state
+            .store()
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        let redis_key = self.get_redis_key(merchant_id);
+        let settings = state.conf().idempotency;
+
+        for _ in 0..MAX_CLAIM_ATTEMPTS {
+            let claim_result = redis_conn
+                .serialize_and_set_key_if_not_exist(
+                    &redis_key,
+                    IdempotencyRecord::InProgress {
+                        fingerprint: self.fingerprint.clone(),
+                    },
+                    Some(i64::from(settings.in_progress_ttl_in_secs)),
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+            if claim_result == redis::SetnxReply::KeySet {
+                logger::info!("Idempotency key claimed: {}", self.idempotency_key);
+                return Ok(ClaimOutcome::Claimed(IdempotencyClaim {
+                    input: self,
+                    redis_key,
+                    redis_conn,
+                    settings,
+                }));
+            }
+
+            match redis_conn
+                .get_and_deserialize_key::<IdempotencyRecord>(&redis_key, "IdempotencyRecord")
+                .await
+            {
+                Ok(record) => return self.resolve_existing_record(record),
+                // The record expired or was released since the attempt at claiming the key, which
+                // is then free to be claimed again
+                Err(error) if error.current_context() == &redis::errors::RedisError::NotFound => {
+                    logger::info!(
+                        "Idempotency record expired while claiming key: {}",
+                        self.idempotency_key
+                    );
+                }
+                Err(error) => {
+                    return Err(error
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to fetch the idempotency record"))
+                }
+            }
+        }
+
+        Err(report!(errors::ApiErrorResponse::InternalServerError)).attach_printable(format!(
+            "Idempotency key could not be claimed in {MAX_CLAIM_ATTEMPTS} attempts"
+        ))
+    }
+
+    /// Returns the outcome of claiming the key for the record of an earlier request with the key
+    fn resolve_existing_record(&self, record: IdempotencyRecord) -> RouterResult<ClaimOutcome> {
+        if record.get_fingerprint() != self.fingerprint {
+            return Err(report!(errors::ApiErrorResponse::IdempotencyKeyReused {
+                idempotency_key: self.idempotency_key.clone(),
+            }));
+        }
+
+        match record {
+            IdempotencyRecord::InProgress { .. } => Err(report!(
+                errors::ApiErrorResponse::IdempotentRequestInProgress {
+                    idempotency_key: self.idempotency_key.clone(),
+                }
+            )),
+            IdempotencyRecord::Completed {
+                response, headers, ..
+            } => {
+                logger::info!(
+                    "Replaying response for idempotency key: {}",
+                    self.idempotency_key
+                );
+                Ok(ClaimOutcome::Replay(IdempotentResponse {
+                    body: response,
+                    headers: headers.into_iter().map(Into::into).collect(),
+                }))
+            }
+        }
+    }
+}
+
+/// An idempotency key claimed by the request being processed.
+///
+/// The claim is to be completed with the response of the request, so that the response is
+/// replayed for retries with the same key, or released if the request did not produce a response
+/// to replay, so that the request can be retried.
+pub struct IdempotencyClaim {
+    input: IdempotencyInput,
+    redis_key: String,
+    redis_conn: Arc<redis::RedisConnectionPool>,
+    settings: IdempotencySettings,
+}
+
+impl IdempotencyClaim {
+    /// Keeps extending the claim for as long as the returned future is polled, which is to be done
+    /// while the request is being processed.
+    pub async fn keep_in_progress(&self) -> Infallible {
+        let ttl_in_secs = self.settings.in_progress_ttl_in_secs;
+        let refresh_interval = Duration::from_secs(u64::from((ttl_in_secs / 3).max(1)));
+
+        loop {
+            tokio::time::sleep(refresh_interval).await;
+
+            if let Err(error) = self
+                .redis_conn
+                .set_expiry(&self.redis_key, i64::from(ttl_in_secs))
+                .await
+            {
+                logger::error!(idempotency_claim_refresh_error=?error);
+            }
+        }
+    }
+
+    /// Stores the response of the request, to be replayed for retries with the same key
+    #[instrument(skip_all)]
+    pub async fn complete(self, response: IdempotentResponse) {
+        let record = IdempotencyRecord::Completed {
+            fingerprint: self.input.fingerprint.clone(),
+            response: response.body,
+            headers: response
+                .headers
+                .iter()
+                .map(IdempotentHeader::from)
+                .collect(),
+        };
+
+        if let Err(error) = self
+            .redis_conn
+            .serialize_and_set_key_with_expiry(
+                &self.redis_key,
+                record,
+                i64::from(self.settings.ttl_in_secs),
+            )
+            .await
+        {
+            logger::error!(idempotency_record_error=?error);
+        }
+    }
+
+    /// Releases the idempotency key when the request did not produce a response to replay, so that
+    /// the request can be retried
+    #[instrument(skip_all)]
+    pub async fn release(self) {
+        if let Err(error) = self.redis_conn.delete_key(&self.redis_key).await {
+            logger::error!(idempotency_release_error=?error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::panic)]
+    use super::*;
+
+    #[test]
+    fn test_headers_round_trip_through_record() {
+        let headers = vec![
+            (
+                "x-request-id".to_string(),
+                Maskable::new_normal("req_1".to_string()),
+            ),
+            (
+                "x-connector-reference".to_string(),
+                Maskable::new_masked(Secret::new("ref_1".to_string())),
+            ),
+        ];
+        let record = IdempotencyRecord::Completed {
+            fingerprint: "fingerprint".to_string(),
+            response: "{}".to_string(),
+            headers: headers.iter().map(IdempotentHeader::from).collect(),
+        };
+
+        let record: IdempotencyRecord =
+            serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+        let IdempotencyRecord::Completed {
+            headers: stored_headers,
+            ..
+        } = record
+        else {
+            panic!("Expected a completed record");
+        };
+        let replayed_headers: Vec<(String, Maskable<String>)> =
+            stored_headers.into_iter().map(Into::into).collect();
+
+        assert_eq!(replayed_headers, headers);
+    }
+
+    #[test]
+    fn test_completed_record_without_headers_deserializes() {
+        let record: IdempotencyRecord = serde_json::from_str(
+            r#"{"status":"completed","fingerprint":"fingerprint","response":"{}"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            record,
+            IdempotencyRecord::Completed { headers, .. } if headers.is_empty()
+        ));
+    }
+}
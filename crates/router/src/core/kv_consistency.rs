@@ -0,0 +1,438 @@
+use api_models::kv_consistency::{
+    KvConsistencyCheckRequest, KvConsistencyCheckResponse, KvDivergence, KvDivergenceKind,
+    KvDivergenceResolution,
+};
+use error_stack::ResultExt;
+use futures::StreamExt;
+use router_env::{instrument, logger, tracing};
+use serde_json::Value;
+use time::{format_description::well_known::Iso8601, OffsetDateTime, PrimitiveDateTime};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    db::{kv_consistency::KvEntry, StorageInterface},
+    routes::SessionState,
+    services::ApplicationResponse,
+};
+
+/// The format `time` serializes `PrimitiveDateTime` fields in, when they are not serialized with
+/// `common_utils::custom_serde::iso8601`
+const DEFAULT_DATE_TIME_FORMAT: &str = "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]";
+
+#[instrument(skip_all)]
+pub async fn check_kv_consistency(
+    state: SessionState,
+    req: KvConsistencyCheckRequest,
+) -> RouterResponse<KvConsistencyCheckResponse> {
+    find_kv_divergences(
+        &*state.store,
+        &req.merchant_id,
+        req.created_gte,
+        req.created_lte,
+        req.repair,
+    )
+    .await
+    .map(ApplicationResponse::Json)
+}
+
+/// Walks the Redis KV hashes of the merchant, and compares the entries created within the
+/// specified time range with the Postgres rows they are drained to
+pub async fn find_kv_divergences(
+    db: &dyn StorageInterface,
+    merchant_id: &common_utils::id_type::MerchantId,
+    created_gte: Option<PrimitiveDateTime>,
+    created_lte: Option<PrimitiveDateTime>,
+    repair: bool,
+) -> RouterResult<KvConsistencyCheckResponse> {
+    let mut partition_key_pages = db
+        .scan_kv_partition_keys_by_merchant_id(merchant_id)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to scan the Redis KV hashes of the merchant")?;
+
+    let mut partition_keys = 0;
+    let mut checked_entries = 0;
+    let mut divergences = Vec::new();
+
+    // The hashes are checked a page at a time, instead of collecting the keys of all the hashes
+    // of the merchant first
+    while let Some(page) = partition_key_pages.next().await {
+        let page = page
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to scan the Redis KV hashes of the merchant")?;
+        partition_keys += page.len();
+
+        for partition_key in &page {
+            check_partition_key(
+                db,
+                partition_key,
+                created_gte,
+                created_lte,
+                repair,
+                &mut checked_entries,
+                &mut divergences,
+            )
+            .await?;
+        }
+    }
+
+    Ok(KvConsistencyCheckResponse {
+        partition_keys,
+        checked_entries,
+        divergences,
+    })
+}
+
+/// Compares the entries of the Redis KV hash created within the specified time range with the
+/// Postgres rows they are drained to
+async fn check_partition_key(
+    db: &dyn StorageInterface,
+    partition_key: &str,
+    created_gte: Option<PrimitiveDateTime>,
+    created_lte: Option<PrimitiveDateTime>,
+    repair: bool,
+    checked_entries: &mut usize,
+    divergences: &mut Vec<KvDivergence>,
+) -> RouterResult<()> {
+    let entries = db
+        .get_kv_entries(partition_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to read the Redis KV hash {partition_key}"))?;
+
+    for (field, value) in entries {
+        let redis_entry = match KvEntry::parse(&field, &value) {
+            Some(Ok(entry)) => entry,
+            Some(Err(error)) => {
+                logger::warn!(
+                    ?error,
+                    %partition_key,
+                    %field,
+                    "Skipping Redis KV entry that could not be deserialized"
+                );
+                continue;
+            }
+            None => continue,
+        };
+
+        let created_at = redis_entry.get_created_at();
+        if created_gte.is_some_and(|gte| created_at < gte)
+            || created_lte.is_some_and(|lte| created_at > lte)
+        {
+            continue;
+        }
+        *checked_entries += 1;
+
+        let database_entry = db
+            .find_kv_entry_in_database(&redis_entry)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!("Failed to find the database row of {partition_key} {field}")
+            })?;
+
+        let Some(database_entry) = database_entry else {
+            divergences.push(KvDivergence {
+                partition_key: partition_key.to_owned(),
+                field,
+                entity: redis_entry.get_entity(),
+                kind: KvDivergenceKind::MissingInDatabase,
+                mismatched_fields: Vec::new(),
+                resolution: KvDivergenceResolution::PendingDrain,
+            });
+            continue;
+        };
+
+        let mismatched_fields = get_mismatched_fields(
+            &redis_entry
+                .to_json()
+                .change_context(errors::ApiErrorResponse::InternalServerError)?,
+            &database_entry
+                .to_json()
+                .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        );
+        if mismatched_fields.is_empty() {
+            continue;
+        }
+
+        // Updates are applied to Redis first, so Redis being ahead of Postgres means the
+        // drainer has not applied the entry yet, or has moved it to the dead letter stream
+        let resolution = if truncate_to_micros(redis_entry.get_modified_at())
+            > truncate_to_micros(database_entry.get_modified_at())
+        {
+            KvDivergenceResolution::PendingDrain
+        } else if repair {
+            let repaired = db
+                .update_kv_entry(partition_key, &field, &value, &database_entry)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!("Failed to repair the Redis KV entry {partition_key} {field}")
+                })?;
+            if repaired {
+                KvDivergenceResolution::Repaired
+            } else {
+                KvDivergenceResolution::UpdatedConcurrently
+            }
+        } else {
+            KvDivergenceResolution::StaleInRedis
+        };
+
+        divergences.push(KvDivergence {
+            partition_key: partition_key.to_owned(),
+            field,
+            entity: redis_entry.get_entity(),
+            kind: KvDivergenceKind::Mismatch,
+            mismatched_fields,
+            resolution,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the top level fields whose values differ between the serialized Redis entry and
+/// Postgres row. Timestamps are compared with microsecond precision, which is the precision
+/// Postgres stores them with.
+fn get_mismatched_fields(redis: &Value, database: &Value) -> Vec<String> {
+    let (Value::Object(redis), Value::Object(database)) = (redis, database) else {
+        return if redis == database {
+            Vec::new()
+        } else {
+            vec![String::new()]
+        };
+    };
+
+    let mut fields = redis
+        .keys()
+        .chain(database.keys())
+        .filter(|field| !is_equal(redis.get(*field), database.get(*field)))
+        .cloned()
+        .collect::<Vec<_>>();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+fn is_equal(redis: Option<&Value>, database: Option<&Value>) -> bool {
+    match (redis, database) {
+        (Some(Value::String(redis)), Some(Value::String(database))) if redis != database => {
+            match (parse_timestamp(redis), parse_timestamp(database)) {
+                (Some(redis), Some(database)) => {
+                    truncate_to_micros(redis) == truncate_to_micros(database)
+                }
+                _ => false,
+            }
+        }
+        // Fields missing in one of the entries are serialized as `null` in the other one
+        (None, Some(Value::Null)) | (Some(Value::Null), None) => true,
+        _ => redis == database,
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<PrimitiveDateTime> {
+    OffsetDateTime::parse(value, &Iso8601::DEFAULT)
+        .map(|date_time| PrimitiveDateTime::new(date_time.date(), date_time.time()))
+        .ok()
+        .or_else(|| {
+            let format = time::format_description::parse(DEFAULT_DATE_TIME_FORMAT).ok()?;
+            PrimitiveDateTime::parse(value, &format).ok()
+        })
+}
+
+fn truncate_to_micros(date_time: PrimitiveDateTime) -> PrimitiveDateTime {
+    let nanosecond = date_time.nanosecond();
+    date_time
+        .replace_nanosecond(nanosecond - nanosecond % 1_000)
+        .unwrap_or(date_time)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+    use storage_impl::MockDb;
+
+    use super::*;
+    use crate::db::kv_consistency::KvConsistencyInterface;
+
+    fn get_refund(refund_status: &str, modified_at: &str) -> diesel_models::Refund {
+        serde_json::from_value(json!({
+            "internal_reference_id": "refid_1",
+            "refund_id": "ref_1",
+            "payment_id": "pay_1",
+            "merchant_id": "merchant_1",
+            "connector_transaction_id": "txn_1",
+            "connector": "stripe",
+            "refund_type": "instant_refund",
+            "total_amount": 100,
+            "currency": "USD",
+            "refund_amount": 100,
+            "refund_status": refund_status,
+            "sent_to_gateway": true,
+            "created_at": "2024-01-01T10:00:00.000Z",
+            "modified_at": modified_at,
+            "attempt_id": "pay_1_1",
+            "updated_by": "redis_kv",
+            "organization_id": "org_1",
+        }))
+        .unwrap()
+    }
+
+    /// Stores the refunds in the Redis KV hash and the database of a mock store, returning the
+    /// store along with the partition key and field of the Redis KV entry
+    async fn get_store(
+        redis_refund: diesel_models::Refund,
+        database_refund: diesel_models::Refund,
+    ) -> (MockDb, String, String) {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        db.refunds.lock().await.push(database_refund);
+
+        let redis_entry = KvEntry::Refund(redis_refund);
+        let (partition_key, field) = redis_entry.get_partition_key_and_field().unwrap();
+        db.kv_hashes
+            .lock()
+            .await
+            .entry(partition_key.clone())
+            .or_default()
+            .insert(field.clone(), redis_entry.to_json().unwrap().to_string());
+
+        (db, partition_key, field)
+    }
+
+    async fn get_redis_refund(
+        db: &MockDb,
+        partition_key: &str,
+        field: &str,
+    ) -> diesel_models::Refund {
+        let kv_hashes = db.kv_hashes.lock().await;
+        let value = kv_hashes
+            .get(partition_key)
+            .and_then(|kv_hash| kv_hash.get(field))
+            .unwrap();
+        serde_json::from_str(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stale_redis_entries_are_repaired() {
+        let redis_refund = get_refund("pending", "2024-01-01T10:00:00.000Z");
+        let database_refund = get_refund("success", "2024-01-01T10:05:00.000Z");
+        let merchant_id = database_refund.merchant_id.clone();
+        let (db, partition_key, field) = get_store(redis_refund, database_refund.clone()).await;
+
+        let response = find_kv_divergences(&db, &merchant_id, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(response.partition_keys, 1);
+        assert_eq!(response.checked_entries, 1);
+        let divergence = response.divergences.first().unwrap();
+        assert_eq!(divergence.resolution, KvDivergenceResolution::StaleInRedis);
+        assert_eq!(
+            divergence.mismatched_fields,
+            vec!["modified_at", "refund_status"]
+        );
+
+        let response = find_kv_divergences(&db, &merchant_id, None, None, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.divergences.first().unwrap().resolution,
+            KvDivergenceResolution::Repaired
+        );
+        assert_eq!(
+            get_redis_refund(&db, &partition_key, &field).await,
+            database_refund
+        );
+
+        let response = find_kv_divergences(&db, &merchant_id, None, None, true)
+            .await
+            .unwrap();
+        assert!(response.divergences.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redis_entries_ahead_of_database_are_not_repaired() {
+        let redis_refund = get_refund("success", "2024-01-01T10:05:00.000Z");
+        let database_refund = get_refund("pending", "2024-01-01T10:00:00.000Z");
+        let merchant_id = database_refund.merchant_id.clone();
+        let (db, partition_key, field) = get_store(redis_refund.clone(), database_refund).await;
+
+        let response = find_kv_divergences(&db, &merchant_id, None, None, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.divergences.first().unwrap().resolution,
+            KvDivergenceResolution::PendingDrain
+        );
+        assert_eq!(
+            get_redis_refund(&db, &partition_key, &field).await,
+            redis_refund
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repair_does_not_overwrite_concurrent_updates() {
+        let redis_refund = get_refund("pending", "2024-01-01T10:00:00.000Z");
+        let database_refund = get_refund("success", "2024-01-01T10:05:00.000Z");
+        let (db, partition_key, field) =
+            get_store(redis_refund.clone(), database_refund.clone()).await;
+
+        // The value the entry was read with before the application updated it
+        let read_value = KvEntry::Refund(get_refund("pending", "2024-01-01T09:55:00.000Z"))
+            .to_json()
+            .unwrap()
+            .to_string();
+        let repaired = db
+            .update_kv_entry(
+                &partition_key,
+                &field,
+                &read_value,
+                &KvEntry::Refund(database_refund),
+            )
+            .await
+            .unwrap();
+
+        assert!(!repaired);
+        assert_eq!(
+            get_redis_refund(&db, &partition_key, &field).await,
+            redis_refund
+        );
+    }
+
+    #[test]
+    fn test_mismatched_fields() {
+        let redis = json!({
+            "status": "succeeded",
+            "amount": 100,
+            "created_at": "2024-01-01T10:00:00.123456789Z",
+            "modified_at": "2024-01-01 10:00:00.123456789",
+            "description": null,
+        });
+        let database = json!({
+            "status": "processing",
+            "amount": 100,
+            "created_at": "2024-01-01T10:00:00.123456Z",
+            "modified_at": "2024-01-01 10:00:00.123456",
+        });
+
+        assert_eq!(get_mismatched_fields(&redis, &database), vec!["status"]);
+        assert!(get_mismatched_fields(&redis, &redis).is_empty());
+    }
+
+    #[test]
+    fn test_timestamps_are_compared_with_microsecond_precision() {
+        let redis = json!({ "modified_at": "2024-01-01T10:00:00.123457Z" });
+        let database = json!({ "modified_at": "2024-01-01T10:00:00.123456999Z" });
+
+        assert_eq!(
+            get_mismatched_fields(&redis, &database),
+            vec!["modified_at"]
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-01 10:00:00.5").unwrap(),
+            parse_timestamp("2024-01-01T10:00:00.5Z").unwrap()
+        );
+        assert!(parse_timestamp("succeeded").is_none());
+    }
+}
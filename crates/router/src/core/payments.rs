@@ -354,6 +354,7 @@ where
                             payment_data,
                             router_data,
                             &key_store,
+                            &business_profile,
                             merchant_account.storage_scheme,
                             &locale,
                         )
@@ -478,6 +479,7 @@ where
                             payment_data,
                             router_data,
                             &key_store,
+                            &business_profile,
                             merchant_account.storage_scheme,
                             &locale,
                         )
@@ -636,7 +638,7 @@ where
 pub async fn call_decision_manager<O>(
     state: &SessionState,
     merchant_account: &domain::MerchantAccount,
-    _business_profile: &domain::BusinessProfile,
+    business_profile: &domain::BusinessProfile,
     payment_data: &mut PaymentData<O>,
 ) -> RouterResult<()>
 where
@@ -663,6 +665,7 @@ where
         state,
         algorithm_ref,
         merchant_account.get_id(),
+        business_profile,
         payment_data,
     )
     .await
@@ -3565,6 +3568,7 @@ where
                 &TransactionData::Payment(payment_data),
                 eligible_connectors,
                 business_profile,
+                None,
             )
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -3612,6 +3616,7 @@ where
                 &TransactionData::Payment(payment_data),
                 eligible_connectors,
                 business_profile,
+                None,
             )
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -4018,7 +4023,7 @@ where
     let profile_wrapper = super::admin::BusinessProfileWrapper::new(business_profile.clone());
     let routing_algorithm_id = profile_wrapper.get_routing_algorithm_id(&transaction_data);
 
-    let connectors = routing::perform_static_routing_v1(
+    let (connectors, success_rate_params) = routing::perform_static_routing_v1(
         state,
         merchant_account.get_id(),
         routing_algorithm_id,
@@ -4035,6 +4040,7 @@ where
         &transaction_data,
         eligible_connectors,
         business_profile,
+        success_rate_params.as_ref(),
     )
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -4120,7 +4126,7 @@ where
         algorithm_ref.algorithm_id
     };
 
-    let connectors = routing::perform_static_routing_v1(
+    let (connectors, success_rate_params) = routing::perform_static_routing_v1(
         state,
         merchant_account.get_id(),
         routing_algorithm_id,
@@ -4136,6 +4142,7 @@ where
         &transaction_data,
         eligible_connectors,
         business_profile,
+        success_rate_params.as_ref(),
     )
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)
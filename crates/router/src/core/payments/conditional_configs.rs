@@ -14,6 +14,7 @@ use super::routing::make_dsl_input;
 use crate::{
     core::{errors, errors::ConditionalConfigError as ConfigError, payments},
     routes,
+    types::domain,
 };
 pub type ConditionalConfigResult<O> = errors::CustomResult<O, ConfigError>;
 
@@ -22,6 +23,7 @@ pub async fn perform_decision_management<F: Clone>(
     state: &routes::SessionState,
     algorithm_ref: routing::RoutingAlgorithmRef,
     merchant_id: &common_utils::id_type::MerchantId,
+    business_profile: &domain::BusinessProfile,
     payment_data: &mut payments::PaymentData<F>,
 ) -> ConditionalConfigResult<ConditionalConfigs> {
     let algorithm_id = if let Some(id) = algorithm_ref.config_algo_id {
@@ -56,8 +58,8 @@ pub async fn perform_decision_management<F: Clone>(
     .await
     .change_context(ConfigError::DslCachePoisoned)?;
 
-    let backend_input =
-        make_dsl_input(payment_data).change_context(ConfigError::InputConstructionError)?;
+    let backend_input = make_dsl_input(payment_data, business_profile)
+        .change_context(ConfigError::InputConstructionError)?;
 
     execute_dsl_and_get_conditional_config(backend_input, &interpreter)
 }
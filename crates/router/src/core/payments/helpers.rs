@@ -3209,6 +3209,18 @@ mod tests {
         let req_cs = Some("1".to_string());
         assert!(authenticate_client_secret(req_cs.as_ref(), &payment_intent).is_err())
     }
+
+    #[test]
+    fn test_validate_timezone() {
+        assert!(validate_timezone(None).is_ok());
+        assert!(validate_timezone(Some("UTC")).is_ok());
+        assert!(validate_timezone(Some("+05:30")).is_ok());
+        assert!(validate_timezone(Some("-08:00")).is_ok());
+        assert!(validate_timezone(Some("Europe/London")).is_ok());
+        assert!(validate_timezone(Some("Mars/Olympus_Mons")).is_err());
+        assert!(validate_timezone(Some("+05:75")).is_err());
+        assert!(validate_timezone(Some("")).is_err());
+    }
 }
 
 // This function will be removed after moving this functionality to server_wrap and using cache instead of config
@@ -4837,6 +4849,14 @@ pub fn validate_session_expiry(session_expiry: u32) -> Result<(), errors::ApiErr
     }
 }
 
+pub fn validate_timezone(timezone: Option<&str>) -> Result<(), errors::ApiErrorResponse> {
+    common_utils::date_time::parse_timezone(timezone)
+        .map(|_| ())
+        .map_err(|_| errors::ApiErrorResponse::InvalidRequestData {
+            message: "timezone should either be UTC, an offset from UTC like +05:30 or an IANA time zone name like Europe/London".to_string(),
+        })
+}
+
 pub fn get_recipient_id_for_open_banking(
     merchant_data: &AdditionalMerchantData,
 ) -> Result<Option<String>, errors::ApiErrorResponse> {
@@ -231,6 +231,7 @@ pub trait PostUpdateTracker<F, D, R: Send>: Send {
         payment_data: D,
         response: types::RouterData<F, R, PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<D>
@@ -26,7 +26,7 @@ use crate::{
                 self as payments_helpers,
                 update_additional_payment_data_with_connector_response_pm_data,
             },
-            tokenization,
+            routing, tokenization,
             types::MultipleCaptureData,
             PaymentData,
         },
@@ -64,6 +64,7 @@ impl<F: Send + Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsAuthor
             types::PaymentsResponseData,
         >,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -80,6 +81,7 @@ impl<F: Send + Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsAuthor
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -281,6 +283,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsIncrementalAu
             types::PaymentsResponseData,
         >,
         key_store: &domain::MerchantKeyStore,
+        _business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         _locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -413,6 +416,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsSyncData> for
         payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsSyncData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -425,6 +429,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsSyncData> for
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -467,6 +472,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsSessionData>
         mut payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsSessionData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -479,6 +485,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsSessionData>
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -499,6 +506,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCaptureData>
         mut payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsCaptureData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -511,6 +519,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCaptureData>
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -529,6 +538,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCancelData> f
         mut payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsCancelData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -541,6 +551,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCancelData> f
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -561,6 +572,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsApproveData>
         mut payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsApproveData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -573,6 +585,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsApproveData>
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -591,6 +604,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsRejectData> f
         mut payment_data: PaymentData<F>,
         router_data: types::RouterData<F, types::PaymentsRejectData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -603,6 +617,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsRejectData> f
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -627,6 +642,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::SetupMandateRequestDa
             types::PaymentsResponseData,
         >,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -644,6 +660,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::SetupMandateRequestDa
             payment_data,
             router_data,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -728,6 +745,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::CompleteAuthorizeData
         payment_data: PaymentData<F>,
         response: types::RouterData<F, types::CompleteAuthorizeData, types::PaymentsResponseData>,
         key_store: &domain::MerchantKeyStore,
+        business_profile: &domain::BusinessProfile,
         storage_scheme: enums::MerchantStorageScheme,
         locale: &Option<String>,
     ) -> RouterResult<PaymentData<F>>
@@ -740,6 +758,7 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::CompleteAuthorizeData
             payment_data,
             response,
             key_store,
+            business_profile,
             storage_scheme,
             locale,
         ))
@@ -778,6 +797,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     mut payment_data: PaymentData<F>,
     router_data: types::RouterData<F, T, types::PaymentsResponseData>,
     key_store: &domain::MerchantKeyStore,
+    business_profile: &domain::BusinessProfile,
     storage_scheme: enums::MerchantStorageScheme,
     locale: &Option<String>,
 ) -> RouterResult<PaymentData<F>> {
@@ -1219,6 +1239,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
 
     // Stage 1
 
+    let previous_attempt_status = payment_data.payment_attempt.status;
     let payment_attempt = payment_data.payment_attempt.clone();
 
     let m_db = state.clone().store;
@@ -1259,6 +1280,31 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
 
     payment_data.payment_attempt = payment_attempt;
 
+    // Feed the outcome of the attempt into the success rate based routing window, counting it
+    // only once, when the attempt transitions into its outcome
+    if routing::success_rate::is_outcome_transition(
+        previous_attempt_status,
+        payment_data.payment_attempt.status,
+    ) {
+        let state = state.clone();
+        let business_profile = business_profile.clone();
+        let payment_attempt = payment_data.payment_attempt.clone();
+        let _task_handle = tokio::spawn(
+            async move {
+                if let Err(error) = routing::success_rate::update_success_rate_for_payment_attempt(
+                    &state,
+                    &business_profile,
+                    &payment_attempt,
+                )
+                .await
+                {
+                    logger::error!(?error, "Failed to update the success rate window");
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
     payment_data.authentication = match payment_data.authentication {
         Some(authentication) => {
             let authentication_update = storage::AuthenticationUpdate::PostAuthorizationUpdate {
@@ -1,3 +1,4 @@
+pub mod success_rate;
 mod transformers;
 
 use std::{
@@ -26,13 +27,14 @@ use kgraph_utils::{
     transformers::{IntoContext, IntoDirValue},
     types::CountryCurrencyFilter,
 };
-use masking::PeekInterface;
+use masking::{ExposeInterface, PeekInterface};
 use rand::{
     distributions::{self, Distribution},
     SeedableRng,
 };
 use rustc_hash::FxHashMap;
 use storage_impl::redis::cache::{CacheKey, CGRAPH_CACHE, ROUTING_CACHE};
+use strum::IntoEnumIterator;
 
 #[cfg(all(
     feature = "v2",
@@ -62,6 +64,7 @@ pub enum CachedAlgorithm {
     Priority(Vec<routing_types::RoutableConnectorChoice>),
     VolumeSplit(Vec<routing_types::ConnectorVolumeSplit>),
     Advanced(backend::VirInterpreterBackend<ConnectorSelection>),
+    SuccessRateBased(routing_types::SuccessRateBasedRoutingConfig),
 }
 
 pub struct SessionFlowRoutingInput<'a> {
@@ -152,6 +155,8 @@ pub fn make_dsl_input_for_payouts(
             .map(api_enums::Country::from_alpha2),
         business_label: payout_data.payout_attempt.business_label.clone(),
         setup_future_usage: None,
+        time_of_day: None,
+        day_of_week: None,
     };
     let payment_method = dsl_inputs::PaymentMethodInput {
         payment_method: payout_data
@@ -163,6 +168,8 @@ pub fn make_dsl_input_for_payouts(
             .clone()
             .map(api_enums::PaymentMethodType::foreign_from),
         card_network: None,
+        card_issuer: None,
+        card_issuer_country: None,
     };
     Ok(dsl_inputs::BackendInput {
         mandate,
@@ -172,8 +179,206 @@ pub fn make_dsl_input_for_payouts(
     })
 }
 
+/// Returns the time of day, as a 24 hour HHMM number, and the day of week of the timestamp in the
+/// timezone of the business profile
+fn get_local_time_of_day_and_day_of_week(
+    timestamp: time::PrimitiveDateTime,
+    timezone: Option<&str>,
+) -> (Option<i64>, Option<euclid_enums::DayOfWeek>) {
+    match common_utils::date_time::parse_timezone(timezone) {
+        Ok(timezone) => {
+            let local_time = timezone.local_date_time(timestamp);
+            (
+                Some(i64::from(local_time.hour()) * 100 + i64::from(local_time.minute())),
+                Some(euclid_enums::DayOfWeek::foreign_from(local_time.weekday())),
+            )
+        }
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to parse the timezone of the business profile"
+            );
+            (None, None)
+        }
+    }
+}
+
+/// Parses the issuing country of a card in the cards info, which is either the alpha-2 code, the
+/// alpha-3 code or the name of the country
+fn get_card_issuer_country(card_issuing_country: &str) -> Option<api_enums::Country> {
+    let normalise = |country: &str| {
+        country
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_uppercase()
+    };
+    let card_issuing_country = normalise(card_issuing_country);
+
+    CountryAlpha2::from_str(&card_issuing_country)
+        .map(api_enums::Country::from_alpha2)
+        .or_else(|_| {
+            api_enums::CountryAlpha3::from_str(&card_issuing_country)
+                .map(api_enums::Country::from_alpha3)
+        })
+        .ok()
+        .or_else(|| {
+            api_enums::Country::iter()
+                .find(|country| normalise(&country.to_string()) == card_issuing_country)
+        })
+}
+
+/// Card details of a payment, which the routing rules on the card are evaluated against
+#[derive(Default)]
+struct CardDslInput {
+    card_bin: Option<String>,
+    card_network: Option<api_enums::CardNetwork>,
+    card_issuer: Option<String>,
+    card_issuing_country: Option<String>,
+}
+
+impl From<&domain::Card> for CardDslInput {
+    fn from(card: &domain::Card) -> Self {
+        Self {
+            card_bin: Some(card.card_number.peek().chars().take(6).collect()),
+            card_network: card.card_network.clone(),
+            card_issuer: card.card_issuer.clone(),
+            card_issuing_country: card.card_issuing_country.clone(),
+        }
+    }
+}
+
+impl From<api_models::payments::AdditionalCardInfo> for CardDslInput {
+    fn from(card: api_models::payments::AdditionalCardInfo) -> Self {
+        Self {
+            card_bin: card.card_isin,
+            card_network: card.card_network,
+            card_issuer: card.card_issuer,
+            card_issuing_country: card.card_issuing_country,
+        }
+    }
+}
+
+impl CardDslInput {
+    /// Fills the card details missing from the payment method data, like the issuer details of
+    /// saved cards, with the additional card info of the attempt, unless it is of another card
+    fn with_additional_card_info(self, additional_card_info: Self) -> Self {
+        if self.card_bin.is_some()
+            && additional_card_info.card_bin.is_some()
+            && self.card_bin != additional_card_info.card_bin
+        {
+            return self;
+        }
+
+        Self {
+            card_bin: self.card_bin.or(additional_card_info.card_bin),
+            card_network: self.card_network.or(additional_card_info.card_network),
+            card_issuer: self.card_issuer.or(additional_card_info.card_issuer),
+            card_issuing_country: self
+                .card_issuing_country
+                .or(additional_card_info.card_issuing_country),
+        }
+    }
+}
+
+/// Parses the card details from the additional payment method data stored on the attempt, which
+/// holds the issuer details looked up from the cards info
+fn get_additional_card_info(
+    payment_attempt: &oss_storage::PaymentAttempt,
+) -> RoutingResult<Option<CardDslInput>> {
+    Ok(payment_attempt
+        .payment_method_data
+        .clone()
+        .map(|pm_data| {
+            pm_data
+                .parse_value::<api_models::payments::AdditionalPaymentData>("AdditionalPaymentData")
+        })
+        .transpose()
+        .change_context(errors::RoutingError::StoredPaymentParsingError {
+            field_name: "payment_method_data",
+        })?
+        .and_then(|pm_data| match pm_data {
+            api_models::payments::AdditionalPaymentData::Card(card) => {
+                Some(CardDslInput::from(*card))
+            }
+            _ => None,
+        }))
+}
+
+/// Routing input of a payment, built from its attempt, its intent and its card details. Shared by
+/// the routing of payments and the replay of stored payments in routing simulations.
+struct PaymentDslInputBuilder<'a> {
+    payment_attempt: &'a oss_storage::PaymentAttempt,
+    payment_intent: &'a oss_storage::PaymentIntent,
+    card: CardDslInput,
+    currency: storage_enums::Currency,
+    billing_country: Option<CountryAlpha2>,
+    mandate: dsl_inputs::MandateData,
+    /// Time the payment is routed at, which the time of day and day of week are taken from
+    routed_at: time::PrimitiveDateTime,
+}
+
+impl PaymentDslInputBuilder<'_> {
+    fn build(self, business_profile: &domain::BusinessProfile) -> dsl_inputs::BackendInput {
+        let payment_method_input = dsl_inputs::PaymentMethodInput {
+            payment_method: self.payment_attempt.payment_method,
+            payment_method_type: self.payment_attempt.payment_method_type,
+            card_network: self.card.card_network,
+            card_issuer: self.card.card_issuer,
+            card_issuer_country: self
+                .card
+                .card_issuing_country
+                .as_deref()
+                .and_then(get_card_issuer_country),
+        };
+
+        let (time_of_day, day_of_week) = get_local_time_of_day_and_day_of_week(
+            self.routed_at,
+            business_profile.timezone.as_deref(),
+        );
+
+        let payment_input = dsl_inputs::PaymentInput {
+            amount: self.payment_intent.amount,
+            card_bin: self.card.card_bin,
+            currency: self.currency,
+            authentication_type: self.payment_attempt.authentication_type,
+            capture_method: self
+                .payment_attempt
+                .capture_method
+                .and_then(|cm| cm.foreign_into()),
+            business_country: self
+                .payment_intent
+                .business_country
+                .map(api_enums::Country::from_alpha2),
+            billing_country: self.billing_country.map(api_enums::Country::from_alpha2),
+            business_label: self.payment_intent.business_label.clone(),
+            setup_future_usage: self.payment_intent.setup_future_usage,
+            time_of_day,
+            day_of_week,
+        };
+
+        let metadata = self
+            .payment_intent
+            .metadata
+            .clone()
+            .map(|val| val.parse_value("routing_parameters"))
+            .transpose()
+            .change_context(errors::RoutingError::MetadataParsingError)
+            .attach_printable("Unable to parse routing_parameters from metadata of payment_intent")
+            .unwrap_or(None);
+
+        dsl_inputs::BackendInput {
+            metadata,
+            payment: payment_input,
+            payment_method: payment_method_input,
+            mandate: self.mandate,
+        }
+    }
+}
+
 pub fn make_dsl_input<F>(
     payment_data: &payments_oss::PaymentData<F>,
+    business_profile: &domain::BusinessProfile,
 ) -> RoutingResult<dsl_inputs::BackendInput>
 where
     F: Clone,
@@ -224,75 +429,56 @@ where
             },
         ),
     };
-    let payment_method_input = dsl_inputs::PaymentMethodInput {
-        payment_method: payment_data.payment_attempt.payment_method,
-        payment_method_type: payment_data.payment_attempt.payment_method_type,
-        card_network: payment_data
-            .payment_method_data
-            .as_ref()
-            .and_then(|pm_data| match pm_data {
-                domain::PaymentMethodData::Card(card) => card.card_network.clone(),
-
-                _ => None,
-            }),
-    };
+    let additional_card_info = get_additional_card_info(&payment_data.payment_attempt)
+        .map_err(|error| {
+            logger::error!(
+                ?error,
+                "Failed to parse the additional card info of the payment attempt"
+            )
+        })
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let card = payment_data
+        .payment_method_data
+        .as_ref()
+        .and_then(|pm_data| match pm_data {
+            domain::PaymentMethodData::Card(card) => Some(CardDslInput::from(card)),
+            _ => None,
+        })
+        .unwrap_or_default()
+        .with_additional_card_info(additional_card_info);
 
-    let payment_input = dsl_inputs::PaymentInput {
-        amount: payment_data.payment_intent.amount,
-        card_bin: payment_data
-            .payment_method_data
-            .as_ref()
-            .and_then(|pm_data| match pm_data {
-                domain::PaymentMethodData::Card(card) => {
-                    Some(card.card_number.peek().chars().take(6).collect())
-                }
-                _ => None,
-            }),
+    Ok(PaymentDslInputBuilder {
+        payment_attempt: &payment_data.payment_attempt,
+        payment_intent: &payment_data.payment_intent,
+        card,
         currency: payment_data.currency,
-        authentication_type: payment_data.payment_attempt.authentication_type,
-        capture_method: payment_data
-            .payment_attempt
-            .capture_method
-            .and_then(|cm| cm.foreign_into()),
-        business_country: payment_data
-            .payment_intent
-            .business_country
-            .map(api_enums::Country::from_alpha2),
         billing_country: payment_data
             .address
             .get_payment_method_billing()
             .and_then(|bic| bic.address.as_ref())
-            .and_then(|add| add.country)
-            .map(api_enums::Country::from_alpha2),
-        business_label: payment_data.payment_intent.business_label.clone(),
-        setup_future_usage: payment_data.payment_intent.setup_future_usage,
-    };
-
-    let metadata = payment_data
-        .payment_intent
-        .metadata
-        .clone()
-        .map(|val| val.parse_value("routing_parameters"))
-        .transpose()
-        .change_context(errors::RoutingError::MetadataParsingError)
-        .attach_printable("Unable to parse routing_parameters from metadata of payment_intent")
-        .unwrap_or(None);
-
-    Ok(dsl_inputs::BackendInput {
-        metadata,
-        payment: payment_input,
-        payment_method: payment_method_input,
+            .and_then(|add| add.country),
         mandate: mandate_data,
-    })
+        routed_at: common_utils::date_time::now(),
+    }
+    .build(business_profile))
 }
 
+/// Performs static routing for the transaction.
+///
+/// Along with the chosen connectors, returns the sliding window parameters if the algorithm is
+/// success rate based, in which case the connectors are to be ranked after eligibility analysis.
 pub async fn perform_static_routing_v1<F: Clone>(
     state: &SessionState,
     merchant_id: &common_utils::id_type::MerchantId,
     algorithm_id: Option<common_utils::id_type::RoutingId>,
     business_profile: &domain::BusinessProfile,
     transaction_data: &routing::TransactionData<'_, F>,
-) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+) -> RoutingResult<(
+    Vec<routing_types::RoutableConnectorChoice>,
+    Option<routing_types::SuccessRateWindowParams>,
+)> {
     let algorithm_id = if let Some(id) = algorithm_id {
         id
     } else {
@@ -316,7 +502,7 @@ pub async fn perform_static_routing_v1<F: Clone>(
             .get_default_fallback_list_of_connector_under_profile()
             .change_context(errors::RoutingError::FallbackConfigFetchFailed)?;
 
-        return Ok(fallback_config);
+        return Ok((fallback_config, None));
     };
     let cached_algorithm = ensure_algorithm_cached_v1(
         state,
@@ -328,24 +514,83 @@ pub async fn perform_static_routing_v1<F: Clone>(
     .await?;
 
     Ok(match cached_algorithm.as_ref() {
-        CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
+        CachedAlgorithm::Single(conn) => (vec![(**conn).clone()], None),
 
-        CachedAlgorithm::Priority(plist) => plist.clone(),
+        CachedAlgorithm::Priority(plist) => (plist.clone(), None),
 
-        CachedAlgorithm::VolumeSplit(splits) => perform_volume_split(splits.to_vec(), None)
-            .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+        CachedAlgorithm::VolumeSplit(splits) => (
+            perform_volume_split(splits.to_vec(), None)
+                .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+            None,
+        ),
 
         CachedAlgorithm::Advanced(interpreter) => {
             let backend_input = match transaction_data {
-                routing::TransactionData::Payment(payment_data) => make_dsl_input(payment_data)?,
+                routing::TransactionData::Payment(payment_data) => {
+                    make_dsl_input(payment_data, business_profile)?
+                }
                 #[cfg(feature = "payouts")]
                 routing::TransactionData::Payout(payout_data) => {
                     make_dsl_input_for_payouts(payout_data)?
                 }
             };
 
-            execute_dsl_and_get_connector_v1(backend_input, interpreter)?
+            (
+                execute_dsl_and_get_connector_v1(backend_input, interpreter)?,
+                None,
+            )
         }
+
+        CachedAlgorithm::SuccessRateBased(config) => {
+            (config.connectors.clone(), Some(config.params.clone()))
+        }
+    })
+}
+
+/// Fetches the sliding window parameters of the payment routing algorithm active on the
+/// profile, if the algorithm is success rate based.
+pub async fn get_success_rate_window_params(
+    state: &SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+    business_profile: &domain::BusinessProfile,
+) -> RoutingResult<Option<routing_types::SuccessRateWindowParams>> {
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(any(feature = "routing_v2", feature = "business_profile_v2"))
+    ))]
+    let algorithm_id = business_profile
+        .routing_algorithm
+        .clone()
+        .map(|val| val.parse_value::<routing_types::RoutingAlgorithmRef>("RoutingAlgorithmRef"))
+        .transpose()
+        .change_context(errors::RoutingError::InvalidRoutingAlgorithmStructure)?
+        .and_then(|algorithm_ref| algorithm_ref.algorithm_id);
+    #[cfg(all(
+        feature = "v2",
+        feature = "routing_v2",
+        feature = "business_profile_v2"
+    ))]
+    let algorithm_id = business_profile.routing_algorithm_id.clone();
+
+    let Some(algorithm_id) = algorithm_id else {
+        return Ok(None);
+    };
+
+    let cached_algorithm = ensure_algorithm_cached_v1(
+        state,
+        merchant_id,
+        &algorithm_id,
+        business_profile.get_id().to_owned(),
+        &api_enums::TransactionType::Payment,
+    )
+    .await?;
+
+    Ok(match cached_algorithm.as_ref() {
+        CachedAlgorithm::SuccessRateBased(config) => Some(config.params.clone()),
+        CachedAlgorithm::Single(_)
+        | CachedAlgorithm::Priority(_)
+        | CachedAlgorithm::VolumeSplit(_)
+        | CachedAlgorithm::Advanced(_) => None,
     })
 }
 
@@ -434,6 +679,27 @@ fn execute_dsl_and_get_connector_v1(
     })
 }
 
+/// Executes the program on the input, returning the name of the matched rule along with the chosen
+/// connectors. Volume splits are seeded, so that replaying the same input chooses the same
+/// connectors.
+pub fn execute_dsl_for_simulation(
+    backend_input: dsl_inputs::BackendInput,
+    interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
+    rng_seed: &str,
+) -> RoutingResult<(Option<String>, Vec<routing_types::RoutableConnectorChoice>)> {
+    let output = interpreter
+        .execute(backend_input)
+        .change_context(errors::RoutingError::DslExecutionError)?;
+
+    let connectors = match output.connector_selection {
+        ConnectorSelection::Priority(plist) => plist,
+        ConnectorSelection::VolumeSplit(splits) => perform_volume_split(splits, Some(rng_seed))
+            .change_context(errors::RoutingError::DslFinalConnectorSelectionFailed)?,
+    };
+
+    Ok((output.rule_name, connectors))
+}
+
 pub async fn refresh_routing_cache_v1(
     state: &SessionState,
     key: String,
@@ -466,6 +732,9 @@ pub async fn refresh_routing_cache_v1(
 
             CachedAlgorithm::Advanced(interpreter)
         }
+        routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+            CachedAlgorithm::SuccessRateBased(config)
+        }
     };
 
     let arc_cached_algorithm = Arc::new(cached_algorithm);
@@ -710,10 +979,12 @@ pub async fn perform_eligibility_analysis<F: Clone>(
     chosen: Vec<routing_types::RoutableConnectorChoice>,
     transaction_data: &routing::TransactionData<'_, F>,
     eligible_connectors: Option<&Vec<api_enums::RoutableConnectors>>,
-    profile_id: common_utils::id_type::ProfileId,
+    business_profile: &domain::BusinessProfile,
 ) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
     let backend_input = match transaction_data {
-        routing::TransactionData::Payment(payment_data) => make_dsl_input(payment_data)?,
+        routing::TransactionData::Payment(payment_data) => {
+            make_dsl_input(payment_data, business_profile)?
+        }
         #[cfg(feature = "payouts")]
         routing::TransactionData::Payout(payout_data) => make_dsl_input_for_payouts(payout_data)?,
     };
@@ -724,7 +995,7 @@ pub async fn perform_eligibility_analysis<F: Clone>(
         chosen,
         backend_input,
         eligible_connectors,
-        profile_id,
+        business_profile.get_id().to_owned(),
         &api_enums::TransactionType::from(transaction_data),
     )
     .await
@@ -769,7 +1040,9 @@ pub async fn perform_fallback_routing<F: Clone>(
         .get_default_fallback_list_of_connector_under_profile()
         .change_context(errors::RoutingError::FallbackConfigFetchFailed)?;
     let backend_input = match transaction_data {
-        routing::TransactionData::Payment(payment_data) => make_dsl_input(payment_data)?,
+        routing::TransactionData::Payment(payment_data) => {
+            make_dsl_input(payment_data, business_profile)?
+        }
         #[cfg(feature = "payouts")]
         routing::TransactionData::Payout(payout_data) => make_dsl_input_for_payouts(payout_data)?,
     };
@@ -793,6 +1066,7 @@ pub async fn perform_eligibility_analysis_with_fallback<F: Clone>(
     transaction_data: &routing::TransactionData<'_, F>,
     eligible_connectors: Option<Vec<api_enums::RoutableConnectors>>,
     business_profile: &domain::BusinessProfile,
+    success_rate_params: Option<&routing_types::SuccessRateWindowParams>,
 ) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
     let mut final_selection = perform_eligibility_analysis(
         state,
@@ -800,10 +1074,31 @@ pub async fn perform_eligibility_analysis_with_fallback<F: Clone>(
         chosen,
         transaction_data,
         eligible_connectors.as_ref(),
-        business_profile.get_id().to_owned(),
+        business_profile,
     )
     .await?;
 
+    // Only the connectors that survived the eligibility analysis are ranked, the fallback
+    // connectors retain their configured order
+    if let (Some(params), routing::TransactionData::Payment(payment_data)) =
+        (success_rate_params, transaction_data)
+    {
+        match success_rate::rank_connectors_by_success_rate(
+            state,
+            business_profile.get_id(),
+            &success_rate::SuccessRateDimension::from(&payment_data.payment_attempt),
+            params,
+            final_selection.clone(),
+        )
+        .await
+        {
+            Ok(ranked_selection) => final_selection = ranked_selection,
+            Err(error) => {
+                logger::error!(?error, "Failed to rank connectors by success rate");
+            }
+        }
+    }
+
     let fallback_selection = perform_fallback_routing(
         state,
         key_store,
@@ -883,8 +1178,15 @@ pub async fn perform_session_flow_routing(
         payment_method: None,
         payment_method_type: None,
         card_network: None,
+        card_issuer: None,
+        card_issuer_country: None,
     };
 
+    let (time_of_day, day_of_week) = get_local_time_of_day_and_day_of_week(
+        common_utils::date_time::now(),
+        business_profile.timezone.as_deref(),
+    );
+
     let payment_input = dsl_inputs::PaymentInput {
         amount: session_input.payment_intent.amount,
         currency: session_input
@@ -909,6 +1211,8 @@ pub async fn perform_session_flow_routing(
             .map(storage_enums::Country::from_alpha2),
         business_label: session_input.payment_intent.business_label.clone(),
         setup_future_usage: session_input.payment_intent.setup_future_usage,
+        time_of_day,
+        day_of_week,
     };
 
     let metadata = session_input
@@ -1014,6 +1318,7 @@ async fn perform_session_routing_for_pm_type(
         MerchantAccountRoutingAlgorithm::V1(algorithm_ref) => &algorithm_ref.algorithm_id,
     };
 
+    let mut success_rate_params = None;
     let chosen_connectors = if let Some(ref algorithm_id) = algorithm_id {
         let cached_algorithm = ensure_algorithm_cached_v1(
             &session_pm_input.state.clone(),
@@ -1035,6 +1340,10 @@ async fn perform_session_routing_for_pm_type(
                 session_pm_input.backend_input.clone(),
                 interpreter,
             )?,
+            CachedAlgorithm::SuccessRateBased(config) => {
+                success_rate_params = Some(config.params.clone());
+                config.connectors.clone()
+            }
         }
     } else {
         routing::helpers::get_merchant_default_config(
@@ -1057,6 +1366,12 @@ async fn perform_session_routing_for_pm_type(
     )
     .await?;
 
+    if let Some(params) = success_rate_params {
+        final_selection =
+            rank_session_connectors_by_success_rate(session_pm_input, &params, final_selection)
+                .await;
+    }
+
     if final_selection.is_empty() {
         let fallback = routing::helpers::get_merchant_default_config(
             &*session_pm_input.state.clone().store,
@@ -1100,6 +1415,7 @@ async fn perform_session_routing_for_pm_type(
     let MerchantAccountRoutingAlgorithm::V1(algorithm_id) = session_pm_input.routing_algorithm;
 
     let profile_wrapper = admin::BusinessProfileWrapper::new(business_profile.clone());
+    let mut success_rate_params = None;
     let chosen_connectors = if let Some(ref algorithm_id) = algorithm_id {
         let cached_algorithm = ensure_algorithm_cached_v1(
             &session_pm_input.state.clone(),
@@ -1121,6 +1437,10 @@ async fn perform_session_routing_for_pm_type(
                 session_pm_input.backend_input.clone(),
                 interpreter,
             )?,
+            CachedAlgorithm::SuccessRateBased(config) => {
+                success_rate_params = Some(config.params.clone());
+                config.connectors.clone()
+            }
         }
     } else {
         profile_wrapper
@@ -1139,6 +1459,12 @@ async fn perform_session_routing_for_pm_type(
     )
     .await?;
 
+    if let Some(params) = success_rate_params {
+        final_selection =
+            rank_session_connectors_by_success_rate(session_pm_input, &params, final_selection)
+                .await;
+    }
+
     if final_selection.is_empty() {
         let fallback = profile_wrapper
             .get_default_fallback_list_of_connector_under_profile()
@@ -1162,6 +1488,38 @@ async fn perform_session_routing_for_pm_type(
         Ok(Some(final_selection))
     }
 }
+/// Ranks the connectors chosen for a payment method type of the session flow by their success
+/// rate, leaving them in their configured order if they cannot be ranked
+async fn rank_session_connectors_by_success_rate(
+    session_pm_input: &SessionRoutingPmTypeInput<'_>,
+    params: &routing_types::SuccessRateWindowParams,
+    connectors: Vec<routing_types::RoutableConnectorChoice>,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let dimension = success_rate::SuccessRateDimension {
+        payment_method: session_pm_input.backend_input.payment_method.payment_method,
+        payment_method_type: session_pm_input
+            .backend_input
+            .payment_method
+            .payment_method_type,
+    };
+
+    match success_rate::rank_connectors_by_success_rate(
+        session_pm_input.state,
+        &session_pm_input.profile_id,
+        &dimension,
+        params,
+        connectors.clone(),
+    )
+    .await
+    {
+        Ok(ranked_connectors) => ranked_connectors,
+        Err(error) => {
+            logger::error!(?error, "Failed to rank session connectors by success rate");
+            connectors
+        }
+    }
+}
+
 pub fn make_dsl_input_for_surcharge(
     payment_attempt: &oss_storage::PaymentAttempt,
     payment_intent: &oss_storage::PaymentIntent,
@@ -1193,6 +1551,8 @@ pub fn make_dsl_input_for_surcharge(
             .map(api_enums::Country::from_alpha2),
         business_label: payment_intent.business_label.clone(),
         setup_future_usage: payment_intent.setup_future_usage,
+        time_of_day: None,
+        day_of_week: None,
     };
     let metadata = payment_intent
         .metadata
@@ -1206,6 +1566,8 @@ pub fn make_dsl_input_for_surcharge(
         payment_method: None,
         payment_method_type: None,
         card_network: None,
+        card_issuer: None,
+        card_issuer_country: None,
     };
     let backend_input = dsl_inputs::BackendInput {
         metadata,
@@ -1215,3 +1577,164 @@ pub fn make_dsl_input_for_surcharge(
     };
     Ok(backend_input)
 }
+
+/// Rebuilds the routing input of a stored payment, for replaying it against a routing algorithm.
+/// The card details are taken from the additional payment method data stored on the attempt, and
+/// the time of day and day of week from the creation time of the attempt.
+pub fn make_dsl_input_for_simulation(
+    payment_attempt: &oss_storage::PaymentAttempt,
+    payment_intent: &oss_storage::PaymentIntent,
+    billing_address: Option<Address>,
+    business_profile: &domain::BusinessProfile,
+) -> RoutingResult<dsl_inputs::BackendInput> {
+    let mandate_data = dsl_inputs::MandateData {
+        mandate_acceptance_type: payment_attempt
+            .customer_acceptance
+            .clone()
+            .map(|acceptance| {
+                acceptance
+                    .expose()
+                    .parse_value::<hyperswitch_domain_models::mandates::CustomerAcceptance>(
+                        "CustomerAcceptance",
+                    )
+            })
+            .transpose()
+            .change_context(errors::RoutingError::StoredPaymentParsingError {
+                field_name: "customer_acceptance",
+            })?
+            .map(|acceptance| match acceptance.acceptance_type {
+                hyperswitch_domain_models::mandates::AcceptanceType::Online => {
+                    euclid_enums::MandateAcceptanceType::Online
+                }
+                hyperswitch_domain_models::mandates::AcceptanceType::Offline => {
+                    euclid_enums::MandateAcceptanceType::Offline
+                }
+            }),
+        mandate_type: payment_attempt.mandate_details.as_ref().map(
+            |mandate_type| match mandate_type {
+                hyperswitch_domain_models::mandates::MandateDataType::SingleUse(_) => {
+                    euclid_enums::MandateType::SingleUse
+                }
+                hyperswitch_domain_models::mandates::MandateDataType::MultiUse(_) => {
+                    euclid_enums::MandateType::MultiUse
+                }
+            },
+        ),
+        payment_type: Some(payment_attempt.mandate_details.as_ref().map_or_else(
+            || euclid_enums::PaymentType::NonMandate,
+            |_| euclid_enums::PaymentType::SetupMandate,
+        )),
+    };
+
+    let card = get_additional_card_info(payment_attempt)?.unwrap_or_default();
+
+    Ok(PaymentDslInputBuilder {
+        payment_attempt,
+        payment_intent,
+        card,
+        // currency is always populated in payment_attempt during payment create
+        currency: payment_attempt
+            .currency
+            .get_required_value("currency")
+            .change_context(errors::RoutingError::DslMissingRequiredField {
+                field_name: "currency".to_string(),
+            })?,
+        billing_country: billing_address
+            .and_then(|bic| bic.address)
+            .and_then(|add| add.country),
+        mandate: mandate_data,
+        routed_at: payment_attempt.created_at,
+    }
+    .build(business_profile))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_local_time_of_day_and_day_of_week() {
+        // Sunday, 2024-03-31 00:30 UTC, right after the clocks in Europe/London were set forward
+        let timestamp = time::macros::datetime!(2024-03-31 00:30);
+
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(timestamp, None),
+            (Some(30), Some(euclid_enums::DayOfWeek::Sunday))
+        );
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(timestamp, Some("-05:00")),
+            (Some(1930), Some(euclid_enums::DayOfWeek::Saturday))
+        );
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(timestamp, Some("+05:30")),
+            (Some(600), Some(euclid_enums::DayOfWeek::Sunday))
+        );
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(timestamp, Some("Europe/London")),
+            (Some(30), Some(euclid_enums::DayOfWeek::Sunday))
+        );
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(
+                time::macros::datetime!(2024-03-31 01:30),
+                Some("Europe/London")
+            ),
+            (Some(230), Some(euclid_enums::DayOfWeek::Sunday))
+        );
+        assert_eq!(
+            get_local_time_of_day_and_day_of_week(timestamp, Some("Mars/Olympus_Mons")),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn test_card_issuer_country() {
+        assert_eq!(
+            get_card_issuer_country("GB"),
+            Some(api_enums::Country::UnitedKingdomOfGreatBritainAndNorthernIreland)
+        );
+        assert_eq!(
+            get_card_issuer_country("ind"),
+            Some(api_enums::Country::India)
+        );
+        assert_eq!(
+            get_card_issuer_country("UNITED STATES OF AMERICA"),
+            Some(api_enums::Country::UnitedStatesOfAmerica)
+        );
+        assert_eq!(get_card_issuer_country("Atlantis"), None);
+    }
+
+    #[test]
+    fn test_card_details_filled_with_additional_card_info() {
+        let additional_card_info = || CardDslInput {
+            card_bin: Some("424242".to_string()),
+            card_network: Some(api_enums::CardNetwork::Visa),
+            card_issuer: Some("Stripe Payments UK Limited".to_string()),
+            card_issuing_country: Some("GB".to_string()),
+        };
+
+        let card = CardDslInput {
+            card_bin: Some("424242".to_string()),
+            ..Default::default()
+        }
+        .with_additional_card_info(additional_card_info());
+        assert_eq!(
+            card.card_issuer.as_deref(),
+            Some("Stripe Payments UK Limited")
+        );
+        assert_eq!(card.card_issuing_country.as_deref(), Some("GB"));
+
+        let card = CardDslInput::default().with_additional_card_info(additional_card_info());
+        assert_eq!(card.card_bin.as_deref(), Some("424242"));
+        assert_eq!(card.card_network, Some(api_enums::CardNetwork::Visa));
+
+        let card = CardDslInput {
+            card_bin: Some("555555".to_string()),
+            ..Default::default()
+        }
+        .with_additional_card_info(additional_card_info());
+        assert_eq!(card.card_issuer, None);
+        assert_eq!(card.card_issuing_country, None);
+    }
+}
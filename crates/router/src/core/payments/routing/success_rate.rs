@@ -0,0 +1,436 @@
+//! Success rate based dynamic routing.
+//!
+//! Attempt outcomes are counted in Redis hashes, one hash per time bucket, keyed by the profile
+//! and the payment method dimension of the attempt. Each hash holds a `total` and a `success`
+//! counter per connector. The success rate of a connector is computed over the last
+//! `bucket_count` buckets, which together form the sliding window; expired buckets are evicted
+//! by Redis through their TTL.
+
+use std::{collections::HashMap, str::FromStr};
+
+use api_models::{enums as api_enums, routing as routing_types};
+use common_utils::date_time;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use router_env::logger;
+
+use super::RoutingResult;
+use crate::{
+    core::errors,
+    types::{domain, storage as oss_storage},
+    SessionState,
+};
+
+const SUCCESS_RATE_KEY_PREFIX: &str = "success_rate";
+
+/// The payment method dimension along which success rates are tracked
+#[derive(Debug, Clone, Copy)]
+pub struct SuccessRateDimension {
+    pub payment_method: Option<storage_enums::PaymentMethod>,
+    pub payment_method_type: Option<storage_enums::PaymentMethodType>,
+}
+
+impl From<&oss_storage::PaymentAttempt> for SuccessRateDimension {
+    fn from(payment_attempt: &oss_storage::PaymentAttempt) -> Self {
+        Self {
+            payment_method: payment_attempt.payment_method,
+            payment_method_type: payment_attempt.payment_method_type,
+        }
+    }
+}
+
+fn get_bucket_key(
+    profile_id: &common_utils::id_type::ProfileId,
+    dimension: &SuccessRateDimension,
+    bucket_start: i64,
+) -> String {
+    format!(
+        "{}_{}_{}_{}_{}",
+        SUCCESS_RATE_KEY_PREFIX,
+        profile_id.get_string_repr(),
+        dimension
+            .payment_method
+            .map(|pm| pm.to_string())
+            .unwrap_or_default(),
+        dimension
+            .payment_method_type
+            .map(|pmt| pmt.to_string())
+            .unwrap_or_default(),
+        bucket_start
+    )
+}
+
+fn get_connector_label(choice: &routing_types::RoutableConnectorChoice) -> String {
+    match choice.merchant_connector_id.as_ref() {
+        Some(mca_id) => format!("{}:{}", choice.connector, mca_id.get_string_repr()),
+        None => choice.connector.to_string(),
+    }
+}
+
+/// Returns the labels the outcomes through the connector are counted under. An outcome is counted
+/// under the connector as well as under its merchant connector account, so that both the
+/// algorithms choosing only the connector and those choosing the merchant connector account see
+/// it under the label [`get_connector_label`] gives their choices.
+fn get_outcome_labels(choice: &routing_types::RoutableConnectorChoice) -> Vec<String> {
+    let connector_choice = routing_types::RoutableConnectorChoice {
+        merchant_connector_id: None,
+        ..choice.clone()
+    };
+
+    std::iter::once(get_connector_label(&connector_choice))
+        .chain(
+            choice
+                .merchant_connector_id
+                .is_some()
+                .then(|| get_connector_label(choice)),
+        )
+        .collect()
+}
+
+fn get_total_field(label: &str) -> String {
+    format!("{label}_total")
+}
+
+fn get_success_field(label: &str) -> String {
+    format!("{label}_success")
+}
+
+/// Returns the start timestamps of all the buckets that make up the window ending at `now`,
+/// starting with the current bucket.
+fn get_window_bucket_starts(now: i64, params: &routing_types::SuccessRateWindowParams) -> Vec<i64> {
+    let bucket_size = params.get_bucket_size_in_secs();
+    let current_bucket_start = now - now.rem_euclid(bucket_size);
+
+    (0..i64::from(params.bucket_count))
+        .map(|index| current_bucket_start - index * bucket_size)
+        .collect()
+}
+
+fn calculate_success_rate(
+    success_count: u32,
+    total_count: u32,
+    params: &routing_types::SuccessRateWindowParams,
+) -> f64 {
+    if total_count == 0 || total_count < params.min_aggregates_size {
+        params.default_success_rate
+    } else {
+        f64::from(success_count) / f64::from(total_count)
+    }
+}
+
+/// Stable sort of the connectors by decreasing success rate, so that connectors with equal
+/// success rates retain their configured order.
+fn sort_connectors_by_success_rate(
+    connectors: Vec<routing_types::RoutableConnectorChoice>,
+    success_rates: &HashMap<String, f64>,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let mut scored = connectors
+        .into_iter()
+        .map(|choice| {
+            let rate = success_rates
+                .get(&get_connector_label(&choice))
+                .copied()
+                .unwrap_or_default();
+            (rate, choice)
+        })
+        .collect::<Vec<_>>();
+
+    scored.sort_by(|(left, _), (right, _)| right.total_cmp(left));
+
+    scored.into_iter().map(|(_, choice)| choice).collect()
+}
+
+async fn fetch_window_counts(
+    state: &SessionState,
+    profile_id: &common_utils::id_type::ProfileId,
+    dimension: &SuccessRateDimension,
+    params: &routing_types::SuccessRateWindowParams,
+) -> RoutingResult<HashMap<String, u32>> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::SuccessRateWindowFetchFailed)
+        .attach_printable("Failed to get redis connection")?;
+
+    let bucket_keys = get_window_bucket_starts(date_time::now_unix_timestamp(), params)
+        .into_iter()
+        .map(|bucket_start| get_bucket_key(profile_id, dimension, bucket_start))
+        .collect::<Vec<_>>();
+
+    let buckets = futures::future::try_join_all(
+        bucket_keys
+            .iter()
+            .map(|key| redis_conn.get_all_hash_fields::<HashMap<String, String>>(key.as_str())),
+    )
+    .await
+    .change_context(errors::RoutingError::SuccessRateWindowFetchFailed)
+    .attach_printable("Failed to read success rate buckets")?;
+
+    let mut counts = HashMap::<String, u32>::new();
+    for (field, value) in buckets.into_iter().flatten() {
+        let value = u32::from_str(&value).unwrap_or_default();
+        let count = counts.entry(field).or_default();
+        *count = count.saturating_add(value);
+    }
+
+    Ok(counts)
+}
+
+/// Ranks the given connectors by their success rate over the sliding window.
+pub async fn rank_connectors_by_success_rate(
+    state: &SessionState,
+    profile_id: &common_utils::id_type::ProfileId,
+    dimension: &SuccessRateDimension,
+    params: &routing_types::SuccessRateWindowParams,
+    connectors: Vec<routing_types::RoutableConnectorChoice>,
+) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    let counts = fetch_window_counts(state, profile_id, dimension, params).await?;
+
+    let success_rates = connectors
+        .iter()
+        .map(|choice| {
+            let label = get_connector_label(choice);
+            let total = counts
+                .get(&get_total_field(&label))
+                .copied()
+                .unwrap_or_default();
+            let success = counts
+                .get(&get_success_field(&label))
+                .copied()
+                .unwrap_or_default();
+            let rate = calculate_success_rate(success, total, params);
+            (label, rate)
+        })
+        .collect::<HashMap<_, _>>();
+
+    logger::debug!(?success_rates, "Success rates of the eligible connectors");
+
+    Ok(sort_connectors_by_success_rate(connectors, &success_rates))
+}
+
+/// Records the outcome of an attempt through the given connector in the current bucket.
+pub async fn update_success_rate_window(
+    state: &SessionState,
+    profile_id: &common_utils::id_type::ProfileId,
+    dimension: &SuccessRateDimension,
+    params: &routing_types::SuccessRateWindowParams,
+    connector: &routing_types::RoutableConnectorChoice,
+    is_success: bool,
+) -> RoutingResult<()> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)
+        .attach_printable("Failed to get redis connection")?;
+
+    let now = date_time::now_unix_timestamp();
+    let bucket_size = params.get_bucket_size_in_secs();
+    let bucket_key = get_bucket_key(profile_id, dimension, now - now.rem_euclid(bucket_size));
+
+    for label in get_outcome_labels(connector) {
+        redis_conn
+            .increment_field_in_hash(&bucket_key, &get_total_field(&label), 1)
+            .await
+            .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)?;
+
+        if is_success {
+            redis_conn
+                .increment_field_in_hash(&bucket_key, &get_success_field(&label), 1)
+                .await
+                .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)?;
+        }
+    }
+
+    // The bucket needs to outlive the whole window it can be a part of
+    redis_conn
+        .set_expiry(
+            &bucket_key,
+            i64::from(params.window_duration_in_secs) + bucket_size,
+        )
+        .await
+        .change_context(errors::RoutingError::SuccessRateWindowUpdateFailed)
+}
+
+/// Returns whether the attempt status is the successful or the failed outcome of an attempt, or
+/// `None` if the attempt has no outcome yet. Once an attempt has an outcome, later transitions
+/// like `Authorized` to `Charged` do not change it.
+pub fn get_attempt_outcome(status: storage_enums::AttemptStatus) -> Option<bool> {
+    match status {
+        storage_enums::AttemptStatus::Charged
+        | storage_enums::AttemptStatus::Authorized
+        | storage_enums::AttemptStatus::PartialCharged
+        | storage_enums::AttemptStatus::PartialChargedAndChargeable => Some(true),
+        storage_enums::AttemptStatus::Failure
+        | storage_enums::AttemptStatus::AuthorizationFailed
+        | storage_enums::AttemptStatus::AuthenticationFailed
+        | storage_enums::AttemptStatus::RouterDeclined => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns whether the transition of the attempt between the statuses gives the attempt its
+/// outcome, in which case the outcome is to be counted in the success rate window
+pub fn is_outcome_transition(
+    previous_status: storage_enums::AttemptStatus,
+    status: storage_enums::AttemptStatus,
+) -> bool {
+    get_attempt_outcome(previous_status).is_none() && get_attempt_outcome(status).is_some()
+}
+
+/// Updates the success rate window with the outcome of the payment attempt, if the profile of
+/// the payment is configured with a success rate based routing algorithm.
+pub async fn update_success_rate_for_payment_attempt(
+    state: &SessionState,
+    business_profile: &domain::BusinessProfile,
+    payment_attempt: &oss_storage::PaymentAttempt,
+) -> RoutingResult<()> {
+    let Some(is_success) = get_attempt_outcome(payment_attempt.status) else {
+        return Ok(());
+    };
+
+    let Some(connector) = payment_attempt
+        .connector
+        .as_deref()
+        .and_then(|connector| api_enums::RoutableConnectors::from_str(connector).ok())
+    else {
+        return Ok(());
+    };
+
+    let Some(params) = super::get_success_rate_window_params(
+        state,
+        &payment_attempt.merchant_id,
+        business_profile,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let connector = routing_types::RoutableConnectorChoice {
+        choice_kind: routing_types::RoutableChoiceKind::FullStruct,
+        connector,
+        merchant_connector_id: payment_attempt.merchant_connector_id.clone(),
+    };
+
+    update_success_rate_window(
+        state,
+        business_profile.get_id(),
+        &SuccessRateDimension::from(payment_attempt),
+        &params,
+        &connector,
+        is_success,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn choice(connector: api_enums::RoutableConnectors) -> routing_types::RoutableConnectorChoice {
+        routing_types::RoutableConnectorChoice {
+            choice_kind: routing_types::RoutableChoiceKind::OnlyConnector,
+            connector,
+            merchant_connector_id: None,
+        }
+    }
+
+    #[test]
+    fn test_window_bucket_starts() {
+        let params = routing_types::SuccessRateWindowParams {
+            window_duration_in_secs: 600,
+            bucket_count: 3,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_window_bucket_starts(1_050, &params),
+            vec![1_000, 800, 600]
+        );
+    }
+
+    #[test]
+    fn test_success_rate_falls_back_to_default_below_min_aggregates() {
+        let params = routing_types::SuccessRateWindowParams {
+            min_aggregates_size: 10,
+            default_success_rate: 0.5,
+            ..Default::default()
+        };
+
+        assert_eq!(calculate_success_rate(1, 4, &params), 0.5);
+        assert_eq!(calculate_success_rate(3, 12, &params), 0.25);
+    }
+
+    #[test]
+    fn test_outcome_counted_only_on_transition_into_outcome() {
+        use storage_enums::AttemptStatus;
+
+        assert!(is_outcome_transition(
+            AttemptStatus::Pending,
+            AttemptStatus::Authorized
+        ));
+        assert!(is_outcome_transition(
+            AttemptStatus::AuthenticationPending,
+            AttemptStatus::Failure
+        ));
+        assert!(!is_outcome_transition(
+            AttemptStatus::Authorized,
+            AttemptStatus::Charged
+        ));
+        assert!(!is_outcome_transition(
+            AttemptStatus::Started,
+            AttemptStatus::Pending
+        ));
+    }
+
+    #[test]
+    fn test_outcomes_counted_under_the_labels_read_by_both_choice_kinds() {
+        let mca_id =
+            common_utils::id_type::MerchantConnectorAccountId::wrap("mca_1".to_string()).unwrap();
+        let full_struct_choice = routing_types::RoutableConnectorChoice {
+            choice_kind: routing_types::RoutableChoiceKind::FullStruct,
+            connector: api_enums::RoutableConnectors::Stripe,
+            merchant_connector_id: Some(mca_id),
+        };
+
+        let labels = get_outcome_labels(&full_struct_choice);
+
+        assert!(labels.contains(&get_connector_label(&choice(
+            api_enums::RoutableConnectors::Stripe
+        ))));
+        assert!(labels.contains(&get_connector_label(&full_struct_choice)));
+        assert_eq!(
+            get_outcome_labels(&choice(api_enums::RoutableConnectors::Stripe)),
+            vec!["stripe".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_connectors_sorted_by_success_rate_with_stable_ties() {
+        let connectors = vec![
+            choice(api_enums::RoutableConnectors::Stripe),
+            choice(api_enums::RoutableConnectors::Adyen),
+            choice(api_enums::RoutableConnectors::Checkout),
+        ];
+        let success_rates = HashMap::from([
+            ("stripe".to_string(), 0.7),
+            ("adyen".to_string(), 0.9),
+            ("checkout".to_string(), 0.7),
+        ]);
+
+        let sorted = sort_connectors_by_success_rate(connectors, &success_rates)
+            .into_iter()
+            .map(|choice| choice.connector)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sorted,
+            vec![
+                api_enums::RoutableConnectors::Adyen,
+                api_enums::RoutableConnectors::Stripe,
+                api_enums::RoutableConnectors::Checkout,
+            ]
+        );
+    }
+}
@@ -37,6 +37,20 @@ impl ForeignFrom<api_models::payments::AcceptanceType> for dsl_enums::MandateAcc
     }
 }
 
+impl ForeignFrom<time::Weekday> for dsl_enums::DayOfWeek {
+    fn foreign_from(from: time::Weekday) -> Self {
+        match from {
+            time::Weekday::Monday => Self::Monday,
+            time::Weekday::Tuesday => Self::Tuesday,
+            time::Weekday::Wednesday => Self::Wednesday,
+            time::Weekday::Thursday => Self::Thursday,
+            time::Weekday::Friday => Self::Friday,
+            time::Weekday::Saturday => Self::Saturday,
+            time::Weekday::Sunday => Self::Sunday,
+        }
+    }
+}
+
 impl ForeignFrom<api_models::payments::MandateType> for dsl_enums::MandateType {
     fn foreign_from(from: api_models::payments::MandateType) -> Self {
         match from {
@@ -788,6 +788,7 @@ pub async fn decide_payout_connector(
                 &TransactionData::<()>::Payout(payout_data),
                 eligible_connectors,
                 &business_profile,
+                None,
             )
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -836,6 +837,7 @@ pub async fn decide_payout_connector(
                 &TransactionData::<()>::Payout(payout_data),
                 eligible_connectors,
                 &business_profile,
+                None,
             )
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -0,0 +1,181 @@
+use api_models::process_tracker as process_tracker_api_types;
+use common_utils::{date_time, ext_traits::StringExt};
+use diesel_models::enums as storage_enums;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    routes::SessionState,
+    services::ApplicationResponse,
+    types::{storage, transformers::ForeignInto},
+    utils,
+};
+
+async fn find_process_by_id(
+    state: &SessionState,
+    process_id: &str,
+) -> RouterResult<storage::ProcessTracker> {
+    state
+        .store
+        .find_process_by_id(process_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("Process tracker task with id {process_id} not found"),
+            })
+        })
+}
+
+#[instrument(skip_all)]
+pub async fn list_processes(
+    state: SessionState,
+    constraints: process_tracker_api_types::ProcessTrackerListConstraints,
+) -> RouterResponse<process_tracker_api_types::ProcessTrackerListResponse> {
+    let runner = constraints
+        .runner
+        .parse_enum::<storage::ProcessTrackerRunner>("ProcessTrackerRunner")
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "runner",
+        })?;
+    let status = constraints
+        .status
+        .map(|status| {
+            status.parse_enum::<storage_enums::ProcessTrackerStatus>("ProcessTrackerStatus")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "status",
+        })?;
+
+    let limit = constraints
+        .limit
+        .unwrap_or(i64::from(consts::DEFAULT_LIST_API_LIMIT));
+    utils::when(
+        !(1..=i64::from(consts::PROCESS_TRACKER_LIST_MAX_LIMIT)).contains(&limit),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "limit should be in between 1 and {}",
+                    consts::PROCESS_TRACKER_LIST_MAX_LIMIT
+                ),
+            })
+        },
+    )?;
+
+    let processes = state
+        .store
+        .as_scheduler()
+        .find_processes_by_runner_status_time(
+            runner,
+            status,
+            constraints.created_gte,
+            constraints.created_lte.unwrap_or(date_time::now()),
+            Some(limit),
+            constraints.offset,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the process tracker tasks")?;
+
+    let data = processes
+        .into_iter()
+        .map(ForeignInto::foreign_into)
+        .collect::<Vec<process_tracker_api_types::ProcessTrackerResponse>>();
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api_types::ProcessTrackerListResponse {
+            size: data.len(),
+            data,
+        },
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_process(
+    state: SessionState,
+    process_id: String,
+) -> RouterResponse<process_tracker_api_types::ProcessTrackerResponse> {
+    let process = find_process_by_id(&state, &process_id).await?;
+
+    Ok(ApplicationResponse::Json(process.foreign_into()))
+}
+
+/// Updates the task only if its status has not changed since it was fetched, so that the update
+/// does not race with the consumer picking up the task
+async fn update_process_if_unchanged(
+    state: &SessionState,
+    process: storage::ProcessTracker,
+    process_update: storage::ProcessTrackerUpdate,
+) -> RouterResult<storage::ProcessTracker> {
+    let process_id = process.id.clone();
+    let expected_status = process.status;
+
+    state
+        .store
+        .as_scheduler()
+        .update_process_if_status(process, expected_status, process_update)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Status of process tracker task with id {process_id} changed while it was being updated"
+                ),
+            })
+        })
+}
+
+/// Schedules the task to be picked up by the scheduler right away
+#[instrument(skip_all)]
+pub async fn retry_process(
+    state: SessionState,
+    process_id: String,
+) -> RouterResponse<process_tracker_api_types::ProcessTrackerResponse> {
+    let process = find_process_by_id(&state, &process_id).await?;
+
+    // The consumer would otherwise race with the retried task
+    if process.status == storage_enums::ProcessTrackerStatus::ProcessStarted {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("Process tracker task with id {process_id} is being processed"),
+        }));
+    }
+
+    let retry_update = process
+        .get_retry_update(date_time::now())
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to construct the retry of the process tracker task")?;
+    let process = update_process_if_unchanged(&state, process, retry_update).await?;
+
+    Ok(ApplicationResponse::Json(process.foreign_into()))
+}
+
+/// Finishes the task with the business status. Tasks left in `process_started` by a consumer
+/// which went away can be cancelled as well, the cancellation only applies if the task is still
+/// in the status it was fetched in.
+#[instrument(skip_all)]
+pub async fn cancel_process(
+    state: SessionState,
+    process_id: String,
+    request: process_tracker_api_types::ProcessTrackerCancelRequest,
+) -> RouterResponse<process_tracker_api_types::ProcessTrackerResponse> {
+    let process = find_process_by_id(&state, &process_id).await?;
+
+    if process.status == storage_enums::ProcessTrackerStatus::Finish {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("Process tracker task with id {process_id} has already finished"),
+        }));
+    }
+
+    let cancel_update = storage::ProcessTrackerUpdate::StatusUpdate {
+        status: storage_enums::ProcessTrackerStatus::Finish,
+        business_status: Some(request.business_status),
+    };
+    let process = update_process_if_unchanged(&state, process, cancel_update).await?;
+
+    Ok(ApplicationResponse::Json(process.foreign_into()))
+}
@@ -0,0 +1,245 @@
+use analytics::{
+    errors::AnalyticsError,
+    lambda_utils::invoke_lambda,
+    reports::{
+        ReportBackend, ReportGenerationTrackingData, ReportKind, ReportSink, ReportsError,
+        ReportsResult,
+    },
+};
+use api_models::analytics::{GenerateReportRequest, GenerateReportResponse};
+use common_utils::{
+    date_time,
+    errors::{CustomResult, ReportSwitchExt},
+    ext_traits::ValueExt,
+};
+use diesel_models::process_tracker::business_status;
+use error_stack::{report, ResultExt};
+use external_services::file_storage::MultipartUpload;
+use router_env::{instrument, logger, metrics::add_attributes, tracing};
+
+use crate::{
+    consts,
+    routes::{metrics, SessionState},
+    services::ApplicationResponse,
+    types::storage,
+};
+
+const REPORT_GENERATION_TAG: &str = "REPORT";
+const REPORT_GENERATION_NAME: &str = "REPORT_GENERATION";
+const REPORT_GENERATION_RUNNER: diesel_models::ProcessTrackerRunner =
+    diesel_models::ProcessTrackerRunner::ReportGenerationWorkflow;
+
+/// Generates the report with the configured backend. Reports generated in process are generated
+/// by the scheduler, and can be downloaded with the returned report ID once generated.
+#[instrument(skip_all)]
+pub async fn generate_report(
+    state: &SessionState,
+    kind: ReportKind,
+    request: GenerateReportRequest,
+    user_id: String,
+) -> CustomResult<ApplicationResponse<GenerateReportResponse>, AnalyticsError> {
+    let report_config = &state.conf.report_download_config;
+    match report_config.backend {
+        ReportBackend::Lambda => {
+            let json_bytes =
+                serde_json::to_vec(&request).map_err(|_| AnalyticsError::UnknownError)?;
+            invoke_lambda(
+                report_config.get_function(kind),
+                &report_config.region,
+                &json_bytes,
+            )
+            .await?;
+
+            Ok(ApplicationResponse::StatusOk)
+        }
+        ReportBackend::InProcess => schedule_report_generation(state, kind, request, user_id)
+            .await
+            .map(ApplicationResponse::Json),
+    }
+}
+
+async fn schedule_report_generation(
+    state: &SessionState,
+    kind: ReportKind,
+    request: GenerateReportRequest,
+    user_id: String,
+) -> CustomResult<GenerateReportResponse, AnalyticsError> {
+    let report_id = common_utils::generate_id(consts::ID_LENGTH, "report");
+    let tracking_data = ReportGenerationTrackingData {
+        kind,
+        format: state.conf.report_download_config.in_process.format,
+        request,
+        user_id,
+    };
+
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        report_id.clone(),
+        REPORT_GENERATION_NAME,
+        REPORT_GENERATION_RUNNER,
+        [REPORT_GENERATION_TAG],
+        tracking_data,
+        date_time::now(),
+    )
+    .change_context(AnalyticsError::UnknownError)
+    .attach_printable("Failed to construct report generation process tracker task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting report generation task to process_tracker: report_id: {report_id}"
+            )
+        })?;
+    metrics::TASKS_ADDED_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &add_attributes([("flow", "ReportGeneration")]),
+    );
+
+    Ok(GenerateReportResponse { report_id })
+}
+
+/// Business status of the report generation tasks whose report has been generated, which are
+/// rescheduled to delete the report once it expires
+pub const REPORT_GENERATED: &str = "REPORT_GENERATED";
+
+/// Uploads the parts of a report to the file storage as they are encoded
+struct ReportUpload(Box<dyn MultipartUpload>);
+
+#[async_trait::async_trait]
+impl ReportSink for ReportUpload {
+    async fn write_part(&mut self, part: Vec<u8>) -> ReportsResult<()> {
+        self.0
+            .upload_part(part)
+            .await
+            .change_context(ReportsError::UploadFailure)
+    }
+}
+
+/// Generates the report of a report generation task and uploads it to the file storage a part
+/// at a time
+#[instrument(skip_all)]
+pub async fn generate_and_upload_report(
+    state: &SessionState,
+    report_id: &str,
+    tracking_data: &ReportGenerationTrackingData,
+) -> CustomResult<(), AnalyticsError> {
+    let mut upload = ReportUpload(
+        state
+            .file_storage_client
+            .start_multipart_upload(&tracking_data.get_file_key(report_id))
+            .await
+            .change_context(AnalyticsError::UnknownError)
+            .attach_printable_lazy(|| format!("Failed to start uploading report {report_id}"))?,
+    );
+
+    let result = state
+        .pool
+        .generate_report(
+            tracking_data.kind,
+            &tracking_data.request.request,
+            &tracking_data.request.auth,
+            &state.conf.report_download_config.in_process,
+            tracking_data.format,
+            &mut upload,
+        )
+        .await;
+
+    if let Err(error) = result {
+        if let Err(abort_error) = upload.0.abort().await {
+            logger::error!(?abort_error, report_id, "Failed to abort report upload");
+        }
+        return Err(error).switch();
+    }
+
+    upload
+        .0
+        .complete()
+        .await
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable_lazy(|| format!("Failed to upload report {report_id}"))
+}
+
+/// Deletes the report of a report generation task from the file storage once it has expired
+#[instrument(skip_all)]
+pub async fn delete_expired_report(
+    state: &SessionState,
+    report_id: &str,
+    tracking_data: &ReportGenerationTrackingData,
+) -> CustomResult<(), AnalyticsError> {
+    state
+        .file_storage_client
+        .delete_file(&tracking_data.get_file_key(report_id))
+        .await
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable_lazy(|| format!("Failed to delete report {report_id}"))
+}
+
+/// Returns the time the reports generated now expire at
+pub fn get_report_expiry(state: &SessionState) -> time::PrimitiveDateTime {
+    date_time::now().saturating_add(time::Duration::days(i64::from(
+        state.conf.report_download_config.in_process.retention_days,
+    )))
+}
+
+pub fn get_report_download_link(state: &SessionState, report_id: &str) -> String {
+    format!(
+        "{}/analytics/v1/report/download/{report_id}",
+        state.base_url
+    )
+}
+
+/// Retrieves a report generated in process. Reports can only be downloaded by the user that
+/// requested them.
+#[instrument(skip_all)]
+pub async fn download_report(
+    state: &SessionState,
+    user_id: &str,
+    report_id: &str,
+) -> CustomResult<ApplicationResponse<()>, AnalyticsError> {
+    let process = state
+        .store
+        .find_process_by_id(report_id)
+        .await
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable("Failed to fetch report generation task")?
+        .filter(|process| {
+            process.runner.as_deref() == Some(REPORT_GENERATION_RUNNER.to_string().as_str())
+        })
+        .ok_or(report!(AnalyticsError::ReportNotFound))?;
+
+    let tracking_data: ReportGenerationTrackingData = process
+        .tracking_data
+        .parse_value("ReportGenerationTrackingData")
+        .change_context(AnalyticsError::UnknownError)?;
+
+    if tracking_data.user_id != user_id {
+        return Err(report!(AnalyticsError::ReportNotFound));
+    }
+
+    match process.business_status.as_str() {
+        REPORT_GENERATED => (),
+        // The report is deleted once it expires
+        business_status::COMPLETED_BY_PT => return Err(report!(AnalyticsError::ReportExpired)),
+        _ => return Err(report!(AnalyticsError::ReportNotGenerated)),
+    }
+
+    let report = state
+        .file_storage_client
+        .retrieve_file(&tracking_data.get_file_key(report_id))
+        .await
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable_lazy(|| format!("Failed to retrieve report {report_id}"))?;
+
+    let content_type = tracking_data
+        .format
+        .get_content_type()
+        .parse::<mime::Mime>()
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable("Failed to parse report content type")?;
+
+    Ok(ApplicationResponse::FileData((report, content_type)))
+}
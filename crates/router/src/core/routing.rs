@@ -1,12 +1,39 @@
 pub mod helpers;
 pub mod transformers;
 
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+use std::collections::HashMap;
+
 use api_models::{
     enums,
     routing::{self as routing_types, RoutingRetrieveQuery},
 };
 use diesel_models::routing_algorithm::RoutingAlgorithm;
 use error_stack::ResultExt;
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+use euclid::backend::EuclidBackend;
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+use hyperswitch_domain_models::payments::payment_intent::{
+    PaymentIntentFetchConstraints, PaymentIntentListParams,
+};
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+use masking::ExposeInterface;
 use rustc_hash::FxHashSet;
 
 use super::payments;
@@ -32,6 +59,12 @@ use crate::{
     },
     utils::{self, OptionExt},
 };
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+use crate::{logger, types::storage};
 pub enum TransactionData<'a, F>
 where
     F: Clone,
@@ -164,6 +197,15 @@ pub async fn create_routing_algorithm_under_profile(
 
     algorithm_helper.validate_connectors_in_routing_config()?;
 
+    let warnings = helpers::get_routing_analysis_warnings(
+        &state,
+        &key_store,
+        business_profile.get_id(),
+        &request.algorithm,
+        transaction_type,
+    )
+    .await;
+
     let algo = RoutingAlgorithmUpdate::create_new_routing_algorithm(
         &request,
         merchant_account.get_id(),
@@ -178,7 +220,10 @@ pub async fn create_routing_algorithm_under_profile(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
 
-    let new_record = record.foreign_into();
+    let new_record = routing_types::RoutingDictionaryRecord {
+        warnings,
+        ..record.foreign_into()
+    };
 
     metrics::ROUTING_CREATE_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
     Ok(service_api::ApplicationResponse::Json(new_record))
@@ -250,6 +295,15 @@ pub async fn create_routing_algorithm_under_profile(
     )
     .await?;
 
+    let warnings = helpers::get_routing_analysis_warnings(
+        &state,
+        &key_store,
+        &profile_id,
+        &algorithm,
+        transaction_type,
+    )
+    .await;
+
     let timestamp = common_utils::date_time::now();
     let algo = RoutingAlgorithm {
         algorithm_id: algorithm_id.clone(),
@@ -268,7 +322,10 @@ pub async fn create_routing_algorithm_under_profile(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
 
-    let new_record = record.foreign_into();
+    let new_record = routing_types::RoutingDictionaryRecord {
+        warnings,
+        ..record.foreign_into()
+    };
 
     metrics::ROUTING_CREATE_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
     Ok(service_api::ApplicationResponse::Json(new_record))
@@ -1115,3 +1172,510 @@ pub async fn update_default_routing_config_for_profile(
         },
     ))
 }
+
+/// Number of payments fetched at a time while replaying payments against a routing algorithm
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+const ROUTING_SIMULATION_PAGE_SIZE: u32 = 500;
+
+/// Maximum number of payments replayed by a routing simulation
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+const ROUTING_SIMULATION_MAX_PAYMENTS: u32 = 10000;
+
+/// Replays the payments of the profile made in the time range against the draft routing
+/// algorithm, comparing the connector chosen by the draft with the connector each payment was
+/// routed to.
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+pub async fn simulate_routing_algorithm(
+    state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    authentication_profile_id: Option<common_utils::id_type::ProfileId>,
+    request: routing_types::RoutingSimulationRequest,
+) -> RouterResponse<routing_types::RoutingSimulationResponse> {
+    metrics::ROUTING_SIMULATE_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+
+    let business_profile = core_utils::validate_and_get_business_profile(
+        db,
+        key_manager_state,
+        &key_store,
+        Some(&request.profile_id),
+        merchant_account.get_id(),
+    )
+    .await?
+    .get_required_value("BusinessProfile")?;
+
+    core_utils::validate_profile_id_from_auth_layer(authentication_profile_id, &business_profile)?;
+
+    helpers::validate_connectors_in_routing_config(
+        &state,
+        &key_store,
+        merchant_account.get_id(),
+        &request.profile_id,
+        &routing_types::RoutingAlgorithm::Advanced(request.algorithm.clone()),
+    )
+    .await?;
+
+    let rule_names = request
+        .algorithm
+        .rules
+        .iter()
+        .map(|rule| rule.name.clone())
+        .collect::<Vec<_>>();
+
+    let interpreter = euclid::backend::VirInterpreterBackend::with_program(request.algorithm)
+        .change_context(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Invalid routing algorithm".to_string(),
+        })
+        .attach_printable("Error initializing DSL interpreter backend")?;
+
+    let limit = request
+        .limit
+        .unwrap_or(ROUTING_SIMULATION_MAX_PAYMENTS)
+        .min(ROUTING_SIMULATION_MAX_PAYMENTS);
+
+    let mut simulation = RoutingSimulation::default();
+    let mut offset = 0;
+    while offset < limit {
+        let page_size = ROUTING_SIMULATION_PAGE_SIZE.min(limit - offset);
+        let constraints = PaymentIntentFetchConstraints::List(Box::new(PaymentIntentListParams {
+            offset,
+            starting_at: Some(request.time_range.start_time),
+            ending_at: request.time_range.end_time,
+            amount_filter: None,
+            connector: None,
+            currency: request.currency.map(|currency| vec![currency]),
+            status: None,
+            payment_method: None,
+            payment_method_type: None,
+            authentication_type: None,
+            merchant_connector_id: None,
+            profile_id: Some(request.profile_id.clone()),
+            customer_id: None,
+            starting_after_id: None,
+            ending_before_id: None,
+            limit: Some(page_size),
+            order: api_models::payments::Order {
+                on: api_models::payments::SortOn::Created,
+                by: api_models::payments::SortBy::Asc,
+            },
+        }));
+
+        let payments = db
+            .get_filtered_payment_intents_attempt(
+                key_manager_state,
+                merchant_account.get_id(),
+                &constraints,
+                &key_store,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the payments to replay")?;
+
+        let is_last_page = u32::try_from(payments.len()).unwrap_or(u32::MAX) < page_size;
+        for (payment_intent, payment_attempt) in payments {
+            simulation.replay_payment(
+                &interpreter,
+                &business_profile,
+                &payment_intent,
+                &payment_attempt,
+            );
+        }
+
+        if is_last_page {
+            break;
+        }
+        offset += page_size;
+    }
+
+    metrics::ROUTING_SIMULATE_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(
+        simulation.into_response(rule_names),
+    ))
+}
+
+/// Total amounts of payments, kept apart for each currency as amounts in different currencies
+/// cannot be added up
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+#[derive(Default)]
+struct AmountsByCurrency(HashMap<enums::Currency, common_utils::types::MinorUnit>);
+
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+impl AmountsByCurrency {
+    fn add(&mut self, currency: enums::Currency, amount: common_utils::types::MinorUnit) {
+        let total = self.0.entry(currency).or_default();
+        *total = *total + amount;
+    }
+
+    fn into_amounts(self) -> Vec<routing_types::CurrencyAmount> {
+        let mut amounts = self
+            .0
+            .into_iter()
+            .map(|(currency, amount)| routing_types::CurrencyAmount { currency, amount })
+            .collect::<Vec<_>>();
+        amounts.sort_by_key(|amount| amount.currency.to_string());
+        amounts
+    }
+}
+
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+#[derive(Default)]
+struct ConnectorSimulation {
+    historical_payment_count: u64,
+    historical_amounts: AmountsByCurrency,
+    simulated_payment_count: u64,
+    simulated_amounts: AmountsByCurrency,
+}
+
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+#[derive(Default)]
+struct RouteChangeTotals {
+    payment_count: u64,
+    amounts: AmountsByCurrency,
+}
+
+/// Running totals of a routing simulation
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+#[derive(Default)]
+struct RoutingSimulation {
+    payment_count: u64,
+    total_amounts: AmountsByCurrency,
+    rerouted_payment_count: u64,
+    rerouted_amounts: AmountsByCurrency,
+    default_selection_count: u64,
+    skipped_payment_count: u64,
+    connectors: HashMap<String, ConnectorSimulation>,
+    route_changes: HashMap<(Option<String>, String), RouteChangeTotals>,
+    rule_matches: HashMap<String, u64>,
+}
+
+#[cfg(all(
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+impl RoutingSimulation {
+    fn replay_payment(
+        &mut self,
+        interpreter: &euclid::backend::VirInterpreterBackend<routing_types::ConnectorSelection>,
+        business_profile: &domain::BusinessProfile,
+        payment_intent: &storage::PaymentIntent,
+        payment_attempt: &storage::PaymentAttempt,
+    ) {
+        let billing_address = payment_intent
+            .billing_details
+            .clone()
+            .and_then(|billing_details| {
+                billing_details
+                    .into_inner()
+                    .expose()
+                    .parse_value::<api_models::payments::Address>("Address")
+                    .ok()
+            });
+
+        let simulated_route = payments::routing::make_dsl_input_for_simulation(
+            payment_attempt,
+            payment_intent,
+            billing_address,
+            business_profile,
+        )
+        .and_then(|backend_input| {
+            let currency = backend_input.payment.currency;
+            payments::routing::execute_dsl_for_simulation(
+                backend_input,
+                interpreter,
+                payment_attempt.payment_id.get_string_repr(),
+            )
+            .map(|(rule_name, connectors)| (currency, rule_name, connectors))
+        });
+
+        match simulated_route {
+            Ok((currency, rule_name, connectors)) => match connectors.first() {
+                Some(choice) => self.record_payment(
+                    rule_name,
+                    payment_attempt.connector.clone(),
+                    choice.connector.to_string(),
+                    currency,
+                    payment_intent.amount,
+                ),
+                None => self.skipped_payment_count += 1,
+            },
+            Err(error) => {
+                logger::debug!(
+                    ?error,
+                    payment_id = payment_attempt.payment_id.get_string_repr(),
+                    "Failed to replay payment against the routing algorithm"
+                );
+                self.skipped_payment_count += 1;
+            }
+        }
+    }
+
+    /// Adds a replayed payment to the totals, along with the rule of the draft algorithm it
+    /// matched, absent if it was routed by the default selection
+    fn record_payment(
+        &mut self,
+        rule_name: Option<String>,
+        historical_connector: Option<String>,
+        simulated_connector: String,
+        currency: enums::Currency,
+        amount: common_utils::types::MinorUnit,
+    ) {
+        self.payment_count += 1;
+        self.total_amounts.add(currency, amount);
+
+        match rule_name {
+            Some(rule_name) => *self.rule_matches.entry(rule_name).or_default() += 1,
+            None => self.default_selection_count += 1,
+        }
+
+        if let Some(connector) = &historical_connector {
+            let summary = self.connectors.entry(connector.clone()).or_default();
+            summary.historical_payment_count += 1;
+            summary.historical_amounts.add(currency, amount);
+        }
+
+        let summary = self
+            .connectors
+            .entry(simulated_connector.clone())
+            .or_default();
+        summary.simulated_payment_count += 1;
+        summary.simulated_amounts.add(currency, amount);
+
+        if historical_connector.as_ref() != Some(&simulated_connector) {
+            self.rerouted_payment_count += 1;
+            self.rerouted_amounts.add(currency, amount);
+
+            let route_change = self
+                .route_changes
+                .entry((historical_connector, simulated_connector))
+                .or_default();
+            route_change.payment_count += 1;
+            route_change.amounts.add(currency, amount);
+        }
+    }
+
+    fn into_response(self, rule_names: Vec<String>) -> routing_types::RoutingSimulationResponse {
+        let mut connectors = self
+            .connectors
+            .into_iter()
+            .map(
+                |(connector, summary)| routing_types::ConnectorSimulationSummary {
+                    connector,
+                    historical_payment_count: summary.historical_payment_count,
+                    historical_amounts: summary.historical_amounts.into_amounts(),
+                    simulated_payment_count: summary.simulated_payment_count,
+                    simulated_amounts: summary.simulated_amounts.into_amounts(),
+                },
+            )
+            .collect::<Vec<_>>();
+        connectors.sort_by(|a, b| a.connector.cmp(&b.connector));
+
+        let mut route_changes = self
+            .route_changes
+            .into_iter()
+            .map(|((historical_connector, simulated_connector), totals)| {
+                routing_types::RouteChange {
+                    historical_connector,
+                    simulated_connector,
+                    payment_count: totals.payment_count,
+                    total_amounts: totals.amounts.into_amounts(),
+                }
+            })
+            .collect::<Vec<_>>();
+        route_changes.sort_by(|a, b| b.payment_count.cmp(&a.payment_count));
+
+        let (rule_matches, unmatched_rules) = rule_names.into_iter().fold(
+            (Vec::new(), Vec::new()),
+            |(mut rule_matches, mut unmatched_rules), rule_name| {
+                match self.rule_matches.get(&rule_name) {
+                    Some(&payment_count) => rule_matches.push(routing_types::RuleMatchCount {
+                        rule_name,
+                        payment_count,
+                    }),
+                    None => unmatched_rules.push(rule_name),
+                }
+                (rule_matches, unmatched_rules)
+            },
+        );
+
+        routing_types::RoutingSimulationResponse {
+            payment_count: self.payment_count,
+            total_amounts: self.total_amounts.into_amounts(),
+            rerouted_payment_count: self.rerouted_payment_count,
+            rerouted_amounts: self.rerouted_amounts.into_amounts(),
+            default_selection_count: self.default_selection_count,
+            skipped_payment_count: self.skipped_payment_count,
+            connectors,
+            route_changes,
+            rule_matches,
+            unmatched_rules,
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "olap",
+    any(feature = "v1", feature = "v2"),
+    not(feature = "routing_v2")
+))]
+mod tests {
+    use common_utils::types::MinorUnit;
+
+    use super::*;
+
+    fn amount(currency: enums::Currency, amount: i64) -> routing_types::CurrencyAmount {
+        routing_types::CurrencyAmount {
+            currency,
+            amount: MinorUnit::new(amount),
+        }
+    }
+
+    #[test]
+    fn test_routing_simulation_totals_are_grouped_by_currency() {
+        let mut simulation = RoutingSimulation::default();
+        simulation.record_payment(
+            Some("rule_1".to_string()),
+            Some("stripe".to_string()),
+            "stripe".to_string(),
+            enums::Currency::USD,
+            MinorUnit::new(100),
+        );
+        simulation.record_payment(
+            Some("rule_1".to_string()),
+            Some("stripe".to_string()),
+            "adyen".to_string(),
+            enums::Currency::EUR,
+            MinorUnit::new(200),
+        );
+        simulation.record_payment(
+            None,
+            None,
+            "adyen".to_string(),
+            enums::Currency::USD,
+            MinorUnit::new(300),
+        );
+        simulation.skipped_payment_count += 1;
+
+        let response = simulation.into_response(vec!["rule_1".to_string(), "rule_2".to_string()]);
+
+        assert_eq!(response.payment_count, 3);
+        assert_eq!(response.skipped_payment_count, 1);
+        assert_eq!(response.default_selection_count, 1);
+        assert_eq!(
+            response.total_amounts,
+            vec![
+                amount(enums::Currency::EUR, 200),
+                amount(enums::Currency::USD, 400)
+            ]
+        );
+        assert_eq!(response.rerouted_payment_count, 2);
+        assert_eq!(
+            response.rerouted_amounts,
+            vec![
+                amount(enums::Currency::EUR, 200),
+                amount(enums::Currency::USD, 300)
+            ]
+        );
+        assert_eq!(response.unmatched_rules, vec!["rule_2".to_string()]);
+        assert_eq!(
+            response
+                .rule_matches
+                .iter()
+                .map(|rule_match| (rule_match.rule_name.as_str(), rule_match.payment_count))
+                .collect::<Vec<_>>(),
+            vec![("rule_1", 2)]
+        );
+    }
+
+    #[test]
+    fn test_routing_simulation_connector_summaries() {
+        let mut simulation = RoutingSimulation::default();
+        simulation.record_payment(
+            None,
+            Some("stripe".to_string()),
+            "adyen".to_string(),
+            enums::Currency::USD,
+            MinorUnit::new(100),
+        );
+        simulation.record_payment(
+            None,
+            Some("stripe".to_string()),
+            "adyen".to_string(),
+            enums::Currency::USD,
+            MinorUnit::new(50),
+        );
+
+        let response = simulation.into_response(Vec::new());
+
+        let connectors = response
+            .connectors
+            .iter()
+            .map(|summary| {
+                (
+                    summary.connector.as_str(),
+                    summary.historical_payment_count,
+                    summary.simulated_payment_count,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(connectors, vec![("adyen", 0, 2), ("stripe", 2, 0)]);
+
+        let route_changes = response
+            .route_changes
+            .iter()
+            .map(|change| {
+                (
+                    change.historical_connector.as_deref(),
+                    change.simulated_connector.as_str(),
+                    change.payment_count,
+                    change.total_amounts.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            route_changes,
+            vec![(
+                Some("stripe"),
+                "adyen",
+                2,
+                vec![amount(enums::Currency::USD, 150)]
+            )]
+        );
+    }
+}
@@ -12,8 +12,12 @@ use storage_impl::redis::cache;
 #[cfg(all(feature = "v2", feature = "routing_v2"))]
 use crate::types::domain::MerchantConnectorAccount;
 use crate::{
-    core::errors::{self, RouterResult},
+    core::{
+        errors::{self, RouterResult},
+        payments,
+    },
     db::StorageInterface,
+    logger,
     routes::SessionState,
     types::{domain, storage},
     utils::StringExt,
@@ -365,6 +369,12 @@ impl<'h> RoutingAlgorithmHelpers<'h> {
                 }
             }
 
+            routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+                for choice in &config.connectors {
+                    self.connector_choice(choice)?;
+                }
+            }
+
             routing_types::RoutingAlgorithm::Advanced(program) => {
                 let check_connector_selection =
                     |selection: &routing_types::ConnectorSelection| -> RouterResult<()> {
@@ -473,6 +483,12 @@ pub async fn validate_connectors_in_routing_config(
             }
         }
 
+        routing_types::RoutingAlgorithm::SuccessRateBased(config) => {
+            for choice in &config.connectors {
+                connector_choice(choice)?;
+            }
+        }
+
         routing_types::RoutingAlgorithm::Advanced(program) => {
             let check_connector_selection =
                 |selection: &routing_types::ConnectorSelection| -> RouterResult<()> {
@@ -504,6 +520,54 @@ pub async fn validate_connectors_in_routing_config(
     Ok(())
 }
 
+/// Analyses the rules of an advanced routing algorithm against the constraint graph of the
+/// merchant's connectors, returning the warnings about rules that do not behave the way they were
+/// likely meant to. The warnings do not prevent the algorithm from being saved, so failures of the
+/// analysis are only logged, and no warnings are returned.
+pub async fn get_routing_analysis_warnings(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    profile_id: &common_utils::id_type::ProfileId,
+    routing_algorithm: &routing_types::RoutingAlgorithm,
+    transaction_type: &storage::enums::TransactionType,
+) -> Option<Vec<routing_types::RoutingAnalysisWarning>> {
+    let routing_types::RoutingAlgorithm::Advanced(program) = routing_algorithm else {
+        return None;
+    };
+
+    let cgraph = match payments::routing::get_merchant_cgraph(
+        state,
+        key_store,
+        profile_id.to_owned(),
+        transaction_type,
+    )
+    .await
+    {
+        Ok(cgraph) => cgraph,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to fetch the constraint graph of the merchant's connectors for analysing the routing algorithm"
+            );
+            return None;
+        }
+    };
+
+    let warnings = euclid::frontend::ast::lowering::lower_program(program.clone())
+        .and_then(|dir_program| {
+            euclid::dssa::analyzer::analyze_warnings(&dir_program, Some(cgraph.as_ref()))
+        })
+        .map_err(|error| {
+            logger::error!(
+                ?error,
+                "Failed to analyze the routing algorithm for warnings"
+            );
+        })
+        .ok();
+
+    warnings.map(|warnings| warnings.into_iter().map(Into::into).collect())
+}
+
 /// Provides the identifier for the specific merchant's routing_dictionary_key
 #[inline(always)]
 pub fn get_routing_dictionary_key(merchant_id: &str) -> String {
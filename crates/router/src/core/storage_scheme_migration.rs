@@ -0,0 +1,829 @@
+//! Migrates the live data of a merchant between the `PostgresOnly` and `RedisKv` storage schemes.
+//!
+//! Switching to `PostgresOnly` waits for the drainer to apply the Redis KV entries of the merchant
+//! to Postgres after switching, so that reads served from Postgres are not stale once the
+//! migration completes.
+//! Switching to `RedisKv` loads the recent Postgres rows of the merchant into the Redis KV hashes
+//! after switching, so that reads served from Redis do not miss them, and then overwrites the
+//! entries that were updated in Postgres by requests still using `PostgresOnly` while loading.
+//!
+//! The migration is run by the scheduler as a process tracker task, and its progress is stored in
+//! Redis. A migration holds a lease on the merchant while it runs, which is renewed periodically,
+//! so that only one migration runs for a merchant at a time, and a migration whose scheduler
+//! stopped is reported as failed once its lease expires.
+
+use std::collections::HashMap;
+
+use api_models::kv_consistency::{
+    KvDivergence, KvDivergenceResolution, StorageSchemeMigrationRequest,
+    StorageSchemeMigrationStage, StorageSchemeMigrationStatus,
+};
+use common_enums::MerchantStorageScheme;
+use common_utils::{date_time, ext_traits::StringExt, id_type};
+use error_stack::{report, Report, ResultExt};
+use router_env::{instrument, logger, metrics::add_attributes, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        kv_consistency,
+    },
+    db::{kv_consistency::KvEntry, StorageInterface},
+    routes::{metrics, SessionState},
+    services::ApplicationResponse,
+    types::{domain, storage},
+};
+
+const STORAGE_SCHEME_MIGRATION_TAG: &str = "STORAGE_SCHEME_MIGRATION";
+const STORAGE_SCHEME_MIGRATION_NAME: &str = "STORAGE_SCHEME_MIGRATION";
+const STORAGE_SCHEME_MIGRATION_RUNNER: diesel_models::ProcessTrackerRunner =
+    diesel_models::ProcessTrackerRunner::StorageSchemeMigrationWorkflow;
+
+/// Time in seconds the progress of a migration is retained for
+const MIGRATION_STATUS_TTL: i64 = 7 * 24 * 60 * 60;
+/// Time in seconds the lease of a migration is held for without being renewed, which covers the
+/// time taken by the scheduler to pick the migration up
+const MIGRATION_LEASE_TTL: i64 = 5 * 60;
+const MIGRATION_LEASE_RENEWAL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const DEFAULT_DRAIN_TIMEOUT: u64 = 10 * 60;
+const DRAIN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+/// Number of warmed entries after which the progress of the migration is saved
+const WARM_PROGRESS_INTERVAL: usize = 100;
+
+/// Tracking data of the process tracker task running a migration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StorageSchemeMigrationTrackingData {
+    pub merchant_id: id_type::MerchantId,
+    pub storage_scheme: MerchantStorageScheme,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub warm_created_gte: time::PrimitiveDateTime,
+    pub drain_timeout: u64,
+}
+
+fn get_migration_status_key(merchant_id: &id_type::MerchantId) -> String {
+    format!("storage_scheme_migration_{}", merchant_id.get_string_repr())
+}
+
+fn get_migration_lease_key(merchant_id: &id_type::MerchantId) -> String {
+    format!(
+        "storage_scheme_migration_lease_{}",
+        merchant_id.get_string_repr()
+    )
+}
+
+/// Acquires the lease on the merchant for the migration, unless another migration holds it.
+/// Returns whether the lease was acquired.
+async fn acquire_migration_lease(
+    state: &SessionState,
+    merchant_id: &id_type::MerchantId,
+    migration_id: &str,
+) -> RouterResult<bool> {
+    let reply = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?
+        .set_key_if_not_exists_with_expiry(
+            &get_migration_lease_key(merchant_id),
+            migration_id,
+            Some(MIGRATION_LEASE_TTL),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to acquire the storage scheme migration lease")?;
+
+    Ok(reply == redis_interface::SetnxReply::KeySet)
+}
+
+/// Returns the ID of the migration holding the lease on the merchant
+async fn find_migration_lease_holder(
+    state: &SessionState,
+    merchant_id: &id_type::MerchantId,
+) -> RouterResult<Option<String>> {
+    state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?
+        .get_key::<Option<String>>(&get_migration_lease_key(merchant_id))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the storage scheme migration lease")
+}
+
+/// Renews the lease of the migration until it is lost, which happens if the lease could not be
+/// renewed before it expired and another migration acquired it
+async fn renew_migration_lease(
+    state: &SessionState,
+    merchant_id: &id_type::MerchantId,
+    migration_id: &str,
+) -> Report<errors::ApiErrorResponse> {
+    let lease_key = get_migration_lease_key(merchant_id);
+
+    loop {
+        tokio::time::sleep(MIGRATION_LEASE_RENEWAL_INTERVAL).await;
+
+        let holder = match find_migration_lease_holder(state, merchant_id).await {
+            Ok(holder) => holder,
+            Err(error) => {
+                logger::error!(?error, "Failed to renew the storage scheme migration lease");
+                continue;
+            }
+        };
+        if holder.as_deref() != Some(migration_id) {
+            return report!(errors::ApiErrorResponse::InternalServerError).attach_printable(
+                "The migration lost its lease, and may have been superseded by another one",
+            );
+        }
+
+        let renewed = match state.store.get_redis_conn() {
+            Ok(redis_conn) => redis_conn.set_expiry(&lease_key, MIGRATION_LEASE_TTL).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = renewed {
+            logger::error!(?error, "Failed to renew the storage scheme migration lease");
+        }
+    }
+}
+
+async fn release_migration_lease(state: &SessionState, merchant_id: &id_type::MerchantId) {
+    let released = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn
+            .delete_key(&get_migration_lease_key(merchant_id))
+            .await
+            .map(|_| ()),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = released {
+        logger::error!(
+            ?error,
+            "Failed to release the storage scheme migration lease"
+        );
+    }
+}
+
+async fn find_migration_status(
+    state: &SessionState,
+    merchant_id: &id_type::MerchantId,
+) -> RouterResult<Option<StorageSchemeMigrationStatus>> {
+    state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?
+        .get_key::<Option<String>>(&get_migration_status_key(merchant_id))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the storage scheme migration status")?
+        .map(|status| {
+            status
+                .parse_struct::<StorageSchemeMigrationStatus>("StorageSchemeMigrationStatus")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+        })
+        .transpose()
+}
+
+async fn save_migration_status(
+    state: &SessionState,
+    status: &mut StorageSchemeMigrationStatus,
+) -> RouterResult<()> {
+    status.updated_at = date_time::now();
+
+    state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?
+        .serialize_and_set_key_with_expiry(
+            &get_migration_status_key(&status.merchant_id),
+            &*status,
+            MIGRATION_STATUS_TTL,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to save the storage scheme migration status")
+}
+
+#[instrument(skip_all)]
+pub async fn start_storage_scheme_migration(
+    state: SessionState,
+    req: StorageSchemeMigrationRequest,
+) -> RouterResponse<StorageSchemeMigrationStatus> {
+    let db = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            key_manager_state,
+            &req.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    // The merchant account is fetched again when the migration runs
+    db.find_merchant_account_by_merchant_id(key_manager_state, &req.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    if req.storage_scheme == MerchantStorageScheme::RedisKv
+        && state.conf.as_ref().is_kv_soft_kill_mode()
+    {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Kv cannot be enabled when application is in soft_kill_mode".to_owned(),
+        })?
+    }
+
+    let migration_id = common_utils::generate_id(consts::ID_LENGTH, "migration");
+    if !acquire_migration_lease(&state, &req.merchant_id, &migration_id).await? {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "A storage scheme migration is already in progress for the merchant"
+                .to_owned(),
+        })?
+    }
+
+    let now = date_time::now();
+    let mut status = StorageSchemeMigrationStatus {
+        migration_id: migration_id.clone(),
+        merchant_id: req.merchant_id.clone(),
+        storage_scheme: req.storage_scheme,
+        stage: match req.storage_scheme {
+            MerchantStorageScheme::PostgresOnly => StorageSchemeMigrationStage::Draining,
+            MerchantStorageScheme::RedisKv => StorageSchemeMigrationStage::Warming,
+        },
+        pending_entries: 0,
+        dead_lettered_entries: 0,
+        warmed_entries: 0,
+        repaired_entries: 0,
+        error: None,
+        started_at: now,
+        updated_at: now,
+    };
+    let tracking_data = StorageSchemeMigrationTrackingData {
+        merchant_id: req.merchant_id,
+        storage_scheme: req.storage_scheme,
+        warm_created_gte: req.warm_created_gte.unwrap_or_else(|| {
+            now.saturating_sub(time::Duration::seconds(i64::from(state.conf.kv_config.ttl)))
+        }),
+        drain_timeout: req.drain_timeout.unwrap_or(DEFAULT_DRAIN_TIMEOUT),
+    };
+
+    if let Err(error) = schedule_migration(&state, &mut status, tracking_data).await {
+        fail_migration(&state, &mut status, get_error_message(&error)).await;
+        release_migration_lease(&state, &status.merchant_id).await;
+        return Err(error);
+    }
+
+    Ok(ApplicationResponse::Json(status))
+}
+
+async fn schedule_migration(
+    state: &SessionState,
+    status: &mut StorageSchemeMigrationStatus,
+    tracking_data: StorageSchemeMigrationTrackingData,
+) -> RouterResult<()> {
+    save_migration_status(state, status).await?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        status.migration_id.clone(),
+        STORAGE_SCHEME_MIGRATION_NAME,
+        STORAGE_SCHEME_MIGRATION_RUNNER,
+        [STORAGE_SCHEME_MIGRATION_TAG],
+        tracking_data,
+        date_time::now(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct storage scheme migration process tracker task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the storage scheme migration")?;
+    metrics::TASKS_ADDED_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &add_attributes([("flow", "StorageSchemeMigration")]),
+    );
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_storage_scheme_migration(
+    state: SessionState,
+    merchant_id: id_type::MerchantId,
+) -> RouterResponse<StorageSchemeMigrationStatus> {
+    let mut status = find_migration_status(&state, &merchant_id)
+        .await?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: "No storage scheme migration was found for the merchant".to_owned(),
+            })
+        })?;
+
+    // The lease of a migration expires if the scheduler running it stopped
+    if status.stage.is_in_progress()
+        && find_migration_lease_holder(&state, &merchant_id)
+            .await?
+            .as_deref()
+            != Some(status.migration_id.as_str())
+    {
+        let message = "The migration stopped before completing, and can be started again";
+        fail_migration(&state, &mut status, message.to_owned()).await;
+    }
+
+    Ok(ApplicationResponse::Json(status))
+}
+
+/// Runs the migration of a process tracker task, while holding the lease on the merchant
+#[instrument(skip_all)]
+pub async fn run_storage_scheme_migration(
+    state: &SessionState,
+    migration_id: &str,
+    tracking_data: &StorageSchemeMigrationTrackingData,
+) -> RouterResult<()> {
+    let merchant_id = &tracking_data.merchant_id;
+
+    let Some(mut status) = find_migration_status(state, merchant_id)
+        .await?
+        .filter(|status| status.migration_id == migration_id && status.stage.is_in_progress())
+    else {
+        logger::warn!(%migration_id, "Skipping storage scheme migration that is not in progress");
+        return Ok(());
+    };
+
+    // The lease expires if the scheduler took longer than its time-to-live to pick the
+    // migration up, in which case it is acquired again, unless another migration has been
+    // started since
+    let holds_lease = match find_migration_lease_holder(state, merchant_id).await? {
+        Some(holder) => holder == migration_id,
+        None => acquire_migration_lease(state, merchant_id, migration_id).await?,
+    };
+    if !holds_lease {
+        let message = "Another storage scheme migration was started for the merchant";
+        fail_migration(state, &mut status, message.to_owned()).await;
+        return Ok(());
+    }
+
+    let result = tokio::select! {
+        result = migrate(state, tracking_data, &mut status) => result,
+        error = renew_migration_lease(state, merchant_id, migration_id) => Err(error),
+    };
+
+    if let Err(error) = result {
+        logger::error!(?error, "Storage scheme migration failed");
+        fail_migration(state, &mut status, get_error_message(&error)).await;
+    }
+    release_migration_lease(state, merchant_id).await;
+
+    Ok(())
+}
+
+async fn migrate(
+    state: &SessionState,
+    tracking_data: &StorageSchemeMigrationTrackingData,
+    status: &mut StorageSchemeMigrationStatus,
+) -> RouterResult<()> {
+    let db = state.store.as_ref();
+    let key_manager_state = &state.into();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            key_manager_state,
+            &tracking_data.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the key store of the merchant")?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(
+            key_manager_state,
+            &tracking_data.merchant_id,
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the merchant account")?;
+
+    match tracking_data.storage_scheme {
+        MerchantStorageScheme::PostgresOnly => {
+            migrate_to_postgres_only(
+                state,
+                merchant_account,
+                &key_store,
+                tracking_data.drain_timeout,
+                status,
+            )
+            .await
+        }
+        MerchantStorageScheme::RedisKv => {
+            migrate_to_redis_kv(
+                state,
+                merchant_account,
+                &key_store,
+                tracking_data.warm_created_gte,
+                status,
+            )
+            .await
+        }
+    }
+}
+
+async fn update_storage_scheme(
+    state: &SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    storage_scheme: MerchantStorageScheme,
+) -> RouterResult<()> {
+    if merchant_account.storage_scheme == storage_scheme {
+        return Ok(());
+    }
+
+    state
+        .store
+        .update_merchant(
+            &state.into(),
+            merchant_account,
+            storage::MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme },
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to switch the storage scheme of the merchant")?;
+
+    Ok(())
+}
+
+async fn migrate_to_postgres_only(
+    state: &SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    drain_timeout: u64,
+    status: &mut StorageSchemeMigrationStatus,
+) -> RouterResult<()> {
+    // The storage scheme is switched first, so that no new entries are written to the Redis KV
+    // store while draining, other than by requests in flight
+    update_storage_scheme(
+        state,
+        merchant_account,
+        key_store,
+        MerchantStorageScheme::PostgresOnly,
+    )
+    .await?;
+
+    // Requests that fetched the merchant account before the switch may still write to the Redis KV
+    // store, the drain covers their entries too
+    if !wait_for_drain(state, drain_timeout, status).await? {
+        let message = format!(
+            "{} after switching the storage scheme of the merchant",
+            get_drain_failure_message(status, drain_timeout)
+        );
+        fail_migration(state, status, message).await;
+        return Ok(());
+    }
+
+    status.stage = StorageSchemeMigrationStage::Completed;
+    save_migration_status(state, status).await
+}
+
+fn get_drain_failure_message(status: &StorageSchemeMigrationStatus, drain_timeout: u64) -> String {
+    if status.dead_lettered_entries > 0 {
+        format!(
+            "{} Redis KV entries are held by the drainer behind entries in its dead letter \
+             stream, which have to be replayed or discarded",
+            status.dead_lettered_entries
+        )
+    } else {
+        format!(
+            "{} Redis KV entries were not applied to Postgres within {drain_timeout} seconds",
+            status.pending_entries
+        )
+    }
+}
+
+/// Waits for the drainer to apply the Redis KV entries of the merchant to Postgres. Returns
+/// whether all the entries were applied within the timeout. Stops waiting early if the only
+/// entries left are held behind dead letter entries, since the drainer does not apply them
+/// until the dead letter entries are replayed.
+async fn wait_for_drain(
+    state: &SessionState,
+    drain_timeout: u64,
+    status: &mut StorageSchemeMigrationStatus,
+) -> RouterResult<bool> {
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(drain_timeout);
+
+    loop {
+        let consistency = kv_consistency::find_kv_divergences(
+            state.store.as_ref(),
+            &status.merchant_id,
+            None,
+            None,
+            false,
+        )
+        .await?;
+        (status.pending_entries, status.dead_lettered_entries) =
+            count_undrained_entries(state.store.as_ref(), &consistency.divergences).await?;
+        save_migration_status(state, status).await?;
+
+        if status.pending_entries == 0 {
+            return Ok(status.dead_lettered_entries == 0);
+        }
+        if tokio::time::Instant::now() + DRAIN_POLL_INTERVAL > deadline {
+            return Ok(false);
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
+/// Returns the number of Redis KV entries the drainer is yet to apply, and the number of those
+/// held behind dead letter entries
+async fn count_undrained_entries(
+    db: &dyn StorageInterface,
+    divergences: &[KvDivergence],
+) -> RouterResult<(usize, usize)> {
+    let mut dead_lettered_partition_keys = HashMap::new();
+    let (mut pending_entries, mut dead_lettered_entries) = (0, 0);
+
+    for divergence in divergences
+        .iter()
+        .filter(|divergence| divergence.resolution == KvDivergenceResolution::PendingDrain)
+    {
+        let dead_lettered = match dead_lettered_partition_keys.get(&divergence.partition_key) {
+            Some(dead_lettered) => *dead_lettered,
+            None => {
+                let dead_lettered = db
+                    .is_kv_partition_key_dead_lettered(&divergence.partition_key)
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to check the drainer dead letter stream")?;
+                dead_lettered_partition_keys.insert(&divergence.partition_key, dead_lettered);
+                dead_lettered
+            }
+        };
+
+        if dead_lettered {
+            dead_lettered_entries += 1;
+        } else {
+            pending_entries += 1;
+        }
+    }
+
+    Ok((pending_entries, dead_lettered_entries))
+}
+
+async fn migrate_to_redis_kv(
+    state: &SessionState,
+    merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    warm_created_gte: time::PrimitiveDateTime,
+    status: &mut StorageSchemeMigrationStatus,
+) -> RouterResult<()> {
+    // Switching before warming lets writes land in Redis, which warming never overwrites
+    update_storage_scheme(
+        state,
+        merchant_account,
+        key_store,
+        MerchantStorageScheme::RedisKv,
+    )
+    .await?;
+
+    let entries = state
+        .store
+        .find_kv_entries_in_database_by_merchant_id(&status.merchant_id, warm_created_gte)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the Postgres rows to load into the Redis KV store")?;
+
+    for entry in entries {
+        let warmed = warm_kv_entry(state.store.as_ref(), &entry, state.conf.kv_config.ttl).await?;
+
+        if warmed {
+            status.warmed_entries += 1;
+            if status.warmed_entries % WARM_PROGRESS_INTERVAL == 0 {
+                save_migration_status(state, status).await?;
+            }
+        }
+    }
+
+    status.stage = StorageSchemeMigrationStage::Verifying;
+    save_migration_status(state, status).await?;
+
+    let consistency = kv_consistency::find_kv_divergences(
+        state.store.as_ref(),
+        &status.merchant_id,
+        Some(warm_created_gte),
+        None,
+        true,
+    )
+    .await?;
+    for divergence in consistency.divergences {
+        match divergence.resolution {
+            KvDivergenceResolution::Repaired => status.repaired_entries += 1,
+            KvDivergenceResolution::PendingDrain => status.pending_entries += 1,
+            KvDivergenceResolution::StaleInRedis | KvDivergenceResolution::UpdatedConcurrently => {}
+        }
+    }
+
+    status.stage = StorageSchemeMigrationStage::Completed;
+    save_migration_status(state, status).await
+}
+
+/// Loads the Postgres row into the Redis KV store, along with the reverse lookups needed to find
+/// it by its other IDs. Returns whether the row was loaded, which it is not if the Redis KV store
+/// already holds it.
+async fn warm_kv_entry(db: &dyn StorageInterface, entry: &KvEntry, ttl: u32) -> RouterResult<bool> {
+    let warmed = db
+        .insert_kv_entry_if_not_exists(entry, ttl)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to load a Postgres row into the Redis KV store")?;
+
+    // The reverse lookups are inserted even if the row was already loaded, since a previous
+    // migration may have stopped after loading the row
+    for reverse_lookup in entry.get_reverse_lookups() {
+        match db
+            .insert_reverse_lookup(reverse_lookup, MerchantStorageScheme::RedisKv)
+            .await
+        {
+            Ok(_) => {}
+            Err(error)
+                if error.current_context().is_db_unique_violation()
+                    || matches!(
+                        error.current_context(),
+                        errors::StorageError::DuplicateValue { .. }
+                    ) => {}
+            Err(error) => {
+                return Err(error)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to insert the reverse lookup of a Postgres row")
+            }
+        }
+    }
+
+    Ok(warmed)
+}
+
+async fn fail_migration(
+    state: &SessionState,
+    status: &mut StorageSchemeMigrationStatus,
+    message: String,
+) {
+    status.stage = StorageSchemeMigrationStage::Failed;
+    status.error = Some(message);
+
+    if let Err(error) = save_migration_status(state, status).await {
+        logger::error!(?error, "Failed to save the status of the failed migration");
+    }
+}
+
+/// Returns the most recent message attached to the error, since the API error itself does not
+/// describe the step of the migration that failed
+fn get_error_message(error: &Report<errors::ApiErrorResponse>) -> String {
+    error
+        .frames()
+        .find_map(|frame| {
+            frame
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| frame.downcast_ref::<String>().cloned())
+        })
+        .unwrap_or_else(|| error.current_context().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use api_models::kv_consistency::{KvDivergenceKind, KvEntity};
+    use serde_json::json;
+    use storage_impl::MockDb;
+
+    use super::*;
+
+    fn get_divergence(resolution: KvDivergenceResolution) -> KvDivergence {
+        KvDivergence {
+            partition_key: "mid_merchant_1_pid_pay_1".to_owned(),
+            field: "pa_pay_1_1".to_owned(),
+            entity: KvEntity::PaymentAttempt,
+            kind: KvDivergenceKind::Mismatch,
+            mismatched_fields: vec!["status".to_owned()],
+            resolution,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warming_inserts_reverse_lookups() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let refund: diesel_models::Refund = serde_json::from_value(json!({
+            "internal_reference_id": "refid_1",
+            "refund_id": "ref_1",
+            "payment_id": "pay_1",
+            "merchant_id": "merchant_1",
+            "connector_transaction_id": "txn_1",
+            "connector": "stripe",
+            "connector_refund_id": "re_1",
+            "refund_type": "instant_refund",
+            "total_amount": 100,
+            "currency": "USD",
+            "refund_amount": 100,
+            "refund_status": "success",
+            "sent_to_gateway": true,
+            "created_at": "2024-01-01T10:00:00.000Z",
+            "modified_at": "2024-01-01T10:00:00.000Z",
+            "attempt_id": "pay_1_1",
+            "updated_by": "postgres_only",
+            "organization_id": "org_1",
+        }))
+        .unwrap();
+        let entry = KvEntry::Refund(refund);
+
+        assert!(warm_kv_entry(&db, &entry, 900).await.unwrap());
+        assert!(!warm_kv_entry(&db, &entry, 900).await.unwrap());
+
+        let (partition_key, field) = entry.get_partition_key_and_field().unwrap();
+        assert!(db
+            .kv_hashes
+            .lock()
+            .await
+            .get(&partition_key)
+            .is_some_and(|kv_hash| kv_hash.contains_key(&field)));
+
+        let reverse_lookups = db.reverse_lookups.lock().await;
+        for lookup_id in [
+            "ref_ref_id_merchant_1_ref_1",
+            "ref_inter_ref_merchant_1_refid_1",
+            "ref_connector_merchant_1_re_1_stripe",
+        ] {
+            let reverse_lookup = reverse_lookups
+                .iter()
+                .find(|reverse_lookup| reverse_lookup.lookup_id == lookup_id)
+                .unwrap();
+            assert_eq!(reverse_lookup.pk_id, partition_key);
+            assert_eq!(reverse_lookup.sk_id, field);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_pending_entries_are_counted_as_undrained() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let divergences = [
+            get_divergence(KvDivergenceResolution::PendingDrain),
+            get_divergence(KvDivergenceResolution::PendingDrain),
+            get_divergence(KvDivergenceResolution::StaleInRedis),
+        ];
+
+        assert_eq!(
+            count_undrained_entries(&db, &divergences).await.unwrap(),
+            (2, 0)
+        );
+    }
+
+    #[test]
+    fn test_drain_failure_message() {
+        let now = date_time::now();
+        let mut status = StorageSchemeMigrationStatus {
+            migration_id: "migration_1".to_owned(),
+            merchant_id: id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1"))
+                .unwrap(),
+            storage_scheme: MerchantStorageScheme::PostgresOnly,
+            stage: StorageSchemeMigrationStage::Draining,
+            pending_entries: 3,
+            dead_lettered_entries: 0,
+            warmed_entries: 0,
+            repaired_entries: 0,
+            error: None,
+            started_at: now,
+            updated_at: now,
+        };
+        assert_eq!(
+            get_drain_failure_message(&status, 60),
+            "3 Redis KV entries were not applied to Postgres within 60 seconds"
+        );
+
+        status.pending_entries = 0;
+        status.dead_lettered_entries = 2;
+        assert!(get_drain_failure_message(&status, 60).contains("dead letter stream"));
+    }
+
+    #[test]
+    fn test_error_message_is_the_most_recent_attachment() {
+        let error = report!(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the merchant account")
+            .attach_printable(String::from("Failed to switch the storage scheme"));
+        assert_eq!(
+            get_error_message(&error),
+            "Failed to switch the storage scheme"
+        );
+
+        let error = report!(errors::ApiErrorResponse::InternalServerError);
+        assert_eq!(
+            get_error_message(&error),
+            errors::ApiErrorResponse::InternalServerError.to_string()
+        );
+    }
+}
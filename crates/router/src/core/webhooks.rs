@@ -6,7 +6,9 @@ pub mod utils;
 pub mod webhook_events;
 
 pub(crate) use self::{
-    incoming::incoming_webhooks_wrapper,
+    incoming::{
+        incoming_webhooks_wrapper, perform_queued_payment_webhook, QueuedPaymentWebhookTrackingData,
+    },
     outgoing::{
         create_event_and_trigger_outgoing_webhook, get_outgoing_webhook_request,
         trigger_webhook_and_raise_event,
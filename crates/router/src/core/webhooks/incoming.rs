@@ -51,6 +51,9 @@ use crate::{
 #[cfg(feature = "payouts")]
 use crate::{core::payouts, types::storage::PayoutAttemptUpdate};
 
+const QUEUED_PAYMENT_WEBHOOK_TASK: &str = "QUEUED_PAYMENT_WEBHOOK";
+const QUEUED_PAYMENT_WEBHOOK_TAG: &str = "PAYMENT_WEBHOOK";
+
 #[allow(clippy::too_many_arguments)]
 pub async fn incoming_webhooks_wrapper<W: types::OutgoingWebhookType>(
     flow: &impl router_env::types::FlowMetric,
@@ -503,95 +506,369 @@ async fn payments_incoming_webhook_flow(
     webhook_details: api::IncomingWebhookDetails,
     source_verified: bool,
 ) -> CustomResult<WebhookResponseTracker, errors::ApiErrorResponse> {
-    let consume_or_trigger_flow = if source_verified {
-        payments::CallConnectorAction::HandleResponse(webhook_details.resource_object)
-    } else {
-        payments::CallConnectorAction::Trigger
+    let payment_id_type = match webhook_details.object_reference_id {
+        webhooks::ObjectReferenceId::PaymentId(id) => id,
+        _ => Err(errors::ApiErrorResponse::WebhookProcessingFailure).attach_printable(
+            "Did not get payment id as object reference id in webhook payments flow",
+        )?,
+    };
+
+    let payment_id = get_payment_id(
+        state.store.as_ref(),
+        &payment_id_type,
+        merchant_account.get_id(),
+        merchant_account.storage_scheme,
+    )
+    .await?;
+
+    // Webhooks received while the payment is locked by another request are queued as process
+    // tracker tasks, which process them once the lock is free. Webhooks received while earlier
+    // webhooks of the payment are still queued are queued behind them, to preserve their order.
+    let lock_action = get_payment_webhook_lock_action(&payment_id);
+
+    let work_item = PaymentWebhookWorkItem {
+        payment_id: payment_id_type,
+        resource_object: source_verified
+            .then(|| masking::Secret::new(webhook_details.resource_object)),
+    };
+
+    let outcome = lock_action
+        .perform_queued_action(
+            &state,
+            merchant_account.get_id().to_owned(),
+            work_item,
+            |work_item| {
+                Box::pin(perform_payment_webhook_work_item_in_order(
+                    state.clone(),
+                    req_state.clone(),
+                    merchant_account.clone(),
+                    business_profile.clone(),
+                    key_store.clone(),
+                    payment_id.clone(),
+                    None,
+                    work_item,
+                ))
+            },
+            |work_item| {
+                queue_payment_webhook_work_item(
+                    &state,
+                    merchant_account.get_id(),
+                    business_profile.get_id(),
+                    &payment_id,
+                    work_item,
+                )
+            },
+        )
+        .await?;
+
+    match outcome {
+        api_locking::QueuedActionOutcome::Performed(Some(webhook_response_tracker)) => {
+            Ok(webhook_response_tracker)
+        }
+        api_locking::QueuedActionOutcome::Performed(None)
+        | api_locking::QueuedActionOutcome::Queued => {
+            logger::info!("Incoming webhook queued for payment: {:?}", payment_id);
+            Ok(WebhookResponseTracker::NoEffect)
+        }
+    }
+}
+
+fn get_payment_webhook_lock_action(
+    payment_id: &common_utils::id_type::PaymentId,
+) -> api_locking::LockAction {
+    api_locking::LockAction::QueueWithOk {
+        input: api_locking::LockingInput {
+            unique_locking_key: payment_id.get_string_repr().to_owned(),
+            api_identifier: lock_utils::ApiIdentifier::Payments,
+            override_lock_retries: None,
+        },
+    }
+}
+
+/// Payment webhook to be processed while holding the lock on the payment
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PaymentWebhookWorkItem {
+    payment_id: api_models::payments::PaymentIdType,
+    /// The resource object of the webhook, if its source was verified
+    resource_object: Option<masking::Secret<Vec<u8>>>,
+}
+
+/// Tracking data of the process tracker task processing a queued payment webhook
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct QueuedPaymentWebhookTrackingData {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub profile_id: common_utils::id_type::ProfileId,
+    pub work_item: PaymentWebhookWorkItem,
+}
+
+/// Returns the name shared by the process tracker tasks of the webhooks queued for the payment
+fn get_queued_payment_webhook_process_name(
+    merchant_id: &common_utils::id_type::MerchantId,
+    payment_id: &common_utils::id_type::PaymentId,
+) -> String {
+    format!(
+        "{QUEUED_PAYMENT_WEBHOOK_TASK}_{}_{}",
+        merchant_id.get_string_repr(),
+        payment_id.get_string_repr()
+    )
+}
+
+/// Finds the process tracker task of the earliest webhook of the payment that is still queued
+async fn find_earliest_queued_payment_webhook(
+    state: &SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+    payment_id: &common_utils::id_type::PaymentId,
+) -> CustomResult<Option<storage::ProcessTracker>, errors::ApiErrorResponse> {
+    state
+        .store
+        .find_earliest_pending_process_by_name(&get_queued_payment_webhook_process_name(
+            merchant_id,
+            payment_id,
+        ))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find queued payment webhook process tracker task")
+}
+
+/// Stores the payment webhook as a process tracker task, to be processed once the lock on the
+/// payment is free and the webhooks of the payment queued before it are processed
+#[instrument(skip_all)]
+async fn queue_payment_webhook_work_item(
+    state: &SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+    profile_id: &common_utils::id_type::ProfileId,
+    payment_id: &common_utils::id_type::PaymentId,
+    work_item: PaymentWebhookWorkItem,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let runner = storage::ProcessTrackerRunner::QueuedPaymentWebhookWorkflow;
+    let process_tracker_id = generate_id(consts::ID_LENGTH, &runner.to_string());
+    let tracking_data = QueuedPaymentWebhookTrackingData {
+        merchant_id: merchant_id.to_owned(),
+        profile_id: profile_id.to_owned(),
+        work_item,
     };
-    let payments_response = match webhook_details.object_reference_id {
-        webhooks::ObjectReferenceId::PaymentId(id) => {
-            let payment_id = get_payment_id(
-                state.store.as_ref(),
-                &id,
+
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        get_queued_payment_webhook_process_name(merchant_id, payment_id),
+        runner,
+        [QUEUED_PAYMENT_WEBHOOK_TAG],
+        tracking_data,
+        common_utils::date_time::now(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct queued payment webhook process tracker task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert queued payment webhook process tracker task")?;
+
+    Ok(())
+}
+
+/// Processes a payment webhook queued while the payment was locked, if the lock on the payment
+/// is free and the webhook is the earliest one still queued for the payment. Returns `None`
+/// otherwise, in which case the webhook is to be processed later.
+#[instrument(skip_all)]
+pub async fn perform_queued_payment_webhook(
+    state: SessionState,
+    process_id: &str,
+    tracking_data: QueuedPaymentWebhookTrackingData,
+) -> CustomResult<Option<WebhookResponseTracker>, errors::ApiErrorResponse> {
+    let key_manager_state = &(&state).into();
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            key_manager_state,
+            &tracking_data.merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id(
+            key_manager_state,
+            &tracking_data.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let business_profile = state
+        .store
+        .find_business_profile_by_profile_id(
+            key_manager_state,
+            &key_store,
+            &tracking_data.profile_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+            id: tracking_data.profile_id.get_string_repr().to_owned(),
+        })?;
+
+    let payment_id = get_payment_id(
+        state.store.as_ref(),
+        &tracking_data.work_item.payment_id,
+        merchant_account.get_id(),
+        merchant_account.storage_scheme,
+    )
+    .await?;
+
+    let outcome = get_payment_webhook_lock_action(&payment_id)
+        .perform_queued_action(
+            &state,
+            merchant_account.get_id().to_owned(),
+            tracking_data.work_item,
+            |work_item| {
+                Box::pin(perform_payment_webhook_work_item_in_order(
+                    state.clone(),
+                    state.get_req_state(),
+                    merchant_account.clone(),
+                    business_profile.clone(),
+                    key_store.clone(),
+                    payment_id.clone(),
+                    Some(process_id.to_owned()),
+                    work_item,
+                ))
+            },
+            // The webhook remains queued in its process tracker task
+            |_work_item| async { Ok(()) },
+        )
+        .await?;
+
+    match outcome {
+        api_locking::QueuedActionOutcome::Performed(webhook_response_tracker) => {
+            Ok(webhook_response_tracker)
+        }
+        api_locking::QueuedActionOutcome::Queued => Ok(None),
+    }
+}
+
+/// Performs the payment webhook while holding the lock on the payment, unless earlier webhooks of
+/// the payment are still queued, so that the webhooks of a payment are processed in the order
+/// they were received in.
+///
+/// `queued_process_id` is the ID of the process tracker task of the webhook if it was queued.
+/// A webhook that was not queued is queued behind the earlier webhooks, a queued webhook remains
+/// queued until the webhooks queued before it are processed. Returns `None` if the webhook was
+/// not performed.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+async fn perform_payment_webhook_work_item_in_order(
+    state: SessionState,
+    req_state: ReqState,
+    merchant_account: domain::MerchantAccount,
+    business_profile: domain::BusinessProfile,
+    key_store: domain::MerchantKeyStore,
+    payment_id: common_utils::id_type::PaymentId,
+    queued_process_id: Option<String>,
+    work_item: PaymentWebhookWorkItem,
+) -> CustomResult<Option<WebhookResponseTracker>, errors::ApiErrorResponse> {
+    let earliest_queued_webhook =
+        find_earliest_queued_payment_webhook(&state, merchant_account.get_id(), &payment_id)
+            .await?;
+
+    match (earliest_queued_webhook, queued_process_id) {
+        (Some(_), None) => {
+            queue_payment_webhook_work_item(
+                &state,
                 merchant_account.get_id(),
-                merchant_account.storage_scheme,
+                business_profile.get_id(),
+                &payment_id,
+                work_item,
             )
             .await?;
+            Ok(None)
+        }
+        (Some(earliest_queued_webhook), Some(queued_process_id))
+            if earliest_queued_webhook.id != queued_process_id =>
+        {
+            Ok(None)
+        }
+        (Some(_), Some(_)) | (None, _) => perform_payment_webhook_work_item(
+            state,
+            req_state,
+            merchant_account,
+            business_profile,
+            key_store,
+            work_item,
+        )
+        .await
+        .map(Some),
+    }
+}
 
-            let lock_action = api_locking::LockAction::Hold {
-                input: api_locking::LockingInput {
-                    unique_locking_key: payment_id.get_string_repr().to_owned(),
-                    api_identifier: lock_utils::ApiIdentifier::Payments,
-                    override_lock_retries: None,
-                },
-            };
-
-            lock_action
-                .clone()
-                .perform_locking_action(&state, merchant_account.get_id().to_owned())
-                .await?;
-
-            let response = Box::pin(payments::payments_core::<
-                api::PSync,
-                api::PaymentsResponse,
-                _,
-                _,
-                _,
-            >(
-                state.clone(),
-                req_state,
-                merchant_account.clone(),
-                None,
-                key_store.clone(),
-                payments::operations::PaymentStatus,
-                api::PaymentsRetrieveRequest {
-                    resource_id: id,
-                    merchant_id: Some(merchant_account.get_id().clone()),
-                    force_sync: true,
-                    connector: None,
-                    param: None,
-                    merchant_connector_details: None,
-                    client_secret: None,
-                    expand_attempts: None,
-                    expand_captures: None,
-                },
-                services::AuthFlow::Merchant,
-                consume_or_trigger_flow,
-                None,
-                HeaderPayload::default(),
-            ))
-            .await;
+#[instrument(skip_all)]
+async fn perform_payment_webhook_work_item(
+    state: SessionState,
+    req_state: ReqState,
+    merchant_account: domain::MerchantAccount,
+    business_profile: domain::BusinessProfile,
+    key_store: domain::MerchantKeyStore,
+    work_item: PaymentWebhookWorkItem,
+) -> CustomResult<WebhookResponseTracker, errors::ApiErrorResponse> {
+    let consume_or_trigger_flow = match work_item.resource_object {
+        Some(resource_object) => {
+            payments::CallConnectorAction::HandleResponse(resource_object.expose())
+        }
+        None => payments::CallConnectorAction::Trigger,
+    };
 
-            lock_action
-                .free_lock_action(&state, merchant_account.get_id().to_owned())
-                .await?;
+    let response = Box::pin(payments::payments_core::<
+        api::PSync,
+        api::PaymentsResponse,
+        _,
+        _,
+        _,
+    >(
+        state.clone(),
+        req_state,
+        merchant_account.clone(),
+        None,
+        key_store.clone(),
+        payments::operations::PaymentStatus,
+        api::PaymentsRetrieveRequest {
+            resource_id: work_item.payment_id,
+            merchant_id: Some(merchant_account.get_id().clone()),
+            force_sync: true,
+            connector: None,
+            param: None,
+            merchant_connector_details: None,
+            client_secret: None,
+            expand_attempts: None,
+            expand_captures: None,
+        },
+        services::AuthFlow::Merchant,
+        consume_or_trigger_flow,
+        None,
+        HeaderPayload::default(),
+    ))
+    .await;
 
-            match response {
-                Ok(value) => value,
-                Err(err)
-                    if matches!(
-                        err.current_context(),
-                        &errors::ApiErrorResponse::PaymentNotFound
-                    ) && state
-                        .conf
-                        .webhooks
-                        .ignore_error
-                        .payment_not_found
-                        .unwrap_or(true) =>
-                {
-                    metrics::WEBHOOK_PAYMENT_NOT_FOUND.add(
-                        &metrics::CONTEXT,
-                        1,
-                        &add_attributes([("merchant_id", merchant_account.get_id().clone())]),
-                    );
-                    return Ok(WebhookResponseTracker::NoEffect);
-                }
-                error @ Err(_) => error?,
-            }
+    let payments_response = match response {
+        Ok(value) => value,
+        Err(err)
+            if matches!(
+                err.current_context(),
+                &errors::ApiErrorResponse::PaymentNotFound
+            ) && state
+                .conf
+                .webhooks
+                .ignore_error
+                .payment_not_found
+                .unwrap_or(true) =>
+        {
+            metrics::WEBHOOK_PAYMENT_NOT_FOUND.add(
+                &metrics::CONTEXT,
+                1,
+                &add_attributes([("merchant_id", merchant_account.get_id().clone())]),
+            );
+            return Ok(WebhookResponseTracker::NoEffect);
         }
-        _ => Err(errors::ApiErrorResponse::WebhookProcessingFailure).attach_printable(
-            "Did not get payment id as object reference id in webhook payments flow",
-        )?,
+        error @ Err(_) => error?,
     };
 
     match payments_response {
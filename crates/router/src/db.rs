@@ -20,6 +20,7 @@ pub mod generic_link;
 pub mod gsm;
 pub mod health_check;
 pub mod kafka_store;
+pub mod kv_consistency;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
@@ -131,6 +132,7 @@ pub trait StorageInterface:
     + user_authentication_method::UserAuthenticationMethodInterface
     + authentication::AuthenticationInterface
     + generic_link::GenericLinkInterface
+    + kv_consistency::KvConsistencyInterface
     + 'static
 {
     fn get_scheduler_db(&self) -> Box<dyn scheduler::SchedulerInterface>;
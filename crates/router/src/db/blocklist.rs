@@ -118,42 +118,109 @@ impl BlocklistInterface for MockDb {
     #[instrument(skip_all)]
     async fn insert_blocklist_entry(
         &self,
-        _pm_blocklist: storage::BlocklistNew,
+        pm_blocklist: storage::BlocklistNew,
     ) -> CustomResult<storage::Blocklist, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut blocklists = self.blocklists.lock().await;
+        if blocklists.iter().any(|blocklist| {
+            blocklist.merchant_id == pm_blocklist.merchant_id
+                && blocklist.fingerprint_id == pm_blocklist.fingerprint_id
+        }) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "fingerprint_id",
+                key: Some(pm_blocklist.fingerprint_id.clone()),
+            })?
+        }
+
+        let blocklist = storage::Blocklist {
+            merchant_id: pm_blocklist.merchant_id,
+            fingerprint_id: pm_blocklist.fingerprint_id,
+            data_kind: pm_blocklist.data_kind,
+            metadata: pm_blocklist.metadata,
+            created_at: pm_blocklist.created_at,
+        };
+        blocklists.push(blocklist.clone());
+        Ok(blocklist)
     }
 
     async fn find_blocklist_entry_by_merchant_id_fingerprint_id(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _fingerprint_id: &str,
+        merchant_id: &common_utils::id_type::MerchantId,
+        fingerprint_id: &str,
     ) -> CustomResult<storage::Blocklist, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        self.blocklists
+            .lock()
+            .await
+            .iter()
+            .find(|blocklist| {
+                blocklist.merchant_id == *merchant_id && blocklist.fingerprint_id == fingerprint_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "cannot find blocklist entry for merchant_id = {merchant_id:?} and fingerprint_id = {fingerprint_id}"
+                ))
+                .into(),
+            )
     }
 
     async fn list_blocklist_entries_by_merchant_id(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
+        merchant_id: &common_utils::id_type::MerchantId,
     ) -> CustomResult<Vec<storage::Blocklist>, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut blocklists = self
+            .blocklists
+            .lock()
+            .await
+            .iter()
+            .filter(|blocklist| blocklist.merchant_id == *merchant_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        blocklists.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(blocklists)
     }
 
     async fn list_blocklist_entries_by_merchant_id_data_kind(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _data_kind: common_enums::BlocklistDataKind,
-        _limit: i64,
-        _offset: i64,
+        merchant_id: &common_utils::id_type::MerchantId,
+        data_kind: common_enums::BlocklistDataKind,
+        limit: i64,
+        offset: i64,
     ) -> CustomResult<Vec<storage::Blocklist>, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let limit = usize::try_from(limit).map_err(|_| errors::StorageError::MockDbError)?;
+        let offset = usize::try_from(offset).map_err(|_| errors::StorageError::MockDbError)?;
+
+        let mut blocklists = self
+            .blocklists
+            .lock()
+            .await
+            .iter()
+            .filter(|blocklist| {
+                blocklist.merchant_id == *merchant_id && blocklist.data_kind == data_kind
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        blocklists.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(blocklists.into_iter().skip(offset).take(limit).collect())
     }
 
     async fn delete_blocklist_entry_by_merchant_id_fingerprint_id(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _fingerprint_id: &str,
+        merchant_id: &common_utils::id_type::MerchantId,
+        fingerprint_id: &str,
     ) -> CustomResult<storage::Blocklist, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut blocklists = self.blocklists.lock().await;
+        let position = blocklists
+            .iter()
+            .position(|blocklist| {
+                blocklist.merchant_id == *merchant_id && blocklist.fingerprint_id == fingerprint_id
+            })
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "cannot find blocklist entry for merchant_id = {merchant_id:?} and fingerprint_id = {fingerprint_id}"
+            )))?;
+
+        Ok(blocklists.remove(position))
     }
 }
 
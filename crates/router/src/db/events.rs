@@ -459,20 +459,24 @@ impl EventInterface for MockDb {
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let locked_events = self.events.lock().await;
-        let events_iter = locked_events.iter().filter(|event| {
-            let mut check = event.merchant_id == Some(merchant_id.to_owned())
-                && event.initial_attempt_id.as_ref() == Some(&event.event_id);
+        let mut filtered_events = locked_events
+            .iter()
+            .filter(|event| {
+                let mut check = event.merchant_id == Some(merchant_id.to_owned())
+                    && event.initial_attempt_id.as_ref() == Some(&event.event_id);
 
-            if let Some(created_after) = created_after {
-                check = check && (event.created_at >= created_after);
-            }
+                if let Some(created_after) = created_after {
+                    check = check && (event.created_at >= created_after);
+                }
 
-            if let Some(created_before) = created_before {
-                check = check && (event.created_at <= created_before);
-            }
+                if let Some(created_before) = created_before {
+                    check = check && (event.created_at <= created_before);
+                }
 
-            check
-        });
+                check
+            })
+            .collect::<Vec<_>>();
+        filtered_events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
         let offset: usize = if let Some(offset) = offset {
             if offset < 0 {
@@ -496,7 +500,8 @@ impl EventInterface for MockDb {
             usize::MAX
         };
 
-        let events = events_iter
+        let events = filtered_events
+            .into_iter()
             .skip(offset)
             .take(limit)
             .cloned()
@@ -597,20 +602,24 @@ impl EventInterface for MockDb {
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let locked_events = self.events.lock().await;
-        let events_iter = locked_events.iter().filter(|event| {
-            let mut check = event.business_profile_id == Some(profile_id.to_owned())
-                && event.initial_attempt_id.as_ref() == Some(&event.event_id);
+        let mut filtered_events = locked_events
+            .iter()
+            .filter(|event| {
+                let mut check = event.business_profile_id == Some(profile_id.to_owned())
+                    && event.initial_attempt_id.as_ref() == Some(&event.event_id);
 
-            if let Some(created_after) = created_after {
-                check = check && (event.created_at >= created_after);
-            }
+                if let Some(created_after) = created_after {
+                    check = check && (event.created_at >= created_after);
+                }
 
-            if let Some(created_before) = created_before {
-                check = check && (event.created_at <= created_before);
-            }
+                if let Some(created_before) = created_before {
+                    check = check && (event.created_at <= created_before);
+                }
 
-            check
-        });
+                check
+            })
+            .collect::<Vec<_>>();
+        filtered_events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
         let offset: usize = if let Some(offset) = offset {
             if offset < 0 {
@@ -634,7 +643,8 @@ impl EventInterface for MockDb {
             usize::MAX
         };
 
-        let events = events_iter
+        let events = filtered_events
+            .into_iter()
             .skip(offset)
             .take(limit)
             .cloned()
@@ -670,7 +680,9 @@ impl EventInterface for MockDb {
             .find(|event| {
                 event.merchant_id == Some(merchant_id.to_owned()) && event.event_id == event_id
             })
-            .ok_or(errors::StorageError::MockDbError)?;
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No event available with merchant_id = {merchant_id:?} and event_id = {event_id}"
+            )))?;
 
         match event {
             domain::EventUpdate::UpdateResponse {
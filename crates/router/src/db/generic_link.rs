@@ -1,4 +1,4 @@
-use error_stack::report;
+use error_stack::{report, ResultExt};
 use router_env::{instrument, tracing};
 
 use crate::{
@@ -133,62 +133,159 @@ impl GenericLinkInterface for Store {
     }
 }
 
+async fn find_mock_generic_link_by_link_id(
+    db: &MockDb,
+    link_id: &str,
+) -> CustomResult<storage::GenericLink, errors::StorageError> {
+    db.generic_links
+        .lock()
+        .await
+        .iter()
+        .find(|generic_link| generic_link.link_id == link_id)
+        .cloned()
+        .ok_or(
+            errors::StorageError::ValueNotFound(format!(
+                "cannot find generic link for link_id = {link_id}"
+            ))
+            .into(),
+        )
+}
+
+async fn insert_mock_generic_link(
+    db: &MockDb,
+    generic_link: storage::GenericLinkNew,
+) -> CustomResult<storage::GenericLink, errors::StorageError> {
+    let mut generic_links = db.generic_links.lock().await;
+    if generic_links
+        .iter()
+        .any(|link| link.link_id == generic_link.link_id)
+    {
+        Err(errors::StorageError::DuplicateValue {
+            entity: "link_id",
+            key: Some(generic_link.link_id.clone()),
+        })?
+    }
+
+    let now = common_utils::date_time::now();
+    let generic_link = storage::GenericLink {
+        link_id: generic_link.link_id,
+        primary_reference: generic_link.primary_reference,
+        merchant_id: generic_link.merchant_id,
+        created_at: generic_link.created_at.unwrap_or(now),
+        last_modified_at: generic_link.last_modified_at.unwrap_or(now),
+        expiry: generic_link.expiry,
+        link_data: generic_link.link_data,
+        link_status: generic_link.link_status,
+        link_type: generic_link.link_type,
+        url: generic_link.url,
+        return_url: generic_link.return_url,
+    };
+    generic_links.push(generic_link.clone());
+    Ok(generic_link)
+}
+
 #[async_trait::async_trait]
 impl GenericLinkInterface for MockDb {
     async fn find_generic_link_by_link_id(
         &self,
-        _generic_link_id: &str,
+        link_id: &str,
     ) -> CustomResult<storage::GenericLinkState, errors::StorageError> {
-        // TODO: Implement function for `MockDb`x
-        Err(errors::StorageError::MockDbError)?
+        find_mock_generic_link_by_link_id(self, link_id)
+            .await
+            .and_then(|generic_link| {
+                storage::GenericLinkState::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse generic link data")
+            })
     }
 
     async fn find_pm_collect_link_by_link_id(
         &self,
-        _generic_link_id: &str,
+        link_id: &str,
     ) -> CustomResult<storage::PaymentMethodCollectLink, errors::StorageError> {
-        // TODO: Implement function for `MockDb`x
-        Err(errors::StorageError::MockDbError)?
+        find_mock_generic_link_by_link_id(self, link_id)
+            .await
+            .and_then(|generic_link| {
+                storage::PaymentMethodCollectLink::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse payment method collect link data")
+            })
     }
 
     async fn find_payout_link_by_link_id(
         &self,
-        _generic_link_id: &str,
+        link_id: &str,
     ) -> CustomResult<storage::PayoutLink, errors::StorageError> {
-        // TODO: Implement function for `MockDb`x
-        Err(errors::StorageError::MockDbError)?
+        find_mock_generic_link_by_link_id(self, link_id)
+            .await
+            .and_then(|generic_link| {
+                storage::PayoutLink::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse payout link data")
+            })
     }
 
     async fn insert_generic_link(
         &self,
-        _generic_link: storage::GenericLinkNew,
+        generic_link: storage::GenericLinkNew,
     ) -> CustomResult<storage::GenericLinkState, errors::StorageError> {
-        // TODO: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        insert_mock_generic_link(self, generic_link)
+            .await
+            .and_then(|generic_link| {
+                storage::GenericLinkState::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse generic link data")
+            })
     }
 
     async fn insert_pm_collect_link(
         &self,
-        _pm_collect_link: storage::GenericLinkNew,
+        pm_collect_link: storage::GenericLinkNew,
     ) -> CustomResult<storage::PaymentMethodCollectLink, errors::StorageError> {
-        // TODO: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        insert_mock_generic_link(self, pm_collect_link)
+            .await
+            .and_then(|generic_link| {
+                storage::PaymentMethodCollectLink::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse payment method collect link data")
+            })
     }
 
     async fn insert_payout_link(
         &self,
-        _pm_collect_link: storage::GenericLinkNew,
+        payout_link: storage::GenericLinkNew,
     ) -> CustomResult<storage::PayoutLink, errors::StorageError> {
-        // TODO: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        insert_mock_generic_link(self, payout_link)
+            .await
+            .and_then(|generic_link| {
+                storage::PayoutLink::try_from(generic_link)
+                    .change_context(errors::StorageError::DeserializationFailed)
+                    .attach_printable("failed to parse payout link data")
+            })
     }
 
     async fn update_payout_link(
         &self,
-        _payout_link: storage::PayoutLink,
-        _payout_link_update: storage::PayoutLinkUpdate,
+        payout_link: storage::PayoutLink,
+        payout_link_update: storage::PayoutLinkUpdate,
     ) -> CustomResult<storage::PayoutLink, errors::StorageError> {
-        // TODO: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut generic_links = self.generic_links.lock().await;
+        let generic_link = generic_links
+            .iter_mut()
+            .find(|generic_link| generic_link.link_id == payout_link.link_id)
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "cannot find payout link for link_id = {}",
+                payout_link.link_id
+            )))?;
+
+        let storage::GenericLinkUpdateInternal { link_status } =
+            storage::GenericLinkUpdateInternal::from(payout_link_update);
+        if let Some(link_status) = link_status {
+            generic_link.link_status = link_status;
+        }
+
+        storage::PayoutLink::try_from(generic_link.clone())
+            .change_context(errors::StorageError::DeserializationFailed)
+            .attach_printable("failed to parse payout link data")
     }
 }
@@ -128,57 +128,173 @@ impl GsmInterface for Store {
     }
 }
 
+fn is_matching_gsm_rule(
+    rule: &storage::GatewayStatusMap,
+    connector: &str,
+    flow: &str,
+    sub_flow: &str,
+    code: &str,
+    message: &str,
+) -> bool {
+    rule.connector == connector
+        && rule.flow == flow
+        && rule.sub_flow == sub_flow
+        && rule.code == code
+        && rule.message == message
+}
+
 #[async_trait::async_trait]
 impl GsmInterface for MockDb {
     async fn add_gsm_rule(
         &self,
-        _rule: storage::GatewayStatusMappingNew,
+        rule: storage::GatewayStatusMappingNew,
     ) -> CustomResult<storage::GatewayStatusMap, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut gsm_rules = self.gateway_status_maps.lock().await;
+        if gsm_rules.iter().any(|gsm_rule| {
+            is_matching_gsm_rule(
+                gsm_rule,
+                &rule.connector,
+                &rule.flow,
+                &rule.sub_flow,
+                &rule.code,
+                &rule.message,
+            )
+        }) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "gateway_status_map",
+                key: Some(format!(
+                    "connector = {}, flow = {}, sub_flow = {}, code = {}, message = {}",
+                    rule.connector, rule.flow, rule.sub_flow, rule.code, rule.message
+                )),
+            })?
+        }
+
+        let now = common_utils::date_time::now();
+        let gsm_rule = storage::GatewayStatusMap {
+            connector: rule.connector,
+            flow: rule.flow,
+            sub_flow: rule.sub_flow,
+            code: rule.code,
+            message: rule.message,
+            status: rule.status,
+            router_error: rule.router_error,
+            decision: rule.decision,
+            created_at: now,
+            last_modified: now,
+            step_up_possible: rule.step_up_possible,
+            unified_code: rule.unified_code,
+            unified_message: rule.unified_message,
+        };
+        gsm_rules.push(gsm_rule.clone());
+        Ok(gsm_rule)
     }
 
     async fn find_gsm_decision(
         &self,
-        _connector: String,
-        _flow: String,
-        _sub_flow: String,
-        _code: String,
-        _message: String,
+        connector: String,
+        flow: String,
+        sub_flow: String,
+        code: String,
+        message: String,
     ) -> CustomResult<String, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        self.find_gsm_rule(connector, flow, sub_flow, code, message)
+            .await
+            .map(|gsm_rule| gsm_rule.decision)
     }
 
     async fn find_gsm_rule(
         &self,
-        _connector: String,
-        _flow: String,
-        _sub_flow: String,
-        _code: String,
-        _message: String,
+        connector: String,
+        flow: String,
+        sub_flow: String,
+        code: String,
+        message: String,
     ) -> CustomResult<storage::GatewayStatusMap, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        self.gateway_status_maps
+            .lock()
+            .await
+            .iter()
+            .find(|gsm_rule| {
+                is_matching_gsm_rule(gsm_rule, &connector, &flow, &sub_flow, &code, &message)
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "cannot find gsm rule for connector = {connector}, flow = {flow}, sub_flow = {sub_flow}, code = {code}, message = {message}"
+                ))
+                .into(),
+            )
     }
 
     async fn update_gsm_rule(
         &self,
-        _connector: String,
-        _flow: String,
-        _sub_flow: String,
-        _code: String,
-        _message: String,
-        _data: storage::GatewayStatusMappingUpdate,
+        connector: String,
+        flow: String,
+        sub_flow: String,
+        code: String,
+        message: String,
+        data: storage::GatewayStatusMappingUpdate,
     ) -> CustomResult<storage::GatewayStatusMap, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut gsm_rules = self.gateway_status_maps.lock().await;
+        let gsm_rule = gsm_rules
+            .iter_mut()
+            .find(|gsm_rule| {
+                is_matching_gsm_rule(gsm_rule, &connector, &flow, &sub_flow, &code, &message)
+            })
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "cannot find gsm rule for connector = {connector}, flow = {flow}, sub_flow = {sub_flow}, code = {code}, message = {message}"
+            )))?;
+
+        let storage::GatewayStatusMappingUpdate {
+            status,
+            router_error,
+            decision,
+            step_up_possible,
+            unified_code,
+            unified_message,
+        } = data;
+
+        if let Some(status) = status {
+            gsm_rule.status = status;
+        }
+        if let Some(router_error) = router_error {
+            gsm_rule.router_error = router_error;
+        }
+        if let Some(decision) = decision {
+            gsm_rule.decision = decision;
+        }
+        if let Some(step_up_possible) = step_up_possible {
+            gsm_rule.step_up_possible = step_up_possible;
+        }
+        if let Some(unified_code) = unified_code {
+            gsm_rule.unified_code = Some(unified_code);
+        }
+        if let Some(unified_message) = unified_message {
+            gsm_rule.unified_message = Some(unified_message);
+        }
+
+        Ok(gsm_rule.clone())
     }
 
     async fn delete_gsm_rule(
         &self,
-        _connector: String,
-        _flow: String,
-        _sub_flow: String,
-        _code: String,
-        _message: String,
+        connector: String,
+        flow: String,
+        sub_flow: String,
+        code: String,
+        message: String,
     ) -> CustomResult<bool, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let mut gsm_rules = self.gateway_status_maps.lock().await;
+        let initial_len = gsm_rules.len();
+        gsm_rules.retain(|gsm_rule| {
+            !is_matching_gsm_rule(gsm_rule, &connector, &flow, &sub_flow, &code, &message)
+        });
+
+        if gsm_rules.len() == initial_len {
+            Err(errors::StorageError::ValueNotFound(format!(
+                "cannot find gsm rule for connector = {connector}, flow = {flow}, sub_flow = {sub_flow}, code = {code}, message = {message}"
+            )))?
+        }
+        Ok(true)
     }
 }
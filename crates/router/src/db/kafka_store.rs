@@ -61,6 +61,7 @@ use crate::{
         generic_link::GenericLinkInterface,
         gsm::GsmInterface,
         health_check::HealthCheckDbInterface,
+        kv_consistency::{KvConsistencyInterface, KvEntry, KvPartitionKeyPages},
         locker_mock_up::LockerMockUpInterface,
         mandate::MandateInterface,
         merchant_account::MerchantAccountInterface,
@@ -2072,6 +2073,17 @@ impl ProcessTrackerInterface for KafkaStore {
         self.diesel_store.update_process(this, process).await
     }
 
+    async fn update_process_if_status(
+        &self,
+        this: storage::ProcessTracker,
+        expected_status: ProcessTrackerStatus,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_process_if_status(this, expected_status, process)
+            .await
+    }
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         task_ids: Vec<String>,
@@ -2108,7 +2120,7 @@ impl ProcessTrackerInterface for KafkaStore {
     async fn finish_process_with_business_status(
         &self,
         this: storage::ProcessTracker,
-        business_status: &'static str,
+        business_status: &str,
     ) -> CustomResult<(), errors::StorageError> {
         self.diesel_store
             .finish_process_with_business_status(this, business_status)
@@ -2126,6 +2138,54 @@ impl ProcessTrackerInterface for KafkaStore {
             .find_processes_by_time_status(time_lower_limit, time_upper_limit, status, limit)
             .await
     }
+
+    async fn find_processes_by_runner_status_time(
+        &self,
+        runner: storage::ProcessTrackerRunner,
+        status: Option<ProcessTrackerStatus>,
+        time_lower_limit: PrimitiveDateTime,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_processes_by_runner_status_time(
+                runner,
+                status,
+                time_lower_limit,
+                time_upper_limit,
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn find_latest_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store.find_latest_process_by_name(name).await
+    }
+
+    async fn find_earliest_pending_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_earliest_pending_process_by_name(name)
+            .await
+    }
+
+    async fn dead_letter_process(
+        &self,
+        this: storage::ProcessTracker,
+        business_status: &str,
+        error_chain: Vec<String>,
+    ) -> CustomResult<(), errors::StorageError> {
+        self.diesel_store
+            .dead_letter_process(this, business_status, error_chain)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -2585,6 +2645,72 @@ impl RoutingAlgorithmInterface for KafkaStore {
     }
 }
 
+#[async_trait::async_trait]
+impl KvConsistencyInterface for KafkaStore {
+    fn scan_kv_partition_keys_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+    ) -> CustomResult<KvPartitionKeyPages, errors::StorageError> {
+        self.diesel_store
+            .scan_kv_partition_keys_by_merchant_id(merchant_id)
+    }
+
+    async fn get_kv_entries(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<std::collections::HashMap<String, String>, errors::StorageError> {
+        self.diesel_store.get_kv_entries(partition_key).await
+    }
+
+    async fn find_kv_entry_in_database(
+        &self,
+        entry: &KvEntry,
+    ) -> CustomResult<Option<KvEntry>, errors::StorageError> {
+        self.diesel_store.find_kv_entry_in_database(entry).await
+    }
+
+    async fn update_kv_entry(
+        &self,
+        partition_key: &str,
+        field: &str,
+        current_value: &str,
+        entry: &KvEntry,
+    ) -> CustomResult<bool, errors::StorageError> {
+        self.diesel_store
+            .update_kv_entry(partition_key, field, current_value, entry)
+            .await
+    }
+
+    async fn find_kv_entries_in_database_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        created_gte: PrimitiveDateTime,
+    ) -> CustomResult<Vec<KvEntry>, errors::StorageError> {
+        self.diesel_store
+            .find_kv_entries_in_database_by_merchant_id(merchant_id, created_gte)
+            .await
+    }
+
+    async fn insert_kv_entry_if_not_exists(
+        &self,
+        entry: &KvEntry,
+        ttl: u32,
+    ) -> CustomResult<bool, errors::StorageError> {
+        self.diesel_store
+            .insert_kv_entry_if_not_exists(entry, ttl)
+            .await
+    }
+
+    async fn is_kv_partition_key_dead_lettered(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        self.diesel_store
+            .is_kv_partition_key_dead_lettered(partition_key)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl GsmInterface for KafkaStore {
     async fn add_gsm_rule(
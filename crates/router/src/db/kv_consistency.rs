@@ -0,0 +1,688 @@
+use std::collections::HashMap;
+
+use api_models::kv_consistency::KvEntity;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::{ext_traits::StringExt, id_type};
+use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use diesel_models::{errors::DatabaseError, schema};
+use error_stack::{report, ResultExt};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use router_env::{instrument, tracing};
+use storage_impl::redis::kv_store::{PartitionKey, RedisConnInterface};
+use time::PrimitiveDateTime;
+
+use super::MockDb;
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    services::Store,
+};
+
+/// An entity stored in a field of a Redis KV hash, or the Postgres row it is drained to
+#[derive(Debug, Clone)]
+pub enum KvEntry {
+    #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+    PaymentIntent(diesel_models::PaymentIntent),
+    #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+    PaymentAttempt(diesel_models::PaymentAttempt),
+    Refund(diesel_models::Refund),
+    Address(diesel_models::Address),
+    #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+    Customer(diesel_models::Customer),
+}
+
+impl KvEntry {
+    /// Deserializes the value of a field of a Redis KV hash, based on the prefix of the field.
+    /// Returns `None` for fields that do not hold any of the supported entities.
+    pub fn parse(field: &str, value: &str) -> Option<CustomResult<Self, errors::ParsingError>> {
+        match field.split('_').next()? {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            "pi" => Some(value.parse_struct("PaymentIntent").map(Self::PaymentIntent)),
+            "pa" if field.contains("_ref_") => Some(value.parse_struct("Refund").map(Self::Refund)),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            "pa" => Some(
+                value
+                    .parse_struct("PaymentAttempt")
+                    .map(Self::PaymentAttempt),
+            ),
+            "add" => Some(value.parse_struct("Address").map(Self::Address)),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            "cust" => Some(value.parse_struct("Customer").map(Self::Customer)),
+            _ => None,
+        }
+    }
+
+    pub fn get_entity(&self) -> KvEntity {
+        match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentIntent(_) => KvEntity::PaymentIntent,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(_) => KvEntity::PaymentAttempt,
+            Self::Refund(_) => KvEntity::Refund,
+            Self::Address(_) => KvEntity::Address,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            Self::Customer(_) => KvEntity::Customer,
+        }
+    }
+
+    pub fn get_created_at(&self) -> PrimitiveDateTime {
+        match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentIntent(payment_intent) => payment_intent.created_at,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(payment_attempt) => payment_attempt.created_at,
+            Self::Refund(refund) => refund.created_at,
+            Self::Address(address) => address.created_at,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            Self::Customer(customer) => customer.created_at,
+        }
+    }
+
+    pub fn get_modified_at(&self) -> PrimitiveDateTime {
+        match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentIntent(payment_intent) => payment_intent.modified_at,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(payment_attempt) => payment_attempt.modified_at,
+            Self::Refund(refund) => refund.modified_at,
+            Self::Address(address) => address.modified_at,
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            Self::Customer(customer) => customer.modified_at,
+        }
+    }
+
+    /// Returns the Redis KV hash and the field of the hash the entity is stored in, or `None` if
+    /// the entity is not stored in the Redis KV store
+    pub fn get_partition_key_and_field(&self) -> Option<(String, String)> {
+        match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentIntent(payment_intent) => Some((
+                PartitionKey::MerchantIdPaymentId {
+                    merchant_id: &payment_intent.merchant_id,
+                    payment_id: &payment_intent.payment_id,
+                }
+                .to_string(),
+                format!("pi_{}", payment_intent.payment_id.get_string_repr()),
+            )),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(payment_attempt) => Some((
+                PartitionKey::MerchantIdPaymentId {
+                    merchant_id: &payment_attempt.merchant_id,
+                    payment_id: &payment_attempt.payment_id,
+                }
+                .to_string(),
+                format!("pa_{}", payment_attempt.attempt_id),
+            )),
+            Self::Refund(refund) => Some((
+                PartitionKey::MerchantIdPaymentId {
+                    merchant_id: &refund.merchant_id,
+                    payment_id: &refund.payment_id,
+                }
+                .to_string(),
+                format!("pa_{}_ref_{}", refund.attempt_id, refund.refund_id),
+            )),
+            // Only the addresses of payments are stored in the Redis KV store
+            Self::Address(address) => address.payment_id.as_ref().map(|payment_id| {
+                (
+                    PartitionKey::MerchantIdPaymentId {
+                        merchant_id: &address.merchant_id,
+                        payment_id,
+                    }
+                    .to_string(),
+                    format!("add_{}", address.address_id),
+                )
+            }),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            Self::Customer(customer) => Some((
+                PartitionKey::MerchantIdCustomerId {
+                    merchant_id: &customer.merchant_id,
+                    customer_id: &customer.customer_id,
+                }
+                .to_string(),
+                format!("cust_{}", customer.customer_id.get_string_repr()),
+            )),
+        }
+    }
+
+    /// Returns the reverse lookups the application inserts along with the entity when storing it
+    /// in the Redis KV store, which are needed to find the entity by its other IDs
+    pub fn get_reverse_lookups(&self) -> Vec<diesel_models::reverse_lookup::ReverseLookupNew> {
+        let Some((partition_key, field)) = self.get_partition_key_and_field() else {
+            return Vec::new();
+        };
+
+        let (source, lookup_ids) = match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(payment_attempt) => {
+                let merchant_id = payment_attempt.merchant_id.get_string_repr();
+                (
+                    "payment_attempt",
+                    [
+                        Some(format!("pa_{merchant_id}_{}", payment_attempt.attempt_id)),
+                        payment_attempt
+                            .connector_transaction_id
+                            .as_ref()
+                            .map(|id| format!("pa_conn_trans_{merchant_id}_{id}")),
+                        payment_attempt
+                            .preprocessing_step_id
+                            .as_ref()
+                            .map(|id| format!("pa_preprocessing_{merchant_id}_{id}")),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>(),
+                )
+            }
+            Self::Refund(refund) => {
+                let merchant_id = refund.merchant_id.get_string_repr();
+                (
+                    "refund",
+                    [
+                        Some(format!("ref_ref_id_{merchant_id}_{}", refund.refund_id)),
+                        Some(format!(
+                            "ref_inter_ref_{merchant_id}_{}",
+                            refund.internal_reference_id
+                        )),
+                        refund.connector_refund_id.as_ref().map(|id| {
+                            format!("ref_connector_{merchant_id}_{id}_{}", refund.connector)
+                        }),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>(),
+                )
+            }
+            _ => return Vec::new(),
+        };
+
+        lookup_ids
+            .into_iter()
+            .map(
+                |lookup_id| diesel_models::reverse_lookup::ReverseLookupNew {
+                    lookup_id,
+                    pk_id: partition_key.clone(),
+                    sk_id: field.clone(),
+                    source: source.to_owned(),
+                    updated_by: common_enums::MerchantStorageScheme::RedisKv.to_string(),
+                },
+            )
+            .collect()
+    }
+
+    /// Serializes the entity the same way it is stored in the Redis KV hash
+    pub fn to_json(&self) -> CustomResult<serde_json::Value, errors::ParsingError> {
+        match self {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentIntent(payment_intent) => serde_json::to_value(payment_intent),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            Self::PaymentAttempt(payment_attempt) => serde_json::to_value(payment_attempt),
+            Self::Refund(refund) => serde_json::to_value(refund),
+            Self::Address(address) => serde_json::to_value(address),
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            Self::Customer(customer) => serde_json::to_value(customer),
+        }
+        .change_context(errors::ParsingError::EncodeError("json"))
+    }
+}
+
+/// Pages of the Redis KV hashes of a merchant, as they are scanned
+pub type KvPartitionKeyPages = BoxStream<'static, CustomResult<Vec<String>, errors::StorageError>>;
+
+#[async_trait::async_trait]
+pub trait KvConsistencyInterface {
+    /// Scans the Redis KV hashes holding the payments and customers of the merchant, a page at
+    /// a time
+    fn scan_kv_partition_keys_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+    ) -> CustomResult<KvPartitionKeyPages, errors::StorageError>;
+
+    /// Returns the fields of the Redis KV hash, along with their serialized values
+    async fn get_kv_entries(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<HashMap<String, String>, errors::StorageError>;
+
+    /// Returns the Postgres row the Redis KV entry is drained to, if it exists
+    async fn find_kv_entry_in_database(
+        &self,
+        entry: &KvEntry,
+    ) -> CustomResult<Option<KvEntry>, errors::StorageError>;
+
+    /// Overwrites the field of the Redis KV hash with the specified entity, only if the field
+    /// still holds the value it was read with. Returns whether the field was overwritten.
+    async fn update_kv_entry(
+        &self,
+        partition_key: &str,
+        field: &str,
+        current_value: &str,
+        entry: &KvEntry,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    /// Returns the Postgres rows of the merchant created on or after the specified time, that
+    /// are stored in Redis KV hashes when the merchant uses the `RedisKv` storage scheme
+    async fn find_kv_entries_in_database_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        created_gte: PrimitiveDateTime,
+    ) -> CustomResult<Vec<KvEntry>, errors::StorageError>;
+
+    /// Stores the entity in its Redis KV hash, unless the hash already holds it. Returns whether
+    /// the entity was stored.
+    async fn insert_kv_entry_if_not_exists(
+        &self,
+        entry: &KvEntry,
+        ttl: u32,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    /// Whether the drainer holds the Redis KV entries of the hash behind an entry it moved to
+    /// the dead letter stream, which are not applied to Postgres until that entry is replayed
+    async fn is_kv_partition_key_dead_lettered(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+fn optional<T>(result: diesel_models::StorageResult<T>) -> diesel_models::StorageResult<Option<T>> {
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(error) if matches!(error.current_context(), DatabaseError::NotFound) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[async_trait::async_trait]
+impl KvConsistencyInterface for Store {
+    #[instrument(skip_all)]
+    fn scan_kv_partition_keys_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+    ) -> CustomResult<KvPartitionKeyPages, errors::StorageError> {
+        let redis_conn = self
+            .get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?;
+
+        // The partition keys of payments and customers, as formatted by `PartitionKey`
+        let pages = ["pid", "cust"].map(|pattern| {
+            redis_conn.scan_keys_in_pages(
+                &format!("mid_{}_{pattern}_*", merchant_id.get_string_repr()),
+                None,
+            )
+        });
+
+        Ok(stream::iter(pages)
+            .flatten()
+            .map(|page| page.change_context(errors::StorageError::KVError))
+            .boxed())
+    }
+
+    #[instrument(skip_all)]
+    async fn get_kv_entries(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<HashMap<String, String>, errors::StorageError> {
+        self.get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .get_all_hash_fields(partition_key)
+            .await
+            .change_context(errors::StorageError::KVError)
+    }
+
+    #[instrument(skip_all)]
+    async fn find_kv_entry_in_database(
+        &self,
+        entry: &KvEntry,
+    ) -> CustomResult<Option<KvEntry>, errors::StorageError> {
+        // Read from the primary, since the Redis KV entries are compared with the rows the
+        // drainer has just written
+        let conn = connection::pg_connection_write(self).await?;
+
+        match entry {
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            KvEntry::PaymentIntent(payment_intent) => {
+                diesel_models::PaymentIntent::find_optional_by_payment_id_merchant_id(
+                    &conn,
+                    &payment_intent.payment_id,
+                    &payment_intent.merchant_id,
+                )
+                .await
+                .map(|row| row.map(KvEntry::PaymentIntent))
+            }
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+            KvEntry::PaymentAttempt(payment_attempt) => optional(
+                diesel_models::PaymentAttempt::find_by_merchant_id_attempt_id(
+                    &conn,
+                    &payment_attempt.merchant_id,
+                    &payment_attempt.attempt_id,
+                )
+                .await,
+            )
+            .map(|row| row.map(KvEntry::PaymentAttempt)),
+            KvEntry::Refund(refund) => optional(
+                diesel_models::Refund::find_by_merchant_id_refund_id(
+                    &conn,
+                    &refund.merchant_id,
+                    &refund.refund_id,
+                )
+                .await,
+            )
+            .map(|row| row.map(KvEntry::Refund)),
+            KvEntry::Address(address) => {
+                diesel_models::Address::find_optional_by_address_id(&conn, &address.address_id)
+                    .await
+                    .map(|row| row.map(KvEntry::Address))
+            }
+            #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+            KvEntry::Customer(customer) => {
+                diesel_models::Customer::find_optional_by_customer_id_merchant_id(
+                    &conn,
+                    &customer.customer_id,
+                    &customer.merchant_id,
+                )
+                .await
+                .map(|row| row.map(KvEntry::Customer))
+            }
+        }
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    #[instrument(skip_all)]
+    async fn update_kv_entry(
+        &self,
+        partition_key: &str,
+        field: &str,
+        current_value: &str,
+        entry: &KvEntry,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let value = entry
+            .to_json()
+            .change_context(errors::StorageError::SerializationFailed)?
+            .to_string();
+
+        // The field is not overwritten if the application updated it after it was read, since
+        // the update would then be lost
+        self.get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .set_hash_field_if_equals(partition_key, field, current_value, &value)
+            .await
+            .change_context(errors::StorageError::KVError)
+    }
+
+    #[instrument(skip_all)]
+    async fn find_kv_entries_in_database_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        created_gte: PrimitiveDateTime,
+    ) -> CustomResult<Vec<KvEntry>, errors::StorageError> {
+        // Read from the primary, so that rows written just before the merchant is switched to
+        // the Redis KV store are not missed because of replication lag
+        let conn = connection::pg_connection_write(self).await?;
+        let mut entries = Vec::new();
+
+        #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "payment_v2")))]
+        {
+            use schema::{payment_attempt, payment_intent};
+
+            let payment_intents = <diesel_models::PaymentIntent as HasTable>::table()
+                .filter(payment_intent::merchant_id.eq(merchant_id.to_owned()))
+                .filter(payment_intent::created_at.ge(created_gte))
+                .get_results_async::<diesel_models::PaymentIntent>(&conn)
+                .await
+                .change_context(DatabaseError::Others)
+                .map_err(|error| report!(errors::StorageError::from(error)))
+                .attach_printable("Failed to filter payment intents by created time")?;
+            entries.extend(payment_intents.into_iter().map(KvEntry::PaymentIntent));
+
+            let payment_attempts = <diesel_models::PaymentAttempt as HasTable>::table()
+                .filter(payment_attempt::merchant_id.eq(merchant_id.to_owned()))
+                .filter(payment_attempt::created_at.ge(created_gte))
+                .get_results_async::<diesel_models::PaymentAttempt>(&conn)
+                .await
+                .change_context(DatabaseError::Others)
+                .map_err(|error| report!(errors::StorageError::from(error)))
+                .attach_printable("Failed to filter payment attempts by created time")?;
+            entries.extend(payment_attempts.into_iter().map(KvEntry::PaymentAttempt));
+        }
+
+        let refunds = <diesel_models::Refund as HasTable>::table()
+            .filter(schema::refund::merchant_id.eq(merchant_id.to_owned()))
+            .filter(schema::refund::created_at.ge(created_gte))
+            .get_results_async::<diesel_models::Refund>(&conn)
+            .await
+            .change_context(DatabaseError::Others)
+            .map_err(|error| report!(errors::StorageError::from(error)))
+            .attach_printable("Failed to filter refunds by created time")?;
+        entries.extend(refunds.into_iter().map(KvEntry::Refund));
+
+        let addresses = <diesel_models::Address as HasTable>::table()
+            .filter(schema::address::merchant_id.eq(merchant_id.to_owned()))
+            .filter(schema::address::payment_id.is_not_null())
+            .filter(schema::address::created_at.ge(created_gte))
+            .get_results_async::<diesel_models::Address>(&conn)
+            .await
+            .change_context(DatabaseError::Others)
+            .map_err(|error| report!(errors::StorageError::from(error)))
+            .attach_printable("Failed to filter addresses by created time")?;
+        entries.extend(addresses.into_iter().map(KvEntry::Address));
+
+        #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+        {
+            let customers = <diesel_models::Customer as HasTable>::table()
+                .filter(schema::customers::merchant_id.eq(merchant_id.to_owned()))
+                .filter(schema::customers::created_at.ge(created_gte))
+                .get_results_async::<diesel_models::Customer>(&conn)
+                .await
+                .change_context(DatabaseError::Others)
+                .map_err(|error| report!(errors::StorageError::from(error)))
+                .attach_printable("Failed to filter customers by created time")?;
+            entries.extend(customers.into_iter().map(KvEntry::Customer));
+        }
+
+        Ok(entries)
+    }
+
+    #[instrument(skip_all)]
+    async fn insert_kv_entry_if_not_exists(
+        &self,
+        entry: &KvEntry,
+        ttl: u32,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let Some((partition_key, field)) = entry.get_partition_key_and_field() else {
+            return Ok(false);
+        };
+        let value = entry
+            .to_json()
+            .change_context(errors::StorageError::SerializationFailed)?
+            .to_string();
+
+        let reply = self
+            .get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .set_hash_field_if_not_exist(&partition_key, &field, value, Some(ttl))
+            .await
+            .change_context(errors::StorageError::KVError)?;
+
+        Ok(reply == redis_interface::HsetnxReply::KeySet)
+    }
+
+    #[cfg(feature = "kv_store")]
+    #[instrument(skip_all)]
+    async fn is_kv_partition_key_dead_lettered(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let stream_name =
+            self.get_drainer_stream_name_by_partition_key(PartitionKey::CombinationKey {
+                combination: partition_key,
+            });
+
+        // The set of the dead letter entries of a global ID, as maintained by the drainer. The
+        // global ID of a Redis KV entry is its partition key.
+        self.get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .exists::<Vec<u8>>(&format!("{stream_name}_dead_letter_held_{partition_key}"))
+            .await
+            .change_context(errors::StorageError::KVError)
+    }
+
+    #[cfg(not(feature = "kv_store"))]
+    async fn is_kv_partition_key_dead_lettered(
+        &self,
+        _partition_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        Ok(false)
+    }
+}
+
+#[async_trait::async_trait]
+impl KvConsistencyInterface for MockDb {
+    fn scan_kv_partition_keys_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+    ) -> CustomResult<KvPartitionKeyPages, errors::StorageError> {
+        let prefixes = ["pid", "cust"]
+            .map(|pattern| format!("mid_{}_{pattern}_", merchant_id.get_string_repr()));
+        let kv_hashes = self.kv_hashes.clone();
+
+        Ok(stream::once(async move {
+            Ok(kv_hashes
+                .lock()
+                .await
+                .keys()
+                .filter(|key| prefixes.iter().any(|prefix| key.starts_with(prefix)))
+                .cloned()
+                .collect())
+        })
+        .boxed())
+    }
+
+    async fn get_kv_entries(
+        &self,
+        partition_key: &str,
+    ) -> CustomResult<HashMap<String, String>, errors::StorageError> {
+        Ok(self
+            .kv_hashes
+            .lock()
+            .await
+            .get(partition_key)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn find_kv_entry_in_database(
+        &self,
+        entry: &KvEntry,
+    ) -> CustomResult<Option<KvEntry>, errors::StorageError> {
+        match entry {
+            KvEntry::Refund(refund) => Ok(self
+                .refunds
+                .lock()
+                .await
+                .iter()
+                .find(|row| {
+                    row.merchant_id == refund.merchant_id && row.refund_id == refund.refund_id
+                })
+                .cloned()
+                .map(KvEntry::Refund)),
+            KvEntry::Address(address) => Ok(self
+                .addresses
+                .lock()
+                .await
+                .iter()
+                .find(|row| row.address_id == address.address_id)
+                .cloned()
+                .map(KvEntry::Address)),
+            // Only refunds and addresses are looked up in the mock database
+            #[allow(unreachable_patterns)]
+            _ => Err(errors::StorageError::MockDbError)?,
+        }
+    }
+
+    async fn update_kv_entry(
+        &self,
+        partition_key: &str,
+        field: &str,
+        current_value: &str,
+        entry: &KvEntry,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let value = entry
+            .to_json()
+            .change_context(errors::StorageError::SerializationFailed)?
+            .to_string();
+
+        let mut kv_hashes = self.kv_hashes.lock().await;
+        match kv_hashes
+            .get_mut(partition_key)
+            .and_then(|kv_hash| kv_hash.get_mut(field))
+        {
+            Some(stored_value) if stored_value == current_value => {
+                *stored_value = value;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn find_kv_entries_in_database_by_merchant_id(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        created_gte: PrimitiveDateTime,
+    ) -> CustomResult<Vec<KvEntry>, errors::StorageError> {
+        let refunds = self
+            .refunds
+            .lock()
+            .await
+            .iter()
+            .filter(|refund| &refund.merchant_id == merchant_id && refund.created_at >= created_gte)
+            .cloned()
+            .map(KvEntry::Refund)
+            .collect::<Vec<_>>();
+        let addresses = self
+            .addresses
+            .lock()
+            .await
+            .iter()
+            .filter(|address| {
+                &address.merchant_id == merchant_id
+                    && address.payment_id.is_some()
+                    && address.created_at >= created_gte
+            })
+            .cloned()
+            .map(KvEntry::Address)
+            .collect::<Vec<_>>();
+
+        Ok(refunds.into_iter().chain(addresses).collect())
+    }
+
+    async fn insert_kv_entry_if_not_exists(
+        &self,
+        entry: &KvEntry,
+        _ttl: u32,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let Some((partition_key, field)) = entry.get_partition_key_and_field() else {
+            return Ok(false);
+        };
+        let value = entry
+            .to_json()
+            .change_context(errors::StorageError::SerializationFailed)?
+            .to_string();
+
+        let mut kv_hashes = self.kv_hashes.lock().await;
+        let kv_hash = kv_hashes.entry(partition_key).or_default();
+        if kv_hash.contains_key(&field) {
+            return Ok(false);
+        }
+        kv_hash.insert(field, value);
+        Ok(true)
+    }
+
+    async fn is_kv_partition_key_dead_lettered(
+        &self,
+        _partition_key: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        Ok(false)
+    }
+}
@@ -554,22 +554,68 @@ impl MerchantAccountInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn list_merchant_accounts_by_organization_id(
         &self,
-        _state: &KeyManagerState,
-        _organization_id: &str,
+        state: &KeyManagerState,
+        organization_id: &str,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let accounts = self
+            .merchant_accounts
+            .lock()
+            .await
+            .iter()
+            .filter(|account| account.organization_id.get_string_repr() == organization_id)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        convert_mock_merchant_accounts(self, state, accounts).await
     }
 
     #[cfg(feature = "olap")]
     async fn list_multiple_merchant_accounts(
         &self,
-        _state: &KeyManagerState,
-        _merchant_ids: Vec<common_utils::id_type::MerchantId>,
+        state: &KeyManagerState,
+        merchant_ids: Vec<common_utils::id_type::MerchantId>,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
-        Err(errors::StorageError::MockDbError)?
+        let accounts = self
+            .merchant_accounts
+            .lock()
+            .await
+            .iter()
+            .filter(|account| merchant_ids.contains(account.get_id()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        convert_mock_merchant_accounts(self, state, accounts).await
     }
 }
 
+/// Decrypts the merchant accounts stored in `MockDb`, each with its own merchant key store
+#[cfg(feature = "olap")]
+async fn convert_mock_merchant_accounts(
+    db: &MockDb,
+    state: &KeyManagerState,
+    accounts: Vec<storage::MerchantAccount>,
+) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
+    let db_master_key = db.get_master_key().to_vec().into();
+    let mut merchant_accounts = Vec::with_capacity(accounts.len());
+
+    for account in accounts {
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(state, account.get_id(), &db_master_key)
+            .await?;
+        let merchant_account = account
+            .convert(
+                state,
+                key_store.key.get_inner(),
+                key_store.merchant_id.clone().into(),
+            )
+            .await
+            .change_context(errors::StorageError::DecryptionError)?;
+        merchant_accounts.push(merchant_account);
+    }
+
+    Ok(merchant_accounts)
+}
+
 #[cfg(feature = "accounts_cache")]
 async fn publish_and_redact_merchant_account_cache(
     store: &dyn super::StorageInterface,
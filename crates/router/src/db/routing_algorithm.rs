@@ -174,64 +174,281 @@ impl RoutingAlgorithmInterface for Store {
     }
 }
 
+fn get_routing_profile_metadata(
+    routing_algorithm: &routing_storage::RoutingAlgorithm,
+) -> routing_storage::RoutingProfileMetadata {
+    routing_storage::RoutingProfileMetadata {
+        profile_id: routing_algorithm.profile_id.clone(),
+        algorithm_id: routing_algorithm.algorithm_id.clone(),
+        name: routing_algorithm.name.clone(),
+        description: routing_algorithm.description.clone(),
+        kind: routing_algorithm.kind,
+        created_at: routing_algorithm.created_at,
+        modified_at: routing_algorithm.modified_at,
+        algorithm_for: routing_algorithm.algorithm_for,
+    }
+}
+
+/// Mimics the SQL `LIMIT` and `OFFSET` clauses on the matching routing algorithms
+fn paginate_routing_profile_metadata<'a>(
+    routing_algorithms: impl Iterator<Item = &'a routing_storage::RoutingAlgorithm>,
+    limit: i64,
+    offset: i64,
+) -> StorageResult<Vec<routing_storage::RoutingProfileMetadata>> {
+    let limit = usize::try_from(limit).map_err(|_| errors::StorageError::MockDbError)?;
+    let offset = usize::try_from(offset).map_err(|_| errors::StorageError::MockDbError)?;
+
+    Ok(routing_algorithms
+        .skip(offset)
+        .take(limit)
+        .map(get_routing_profile_metadata)
+        .collect())
+}
+
 #[async_trait::async_trait]
 impl RoutingAlgorithmInterface for MockDb {
     async fn insert_routing_algorithm(
         &self,
-        _routing_algorithm: routing_storage::RoutingAlgorithm,
+        routing_algorithm: routing_storage::RoutingAlgorithm,
     ) -> StorageResult<routing_storage::RoutingAlgorithm> {
-        Err(errors::StorageError::MockDbError)?
+        let mut routing_algorithms = self.routing_algorithms.lock().await;
+        if routing_algorithms
+            .iter()
+            .any(|algorithm| algorithm.algorithm_id == routing_algorithm.algorithm_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "algorithm_id",
+                key: Some(routing_algorithm.algorithm_id.get_string_repr().to_owned()),
+            })?
+        }
+        routing_algorithms.push(routing_algorithm.clone());
+        Ok(routing_algorithm)
     }
 
     async fn find_routing_algorithm_by_profile_id_algorithm_id(
         &self,
-        _profile_id: &common_utils::id_type::ProfileId,
-        _algorithm_id: &common_utils::id_type::RoutingId,
+        profile_id: &common_utils::id_type::ProfileId,
+        algorithm_id: &common_utils::id_type::RoutingId,
     ) -> StorageResult<routing_storage::RoutingAlgorithm> {
-        Err(errors::StorageError::MockDbError)?
+        self.routing_algorithms
+            .lock()
+            .await
+            .iter()
+            .find(|algorithm| {
+                algorithm.algorithm_id == *algorithm_id && algorithm.profile_id == *profile_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "cannot find routing algorithm for algorithm_id = {algorithm_id:?} and profile_id = {profile_id:?}"
+                ))
+                .into(),
+            )
     }
 
     async fn find_routing_algorithm_by_algorithm_id_merchant_id(
         &self,
-        _algorithm_id: &common_utils::id_type::RoutingId,
-        _merchant_id: &common_utils::id_type::MerchantId,
+        algorithm_id: &common_utils::id_type::RoutingId,
+        merchant_id: &common_utils::id_type::MerchantId,
     ) -> StorageResult<routing_storage::RoutingAlgorithm> {
-        Err(errors::StorageError::MockDbError)?
+        self.routing_algorithms
+            .lock()
+            .await
+            .iter()
+            .find(|algorithm| {
+                algorithm.algorithm_id == *algorithm_id && algorithm.merchant_id == *merchant_id
+            })
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "cannot find routing algorithm for algorithm_id = {algorithm_id:?} and merchant_id = {merchant_id:?}"
+                ))
+                .into(),
+            )
     }
 
     async fn find_routing_algorithm_metadata_by_algorithm_id_profile_id(
         &self,
-        _algorithm_id: &common_utils::id_type::RoutingId,
-        _profile_id: &common_utils::id_type::ProfileId,
+        algorithm_id: &common_utils::id_type::RoutingId,
+        profile_id: &common_utils::id_type::ProfileId,
     ) -> StorageResult<routing_storage::RoutingProfileMetadata> {
-        Err(errors::StorageError::MockDbError)?
+        self.routing_algorithms
+            .lock()
+            .await
+            .iter()
+            .find(|algorithm| {
+                algorithm.algorithm_id == *algorithm_id && algorithm.profile_id == *profile_id
+            })
+            .map(get_routing_profile_metadata)
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "cannot find routing algorithm for algorithm_id = {algorithm_id:?} and profile_id = {profile_id:?}"
+                ))
+                .into(),
+            )
     }
 
     async fn list_routing_algorithm_metadata_by_profile_id(
         &self,
-        _profile_id: &common_utils::id_type::ProfileId,
-        _limit: i64,
-        _offset: i64,
+        profile_id: &common_utils::id_type::ProfileId,
+        limit: i64,
+        offset: i64,
     ) -> StorageResult<Vec<routing_storage::RoutingProfileMetadata>> {
-        Err(errors::StorageError::MockDbError)?
+        let routing_algorithms = self.routing_algorithms.lock().await;
+        paginate_routing_profile_metadata(
+            routing_algorithms
+                .iter()
+                .filter(|algorithm| algorithm.profile_id == *profile_id),
+            limit,
+            offset,
+        )
     }
 
     async fn list_routing_algorithm_metadata_by_merchant_id(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _limit: i64,
-        _offset: i64,
+        merchant_id: &common_utils::id_type::MerchantId,
+        limit: i64,
+        offset: i64,
     ) -> StorageResult<Vec<routing_storage::RoutingProfileMetadata>> {
-        Err(errors::StorageError::MockDbError)?
+        let routing_algorithms = self.routing_algorithms.lock().await;
+        let mut matching_algorithms = routing_algorithms
+            .iter()
+            .filter(|algorithm| algorithm.merchant_id == *merchant_id)
+            .collect::<Vec<_>>();
+        matching_algorithms.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        paginate_routing_profile_metadata(matching_algorithms.into_iter(), limit, offset)
     }
 
     async fn list_routing_algorithm_metadata_by_merchant_id_transaction_type(
         &self,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _transaction_type: &common_enums::TransactionType,
-        _limit: i64,
-        _offset: i64,
+        merchant_id: &common_utils::id_type::MerchantId,
+        transaction_type: &common_enums::TransactionType,
+        limit: i64,
+        offset: i64,
     ) -> StorageResult<Vec<routing_storage::RoutingProfileMetadata>> {
-        Err(errors::StorageError::MockDbError)?
+        let routing_algorithms = self.routing_algorithms.lock().await;
+        let mut matching_algorithms = routing_algorithms
+            .iter()
+            .filter(|algorithm| {
+                algorithm.merchant_id == *merchant_id
+                    && algorithm.algorithm_for == *transaction_type
+            })
+            .collect::<Vec<_>>();
+        matching_algorithms.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        paginate_routing_profile_metadata(matching_algorithms.into_iter(), limit, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use common_utils::id_type;
+    use diesel_models::{enums, routing_algorithm as routing_storage};
+    use time::macros::datetime;
+
+    use crate::db::{routing_algorithm::RoutingAlgorithmInterface, MockDb};
+
+    fn create_routing_algorithm(
+        algorithm_id: &'static str,
+        algorithm_for: enums::TransactionType,
+        modified_at: time::PrimitiveDateTime,
+    ) -> routing_storage::RoutingAlgorithm {
+        #[allow(clippy::unwrap_used)]
+        routing_storage::RoutingAlgorithm {
+            algorithm_id: id_type::RoutingId::try_from(Cow::from(algorithm_id)).unwrap(),
+            profile_id: id_type::ProfileId::try_from(Cow::from("profile_1")).unwrap(),
+            merchant_id: id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap(),
+            name: algorithm_id.to_string(),
+            description: None,
+            kind: enums::RoutingAlgorithmKind::Single,
+            algorithm_data: serde_json::Value::Null,
+            created_at: datetime!(2024-01-01 0:00),
+            modified_at,
+            algorithm_for,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mockdb_routing_algorithm_interface() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        for (algorithm_id, algorithm_for, modified_at) in [
+            (
+                "routing_1",
+                enums::TransactionType::Payment,
+                datetime!(2024-01-01 0:00),
+            ),
+            (
+                "routing_2",
+                enums::TransactionType::Payout,
+                datetime!(2024-01-02 0:00),
+            ),
+            (
+                "routing_3",
+                enums::TransactionType::Payment,
+                datetime!(2024-01-03 0:00),
+            ),
+        ] {
+            mockdb
+                .insert_routing_algorithm(create_routing_algorithm(
+                    algorithm_id,
+                    algorithm_for,
+                    modified_at,
+                ))
+                .await
+                .unwrap();
+        }
+
+        assert!(mockdb
+            .insert_routing_algorithm(create_routing_algorithm(
+                "routing_1",
+                enums::TransactionType::Payment,
+                datetime!(2024-01-04 0:00),
+            ))
+            .await
+            .is_err());
+
+        let merchant_id = id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        let algorithm_ids = mockdb
+            .list_routing_algorithm_metadata_by_merchant_id_transaction_type(
+                &merchant_id,
+                &enums::TransactionType::Payment,
+                10,
+                0,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|metadata| metadata.algorithm_id.get_string_repr().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(algorithm_ids, vec!["routing_3", "routing_1"]);
+
+        let algorithm_ids = mockdb
+            .list_routing_algorithm_metadata_by_merchant_id(&merchant_id, 1, 1)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|metadata| metadata.algorithm_id.get_string_repr().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(algorithm_ids, vec!["routing_2"]);
+
+        let error = mockdb
+            .find_routing_algorithm_by_algorithm_id_merchant_id(
+                &id_type::RoutingId::try_from(Cow::from("routing_4")).unwrap(),
+                &merchant_id,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            crate::core::errors::StorageError::ValueNotFound(_)
+        ));
     }
 }
@@ -21,6 +21,8 @@ pub mod files;
 pub mod fraud_check;
 pub mod gsm;
 pub mod health;
+#[cfg(feature = "olap")]
+pub mod kv_consistency;
 pub mod lock_utils;
 pub mod locker_migration;
 pub mod mandates;
@@ -35,6 +37,8 @@ pub mod payouts;
 #[cfg(any(feature = "olap", feature = "oltp"))]
 pub mod pm_auth;
 pub mod poll;
+#[cfg(feature = "olap")]
+pub mod process_tracker;
 #[cfg(feature = "recon")]
 pub mod recon;
 pub mod refunds;
@@ -65,7 +69,9 @@ pub use self::app::{
     Poll, Refunds, SessionState, User, Webhooks,
 };
 #[cfg(feature = "olap")]
-pub use self::app::{Blocklist, Organization, Routing, Verify, WebhookEvents};
+pub use self::app::{
+    Blocklist, KvConsistency, Organization, ProcessTracker, Routing, Verify, WebhookEvents,
+};
 #[cfg(feature = "payouts")]
 pub use self::app::{PayoutLink, Payouts};
 #[cfg(all(
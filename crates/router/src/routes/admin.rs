@@ -3,7 +3,7 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::{admin::*, api_locking},
+    core::{admin::*, api_locking, storage_scheme_migration},
     services::{api, authentication as auth, authorization::permissions::Permission},
     types::api::admin,
 };
@@ -1072,6 +1072,59 @@ pub async fn merchant_account_kv_status(
     .await
 }
 
+/// Merchant Account - Start Storage Scheme Migration
+///
+/// Migrate the live data of the Merchant Account to another storage scheme. The migration is run
+/// by the scheduler.
+#[instrument(skip_all, fields(flow = ?Flow::StorageSchemeMigrationStart))]
+pub async fn merchant_account_start_storage_scheme_migration(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<common_utils::id_type::MerchantId>,
+    json_payload: web::Json<api_models::kv_consistency::StorageSchemeMigrationRequest>,
+) -> HttpResponse {
+    let flow = Flow::StorageSchemeMigrationStart;
+    let mut payload = json_payload.into_inner();
+    payload.merchant_id = path.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req, _| storage_scheme_migration::start_storage_scheme_migration(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+/// Merchant Account - Retrieve Storage Scheme Migration
+///
+/// Retrieve the progress of the storage scheme migration of the Merchant Account
+#[instrument(skip_all, fields(flow = ?Flow::StorageSchemeMigrationRetrieve))]
+pub async fn merchant_account_retrieve_storage_scheme_migration(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<common_utils::id_type::MerchantId>,
+) -> HttpResponse {
+    let flow = Flow::StorageSchemeMigrationRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id, _| {
+            storage_scheme_migration::retrieve_storage_scheme_migration(state, merchant_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
 /// Merchant Account - KV Status
 ///
 /// Toggle KV mode for the Merchant Account
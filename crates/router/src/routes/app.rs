@@ -12,7 +12,10 @@ use common_enums::TransactionType;
 #[cfg(feature = "partial-auth")]
 use common_utils::crypto::Blake3;
 #[cfg(feature = "email")]
-use external_services::email::{ses::AwsSes, EmailService};
+use external_services::email::{
+    file_sink::FileSinkEmailClient, ses::AwsSes, smtp::SmtpServer, AvailableEmailClients,
+    EmailService,
+};
 use external_services::file_storage::FileStorageInterface;
 use hyperswitch_interfaces::{
     encryption_interface::EncryptionManagementInterface,
@@ -59,7 +62,8 @@ use super::webhooks::*;
 #[cfg(feature = "olap")]
 use super::{
     admin::*, api_keys::*, apple_pay_certificates_migration, connector_onboarding::*, disputes::*,
-    files::*, gsm::*, payment_link::*, user::*, user_role::*, webhook_events::*,
+    files::*, gsm::*, kv_consistency, payment_link::*, process_tracker, user::*, user_role::*,
+    webhook_events::*,
 };
 use super::{cache::*, health::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
@@ -114,6 +118,16 @@ impl scheduler::SchedulerSessionState for SessionState {
     fn get_db(&self) -> Box<dyn SchedulerInterface> {
         self.store.get_scheduler_db()
     }
+
+    fn get_retry_policy(
+        &self,
+        runner: diesel_models::process_tracker::ProcessTrackerRunner,
+    ) -> Option<scheduler::RetryPolicy> {
+        self.conf
+            .scheduler
+            .as_ref()
+            .and_then(|scheduler| scheduler.retry_policies.get(&runner).copied())
+    }
 }
 impl SessionState {
     pub fn get_req_state(&self) -> ReqState {
@@ -258,12 +272,25 @@ impl AsRef<Self> for AppState {
     }
 }
 
+/// # Panics
+///
+/// Panics if the SMTP or file sink email client can't be created
 #[cfg(feature = "email")]
-pub async fn create_email_client(settings: &settings::Settings<RawSecret>) -> impl EmailService {
+#[allow(clippy::expect_used)]
+pub async fn create_email_client(
+    settings: &settings::Settings<RawSecret>,
+) -> Arc<dyn EmailService> {
     match settings.email.active_email_client {
-        external_services::email::AvailableEmailClients::SES => {
-            AwsSes::create(&settings.email, settings.proxy.https_url.to_owned()).await
+        AvailableEmailClients::SES => {
+            Arc::new(AwsSes::create(&settings.email, settings.proxy.https_url.to_owned()).await)
         }
+        AvailableEmailClients::SMTP => Arc::new(
+            SmtpServer::create(&settings.email).expect("Failed to create SMTP email client"),
+        ),
+        AvailableEmailClients::FileSink => Arc::new(
+            FileSinkEmailClient::create(&settings.email)
+                .expect("Failed to create file sink email client"),
+        ),
     }
 }
 
@@ -351,7 +378,7 @@ impl AppState {
             }
 
             #[cfg(feature = "email")]
-            let email_client = Arc::new(create_email_client(&conf).await);
+            let email_client = create_email_client(&conf).await;
 
             let file_storage_client = conf.file_storage.get_file_storage_client().await;
 
@@ -745,6 +772,9 @@ impl Routing {
                     routing::routing_unlink_config(state, req, payload, &TransactionType::Payment)
                 })),
             )
+            .service(
+                web::resource("/simulate").route(web::post().to(routing::routing_simulate_config)),
+            )
             .service(
                 web::resource("/decision")
                     .route(web::put().to(routing::upsert_decision_manager_config))
@@ -1217,6 +1247,11 @@ impl MerchantAccount {
                     .route(web::post().to(merchant_account_toggle_kv))
                     .route(web::get().to(merchant_account_kv_status)),
             )
+            .service(
+                web::resource("/{id}/kv/migration")
+                    .route(web::post().to(merchant_account_start_storage_scheme_migration))
+                    .route(web::get().to(merchant_account_retrieve_storage_scheme_migration)),
+            )
             .service(
                 web::resource("/transfer").route(web::post().to(merchant_account_transfer_keys)),
             )
@@ -1510,7 +1545,10 @@ impl Cache {
     pub fn server(state: AppState) -> Scope {
         web::scope("/cache")
             .app_data(web::Data::new(state))
+            .service(web::resource("/invalidate").route(web::post().to(invalidate_by_pattern)))
             .service(web::resource("/invalidate/{key}").route(web::post().to(invalidate)))
+            .service(web::resource("/stats").route(web::get().to(get_statistics)))
+            .service(web::resource("/{name}/keys").route(web::get().to(list_keys)))
     }
 }
 
@@ -1677,6 +1715,44 @@ impl Gsm {
     }
 }
 
+#[cfg(feature = "olap")]
+pub struct ProcessTracker;
+
+#[cfg(feature = "olap")]
+impl ProcessTracker {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/process_tracker")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/list").route(web::get().to(process_tracker::list_processes)))
+            .service(
+                web::resource("/{process_id}")
+                    .route(web::get().to(process_tracker::retrieve_process)),
+            )
+            .service(
+                web::resource("/{process_id}/retry")
+                    .route(web::post().to(process_tracker::retry_process)),
+            )
+            .service(
+                web::resource("/{process_id}/cancel")
+                    .route(web::post().to(process_tracker::cancel_process)),
+            )
+    }
+}
+
+#[cfg(feature = "olap")]
+pub struct KvConsistency;
+
+#[cfg(feature = "olap")]
+impl KvConsistency {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/kv_consistency")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/check").route(web::post().to(kv_consistency::check_kv_consistency)),
+            )
+    }
+}
+
 #[cfg(feature = "olap")]
 pub struct Verify;
 
@@ -1,4 +1,5 @@
 use actix_web::{web, HttpRequest, Responder};
+use api_models::cache as cache_api_types;
 use router_env::{instrument, tracing, Flow};
 
 use super::AppState;
@@ -28,3 +29,61 @@ pub async fn invalidate(
     )
     .await
 }
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheInvalidatePattern))]
+pub async fn invalidate_by_pattern(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<cache_api_types::CacheInvalidateRequest>,
+) -> impl Responder {
+    let flow = Flow::CacheInvalidatePattern;
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, payload, _| cache::invalidate_by_pattern(state, payload),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheStatistics))]
+pub async fn get_statistics(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let flow = Flow::CacheStatistics;
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, _, _, _| cache::get_statistics(state),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheKeysList))]
+pub async fn list_keys(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<cache_api_types::CacheKeysListConstraints>,
+) -> impl Responder {
+    let flow = Flow::CacheKeysList;
+    let name = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        query_payload.into_inner(),
+        |state, _, constraints, _| cache::list_keys(state, name.clone(), constraints),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
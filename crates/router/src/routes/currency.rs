@@ -34,17 +34,25 @@ pub async fn convert_forex(
     let amount = params.amount;
     let to_currency = &params.to_currency;
     let from_currency = &params.from_currency;
+    let rounding_strategy = params.rounding_strategy.unwrap_or_default();
+    let rates_timestamp = params.rates_timestamp;
+    let payment_id = params.payment_id.clone();
     Box::pin(api::server_wrap(
         flow,
         state.clone(),
         &req,
         (),
-        |state, _, _, _| {
+        |state, auth: auth::AuthenticationData, _, _| {
             currency::convert_forex(
                 state,
+                auth.merchant_account,
+                auth.key_store,
                 amount.get_amount_as_i64(),
                 to_currency.to_string(),
                 from_currency.to_string(),
+                rounding_strategy,
+                rates_timestamp,
+                payment_id.clone(),
             )
         },
         auth::auth_type(
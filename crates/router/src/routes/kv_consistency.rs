@@ -0,0 +1,30 @@
+use actix_web::{web, HttpRequest, Responder};
+use api_models::kv_consistency as kv_consistency_api_types;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, kv_consistency},
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::KvConsistencyCheck))]
+pub async fn check_kv_consistency(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<kv_consistency_api_types::KvConsistencyCheckRequest>,
+) -> impl Responder {
+    let flow = Flow::KvConsistencyCheck;
+    let payload = json_payload.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req, _| kv_consistency::check_kv_consistency(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
@@ -37,6 +37,8 @@ pub enum ApiIdentifier {
     Recon,
     Poll,
     ApplePayCertificatesMigration,
+    ProcessTracker,
+    KvConsistency,
 }
 
 impl From<Flow> for ApiIdentifier {
@@ -63,6 +65,7 @@ impl From<Flow> for ApiIdentifier {
             | Flow::RoutingUpdateConfig
             | Flow::RoutingUpdateDefaultConfig
             | Flow::RoutingDeleteConfig
+            | Flow::RoutingSimulateConfig
             | Flow::DecisionManagerDeleteConfig
             | Flow::DecisionManagerRetrieveConfig
             | Flow::DecisionManagerUpsertConfig => Self::Routing,
@@ -177,7 +180,10 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::CreateFile | Flow::DeleteFile | Flow::RetrieveFile => Self::Files,
 
-            Flow::CacheInvalidate => Self::Cache,
+            Flow::CacheInvalidate
+            | Flow::CacheInvalidatePattern
+            | Flow::CacheStatistics
+            | Flow::CacheKeysList => Self::Cache,
 
             Flow::BusinessProfileCreate
             | Flow::BusinessProfileUpdate
@@ -203,6 +209,15 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::ApplePayCertificatesMigration => Self::ApplePayCertificatesMigration,
 
+            Flow::ProcessTrackerList
+            | Flow::ProcessTrackerRetrieve
+            | Flow::ProcessTrackerRetry
+            | Flow::ProcessTrackerCancel => Self::ProcessTracker,
+
+            Flow::KvConsistencyCheck
+            | Flow::StorageSchemeMigrationStart
+            | Flow::StorageSchemeMigrationRetrieve => Self::KvConsistency,
+
             Flow::UserConnectAccount
             | Flow::UserSignUp
             | Flow::UserSignIn
@@ -62,7 +62,7 @@ use crate::{
     core::{
         api_locking,
         errors::{self, CustomResult},
-        payments,
+        idempotency, payments,
     },
     events::{
         api_logs::{ApiEvent, ApiEventMetric, ApiEventsType},
@@ -697,7 +697,14 @@ pub async fn server_wrap_util<'a, 'b, U, T, Q, F, Fut, E, OErr>(
     func: F,
     api_auth: &dyn AuthenticateAndFetch<U, SessionState>,
     lock_action: api_locking::LockAction,
-) -> CustomResult<ApplicationResponse<Q>, OErr>
+    idempotency_input: Option<idempotency::IdempotencyInput>,
+) -> CustomResult<
+    (
+        ApplicationResponse<Q>,
+        Option<idempotency::IdempotencyClaim>,
+    ),
+    OErr,
+>
 where
     F: Fn(SessionState, U, T, ReqState) -> Fut,
     'b: 'a,
@@ -782,20 +789,65 @@ where
 
     tracing::Span::current().record("merchant_id", merchant_id.get_string_repr().to_owned());
 
-    let output = {
-        lock_action
-            .clone()
-            .perform_locking_action(&session_state, merchant_id.to_owned())
-            .await
-            .switch()?;
-        let res = func(session_state.clone(), auth_out, payload, request_state)
-            .await
+    let claim_outcome = match idempotency_input {
+        Some(idempotency_input) => Some(
+            idempotency_input
+                .claim(&session_state, &merchant_id)
+                .await
+                .switch()?,
+        ),
+        None => None,
+    };
+
+    let (output, idempotency_claim) = match claim_outcome {
+        Some(idempotency::ClaimOutcome::Replay(response)) => (
+            Ok(ApplicationResponse::IdempotentReplay((
+                response.body,
+                response.headers,
+            ))),
+            None,
+        ),
+        claim_outcome => {
+            let idempotency_claim = match claim_outcome {
+                Some(idempotency::ClaimOutcome::Claimed(idempotency_claim)) => {
+                    Some(idempotency_claim)
+                }
+                Some(idempotency::ClaimOutcome::Replay(_)) | None => None,
+            };
+            let lock_result = lock_action
+                .clone()
+                .perform_locking_action(&session_state, merchant_id.to_owned())
+                .await
+                .switch();
+            if let Err(error) = lock_result {
+                if let Some(idempotency_claim) = idempotency_claim {
+                    idempotency_claim.release().await;
+                }
+                return Err(error);
+            }
+            let work = func(session_state.clone(), auth_out, payload, request_state);
+            let res = match idempotency_claim.as_ref() {
+                Some(idempotency_claim) => tokio::select! {
+                    res = work => res,
+                    never = idempotency_claim.keep_in_progress() => match never {},
+                },
+                None => work.await,
+            }
             .switch();
-        lock_action
-            .free_lock_action(&session_state, merchant_id.to_owned())
-            .await
-            .switch()?;
-        res
+            // Failed requests are not replayed, so that they can be retried with the same key
+            let idempotency_claim = match (res.is_err(), idempotency_claim) {
+                (true, Some(idempotency_claim)) => {
+                    idempotency_claim.release().await;
+                    None
+                }
+                (_, idempotency_claim) => idempotency_claim,
+            };
+            lock_action
+                .free_lock_action(&session_state, merchant_id.to_owned())
+                .await
+                .switch()?;
+            (res, idempotency_claim)
+        }
     };
     let request_duration = Instant::now()
         .saturating_duration_since(start_instant)
@@ -865,7 +917,7 @@ where
         merchant_id.to_owned(),
     );
 
-    output
+    output.map(|response| (response, idempotency_claim))
 }
 
 #[instrument(
@@ -918,6 +970,17 @@ where
         tag = ?Tag::BeginRequest, payload = ?payload,
     headers = ?incoming_header_to_log);
 
+    let idempotency_input = match idempotency::IdempotencyInput::from_request(
+        &flow,
+        incoming_request_header,
+        request_method,
+        url_path,
+        &payload,
+    ) {
+        Ok(idempotency_input) => idempotency_input,
+        Err(error) => return log_and_return_error_response(error),
+    };
+
     let server_wrap_util_res = metrics::request::record_request_time_metric(
         server_wrap_util(
             &flow,
@@ -928,18 +991,32 @@ where
             func,
             api_auth,
             lock_action,
+            idempotency_input,
         ),
         &flow,
     )
     .await
-    .map(|response| {
+    .map(|(response, idempotency_claim)| {
         logger::info!(api_response =? response);
-        response
+        (response, idempotency_claim)
     });
 
+    let (server_wrap_util_res, idempotency_claim) = match server_wrap_util_res {
+        Ok((response, idempotency_claim)) => (Ok(response), idempotency_claim),
+        Err(error) => (Err(error), None),
+    };
+    // The response to be replayed for retries of the request with the same idempotency key
+    let mut idempotent_response = None;
+
     let res = match server_wrap_util_res {
         Ok(ApplicationResponse::Json(response)) => match serde_json::to_string(&response) {
-            Ok(res) => http_response_json(res),
+            Ok(res) => {
+                idempotent_response = Some(idempotency::IdempotentResponse {
+                    body: res.clone(),
+                    headers: Vec::new(),
+                });
+                http_response_json(res)
+            }
             Err(_) => http_response_err(
                 r#"{
                     "error": {
@@ -1042,7 +1119,13 @@ where
                 }
             });
             match serde_json::to_string(&response) {
-                Ok(res) => http_response_json_with_headers(res, headers, request_elapsed_time),
+                Ok(res) => {
+                    idempotent_response = Some(idempotency::IdempotentResponse {
+                        body: res.clone(),
+                        headers: headers.clone(),
+                    });
+                    http_response_json_with_headers(res, headers, request_elapsed_time)
+                }
                 Err(_) => http_response_err(
                     r#"{
                         "error": {
@@ -1052,9 +1135,23 @@ where
                 ),
             }
         }
+        Ok(ApplicationResponse::IdempotentReplay((response, mut headers))) => {
+            headers.push((
+                crate::headers::IDEMPOTENT_REPLAYED.to_string(),
+                "true".to_string().into(),
+            ));
+            http_response_json_with_headers(response, headers, None)
+        }
         Err(error) => log_and_return_error_response(error),
     };
 
+    if let Some(idempotency_claim) = idempotency_claim {
+        match idempotent_response {
+            Some(response) => idempotency_claim.complete(response).await,
+            None => idempotency_claim.release().await,
+        }
+    }
+
     let response_code = res.status().as_u16();
     tracing::Span::current().record("status_code", response_code);
 
@@ -56,6 +56,10 @@ pub enum EmailBody {
         api_key_name: String,
         prefix: String,
     },
+    ReportGenerated {
+        report_kind: String,
+        link: String,
+    },
 }
 
 pub mod html {
@@ -144,6 +148,11 @@ Email         : {user_email}
                 prefix = prefix,
                 expires_in = expires_in,
             ),
+            EmailBody::ReportGenerated { report_kind, link } => format!(
+                include_str!("assets/report_generated.html"),
+                report_kind = report_kind,
+                link = link,
+            ),
         }
     }
 }
@@ -529,3 +538,28 @@ impl EmailData for ApiKeyExpiryReminder {
         })
     }
 }
+
+pub struct ReportGenerated {
+    pub recipient_email: domain::UserEmail,
+    pub subject: &'static str,
+    pub report_kind: String,
+    pub link: String,
+}
+
+#[async_trait::async_trait]
+impl EmailData for ReportGenerated {
+    async fn get_email_data(&self) -> CustomResult<EmailContents, EmailError> {
+        let recipient = self.recipient_email.clone().into_inner();
+
+        let body = html::get_html_body(EmailBody::ReportGenerated {
+            report_kind: self.report_kind.clone(),
+            link: self.link.clone(),
+        });
+
+        Ok(EmailContents {
+            subject: self.subject.to_string(),
+            body: external_services::email::IntermediateString::new(body),
+            recipient,
+        })
+    }
+}
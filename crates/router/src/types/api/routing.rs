@@ -3,7 +3,8 @@ pub use api_models::{
     routing::{
         ConnectorVolumeSplit, RoutableChoiceKind, RoutableConnectorChoice, RoutingAlgorithm,
         RoutingAlgorithmKind, RoutingAlgorithmRef, RoutingConfigRequest, RoutingDictionary,
-        RoutingDictionaryRecord, StraightThroughAlgorithm,
+        RoutingDictionaryRecord, StraightThroughAlgorithm, SuccessRateBasedRoutingConfig,
+        SuccessRateWindowParams,
     },
 };
 
@@ -43,8 +43,8 @@ pub mod user_role;
 use std::collections::HashMap;
 
 pub use diesel_models::{
-    process_tracker::business_status, ProcessTracker, ProcessTrackerNew, ProcessTrackerRunner,
-    ProcessTrackerUpdate,
+    process_tracker::business_status, ProcessTracker, ProcessTrackerNew, ProcessTrackerRetryEvent,
+    ProcessTrackerRunner, ProcessTrackerUpdate,
 };
 pub use hyperswitch_domain_models::payments::{
     payment_attempt::{PaymentAttempt, PaymentAttemptNew, PaymentAttemptUpdate},
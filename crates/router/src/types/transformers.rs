@@ -2,7 +2,7 @@
 use actix_web::http::header::HeaderMap;
 use api_models::{
     enums as api_enums, gsm as gsm_api_types, payment_methods, payments,
-    routing::ConnectorSelection,
+    process_tracker as process_tracker_api_types, routing::ConnectorSelection,
 };
 use common_utils::{
     consts::X_HS_LATENCY,
@@ -1539,6 +1539,53 @@ impl ForeignFrom<storage::GatewayStatusMap> for gsm_api_types::GsmResponse {
     }
 }
 
+impl ForeignFrom<storage::ProcessTracker> for process_tracker_api_types::ProcessTrackerResponse {
+    fn foreign_from(value: storage::ProcessTracker) -> Self {
+        // Retries are recorded among the events of the task as serialized retry events
+        let (retry_history, event) = value.event.into_iter().fold(
+            (Vec::new(), Vec::new()),
+            |(mut retry_history, mut event), entry| {
+                match entry
+                    .parse_struct::<storage::ProcessTrackerRetryEvent>("ProcessTrackerRetryEvent")
+                {
+                    Ok(retry_event) => retry_history.push(retry_event.foreign_into()),
+                    Err(_) => event.push(entry),
+                }
+                (retry_history, event)
+            },
+        );
+
+        Self {
+            id: value.id,
+            name: value.name,
+            tag: value.tag,
+            runner: value.runner,
+            status: value.status.to_string(),
+            business_status: value.business_status,
+            retry_count: value.retry_count,
+            schedule_time: value.schedule_time,
+            tracking_data: value.tracking_data,
+            retry_history,
+            event,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl ForeignFrom<storage::ProcessTrackerRetryEvent>
+    for process_tracker_api_types::ProcessTrackerRetryResponse
+{
+    fn foreign_from(value: storage::ProcessTrackerRetryEvent) -> Self {
+        Self {
+            retry_count: value.retry_count,
+            previous_status: value.previous_status.to_string(),
+            retried_at: value.retried_at,
+            schedule_time: value.schedule_time,
+        }
+    }
+}
+
 #[cfg(all(feature = "v2", feature = "customer_v2"))]
 impl ForeignFrom<&domain::Customer> for payments::CustomerDetailsResponse {
     fn foreign_from(_customer: &domain::Customer) -> Self {
@@ -1,8 +1,18 @@
 use std::{collections::HashMap, ops::Deref, str::FromStr, sync::Arc, time::Duration};
 
 use api_models::enums;
-use common_utils::{date_time, errors::CustomResult, events::ApiEventMetric, ext_traits::AsyncExt};
-use currency_conversion::types::{CurrencyFactors, ExchangeRates};
+use common_utils::{
+    date_time, errors::CustomResult, events::ApiEventMetric, ext_traits::AsyncExt, types::MinorUnit,
+};
+use currency_conversion::{
+    conversion::ConversionOptions,
+    error::CurrencyConversionError,
+    providers::{
+        ExchangeRatesProvider, ExchangeRatesSnapshot, FallbackChain, FileRatesProvider,
+        StalenessPolicy, StaticRatesProvider,
+    },
+    types::{CurrencyFactors, ExchangeRates},
+};
 use error_stack::ResultExt;
 use masking::PeekInterface;
 use once_cell::sync::Lazy;
@@ -13,11 +23,17 @@ use tokio::{sync::RwLock, time::sleep};
 
 use crate::{
     logger,
-    routes::app::settings::{Conversion, DefaultExchangeRates},
+    routes::app::settings::{Conversion, DefaultExchangeRates, ForexRatesSource},
     services, SessionState,
 };
 const REDIX_FOREX_CACHE_KEY: &str = "{forex_cache}_lock";
 const REDIX_FOREX_CACHE_DATA: &str = "{forex_cache}_data";
+/// Prefix of the keys holding the snapshots of exchange rates, suffixed by their timestamp
+const REDIX_FOREX_CACHE_HISTORY: &str = "{forex_cache}_history";
+/// Sorted set of the timestamps of the stored snapshots, scored by the timestamp
+const REDIX_FOREX_CACHE_HISTORY_INDEX: &str = "{forex_cache}_history_index";
+/// 90 days
+const DEFAULT_FOREX_HISTORY_RETENTION: u32 = 7_776_000;
 const FOREX_API_TIMEOUT: u64 = 5;
 const FOREX_BASE_URL: &str = "https://openexchangerates.org/api/latest.json?app_id=";
 const FOREX_BASE_CURRENCY: &str = "&base=USD";
@@ -27,7 +43,11 @@ const FALLBACK_FOREX_API_CURRENCY_PREFIX: &str = "USD";
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FxExchangeRatesCacheEntry {
     data: Arc<ExchangeRates>,
+    /// Time at which the rates were published by the source
     timestamp: i64,
+    /// Time at which the rates were fetched, used for expiring the cache
+    #[serde(default)]
+    fetched_at: i64,
 }
 
 static FX_EXCHANGE_RATES_CACHE: Lazy<RwLock<Option<FxExchangeRatesCacheEntry>>> =
@@ -69,6 +89,10 @@ pub enum ForexCacheError {
     RedisLockReleaseFailed,
     #[error("Error writing to redis")]
     RedisWriteError,
+    #[error("Exchange rates not available for the requested time")]
+    HistoricalRatesNotFound,
+    #[error("Invalid FX spread configured for the merchant")]
+    InvalidFxSpread,
     #[error("Not able to acquire write lock")]
     WriteLockNotAcquired,
 }
@@ -96,13 +120,15 @@ impl Deref for FloatDecimal {
 
 impl FxExchangeRatesCacheEntry {
     fn new(exchange_rate: ExchangeRates) -> Self {
+        let now = date_time::now_unix_timestamp();
         Self {
             data: Arc::new(exchange_rate),
-            timestamp: date_time::now_unix_timestamp(),
+            timestamp: now,
+            fetched_at: now,
         }
     }
     fn is_expired(&self, call_delay: i64) -> bool {
-        self.timestamp + call_delay < date_time::now_unix_timestamp()
+        self.fetched_at + call_delay < date_time::now_unix_timestamp()
     }
 }
 
@@ -224,21 +250,12 @@ async fn successive_fetch_and_save_forex(
             if !lock_acquired {
                 return stale_redis_data.ok_or(ForexCacheError::CouldNotAcquireLock.into());
             }
-            let api_rates = fetch_forex_rates(state).await;
-            match api_rates {
+            match fetch_forex_rates_from_sources(state).await {
                 Ok(rates) => successive_save_data_to_redis_local(state, rates).await,
-                Err(error) => {
-                    // API not able to fetch data call secondary service
+                Err(error) => stale_redis_data.ok_or({
                     logger::error!(?error);
-                    let secondary_api_rates = fallback_fetch_forex_rates(state).await;
-                    match secondary_api_rates {
-                        Ok(rates) => Ok(successive_save_data_to_redis_local(state, rates).await?),
-                        Err(error) => stale_redis_data.ok_or({
-                            logger::error!(?error);
-                            ForexCacheError::ApiUnresponsive.into()
-                        }),
-                    }
-                }
+                    ForexCacheError::ApiUnresponsive.into()
+                }),
             }
         }
         Err(error) => stale_redis_data.ok_or({
@@ -252,6 +269,11 @@ async fn successive_save_data_to_redis_local(
     state: &SessionState,
     forex: FxExchangeRatesCacheEntry,
 ) -> CustomResult<FxExchangeRatesCacheEntry, ForexCacheError> {
+    save_forex_to_redis_history(state, &forex)
+        .await
+        .map_err(|error| logger::error!(?error))
+        .ok();
+
     Ok(save_forex_to_redis(state, &forex)
         .await
         .async_and_then(|_rates| async { release_redis_lock(state).await })
@@ -370,8 +392,130 @@ async fn fetch_forex_rates(
     )))
 }
 
+struct ForexApiProvider<'a> {
+    state: &'a SessionState,
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesProvider for ForexApiProvider<'_> {
+    fn name(&self) -> &'static str {
+        "forex_api"
+    }
+
+    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+        fetch_forex_rates(self.state)
+            .await
+            .map(ExchangeRatesSnapshot::from)
+            .map_err(|error| CurrencyConversionError::RatesSourceUnavailable(format!("{error:?}")))
+    }
+}
+
+struct FallbackForexApiProvider<'a> {
+    state: &'a SessionState,
+}
+
+#[async_trait::async_trait]
+impl ExchangeRatesProvider for FallbackForexApiProvider<'_> {
+    fn name(&self) -> &'static str {
+        "fallback_forex_api"
+    }
+
+    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot, CurrencyConversionError> {
+        fetch_fallback_forex_rates(self.state)
+            .await
+            .map(ExchangeRatesSnapshot::from)
+            .map_err(|error| CurrencyConversionError::RatesSourceUnavailable(format!("{error:?}")))
+    }
+}
+
+impl From<ExchangeRatesSnapshot> for FxExchangeRatesCacheEntry {
+    fn from(value: ExchangeRatesSnapshot) -> Self {
+        Self {
+            data: Arc::new(value.rates),
+            timestamp: value.timestamp,
+            fetched_at: date_time::now_unix_timestamp(),
+        }
+    }
+}
+
+impl From<FxExchangeRatesCacheEntry> for ExchangeRatesSnapshot {
+    fn from(value: FxExchangeRatesCacheEntry) -> Self {
+        Self {
+            rates: value.data.as_ref().clone(),
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+fn get_forex_rates_sources_chain(
+    state: &SessionState,
+) -> CustomResult<FallbackChain<'_>, ForexCacheError> {
+    let forex_api = state.conf.forex_api.get_inner();
+    let staleness_policy = StalenessPolicy {
+        max_age: forex_api.max_rates_age,
+        serve_stale: forex_api.serve_stale_rates,
+    };
+
+    if forex_api.rates_sources.is_empty() {
+        return Ok(FallbackChain::new(staleness_policy)
+            .with_provider(ForexApiProvider { state })
+            .with_provider(FallbackForexApiProvider { state }));
+    }
+
+    forex_api.rates_sources.iter().try_fold(
+        FallbackChain::new(staleness_policy),
+        |chain, source| {
+            Ok(match source {
+                ForexRatesSource::ForexApi => chain.with_provider(ForexApiProvider { state }),
+                ForexRatesSource::FallbackForexApi => {
+                    chain.with_provider(FallbackForexApiProvider { state })
+                }
+                ForexRatesSource::File {
+                    path,
+                    format,
+                    base_currency,
+                } => chain.with_provider(FileRatesProvider {
+                    path: path.clone(),
+                    format: *format,
+                    base_currency: *base_currency,
+                }),
+                ForexRatesSource::Static(default_exchange_rates) => {
+                    chain.with_provider(StaticRatesProvider::new(
+                        ExchangeRates::try_from(default_exchange_rates.clone())?,
+                        default_exchange_rates.timestamp,
+                    ))
+                }
+            })
+        },
+    )
+}
+
+async fn fetch_forex_rates_from_sources(
+    state: &SessionState,
+) -> CustomResult<FxExchangeRatesCacheEntry, ForexCacheError> {
+    let snapshot = get_forex_rates_sources_chain(state)?
+        .fetch_exchange_rates(date_time::now_unix_timestamp())
+        .await
+        .change_context(ForexCacheError::ApiUnresponsive)?;
+
+    Ok(FxExchangeRatesCacheEntry::from(snapshot))
+}
+
 pub async fn fallback_fetch_forex_rates(
     state: &SessionState,
+) -> CustomResult<FxExchangeRatesCacheEntry, ForexCacheError> {
+    let rates = fetch_fallback_forex_rates(state).await?;
+    match acquire_redis_lock(state).await {
+        Ok(_) => Ok(successive_save_data_to_redis_local(state, rates).await?),
+        Err(e) => {
+            logger::error!(?e);
+            Ok(rates)
+        }
+    }
+}
+
+async fn fetch_fallback_forex_rates(
+    state: &SessionState,
 ) -> CustomResult<FxExchangeRatesCacheEntry, ForexCacheError> {
     let fallback_forex_api_key = state.conf.forex_api.get_inner().fallback_api_key.peek();
 
@@ -426,15 +570,10 @@ pub async fn fallback_fetch_forex_rates(
         };
     }
 
-    let rates =
-        FxExchangeRatesCacheEntry::new(ExchangeRates::new(enums::Currency::USD, conversions));
-    match acquire_redis_lock(state).await {
-        Ok(_) => Ok(successive_save_data_to_redis_local(state, rates).await?),
-        Err(e) => {
-            logger::error!(?e);
-            Ok(rates)
-        }
-    }
+    Ok(FxExchangeRatesCacheEntry::new(ExchangeRates::new(
+        enums::Currency::USD,
+        conversions,
+    )))
 }
 
 async fn release_redis_lock(
@@ -484,6 +623,123 @@ async fn save_forex_to_redis(
         .change_context(ForexCacheError::RedisWriteError)
 }
 
+fn get_forex_history_key(timestamp: i64) -> String {
+    format!("{REDIX_FOREX_CACHE_HISTORY}_{timestamp}")
+}
+
+/// Unix timestamps are well within the range of integers exactly representable by an `f64`
+#[allow(clippy::as_conversions)]
+fn get_forex_history_score(timestamp: i64) -> f64 {
+    timestamp as f64
+}
+
+async fn save_forex_to_redis_history(
+    app_state: &SessionState,
+    forex_exchange_cache_entry: &FxExchangeRatesCacheEntry,
+) -> CustomResult<(), ForexCacheError> {
+    let retention = i64::from(
+        app_state
+            .conf
+            .forex_api
+            .get_inner()
+            .rates_history_retention
+            .unwrap_or(DEFAULT_FOREX_HISTORY_RETENTION),
+    );
+    let now = date_time::now_unix_timestamp();
+    let timestamp = forex_exchange_cache_entry.timestamp;
+    let redis_conn = app_state
+        .store
+        .get_redis_conn()
+        .change_context(ForexCacheError::RedisConnectionError)?;
+
+    // Snapshots expire once they are older than the retention period, irrespective of when
+    // they were fetched
+    let ttl = retention.saturating_sub(now.saturating_sub(timestamp));
+    if ttl > 0 {
+        redis_conn
+            .serialize_and_set_key_if_not_exist(
+                &get_forex_history_key(timestamp),
+                forex_exchange_cache_entry.data.as_ref(),
+                Some(ttl),
+            )
+            .await
+            .change_context(ForexCacheError::RedisWriteError)?;
+        redis_conn
+            .add_member_to_sorted_set(
+                REDIX_FOREX_CACHE_HISTORY_INDEX,
+                get_forex_history_score(timestamp),
+                timestamp.to_string(),
+            )
+            .await
+            .change_context(ForexCacheError::RedisWriteError)?;
+    }
+
+    redis_conn
+        .remove_sorted_set_members_by_score(
+            REDIX_FOREX_CACHE_HISTORY_INDEX,
+            f64::NEG_INFINITY,
+            get_forex_history_score(now.saturating_sub(retention)),
+        )
+        .await
+        .change_context(ForexCacheError::RedisWriteError)
+        .map(|_| ())
+}
+
+/// Retrieves the exchange rates that were valid at `timestamp`, which are the latest rates
+/// stored at or before it, like the rates used for a payment that is being refunded
+pub async fn get_forex_rates_at(
+    state: &SessionState,
+    timestamp: i64,
+) -> CustomResult<ExchangeRates, ForexCacheError> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(ForexCacheError::RedisConnectionError)?;
+
+    let snapshot_timestamp = redis_conn
+        .get_sorted_set_members_by_score_rev(
+            REDIX_FOREX_CACHE_HISTORY_INDEX,
+            get_forex_history_score(timestamp),
+            f64::NEG_INFINITY,
+            Some(1),
+        )
+        .await
+        .change_context(ForexCacheError::EntryNotFound)?
+        .into_iter()
+        .next()
+        .ok_or(ForexCacheError::HistoricalRatesNotFound)?
+        .parse::<i64>()
+        .change_context(ForexCacheError::ParsingError)?;
+
+    redis_conn
+        .get_and_deserialize_key(&get_forex_history_key(snapshot_timestamp), "ExchangeRates")
+        .await
+        .map_err(|error| match error.current_context() {
+            redis_interface::errors::RedisError::NotFound => {
+                error.change_context(ForexCacheError::HistoricalRatesNotFound)
+            }
+            _ => error.change_context(ForexCacheError::EntryNotFound),
+        })
+}
+
+/// Retrieves the FX spread configured for the merchant, no spread is applied if not configured
+pub async fn get_merchant_fx_spread(
+    state: &SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+) -> CustomResult<Decimal, ForexCacheError> {
+    match state
+        .store
+        .find_config_by_key(&merchant_id.get_fx_spread_key())
+        .await
+    {
+        Ok(config) => Decimal::from_str(config.config.trim())
+            .change_context(ForexCacheError::InvalidFxSpread)
+            .attach_printable_lazy(|| format!("Invalid FX spread: {}", config.config)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(Decimal::ZERO),
+        Err(error) => Err(error.change_context(ForexCacheError::EntryNotFound)),
+    }
+}
+
 async fn retrieve_forex_from_redis(
     app_state: &SessionState,
 ) -> CustomResult<Option<FxExchangeRatesCacheEntry>, ForexCacheError> {
@@ -501,7 +757,7 @@ async fn is_redis_expired(
     call_delay: i64,
 ) -> Option<Arc<ExchangeRates>> {
     redis_cache.and_then(|cache| {
-        if cache.timestamp + call_delay > date_time::now_unix_timestamp() {
+        if cache.fetched_at + call_delay > date_time::now_unix_timestamp() {
             Some(cache.data.clone())
         } else {
             None
@@ -511,19 +767,30 @@ async fn is_redis_expired(
 
 pub async fn convert_currency(
     state: SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
     amount: i64,
     to_currency: String,
     from_currency: String,
+    rounding_strategy: enums::RoundingStrategy,
+    rates_timestamp: Option<i64>,
 ) -> CustomResult<api_models::currency::CurrencyConversionResponse, ForexCacheError> {
-    let forex_api = state.conf.forex_api.get_inner();
-    let rates = get_forex_rates(
-        &state,
-        forex_api.call_delay,
-        forex_api.local_fetch_retry_delay,
-        forex_api.local_fetch_retry_count,
-    )
-    .await
-    .change_context(ForexCacheError::ApiError)?;
+    let rates = match rates_timestamp {
+        Some(timestamp) => get_forex_rates_at(&state, timestamp).await?,
+        None => {
+            let forex_api = state.conf.forex_api.get_inner();
+            get_forex_rates(
+                &state,
+                forex_api.call_delay,
+                forex_api.local_fetch_retry_delay,
+                forex_api.local_fetch_retry_count,
+            )
+            .await
+            .change_context(ForexCacheError::ApiError)?
+            .data
+            .as_ref()
+            .clone()
+        }
+    };
 
     let to_currency = enums::Currency::from_str(to_currency.as_str())
         .change_context(ForexCacheError::CurrencyNotAcceptable)?;
@@ -531,12 +798,22 @@ pub async fn convert_currency(
     let from_currency = enums::Currency::from_str(from_currency.as_str())
         .change_context(ForexCacheError::CurrencyNotAcceptable)?;
 
-    let converted_amount =
-        currency_conversion::conversion::convert(&rates.data, from_currency, to_currency, amount)
-            .change_context(ForexCacheError::ConversionError)?;
+    let options = ConversionOptions {
+        rounding_strategy,
+        spread: get_merchant_fx_spread(&state, merchant_id).await?,
+    };
+    let converted_amount = currency_conversion::conversion::convert_with_options(
+        &rates,
+        from_currency,
+        to_currency,
+        amount,
+        options,
+    )
+    .change_context(ForexCacheError::ConversionError)?;
 
     Ok(api_models::currency::CurrencyConversionResponse {
-        converted_amount: converted_amount.to_string(),
+        converted_amount: converted_amount.amount.to_string(),
+        converted_minor_amount: MinorUnit::new(converted_amount.minor_amount),
         currency: to_currency.to_string(),
     })
 }
@@ -5,5 +5,9 @@ pub mod attach_payout_account_workflow;
 pub mod outgoing_webhook_retry;
 pub mod payment_method_status_update;
 pub mod payment_sync;
+pub mod queued_payment_webhook;
 pub mod refund_router;
+#[cfg(feature = "olap")]
+pub mod report_generation;
+pub mod storage_scheme_migration;
 pub mod tokenized_data;
@@ -0,0 +1,95 @@
+use common_utils::ext_traits::ValueExt;
+use diesel_models::process_tracker::business_status;
+use scheduler::{consumer, utils as pt_utils, workflows::ProcessTrackerWorkflow};
+
+use crate::{
+    core::webhooks::{self, QueuedPaymentWebhookTrackingData},
+    errors, logger,
+    routes::SessionState,
+    types::storage,
+};
+
+/// Delays in seconds between the attempts at processing a queued webhook, along with the number
+/// of attempts made with each delay, while the payment is locked by other requests or earlier
+/// webhooks of the payment are still queued
+const RETRY_FREQUENCIES: [(i32, i32); 2] = [(5, 12), (60, 15)];
+
+pub struct QueuedPaymentWebhookWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for QueuedPaymentWebhookWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: QueuedPaymentWebhookTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("QueuedPaymentWebhookTrackingData")?;
+
+        let webhook_response_tracker =
+            webhooks::perform_queued_payment_webhook(state.clone(), &process.id, tracking_data)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, process_id = %process.id, "Failed to process queued payment webhook");
+                    errors::ProcessTrackerError::FlowExecutionError {
+                        flow: "QueuedPaymentWebhook",
+                    }
+                })?;
+
+        match webhook_response_tracker {
+            Some(webhook_response_tracker) => {
+                logger::info!(
+                    ?webhook_response_tracker,
+                    process_id = %process.id,
+                    "Processed queued payment webhook"
+                );
+                db.as_scheduler()
+                    .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                    .await?;
+            }
+            // The payment is still locked by another request, or earlier webhooks of the payment
+            // are still queued
+            None => {
+                let time_delta = pt_utils::get_delay(process.retry_count + 1, &RETRY_FREQUENCIES);
+
+                match pt_utils::get_time_from_delta(time_delta) {
+                    Some(schedule_time) => {
+                        db.as_scheduler()
+                            .retry_process(process, schedule_time)
+                            .await?
+                    }
+                    // The webhook is kept in the dead letter status, to be processed manually
+                    None => {
+                        logger::error!(
+                            process_id = %process.id,
+                            "Retries exceeded for queued payment webhook"
+                        );
+                        db.as_scheduler()
+                            .dead_letter_process(
+                                process,
+                                business_status::RETRIES_EXCEEDED,
+                                vec![String::from(
+                                    "Queued payment webhook could not be processed within its retry schedule",
+                                )],
+                            )
+                            .await?
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -0,0 +1,113 @@
+use analytics::reports::ReportGenerationTrackingData;
+use common_utils::ext_traits::ValueExt;
+use diesel_models::process_tracker::business_status;
+use scheduler::{consumer, workflows::ProcessTrackerWorkflow, SchedulerSessionState};
+
+use crate::{
+    core::reports,
+    errors, logger,
+    routes::SessionState,
+    types::storage::{self, enums as storage_enums},
+};
+#[cfg(feature = "email")]
+use crate::{services::email::types::ReportGenerated, types::domain::UserEmail};
+
+pub struct ReportGenerationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for ReportGenerationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let tracking_data: ReportGenerationTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("ReportGenerationTrackingData")?;
+
+        // The task is run again once the generated report expires, to delete it
+        if process.business_status == reports::REPORT_GENERATED {
+            reports::delete_expired_report(state, &process.id, &tracking_data)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, report_id = %process.id, "Failed to delete report");
+                    errors::ProcessTrackerError::FlowExecutionError {
+                        flow: "ReportGeneration",
+                    }
+                })?;
+
+            state
+                .get_db()
+                .as_scheduler()
+                .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                .await?;
+            return Ok(());
+        }
+
+        reports::generate_and_upload_report(state, &process.id, &tracking_data)
+            .await
+            .map_err(|error| {
+                logger::error!(?error, report_id = %process.id, "Failed to generate report");
+                errors::ProcessTrackerError::FlowExecutionError {
+                    flow: "ReportGeneration",
+                }
+            })?;
+
+        #[cfg(feature = "email")]
+        {
+            let email_contents = ReportGenerated {
+                recipient_email: UserEmail::new(tracking_data.request.email.clone()).map_err(
+                    |error| {
+                        logger::error!(?error, "Failed to convert recipient's email to UserEmail");
+                        errors::ProcessTrackerError::EApiErrorResponse
+                    },
+                )?,
+                subject: "Your report is ready",
+                report_kind: tracking_data.kind.to_string(),
+                link: reports::get_report_download_link(state, &process.id),
+            };
+
+            // The report can still be downloaded with its ID if the email could not be sent
+            if let Err(error) = state
+                .email_client
+                .clone()
+                .compose_and_send_email(
+                    Box::new(email_contents),
+                    state.conf.proxy.https_url.as_ref(),
+                )
+                .await
+            {
+                logger::error!(?error, report_id = %process.id, "Failed to send report email");
+            }
+        }
+
+        state
+            .get_db()
+            .as_scheduler()
+            .update_process(
+                process,
+                storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: Some(0),
+                    schedule_time: Some(reports::get_report_expiry(state)),
+                    tracking_data: None,
+                    business_status: Some(String::from(reports::REPORT_GENERATED)),
+                    status: Some(storage_enums::ProcessTrackerStatus::New),
+                    updated_at: Some(common_utils::date_time::now()),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -0,0 +1,54 @@
+use common_utils::ext_traits::ValueExt;
+use diesel_models::process_tracker::business_status;
+use scheduler::{consumer, workflows::ProcessTrackerWorkflow};
+
+use crate::{
+    core::storage_scheme_migration::{self, StorageSchemeMigrationTrackingData},
+    errors, logger,
+    routes::SessionState,
+    types::storage,
+};
+
+pub struct StorageSchemeMigrationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for StorageSchemeMigrationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let tracking_data: StorageSchemeMigrationTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("StorageSchemeMigrationTrackingData")?;
+
+        // Failures of the migration itself are recorded in its status, which is reported to the
+        // operator, so the task is not retried
+        storage_scheme_migration::run_storage_scheme_migration(state, &process.id, &tracking_data)
+            .await
+            .map_err(|error| {
+                logger::error!(?error, migration_id = %process.id, "Failed to run storage scheme migration");
+                errors::ProcessTrackerError::FlowExecutionError {
+                    flow: "StorageSchemeMigration",
+                }
+            })?;
+
+        state
+            .get_db()
+            .as_scheduler()
+            .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -242,6 +242,8 @@ pub enum Flow {
     RoutingUpdateDefaultConfig,
     /// Routing delete config
     RoutingDeleteConfig,
+    /// Routing simulate config
+    RoutingSimulateConfig,
     /// Add record to blocklist
     AddToBlocklist,
     /// Delete record from blocklist
@@ -288,6 +290,12 @@ pub enum Flow {
     RetrieveDisputeEvidence,
     /// Invalidate cache flow
     CacheInvalidate,
+    /// Invalidate cache keys by pattern flow
+    CacheInvalidatePattern,
+    /// Cache statistics flow
+    CacheStatistics,
+    /// List cache keys flow
+    CacheKeysList,
     /// Payment Link Retrieve flow
     PaymentLinkRetrieve,
     /// payment Link Initiate flow
@@ -494,6 +502,20 @@ pub enum Flow {
     RefundsManualUpdate,
     /// Manually update the payment details like status, error code, error message etc.
     PaymentsManualUpdate,
+    /// List the process tracker tasks
+    ProcessTrackerList,
+    /// Retrieve a process tracker task
+    ProcessTrackerRetrieve,
+    /// Retry a process tracker task
+    ProcessTrackerRetry,
+    /// Cancel a process tracker task
+    ProcessTrackerCancel,
+    /// Check the consistency of the Redis KV store with Postgres
+    KvConsistencyCheck,
+    /// Start the migration of a merchant to another storage scheme
+    StorageSchemeMigrationStart,
+    /// Retrieve the progress of the migration of a merchant to another storage scheme
+    StorageSchemeMigrationRetrieve,
 }
 
 ///
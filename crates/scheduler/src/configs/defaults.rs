@@ -7,6 +7,9 @@ impl Default for super::settings::SchedulerSettings {
             graceful_shutdown_interval: 60000,
             loop_interval: 5000,
             server: super::settings::Server::default(),
+            recurring_tasks: std::collections::HashMap::new(),
+            retry_policies: std::collections::HashMap::new(),
+            queue: super::settings::QueueTransport::default(),
         }
     }
 }
@@ -41,3 +44,26 @@ impl Default for super::settings::Server {
         }
     }
 }
+
+impl Default for super::settings::RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: 60,
+            multiplier: 2,
+            max_delay: 3600,
+            jitter_percentage: 20,
+            max_age: None,
+        }
+    }
+}
+
+impl Default for super::settings::KafkaQueueSettings {
+    fn default() -> Self {
+        Self {
+            brokers: Vec::new(),
+            poll_timeout: 1000,
+            send_timeout: 5000,
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use diesel_models::process_tracker::ProcessTrackerRunner;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::Deserialize;
 
@@ -10,6 +13,12 @@ pub struct SchedulerSettings {
     pub loop_interval: u64,
     pub graceful_shutdown_interval: u64,
     pub server: Server,
+    /// Recurring tasks, keyed by the name of the task
+    pub recurring_tasks: HashMap<String, RecurringTaskSettings>,
+    /// Retry policies applied when the workflow of a runner fails
+    pub retry_policies: HashMap<ProcessTrackerRunner, RetryPolicy>,
+    /// Transport used to hand over batches of tasks from the producer to the consumer
+    pub queue: QueueTransport,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -37,3 +46,72 @@ pub struct ConsumerSettings {
     pub disabled: bool,
     pub consumer_group: String,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecurringTaskSettings {
+    /// Workflow that executes each run of the task
+    pub runner: ProcessTrackerRunner,
+    /// Five field cron expression (minute, hour, day of month, month, day of week)
+    pub cron: String,
+    /// `UTC`, a fixed offset from UTC like `+05:30` or an IANA time zone name like
+    /// `Europe/London`, defaults to UTC
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub overlap_policy: OverlapPolicy,
+    /// Expiry of the lease held while a run of the task is in progress (in seconds)
+    pub lease_ttl: Option<i64>,
+    /// Tracking data passed to every run of the task
+    #[serde(default)]
+    pub tracking_data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// The next run is scheduled only after the previous run has finished, and a run that is
+    /// picked up while another run of the same task holds the lease is skipped
+    #[default]
+    Skip,
+    /// Runs are scheduled as per the cron expression, irrespective of the previous run
+    Allow,
+}
+
+/// Exponential backoff with jitter for the retries of a failed workflow. A task that has been
+/// attempted `max_attempts` times, or that would be retried after `max_age` has passed since it
+/// was created, is moved to the dead letter status.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Maximum number of executions of the task, including the first one
+    pub max_attempts: i32,
+    /// Delay before the first retry (in seconds)
+    pub initial_delay: i64,
+    /// Factor by which the delay grows with every retry
+    pub multiplier: u32,
+    /// Upper bound on the delay between retries (in seconds)
+    pub max_delay: i64,
+    /// Percentage of the delay by which the delay is randomly shortened
+    pub jitter_percentage: u8,
+    /// Maximum time since the creation of the task after which it is not retried (in seconds)
+    pub max_age: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "transport", rename_all = "snake_case")]
+pub enum QueueTransport {
+    /// The `stream` is a Redis stream, read through Redis consumer groups
+    #[default]
+    RedisStream,
+    /// The `stream` is a Kafka topic, read through Kafka consumer groups
+    Kafka(KafkaQueueSettings),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KafkaQueueSettings {
+    pub brokers: Vec<String>,
+    /// Time to wait for a batch to be available when polling the topic (in milliseconds)
+    pub poll_timeout: u64,
+    /// Time to wait for a batch to be acknowledged by the brokers (in milliseconds)
+    pub send_timeout: u64,
+}
@@ -21,6 +21,18 @@ impl super::settings::SchedulerSettings {
 
         self.server.validate()?;
 
+        self.recurring_tasks
+            .iter()
+            .try_for_each(|(name, task)| task.validate(name))?;
+
+        self.retry_policies
+            .iter()
+            .try_for_each(|(runner, policy)| policy.validate(*runner))?;
+
+        if let super::settings::QueueTransport::Kafka(kafka) = &self.queue {
+            kafka.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -44,3 +56,70 @@ impl super::settings::Server {
         })
     }
 }
+
+impl super::settings::RecurringTaskSettings {
+    pub fn validate(&self, name: &str) -> Result<(), ApplicationError> {
+        let invalid_value = |error: String| {
+            ApplicationError::InvalidConfigurationValueError(format!(
+                "recurring task {name}: {error}"
+            ))
+        };
+
+        crate::recurring::CronSchedule::parse(&self.cron).map_err(invalid_value)?;
+        crate::recurring::parse_timezone(self.timezone.as_deref()).map_err(invalid_value)?;
+
+        common_utils::fp_utils::when(self.lease_ttl.is_some_and(|ttl| ttl <= 0), || {
+            Err(invalid_value("lease ttl must be positive".into()))
+        })
+    }
+}
+
+impl super::settings::RetryPolicy {
+    pub fn validate(
+        &self,
+        runner: diesel_models::process_tracker::ProcessTrackerRunner,
+    ) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        let invalid_value = |error: &str| {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "retry policy of {runner}: {error}"
+            )))
+        };
+
+        when(self.max_attempts < 1, || {
+            invalid_value("max attempts must be at least 1")
+        })?;
+
+        when(
+            self.initial_delay < 0 || self.max_delay < self.initial_delay,
+            || {
+                invalid_value(
+                    "delays must be non-negative, with the max delay not below the initial delay",
+                )
+            },
+        )?;
+
+        when(self.multiplier < 1, || {
+            invalid_value("multiplier must be at least 1")
+        })?;
+
+        when(self.jitter_percentage > 100, || {
+            invalid_value("jitter percentage must not exceed 100")
+        })?;
+
+        when(self.max_age.is_some_and(|max_age| max_age <= 0), || {
+            invalid_value("max age must be positive")
+        })
+    }
+}
+
+impl super::settings::KafkaQueueSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(self.brokers.is_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "scheduler kafka brokers must not be empty".into(),
+            ))
+        })
+    }
+}
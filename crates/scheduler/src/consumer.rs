@@ -12,7 +12,7 @@ use diesel_models::enums;
 pub use diesel_models::{self, process_tracker as storage};
 use error_stack::ResultExt;
 use futures::future;
-use redis_interface::{RedisConnectionPool, RedisEntryId};
+use redis_interface::RedisConnectionPool;
 use router_env::{
     instrument,
     tracing::{self, Instrument},
@@ -25,7 +25,8 @@ use super::env::logger;
 pub use super::workflows::ProcessTrackerWorkflow;
 use crate::{
     configs::settings::SchedulerSettings, db::process_tracker::ProcessTrackerInterface, errors,
-    metrics, utils as pt_utils, SchedulerAppState, SchedulerInterface, SchedulerSessionState,
+    metrics, queue::SchedulerQueue, recurring, utils as pt_utils, SchedulerAppState,
+    SchedulerInterface, SchedulerSessionState,
 };
 
 // Valid consumer business statuses
@@ -64,6 +65,8 @@ where
     let mut shutdown_interval =
         tokio::time::interval(Duration::from_millis(settings.graceful_shutdown_interval));
 
+    let queue = SchedulerQueue::from_settings(&settings)?;
+
     let consumer_operation_counter = sync::Arc::new(atomic::AtomicU64::new(0));
     let signal = get_allowed_signals()
         .map_err(|error| {
@@ -92,6 +95,7 @@ where
                     pt_utils::consumer_operation_handler(
                         session_state.clone(),
                         settings.clone(),
+                        queue.for_tenant(tenant.as_str()),
                         |error| {
                             logger::error!(?error, "Failed to perform consumer operation");
                         },
@@ -138,21 +142,24 @@ where
 pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
     state: &T,
     settings: &SchedulerSettings,
+    queue: &SchedulerQueue,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) -> CustomResult<(), errors::ProcessTrackerError> {
     let stream_name = settings.stream.clone();
     let group_name = settings.consumer.consumer_group.clone();
     let consumer_name = format!("consumer_{}", Uuid::new_v4());
 
-    let _group_created = &mut state
-        .get_db()
-        .consumer_group_create(&stream_name, &group_name, &RedisEntryId::AfterLastID)
+    let _group_created = &mut queue
+        .create_consumer_group(state.get_db().as_scheduler(), &stream_name, &group_name)
         .await;
 
-    let mut tasks = state
-        .get_db()
-        .as_scheduler()
-        .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
+    let mut tasks = queue
+        .fetch_consumer_tasks(
+            state.get_db().as_scheduler(),
+            &stream_name,
+            &group_name,
+            &consumer_name,
+        )
         .await?;
 
     if !tasks.is_empty() {
@@ -167,12 +174,17 @@ pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
 
         metrics::TASK_CONSUMED.add(&metrics::CONTEXT, 1, &[]);
 
-        handler.push(tokio::task::spawn(start_workflow(
-            state.clone(),
-            task.clone(),
-            pickup_time,
-            workflow_selector,
-        )))
+        let workflow = start_workflow(state.clone(), task.clone(), pickup_time, workflow_selector);
+
+        handler.push(match recurring::get_recurring_task(settings, task) {
+            Some(recurring_task) => tokio::task::spawn(recurring::run_recurring_workflow(
+                state.clone(),
+                task.clone(),
+                recurring_task.clone(),
+                workflow,
+            )),
+            None => tokio::task::spawn(workflow),
+        })
     }
     future::join_all(handler).await;
 
@@ -189,6 +201,18 @@ pub async fn fetch_consumer_tasks(
 ) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError> {
     let batches = pt_utils::get_batches(redis_conn, stream_name, group_name, consumer_name).await?;
 
+    start_batch_tasks(db, batches).await
+}
+
+/// Marks the valid tasks of the batches read from the queue as started, and returns them
+#[instrument(skip_all)]
+pub async fn start_batch_tasks<T>(
+    db: &T,
+    batches: Vec<types::ProcessTrackerBatch>,
+) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError>
+where
+    T: ProcessTrackerInterface + ?Sized,
+{
     // Returning early to avoid execution of database queries when `batches` is empty
     if batches.is_empty() {
         return Ok(Vec::new());
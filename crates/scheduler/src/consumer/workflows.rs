@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use common_utils::errors::CustomResult;
 pub use diesel_models::process_tracker as storage;
 use diesel_models::process_tracker::business_status;
+use error_stack::ResultExt;
 use router_env::logger;
 
-use crate::{errors, SchedulerSessionState};
+use crate::{errors, retry_policy, SchedulerSessionState};
 
 pub type WorkflowSelectorFn =
     fn(&storage::ProcessTracker) -> Result<(), errors::ProcessTrackerError>;
@@ -33,12 +34,26 @@ pub trait ProcessTrackerWorkflows<T>: Send + Sync {
         let output = operation.execute_workflow(app_state, process.clone()).await;
         match output {
             Ok(_) => operation.success_handler(app_state, process).await,
-            Err(error) => match operation
-                .error_handler(app_state, process.clone(), error)
-                .await
-            {
-                Ok(_) => (),
-                Err(error) => {
+            Err(error) => {
+                // Failures are retried as per the retry policy of the runner when one is
+                // configured, and handled by the workflow otherwise
+                let result = match retry_policy::get_retry_policy(app_state, &process) {
+                    Some(policy) => retry_policy::retry_or_dead_letter(
+                        app_state.get_db().as_scheduler(),
+                        process.clone(),
+                        &policy,
+                        &error,
+                    )
+                    .await
+                    .change_context(errors::ProcessTrackerError::ProcessUpdateFailed),
+                    None => {
+                        operation
+                            .error_handler(app_state, process.clone(), error)
+                            .await
+                    }
+                };
+
+                if let Err(error) = result {
                     logger::error!(
                         ?error,
                         "Failed to handle process tracker workflow execution error"
@@ -55,7 +70,7 @@ pub trait ProcessTrackerWorkflows<T>: Send + Sync {
                         logger::error!(?error, "Failed to update process business status");
                     }
                 }
-            },
+            }
         };
         Ok(())
     }
@@ -26,6 +26,15 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError>;
 
+    /// Updates the process only if its status is still the expected one, returns `None` if the
+    /// status of the process has changed in the meantime
+    async fn update_process_if_status(
+        &self,
+        this: storage::ProcessTracker,
+        expected_status: storage_enums::ProcessTrackerStatus,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         task_ids: Vec<String>,
@@ -52,7 +61,15 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
     async fn finish_process_with_business_status(
         &self,
         this: storage::ProcessTracker,
-        business_status: &'static str,
+        business_status: &str,
+    ) -> CustomResult<(), errors::StorageError>;
+
+    /// Moves the process to the dead letter status, recording the errors it failed with
+    async fn dead_letter_process(
+        &self,
+        this: storage::ProcessTracker,
+        business_status: &str,
+        error_chain: Vec<String>,
     ) -> CustomResult<(), errors::StorageError>;
 
     async fn find_processes_by_time_status(
@@ -62,6 +79,29 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         status: storage_enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn find_processes_by_runner_status_time(
+        &self,
+        runner: storage::ProcessTrackerRunner,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        time_lower_limit: PrimitiveDateTime,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Finds the process with the given name that is scheduled the latest
+    async fn find_latest_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Finds the process with the given name that was created the earliest, among the processes
+    /// that are neither finished nor dead lettered
+    async fn find_earliest_pending_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -106,6 +146,49 @@ impl ProcessTrackerInterface for Store {
         .map_err(|error| report!(errors::StorageError::from(error)))
     }
 
+    async fn find_processes_by_runner_status_time(
+        &self,
+        runner: storage::ProcessTrackerRunner,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        time_lower_limit: PrimitiveDateTime,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_processes_by_runner_status_time(
+            &conn,
+            &runner.to_string(),
+            status,
+            time_lower_limit,
+            time_upper_limit,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn find_latest_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_latest_process_by_name(&conn, name)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn find_earliest_pending_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_earliest_pending_process_by_name(&conn, name)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
@@ -127,6 +210,18 @@ impl ProcessTrackerInterface for Store {
             .map_err(|error| report!(errors::StorageError::from(error)))
     }
 
+    async fn update_process_if_status(
+        &self,
+        this: storage::ProcessTracker,
+        expected_status: storage_enums::ProcessTrackerStatus,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        this.update_if_status(&conn, expected_status, process)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
     async fn reset_process(
         &self,
         this: storage::ProcessTracker,
@@ -138,6 +233,7 @@ impl ProcessTrackerInterface for Store {
                 status: storage_enums::ProcessTrackerStatus::New,
                 retry_count: 0,
                 schedule_time,
+                event: None,
             },
         )
         .await?;
@@ -150,23 +246,17 @@ impl ProcessTrackerInterface for Store {
         schedule_time: PrimitiveDateTime,
     ) -> CustomResult<(), errors::StorageError> {
         metrics::TASK_RETRIED.add(&metrics::CONTEXT, 1, &[]);
-        let retry_count = this.retry_count + 1;
-        self.update_process(
-            this,
-            storage::ProcessTrackerUpdate::StatusRetryUpdate {
-                status: storage_enums::ProcessTrackerStatus::Pending,
-                retry_count,
-                schedule_time,
-            },
-        )
-        .await?;
+        let retry_update = this
+            .get_retry_update(schedule_time)
+            .map_err(|error| report!(errors::StorageError::from(error)))?;
+        self.update_process(this, retry_update).await?;
         Ok(())
     }
 
     async fn finish_process_with_business_status(
         &self,
         this: storage::ProcessTracker,
-        business_status: &'static str,
+        business_status: &str,
     ) -> CustomResult<(), errors::StorageError> {
         self.update_process(
             this,
@@ -181,6 +271,20 @@ impl ProcessTrackerInterface for Store {
         Ok(())
     }
 
+    async fn dead_letter_process(
+        &self,
+        this: storage::ProcessTracker,
+        business_status: &str,
+        error_chain: Vec<String>,
+    ) -> CustomResult<(), errors::StorageError> {
+        let dead_letter_update = this.get_dead_letter_update(business_status, error_chain);
+        self.update_process(this, dead_letter_update)
+            .await
+            .attach_printable("Failed to move process to dead letter status")?;
+        metrics::TASK_DEAD_LETTERED.add(&metrics::CONTEXT, 1, &[]);
+        Ok(())
+    }
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         task_ids: Vec<String>,
@@ -230,11 +334,87 @@ impl ProcessTrackerInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn find_processes_by_runner_status_time(
+        &self,
+        runner: storage::ProcessTrackerRunner,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        time_lower_limit: PrimitiveDateTime,
+        time_upper_limit: PrimitiveDateTime,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let runner = runner.to_string();
+        let mut processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.runner.as_deref() == Some(runner.as_str())
+                    && status.map_or(true, |status| process.status == status)
+                    && process.created_at >= time_lower_limit
+                    && process.created_at <= time_upper_limit
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let offset = offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .unwrap_or_default();
+        let limit = limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(processes.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn find_latest_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        Ok(self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| process.name.as_deref() == Some(name))
+            .max_by_key(|process| process.schedule_time)
+            .cloned())
+    }
+
+    async fn find_earliest_pending_process_by_name(
+        &self,
+        name: &str,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        Ok(self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.name.as_deref() == Some(name)
+                    && !matches!(
+                        process.status,
+                        storage_enums::ProcessTrackerStatus::Finish
+                            | storage_enums::ProcessTrackerStatus::DeadLetter
+                    )
+            })
+            .min_by_key(|process| process.created_at)
+            .cloned())
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
         let mut processes = self.processes.lock().await;
+        if processes.iter().any(|process| process.id == new.id) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "process_tracker",
+                key: Some(new.id.clone()),
+            })?
+        }
         let process = storage::ProcessTracker {
             id: new.id,
             name: new.name,
@@ -263,6 +443,16 @@ impl ProcessTrackerInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn update_process_if_status(
+        &self,
+        _this: storage::ProcessTracker,
+        _expected_status: storage_enums::ProcessTrackerStatus,
+        _process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
     async fn reset_process(
         &self,
         _this: storage::ProcessTracker,
@@ -284,12 +474,35 @@ impl ProcessTrackerInterface for MockDb {
     async fn finish_process_with_business_status(
         &self,
         _this: storage::ProcessTracker,
-        _business_status: &'static str,
+        _business_status: &str,
     ) -> CustomResult<(), errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn dead_letter_process(
+        &self,
+        this: storage::ProcessTracker,
+        business_status: &str,
+        error_chain: Vec<String>,
+    ) -> CustomResult<(), errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let process = processes
+            .iter_mut()
+            .find(|process| process.id == this.id)
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No process tracker task found for id = {}",
+                    this.id
+                ))
+            })?;
+        process.status = storage_enums::ProcessTrackerStatus::DeadLetter;
+        process.business_status = String::from(business_status);
+        process.event.extend(error_chain);
+        process.updated_at = common_utils::date_time::now();
+        Ok(())
+    }
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         _task_ids: Vec<String>,
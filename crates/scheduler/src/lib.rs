@@ -6,6 +6,9 @@ pub mod errors;
 pub mod flow;
 pub mod metrics;
 pub mod producer;
+pub mod queue;
+pub mod recurring;
+pub mod retry_policy;
 pub mod scheduler;
 pub mod settings;
 pub mod utils;
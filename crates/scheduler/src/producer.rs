@@ -15,8 +15,8 @@ use super::{
     metrics,
 };
 use crate::{
-    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow,
-    scheduler::SchedulerInterface, utils::*, SchedulerAppState, SchedulerSessionState,
+    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow, queue::SchedulerQueue,
+    recurring, scheduler::SchedulerInterface, utils::*, SchedulerAppState, SchedulerSessionState,
 };
 
 #[instrument(skip_all)]
@@ -46,6 +46,8 @@ where
 
     tokio::time::sleep(Duration::from_millis(timeout.sample(&mut rng))).await;
 
+    let queue = SchedulerQueue::from_settings(&scheduler_settings)?;
+
     let mut interval =
         tokio::time::interval(Duration::from_millis(scheduler_settings.loop_interval));
 
@@ -70,7 +72,10 @@ where
                 let tenants = state.get_tenants();
                 for tenant in tenants {
                     let session_state = app_state_to_session_state(state, tenant.as_str())?;
-                    match run_producer_flow(&session_state, &scheduler_settings).await {
+                    let tenant_queue = queue.for_tenant(tenant.as_str());
+                    match run_producer_flow(&session_state, &scheduler_settings, &tenant_queue)
+                        .await
+                    {
                         Ok(_) => (),
                         Err(error) => {
                             // Intentionally not propagating error to caller.
@@ -102,11 +107,14 @@ where
 pub async fn run_producer_flow<T>(
     state: &T,
     settings: &SchedulerSettings,
+    queue: &SchedulerQueue,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
     T: SchedulerSessionState,
 {
     lock_acquire_release::<_, _, _>(state.get_db().as_scheduler(), settings, move || async {
+        recurring::materialise_recurring_tasks(state.get_db().as_scheduler(), settings).await?;
+
         let tasks = fetch_producer_tasks(state.get_db().as_scheduler(), settings).await?;
         debug!("Producer count of tasks {}", tasks.len());
 
@@ -117,6 +125,7 @@ where
             SchedulerFlow::Producer,
             tasks,
             settings,
+            queue,
         )
         .await?;
 
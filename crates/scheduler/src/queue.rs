@@ -0,0 +1,406 @@
+//! Transports used to hand over batches of tasks from the producer to the consumer.
+//!
+//! Batches are appended to a queue (a Redis stream or a Kafka topic) named after the `stream`
+//! setting, and read by the consumers through a consumer group. A batch is acknowledged as soon
+//! as it is read, so that it is delivered to only one consumer of the group.
+//!
+//! Each tenant has its own queue. Redis streams are scoped to the tenant by the key prefix of
+//! the Redis connection of the tenant, while the name of a message broker queue is prefixed
+//! with the tenant.
+
+pub mod kafka;
+
+use std::{collections::HashMap, sync::Arc};
+
+use common_utils::errors::CustomResult;
+use diesel_models::process_tracker as storage;
+use error_stack::ResultExt;
+use redis_interface::RedisEntryId;
+
+use crate::{
+    configs::settings::{QueueTransport, SchedulerSettings},
+    consumer::{self, types::ProcessTrackerBatch},
+    errors,
+    scheduler::SchedulerInterface,
+};
+
+/// A queue backed by a message broker with consumer groups, like Kafka
+#[async_trait::async_trait]
+pub trait MessageBrokerQueue: Send + Sync {
+    /// Joins the consumer group of the queue, creating the group if it does not exist. A new
+    /// consumer group only receives the batches appended after it was created.
+    async fn create_consumer_group(
+        &self,
+        queue_name: &str,
+        group_name: &str,
+    ) -> CustomResult<(), errors::ProcessTrackerError>;
+
+    /// Appends the batch to the queue
+    async fn append_batch(
+        &self,
+        queue_name: &str,
+        batch: &ProcessTrackerBatch,
+    ) -> CustomResult<(), errors::ProcessTrackerError>;
+
+    /// Reads the next batch that has not been delivered to the consumer group yet, and
+    /// acknowledges it
+    async fn read_batches(
+        &self,
+        queue_name: &str,
+        group_name: &str,
+        consumer_name: &str,
+    ) -> CustomResult<Vec<ProcessTrackerBatch>, errors::ProcessTrackerError>;
+}
+
+#[derive(Clone)]
+pub enum SchedulerQueue {
+    /// Redis streams and consumer groups, through the scheduler database
+    RedisStream,
+    MessageBroker {
+        broker: Arc<dyn MessageBrokerQueue>,
+        /// Tenant the queue is scoped to, the queue cannot be used until it is scoped to one
+        tenant: Option<String>,
+    },
+}
+
+impl SchedulerQueue {
+    pub fn from_settings(
+        settings: &SchedulerSettings,
+    ) -> CustomResult<Self, errors::ProcessTrackerError> {
+        match &settings.queue {
+            QueueTransport::RedisStream => Ok(Self::RedisStream),
+            QueueTransport::Kafka(kafka_settings) => Ok(Self::MessageBroker {
+                broker: Arc::new(kafka::KafkaQueue::new(kafka_settings)?),
+                tenant: None,
+            }),
+        }
+    }
+
+    /// Scopes the queue to the tenant, so that batches are handed over only between the
+    /// producer and the consumers of the same tenant
+    pub fn for_tenant(&self, tenant: &str) -> Self {
+        match self {
+            Self::RedisStream => Self::RedisStream,
+            Self::MessageBroker { broker, .. } => Self::MessageBroker {
+                broker: broker.clone(),
+                tenant: Some(tenant.to_owned()),
+            },
+        }
+    }
+
+    fn get_tenant_queue_name(
+        tenant: Option<&str>,
+        queue_name: &str,
+    ) -> CustomResult<String, errors::ProcessTrackerError> {
+        tenant
+            .map(|tenant| format!("{tenant}_{queue_name}"))
+            .ok_or(errors::ProcessTrackerError::ConfigurationError)
+            .attach_printable("Scheduler queue is not scoped to a tenant")
+    }
+
+    pub async fn create_consumer_group(
+        &self,
+        db: &dyn SchedulerInterface,
+        queue_name: &str,
+        group_name: &str,
+    ) -> CustomResult<(), errors::ProcessTrackerError> {
+        match self {
+            Self::RedisStream => Ok(db
+                .consumer_group_create(queue_name, group_name, &RedisEntryId::AfterLastID)
+                .await
+                .map_err(errors::ProcessTrackerError::ERedisError)?),
+            Self::MessageBroker { broker, tenant } => {
+                broker
+                    .create_consumer_group(
+                        &Self::get_tenant_queue_name(tenant.as_deref(), queue_name)?,
+                        group_name,
+                    )
+                    .await
+            }
+        }
+    }
+
+    pub async fn append_batch(
+        &self,
+        db: &dyn SchedulerInterface,
+        batch: &ProcessTrackerBatch,
+    ) -> CustomResult<(), errors::ProcessTrackerError> {
+        match self {
+            Self::RedisStream => db
+                .stream_append_entry(
+                    &batch.stream_name,
+                    &RedisEntryId::AutoGeneratedID,
+                    batch.to_redis_field_value_pairs()?,
+                )
+                .await
+                .change_context(errors::ProcessTrackerError::BatchInsertionFailed),
+            Self::MessageBroker { broker, tenant } => {
+                broker
+                    .append_batch(
+                        &Self::get_tenant_queue_name(tenant.as_deref(), &batch.stream_name)?,
+                        batch,
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// Reads the next batch from the queue, and marks its tasks as started
+    pub async fn fetch_consumer_tasks(
+        &self,
+        db: &dyn SchedulerInterface,
+        queue_name: &str,
+        group_name: &str,
+        consumer_name: &str,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError> {
+        match self {
+            Self::RedisStream => {
+                db.fetch_consumer_tasks(queue_name, group_name, consumer_name)
+                    .await
+            }
+            Self::MessageBroker { broker, tenant } => {
+                let batches = broker
+                    .read_batches(
+                        &Self::get_tenant_queue_name(tenant.as_deref(), queue_name)?,
+                        group_name,
+                        consumer_name,
+                    )
+                    .await?;
+                consumer::start_batch_tasks(db, batches).await
+            }
+        }
+    }
+}
+
+/// Encodes the batch as a JSON object with the same fields as a Redis stream entry
+pub fn encode_batch(
+    batch: &ProcessTrackerBatch,
+) -> CustomResult<Vec<u8>, errors::ProcessTrackerError> {
+    let fields = batch
+        .to_redis_field_value_pairs()?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    serde_json::to_vec(&fields)
+        .change_context(errors::ProcessTrackerError::SerializationFailed)
+        .attach_printable("Failed to serialize process tracker batch")
+}
+
+pub fn decode_batch(
+    payload: &[u8],
+) -> CustomResult<ProcessTrackerBatch, errors::ProcessTrackerError> {
+    let fields = serde_json::from_slice::<HashMap<String, Option<String>>>(payload)
+        .change_context(errors::ProcessTrackerError::DeserializationFailed)
+        .attach_printable("Failed to deserialize process tracker batch")?;
+
+    ProcessTrackerBatch::from_redis_stream_entry(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use diesel_models::enums::ProcessTrackerStatus;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    /// In-process stand-in for a message broker, with the same delivery semantics as Kafka
+    #[derive(Default)]
+    struct InMemoryQueue {
+        queues: Mutex<HashMap<String, Vec<Vec<u8>>>>,
+        offsets: Mutex<HashMap<(String, String), usize>>,
+    }
+
+    #[async_trait::async_trait]
+    impl MessageBrokerQueue for InMemoryQueue {
+        async fn create_consumer_group(
+            &self,
+            queue_name: &str,
+            group_name: &str,
+        ) -> CustomResult<(), errors::ProcessTrackerError> {
+            let latest_offset = self.queues.lock().await.get(queue_name).map_or(0, Vec::len);
+            self.offsets
+                .lock()
+                .await
+                .entry((queue_name.to_owned(), group_name.to_owned()))
+                .or_insert(latest_offset);
+            Ok(())
+        }
+
+        async fn append_batch(
+            &self,
+            queue_name: &str,
+            batch: &ProcessTrackerBatch,
+        ) -> CustomResult<(), errors::ProcessTrackerError> {
+            let payload = encode_batch(batch)?;
+            self.queues
+                .lock()
+                .await
+                .entry(queue_name.to_owned())
+                .or_default()
+                .push(payload);
+            Ok(())
+        }
+
+        async fn read_batches(
+            &self,
+            queue_name: &str,
+            group_name: &str,
+            _consumer_name: &str,
+        ) -> CustomResult<Vec<ProcessTrackerBatch>, errors::ProcessTrackerError> {
+            let queues = self.queues.lock().await;
+            let mut offsets = self.offsets.lock().await;
+            let offset = offsets
+                .get_mut(&(queue_name.to_owned(), group_name.to_owned()))
+                .ok_or(errors::ProcessTrackerError::BatchNotFound)?;
+
+            match queues
+                .get(queue_name)
+                .and_then(|payloads| payloads.get(*offset))
+            {
+                Some(payload) => {
+                    let batch = decode_batch(payload)?;
+                    *offset += 1;
+                    Ok(vec![batch])
+                }
+                None => Ok(Vec::new()),
+            }
+        }
+    }
+
+    fn batch(id: &str) -> ProcessTrackerBatch {
+        let now = common_utils::date_time::now();
+        let process = storage::ProcessTracker {
+            id: format!("{id}_process"),
+            name: Some("task".to_string()),
+            tag: Vec::new(),
+            runner: Some("PAYMENTS_SYNC_WORKFLOW".to_string()),
+            retry_count: 0,
+            schedule_time: Some(now),
+            rule: String::new(),
+            tracking_data: serde_json::Value::Null,
+            business_status: storage::business_status::PENDING.to_string(),
+            status: ProcessTrackerStatus::Processing,
+            event: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        ProcessTrackerBatch {
+            id: id.to_string(),
+            group_name: "SCHEDULER_GROUP".to_string(),
+            stream_name: "SCHEDULER_STREAM".to_string(),
+            connection_name: String::new(),
+            created_time: now,
+            rule: String::new(),
+            trackers: vec![process],
+        }
+    }
+
+    async fn read_batch_ids(queue: &dyn MessageBrokerQueue, group_name: &str) -> Vec<String> {
+        queue
+            .read_batches("SCHEDULER_STREAM", group_name, "consumer")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|batch| batch.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_encoding() {
+        let batch = batch("batch");
+        let decoded = decode_batch(&encode_batch(&batch).unwrap()).unwrap();
+
+        assert_eq!(decoded.id, batch.id);
+        assert_eq!(decoded.stream_name, batch.stream_name);
+        assert_eq!(decoded.trackers.len(), 1);
+        assert_eq!(decoded.trackers.first().unwrap().id, "batch_process");
+        assert!(decode_batch(b"{\"id\": \"batch\"}").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batches_are_delivered_once_per_consumer_group() {
+        let queue = InMemoryQueue::default();
+
+        // Batches appended before the consumer group is created are not delivered to it
+        queue
+            .append_batch("SCHEDULER_STREAM", &batch("batch_0"))
+            .await
+            .unwrap();
+        for group_name in ["group_a", "group_b"] {
+            queue
+                .create_consumer_group("SCHEDULER_STREAM", group_name)
+                .await
+                .unwrap();
+        }
+        queue
+            .append_batch("SCHEDULER_STREAM", &batch("batch_1"))
+            .await
+            .unwrap();
+        queue
+            .append_batch("SCHEDULER_STREAM", &batch("batch_2"))
+            .await
+            .unwrap();
+
+        assert_eq!(read_batch_ids(&queue, "group_a").await, vec!["batch_1"]);
+        assert_eq!(read_batch_ids(&queue, "group_a").await, vec!["batch_2"]);
+        assert!(read_batch_ids(&queue, "group_a").await.is_empty());
+
+        // Joining an existing consumer group does not reset its offset
+        queue
+            .create_consumer_group("SCHEDULER_STREAM", "group_a")
+            .await
+            .unwrap();
+        assert!(read_batch_ids(&queue, "group_a").await.is_empty());
+
+        assert_eq!(read_batch_ids(&queue, "group_b").await, vec!["batch_1"]);
+    }
+
+    #[tokio::test]
+    async fn test_batches_are_handed_over_within_tenant() {
+        let db = storage_impl::mock_db::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let broker = Arc::new(InMemoryQueue::default());
+        let queue = SchedulerQueue::MessageBroker {
+            broker: broker.clone(),
+            tenant: None,
+        };
+
+        // A queue that is not scoped to a tenant cannot be used
+        assert!(queue.append_batch(&db, &batch("batch_0")).await.is_err());
+
+        for tenant in ["tenant_a", "tenant_b"] {
+            queue
+                .for_tenant(tenant)
+                .create_consumer_group(&db, "SCHEDULER_STREAM", "SCHEDULER_GROUP")
+                .await
+                .unwrap();
+        }
+        queue
+            .for_tenant("tenant_a")
+            .append_batch(&db, &batch("batch_1"))
+            .await
+            .unwrap();
+
+        let read_tenant_batch_ids = |tenant: &'static str| {
+            let broker = broker.clone();
+            async move {
+                broker
+                    .read_batches(
+                        &format!("{tenant}_SCHEDULER_STREAM"),
+                        "SCHEDULER_GROUP",
+                        "consumer",
+                    )
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|batch| batch.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert!(read_tenant_batch_ids("tenant_b").await.is_empty());
+        assert_eq!(read_tenant_batch_ids("tenant_a").await, vec!["batch_1"]);
+    }
+}
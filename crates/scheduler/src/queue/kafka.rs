@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use common_utils::errors::CustomResult;
+use error_stack::ResultExt;
+use rdkafka::{
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    producer::{FutureProducer, FutureRecord},
+    ClientConfig, Message,
+};
+
+use super::{decode_batch, encode_batch, MessageBrokerQueue};
+use crate::{configs::settings::KafkaQueueSettings, consumer::types::ProcessTrackerBatch, errors};
+
+/// Uses a Kafka topic as the queue, with each batch being a message keyed by the batch ID
+pub struct KafkaQueue {
+    client_config: ClientConfig,
+    producer: FutureProducer,
+    /// Consumers keyed by the topic and the consumer group they are subscribed with, created
+    /// when the consumer group is first joined since the producer never reads batches
+    consumers: Mutex<HashMap<(String, String), Arc<StreamConsumer>>>,
+    poll_timeout: Duration,
+    send_timeout: Duration,
+}
+
+impl KafkaQueue {
+    pub fn new(settings: &KafkaQueueSettings) -> CustomResult<Self, errors::ProcessTrackerError> {
+        let mut client_config = ClientConfig::new();
+        client_config.set("bootstrap.servers", settings.brokers.join(","));
+
+        let producer = client_config
+            .create()
+            .change_context(errors::ProcessTrackerError::ConfigurationError)
+            .attach_printable("Failed to create kafka producer for the scheduler queue")?;
+
+        Ok(Self {
+            client_config,
+            producer,
+            consumers: Mutex::new(HashMap::new()),
+            poll_timeout: Duration::from_millis(settings.poll_timeout),
+            send_timeout: Duration::from_millis(settings.send_timeout),
+        })
+    }
+
+    fn get_consumer(
+        &self,
+        queue_name: &str,
+        group_name: &str,
+    ) -> CustomResult<Arc<StreamConsumer>, errors::ProcessTrackerError> {
+        let mut consumers = self
+            .consumers
+            .lock()
+            .map_err(|_| errors::ProcessTrackerError::UnexpectedFlow)
+            .attach_printable("Kafka consumers of the scheduler queue are poisoned")?;
+
+        let key = (queue_name.to_owned(), group_name.to_owned());
+        if let Some(consumer) = consumers.get(&key) {
+            return Ok(consumer.clone());
+        }
+
+        let consumer: StreamConsumer = self
+            .client_config
+            .clone()
+            .set("group.id", group_name)
+            // Offsets are committed as soon as a batch is read, like the acknowledgement of
+            // Redis stream entries
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "latest")
+            .create()
+            .change_context(errors::ProcessTrackerError::ConfigurationError)
+            .attach_printable("Failed to create kafka consumer for the scheduler queue")?;
+
+        consumer
+            .subscribe(&[queue_name])
+            .change_context(errors::ProcessTrackerError::ConfigurationError)
+            .attach_printable_lazy(|| format!("Failed to subscribe to topic {queue_name}"))?;
+
+        let consumer = Arc::new(consumer);
+        consumers.insert(key, consumer.clone());
+        Ok(consumer)
+    }
+}
+
+#[async_trait::async_trait]
+impl MessageBrokerQueue for KafkaQueue {
+    async fn create_consumer_group(
+        &self,
+        queue_name: &str,
+        group_name: &str,
+    ) -> CustomResult<(), errors::ProcessTrackerError> {
+        // Kafka creates the consumer group when the first consumer of the group subscribes
+        self.get_consumer(queue_name, group_name).map(|_| ())
+    }
+
+    async fn append_batch(
+        &self,
+        queue_name: &str,
+        batch: &ProcessTrackerBatch,
+    ) -> CustomResult<(), errors::ProcessTrackerError> {
+        let payload = encode_batch(batch)?;
+
+        self.producer
+            .send(
+                FutureRecord::to(queue_name)
+                    .key(batch.id.as_str())
+                    .payload(payload.as_slice()),
+                self.send_timeout,
+            )
+            .await
+            .map_err(|(error, _message)| error)
+            .change_context(errors::ProcessTrackerError::BatchInsertionFailed)
+            .attach_printable_lazy(|| format!("Failed to send batch {} to kafka", batch.id))?;
+
+        Ok(())
+    }
+
+    async fn read_batches(
+        &self,
+        queue_name: &str,
+        group_name: &str,
+        _consumer_name: &str,
+    ) -> CustomResult<Vec<ProcessTrackerBatch>, errors::ProcessTrackerError> {
+        let consumer = self.get_consumer(queue_name, group_name)?;
+
+        let message = match tokio::time::timeout(self.poll_timeout, consumer.recv()).await {
+            Ok(message) => message
+                .change_context(errors::ProcessTrackerError::BatchNotFound)
+                .attach_printable("Failed to read batch from kafka")?,
+            // No batch was appended within the poll timeout
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let batch = decode_batch(message.payload().unwrap_or_default())?;
+
+        consumer
+            .commit_message(&message, CommitMode::Sync)
+            .change_context(errors::ProcessTrackerError::BatchUpdateFailed)
+            .attach_printable("Failed to commit offset of batch read from kafka")?;
+
+        Ok(vec![batch])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_subscribed_topics(consumer: &StreamConsumer) -> Vec<String> {
+        consumer
+            .subscription()
+            .unwrap()
+            .elements()
+            .iter()
+            .map(|element| element.topic().to_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_consumer_per_topic_and_group() {
+        // Consumers connect to the brokers lazily, so no broker needs to be running
+        let queue = KafkaQueue::new(&KafkaQueueSettings {
+            brokers: vec!["localhost:9092".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let consumer_a = queue.get_consumer("tenant_a_STREAM", "GROUP").unwrap();
+        let consumer_b = queue.get_consumer("tenant_b_STREAM", "GROUP").unwrap();
+        let other_group = queue
+            .get_consumer("tenant_a_STREAM", "OTHER_GROUP")
+            .unwrap();
+
+        assert!(Arc::ptr_eq(
+            &consumer_a,
+            &queue.get_consumer("tenant_a_STREAM", "GROUP").unwrap()
+        ));
+        assert!(!Arc::ptr_eq(&consumer_a, &other_group));
+        assert_eq!(get_subscribed_topics(&consumer_a), vec!["tenant_a_STREAM"]);
+        assert_eq!(get_subscribed_topics(&consumer_b), vec!["tenant_b_STREAM"]);
+        assert_eq!(queue.consumers.lock().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,537 @@
+//! Recurring tasks defined in the scheduler configuration.
+//!
+//! The producer materialises the next run of each recurring task as a regular process tracker
+//! entry, which is then picked up by the consumer like any other task. Runs of a task with the
+//! [`OverlapPolicy::Skip`] policy are guarded by a lease held in Redis while the run is in
+//! progress.
+
+pub use common_utils::date_time::parse_timezone;
+use common_utils::{date_time::Timezone, errors::CustomResult};
+use diesel_models::{enums::ProcessTrackerStatus, process_tracker as storage};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+use storage_impl::errors::StorageError;
+use time::{Date, Duration, Month, PrimitiveDateTime, UtcOffset};
+
+use crate::{
+    configs::settings::{OverlapPolicy, RecurringTaskSettings, SchedulerSettings},
+    env::logger,
+    errors,
+    scheduler::SchedulerInterface,
+    SchedulerSessionState,
+};
+
+/// Tag added to the process tracker entries of recurring tasks
+pub const RECURRING_TASK_TAG: &str = "RECURRING";
+
+const RECURRING_TASK_LEASE_TAG: &str = "RECURRING_TASK_LEASE";
+
+/// Lease expiry used when the recurring task does not configure one (in seconds)
+const DEFAULT_LEASE_TTL: i64 = 300;
+
+/// Maximum number of years to look ahead for the next run of a cron schedule
+const MAX_LOOKAHEAD_YEARS: i64 = 5;
+
+/// A parsed five field cron expression. Each field is stored as a bit mask of the values it
+/// matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parses an expression of the form `minute hour day-of-month month day-of-week`. Each field
+    /// may be `*`, a value, a range (`1-5`), a step (`*/15`, `0-30/10`, `5/15`) or a comma
+    /// separated list of these. Day of week `0` and `7` both denote Sunday.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "cron expression '{expression}' must have exactly five fields"
+            ));
+        };
+
+        let days_of_week_mask = parse_cron_field(days_of_week, 0, 7)?;
+        // Fold Sunday as `7` into Sunday as `0`
+        let days_of_week_mask = (days_of_week_mask | (days_of_week_mask >> 7)) & 0x7f;
+
+        Ok(Self {
+            minutes: parse_cron_field(minutes, 0, 59)?,
+            hours: parse_cron_field(hours, 0, 23)?,
+            days_of_month: parse_cron_field(days_of_month, 1, 31)?,
+            months: parse_cron_field(months, 1, 12)?,
+            days_of_week: days_of_week_mask,
+            day_of_month_restricted: !days_of_month.starts_with('*'),
+            day_of_week_restricted: !days_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns the first time (in UTC) strictly after `after` that matches the schedule, with
+    /// the schedule being evaluated in the local time of the given timezone. Local times that
+    /// are skipped when the clocks are set forward do not match the schedule, and local times
+    /// that repeat when the clocks are set back match only once.
+    pub fn next_after(
+        &self,
+        after: PrimitiveDateTime,
+        timezone: Timezone,
+    ) -> Option<PrimitiveDateTime> {
+        let local_after =
+            timezone.local_date_time(after.replace_second(0).ok()?.replace_nanosecond(0).ok()?);
+        let mut candidate = PrimitiveDateTime::new(local_after.date(), local_after.time())
+            .checked_add(Duration::minutes(1))?;
+        let limit = candidate.checked_add(Duration::days(366 * MAX_LOOKAHEAD_YEARS))?;
+
+        while candidate <= limit {
+            let date = candidate.date();
+
+            if !mask_contains(self.months, u8::from(date.month())) {
+                let (year, month) = match date.month() {
+                    Month::December => (date.year().checked_add(1)?, Month::January),
+                    month => (date.year(), month.next()),
+                };
+                candidate = Date::from_calendar_date(year, month, 1).ok()?.midnight();
+                continue;
+            }
+
+            if !self.matches_day(date) {
+                candidate = date.next_day()?.midnight();
+                continue;
+            }
+
+            if !mask_contains(self.hours, candidate.hour()) {
+                candidate = candidate
+                    .replace_minute(0)
+                    .ok()?
+                    .checked_add(Duration::hours(1))?;
+                continue;
+            }
+
+            if !mask_contains(self.minutes, candidate.minute()) {
+                candidate = candidate.checked_add(Duration::minutes(1))?;
+                continue;
+            }
+
+            let next_run = timezone
+                .instants_at(candidate)
+                .into_iter()
+                .find(|next_run| *next_run > after.assume_utc());
+            if let Some(next_run) = next_run {
+                let next_run = next_run.to_offset(UtcOffset::UTC);
+                return Some(PrimitiveDateTime::new(next_run.date(), next_run.time()));
+            }
+
+            candidate = candidate.checked_add(Duration::minutes(1))?;
+        }
+
+        None
+    }
+
+    /// When both the day of month and the day of week are restricted, a day matching either of
+    /// them matches the schedule, as with the standard cron implementations
+    fn matches_day(&self, date: Date) -> bool {
+        let day_of_month = mask_contains(self.days_of_month, date.day());
+        let day_of_week =
+            mask_contains(self.days_of_week, date.weekday().number_days_from_sunday());
+
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn mask_contains(mask: u64, value: u8) -> bool {
+    value < 64 && mask & (1 << value) != 0
+}
+
+fn parse_cron_field(field: &str, min: u8, max: u8) -> Result<u64, String> {
+    let parse_value = |value: &str| {
+        value
+            .parse::<u8>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| format!("invalid value '{value}' in cron field '{field}'"))
+    };
+
+    field.split(',').try_fold(0_u64, |mut mask, part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                Some(
+                    step.parse::<usize>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("invalid step in cron field '{field}'"))?,
+                ),
+            ),
+            None => (part, None),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+            // `5/15` denotes every 15th value starting at 5
+            None if step.is_some() => (parse_value(range)?, max),
+            None => {
+                let value = parse_value(range)?;
+                (value, value)
+            }
+        };
+
+        if start > end {
+            return Err(format!("invalid range '{range}' in cron field '{field}'"));
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1)) {
+            mask |= 1 << value;
+        }
+
+        Ok(mask)
+    })
+}
+
+/// Materialises the next run of each of the configured recurring tasks
+#[instrument(skip_all)]
+pub async fn materialise_recurring_tasks(
+    db: &dyn SchedulerInterface,
+    settings: &SchedulerSettings,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let now = common_utils::date_time::now();
+
+    for (name, task) in &settings.recurring_tasks {
+        // Intentionally not propagating the error, so that a failure with one recurring task
+        // does not prevent the other tasks from being scheduled.
+        if let Err(error) = materialise_next_run(db, name, task, now).await {
+            logger::error!(?error, recurring_task = %name, "Failed to schedule recurring task");
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether the run following the latest run of a recurring task is to be scheduled
+fn is_next_run_due(
+    latest_run: &storage::ProcessTracker,
+    overlap_policy: OverlapPolicy,
+    now: PrimitiveDateTime,
+) -> bool {
+    let is_upcoming = latest_run
+        .schedule_time
+        .is_some_and(|schedule_time| schedule_time > now);
+    // A run moved to the dead letter status is never picked up again
+    let is_finished = matches!(
+        latest_run.status,
+        ProcessTrackerStatus::Finish | ProcessTrackerStatus::DeadLetter
+    );
+
+    !is_upcoming && (overlap_policy == OverlapPolicy::Allow || is_finished)
+}
+
+async fn materialise_next_run(
+    db: &dyn SchedulerInterface,
+    name: &str,
+    task: &RecurringTaskSettings,
+    now: PrimitiveDateTime,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let latest_run = db
+        .find_latest_process_by_name(name)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+
+    if latest_run.is_some_and(|latest_run| !is_next_run_due(&latest_run, task.overlap_policy, now))
+    {
+        return Ok(());
+    }
+
+    let schedule = CronSchedule::parse(&task.cron).map_err(|error| {
+        report!(errors::ProcessTrackerError::ConfigurationError).attach_printable(error)
+    })?;
+    let timezone = parse_timezone(task.timezone.as_deref()).map_err(|error| {
+        report!(errors::ProcessTrackerError::ConfigurationError).attach_printable(error)
+    })?;
+    let next_run = schedule.next_after(now, timezone).ok_or_else(|| {
+        report!(errors::ProcessTrackerError::ConfigurationError)
+            .attach_printable("Cron expression of recurring task has no upcoming run")
+    })?;
+
+    // The ID is derived from the schedule time, so that the same run is never inserted twice
+    let process_tracker_id = format!(
+        "{RECURRING_TASK_TAG}_{name}_{}",
+        next_run.assume_utc().unix_timestamp()
+    );
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        name,
+        task.runner,
+        [RECURRING_TASK_TAG],
+        task.tracking_data.clone(),
+        next_run,
+    )
+    .change_context(errors::ProcessTrackerError::SerializationFailed)?;
+
+    match db.insert_process(process_tracker_entry).await {
+        Ok(process) => {
+            logger::info!(pt.id = %process.id, recurring_task = %name, "Scheduled recurring task");
+            Ok(())
+        }
+        Err(error)
+            if error.current_context().is_db_unique_violation()
+                || matches!(error.current_context(), StorageError::DuplicateValue { .. }) =>
+        {
+            Ok(())
+        }
+        Err(error) => {
+            Err(error.change_context(errors::ProcessTrackerError::ProcessInsertionFailed))
+        }
+    }
+}
+
+/// Returns the definition of the recurring task the process is a run of, if any
+pub fn get_recurring_task<'a>(
+    settings: &'a SchedulerSettings,
+    process: &storage::ProcessTracker,
+) -> Option<&'a RecurringTaskSettings> {
+    process
+        .tag
+        .iter()
+        .any(|tag| tag == RECURRING_TASK_TAG)
+        .then_some(process.name.as_deref())
+        .flatten()
+        .and_then(|name| settings.recurring_tasks.get(name))
+}
+
+/// Executes the workflow of a run of a recurring task. Unless overlapping runs are allowed, the
+/// workflow is executed only if the lease of the task could be acquired, and the run is finished
+/// without executing the workflow otherwise.
+#[instrument(skip_all, fields(pt.id = %process.id))]
+pub async fn run_recurring_workflow<T, F>(
+    state: T,
+    process: storage::ProcessTracker,
+    task: RecurringTaskSettings,
+    workflow: F,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerSessionState,
+    F: std::future::Future<Output = CustomResult<(), errors::ProcessTrackerError>>,
+{
+    if task.overlap_policy == OverlapPolicy::Allow {
+        return workflow.await;
+    }
+
+    let db = state.get_db();
+    let lease_key = format!(
+        "{RECURRING_TASK_LEASE_TAG}_{}",
+        process.name.as_deref().unwrap_or_default()
+    );
+    let lease_acquired = db
+        .acquire_pt_lock(
+            RECURRING_TASK_LEASE_TAG,
+            &lease_key,
+            &process.id,
+            task.lease_ttl.unwrap_or(DEFAULT_LEASE_TTL),
+        )
+        .await
+        .map_err(errors::ProcessTrackerError::ERedisError)?;
+
+    if !lease_acquired {
+        logger::warn!(
+            pt.name = ?process.name,
+            "Skipping run of recurring task since a previous run is still in progress"
+        );
+        return db
+            .as_scheduler()
+            .finish_process_with_business_status(
+                process,
+                storage::business_status::RECURRING_RUN_SKIPPED,
+            )
+            .await
+            .change_context(errors::ProcessTrackerError::ProcessUpdateFailed);
+    }
+
+    let result = workflow.await;
+
+    if let Err(error) = db
+        .release_pt_lock(RECURRING_TASK_LEASE_TAG, &lease_key)
+        .await
+    {
+        logger::error!(?error, "Failed to release lease of recurring task");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const UTC: Timezone = Timezone::Offset(UtcOffset::UTC);
+
+    fn datetime(year: i32, month: Month, day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+        Date::from_calendar_date(year, month, day)
+            .unwrap()
+            .with_hms(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cron_field_parsing() {
+        assert_eq!(parse_cron_field("*", 0, 5).unwrap(), 0b11_1111);
+        assert_eq!(parse_cron_field("1,3-4", 0, 5).unwrap(), 0b1_1010);
+        assert_eq!(parse_cron_field("*/2", 0, 5).unwrap(), 0b1_0101);
+        assert_eq!(parse_cron_field("1/2", 0, 5).unwrap(), 0b10_1010);
+        assert!(parse_cron_field("6", 0, 5).is_err());
+        assert!(parse_cron_field("4-2", 0, 5).is_err());
+        assert!(parse_cron_field("*/0", 0, 5).is_err());
+        assert!(CronSchedule::parse("* * * *").is_err());
+    }
+
+    #[test]
+    fn test_next_run_computation() {
+        let every_quarter_hour = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_quarter_hour.next_after(datetime(2024, Month::January, 31, 23, 50), UTC),
+            Some(datetime(2024, Month::February, 1, 0, 0))
+        );
+
+        let leap_day = CronSchedule::parse("30 9 29 2 *").unwrap();
+        assert_eq!(
+            leap_day.next_after(datetime(2024, Month::March, 1, 0, 0), UTC),
+            Some(datetime(2028, Month::February, 29, 9, 30))
+        );
+
+        // Either the first day of the month or a Monday
+        let first_or_monday = CronSchedule::parse("0 0 1 * 1").unwrap();
+        assert_eq!(
+            first_or_monday.next_after(datetime(2024, Month::June, 1, 0, 0), UTC),
+            Some(datetime(2024, Month::June, 3, 0, 0))
+        );
+
+        let sunday = CronSchedule::parse("0 12 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(datetime(2024, Month::June, 3, 0, 0), UTC),
+            Some(datetime(2024, Month::June, 9, 12, 0))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(datetime(2024, Month::January, 1, 0, 0), UTC),
+            None
+        );
+    }
+
+    fn latest_run(
+        status: ProcessTrackerStatus,
+        schedule_time: PrimitiveDateTime,
+    ) -> storage::ProcessTracker {
+        storage::ProcessTracker {
+            id: String::from("RECURRING_test_task"),
+            name: Some(String::from("test_task")),
+            tag: vec![String::from(RECURRING_TASK_TAG)],
+            runner: None,
+            retry_count: 0,
+            schedule_time: Some(schedule_time),
+            rule: String::new(),
+            tracking_data: serde_json::Value::Null,
+            business_status: String::new(),
+            status,
+            event: vec![],
+            created_at: schedule_time,
+            updated_at: schedule_time,
+        }
+    }
+
+    #[test]
+    fn test_next_run_scheduling() {
+        let now = datetime(2024, Month::June, 1, 12, 0);
+        let past = datetime(2024, Month::June, 1, 11, 0);
+        let upcoming = datetime(2024, Month::June, 1, 13, 0);
+
+        for status in [
+            ProcessTrackerStatus::Finish,
+            ProcessTrackerStatus::DeadLetter,
+        ] {
+            assert!(is_next_run_due(
+                &latest_run(status, past),
+                OverlapPolicy::Skip,
+                now
+            ));
+        }
+        assert!(!is_next_run_due(
+            &latest_run(ProcessTrackerStatus::ProcessStarted, past),
+            OverlapPolicy::Skip,
+            now
+        ));
+        assert!(is_next_run_due(
+            &latest_run(ProcessTrackerStatus::ProcessStarted, past),
+            OverlapPolicy::Allow,
+            now
+        ));
+        assert!(!is_next_run_due(
+            &latest_run(ProcessTrackerStatus::New, upcoming),
+            OverlapPolicy::Allow,
+            now
+        ));
+    }
+
+    #[test]
+    fn test_next_run_computation_with_timezone() {
+        let offset = parse_timezone(Some("+05:30")).unwrap();
+        let daily = CronSchedule::parse("0 9 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(datetime(2024, Month::June, 1, 4, 0), offset),
+            Some(datetime(2024, Month::June, 2, 3, 30))
+        );
+        assert_eq!(
+            daily.next_after(datetime(2024, Month::June, 1, 3, 0), offset),
+            Some(datetime(2024, Month::June, 1, 3, 30))
+        );
+
+        assert_eq!(
+            parse_timezone(Some("-08:00")).unwrap(),
+            Timezone::Offset(UtcOffset::from_hms(-8, 0, 0).unwrap())
+        );
+        assert_eq!(parse_timezone(None).unwrap(), UTC);
+        assert!(parse_timezone(Some("Asia/Kolkata")).is_ok());
+        assert!(parse_timezone(Some("Mars/Olympus_Mons")).is_err());
+    }
+
+    #[test]
+    fn test_next_run_computation_across_daylight_saving_time() {
+        let london = parse_timezone(Some("Europe/London")).unwrap();
+        let daily = CronSchedule::parse("0 9 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(datetime(2024, Month::January, 15, 0, 0), london),
+            Some(datetime(2024, Month::January, 15, 9, 0))
+        );
+        assert_eq!(
+            daily.next_after(datetime(2024, Month::June, 15, 0, 0), london),
+            Some(datetime(2024, Month::June, 15, 8, 0))
+        );
+
+        // 01:30 is skipped when the clocks are set forward on 31 March 2024
+        let half_past_one = CronSchedule::parse("30 1 * * *").unwrap();
+        assert_eq!(
+            half_past_one.next_after(datetime(2024, Month::March, 30, 12, 0), london),
+            Some(datetime(2024, Month::April, 1, 0, 30))
+        );
+
+        // 01:30 occurs twice when the clocks are set back on 27 October 2024, the task runs once
+        assert_eq!(
+            half_past_one.next_after(datetime(2024, Month::October, 26, 12, 0), london),
+            Some(datetime(2024, Month::October, 27, 0, 30))
+        );
+        assert_eq!(
+            half_past_one.next_after(datetime(2024, Month::October, 27, 0, 30), london),
+            Some(datetime(2024, Month::October, 28, 1, 30))
+        );
+    }
+}
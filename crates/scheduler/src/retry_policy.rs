@@ -0,0 +1,246 @@
+use common_utils::errors::CustomResult;
+use diesel_models::process_tracker::{self as storage, business_status};
+use error_stack::{AttachmentKind, FrameKind, Report};
+use rand::Rng;
+use router_env::{instrument, tracing};
+use storage_impl::errors::StorageError;
+use time::{Duration, PrimitiveDateTime};
+
+use crate::{
+    configs::settings::RetryPolicy, env::logger, errors, scheduler::SchedulerInterface,
+    SchedulerSessionState,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry the task at the specified time
+    Retry(PrimitiveDateTime),
+    /// Move the task to the dead letter status with the specified business status
+    DeadLetter(&'static str),
+}
+
+impl RetryPolicy {
+    /// Delay before the specified retry (starting at 1), without any jitter
+    pub fn get_delay(&self, retry_count: i32) -> i64 {
+        let exponent = u32::try_from(retry_count.saturating_sub(1)).unwrap_or_default();
+
+        i64::from(self.multiplier)
+            .checked_pow(exponent)
+            .and_then(|factor| self.initial_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Delay before the specified retry (starting at 1), randomly shortened by up to
+    /// `jitter_percentage` percent of the delay
+    pub fn get_delay_with_jitter(&self, retry_count: i32) -> i64 {
+        let delay = self.get_delay(retry_count);
+        let max_jitter = delay.saturating_mul(i64::from(self.jitter_percentage)) / 100;
+
+        delay - rand::thread_rng().gen_range(0..=max_jitter.max(0))
+    }
+}
+
+/// Decides whether a task whose workflow failed at `now` is to be retried, as per the policy
+pub fn get_retry_decision(
+    policy: &RetryPolicy,
+    process: &storage::ProcessTracker,
+    now: PrimitiveDateTime,
+) -> RetryDecision {
+    // The number of executions of the task so far, which is also the number of the next retry
+    let attempts = process.retry_count.saturating_add(1);
+    if attempts >= policy.max_attempts {
+        return RetryDecision::DeadLetter(business_status::RETRIES_EXCEEDED);
+    }
+
+    let schedule_time =
+        now.saturating_add(Duration::seconds(policy.get_delay_with_jitter(attempts)));
+    let is_expired = policy.max_age.is_some_and(|max_age| {
+        schedule_time
+            > process
+                .created_at
+                .saturating_add(Duration::seconds(max_age))
+    });
+
+    if is_expired {
+        RetryDecision::DeadLetter(business_status::MAX_AGE_EXCEEDED)
+    } else {
+        RetryDecision::Retry(schedule_time)
+    }
+}
+
+/// Returns one entry per context and printable attachment of the error, starting with the most
+/// recent one
+pub fn get_error_chain(error: &Report<errors::ProcessTrackerError>) -> Vec<String> {
+    error
+        .frames()
+        .filter_map(|frame| match frame.kind() {
+            FrameKind::Context(context) => Some(context.to_string()),
+            FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                Some(attachment.to_string())
+            }
+            FrameKind::Attachment(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the retry policy configured for the runner of the process, if any
+pub fn get_retry_policy<T: SchedulerSessionState>(
+    state: &T,
+    process: &storage::ProcessTracker,
+) -> Option<RetryPolicy> {
+    process
+        .runner
+        .as_deref()
+        .and_then(|runner| runner.parse::<storage::ProcessTrackerRunner>().ok())
+        .and_then(|runner| state.get_retry_policy(runner))
+}
+
+/// Retries the task whose workflow failed as per the policy, or moves it to the dead letter
+/// status along with the error chain once the policy is exhausted
+#[instrument(skip_all, fields(pt.id = %process.id))]
+pub async fn retry_or_dead_letter(
+    db: &dyn SchedulerInterface,
+    process: storage::ProcessTracker,
+    policy: &RetryPolicy,
+    error: &Report<errors::ProcessTrackerError>,
+) -> CustomResult<(), StorageError> {
+    match get_retry_decision(policy, &process, common_utils::date_time::now()) {
+        RetryDecision::Retry(schedule_time) => {
+            logger::info!(
+                pt.retry_count = process.retry_count,
+                %schedule_time,
+                "Retrying failed workflow"
+            );
+            db.retry_process(process, schedule_time).await
+        }
+        RetryDecision::DeadLetter(business_status) => {
+            logger::error!(?error, %business_status, "Moving process to dead letter status");
+            db.dead_letter_process(process, business_status, get_error_chain(error))
+                .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::panic)]
+    use diesel_models::enums::ProcessTrackerStatus;
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_delay: 60,
+            multiplier: 3,
+            max_delay: 500,
+            jitter_percentage: 0,
+            max_age: None,
+        }
+    }
+
+    fn process(retry_count: i32, created_at: PrimitiveDateTime) -> storage::ProcessTracker {
+        storage::ProcessTracker {
+            id: "process".to_string(),
+            name: None,
+            tag: Vec::new(),
+            runner: None,
+            retry_count,
+            schedule_time: None,
+            rule: String::new(),
+            tracking_data: serde_json::Value::Null,
+            business_status: business_status::PENDING.to_string(),
+            status: ProcessTrackerStatus::ProcessStarted,
+            event: Vec::new(),
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_exponential_delay() {
+        let policy = policy();
+        let delays = (1..=4)
+            .map(|retry| policy.get_delay(retry))
+            .collect::<Vec<_>>();
+        assert_eq!(delays, vec![60, 180, 500, 500]);
+        assert_eq!(policy.get_delay(i32::MAX), 500);
+    }
+
+    #[test]
+    fn test_delay_with_jitter() {
+        let policy = RetryPolicy {
+            jitter_percentage: 50,
+            ..policy()
+        };
+        for _ in 0..100 {
+            let delay = policy.get_delay_with_jitter(2);
+            assert!((90..=180).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn test_retry_decision() {
+        let now = common_utils::date_time::now();
+        let policy = policy();
+
+        assert_eq!(
+            get_retry_decision(&policy, &process(1, now), now),
+            RetryDecision::Retry(now.saturating_add(Duration::seconds(180)))
+        );
+        assert_eq!(
+            get_retry_decision(&policy, &process(3, now), now),
+            RetryDecision::DeadLetter(business_status::RETRIES_EXCEEDED)
+        );
+
+        let policy = RetryPolicy {
+            max_age: Some(120),
+            ..policy
+        };
+        assert_eq!(
+            get_retry_decision(&policy, &process(0, now), now),
+            RetryDecision::Retry(now.saturating_add(Duration::seconds(60)))
+        );
+        assert_eq!(
+            get_retry_decision(&policy, &process(1, now), now),
+            RetryDecision::DeadLetter(business_status::MAX_AGE_EXCEEDED)
+        );
+    }
+
+    #[test]
+    fn test_error_chain() {
+        let error = error_stack::report!(errors::ProcessTrackerError::NotImplemented)
+            .attach_printable("Connector returned an unexpected response")
+            .change_context(errors::ProcessTrackerError::FlowExecutionError {
+                flow: "payments_sync",
+            });
+        assert_eq!(
+            get_error_chain(&error),
+            vec![
+                "Failed while executing: payments_sync".to_string(),
+                "Connector returned an unexpected response".to_string(),
+                errors::ProcessTrackerError::NotImplemented.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_update_keeps_previous_events() {
+        let now = common_utils::date_time::now();
+        let process = storage::ProcessTracker {
+            event: vec!["retry event".to_string()],
+            ..process(3, now)
+        };
+
+        let storage::ProcessTrackerUpdate::DeadLetterUpdate {
+            business_status,
+            event,
+        } = process
+            .get_dead_letter_update(business_status::RETRIES_EXCEEDED, vec!["error".to_string()])
+        else {
+            panic!("Expected a dead letter update");
+        };
+        assert_eq!(business_status, business_status::RETRIES_EXCEEDED);
+        assert_eq!(event, vec!["retry event".to_string(), "error".to_string()]);
+    }
+}
@@ -10,7 +10,7 @@ use tokio::sync::mpsc;
 
 use super::env::logger::error;
 pub use crate::{
-    configs::settings::SchedulerSettings,
+    configs::settings::{RetryPolicy, SchedulerSettings},
     consumer::{self, workflows},
     db::{process_tracker::ProcessTrackerInterface, queue::QueueInterface},
     errors,
@@ -57,6 +57,10 @@ pub trait SchedulerAppState: Send + Sync + Clone {
 #[async_trait::async_trait]
 pub trait SchedulerSessionState: Send + Sync + Clone {
     fn get_db(&self) -> Box<dyn SchedulerInterface>;
+    fn get_retry_policy(
+        &self,
+        runner: diesel_models::process_tracker::ProcessTrackerRunner,
+    ) -> Option<RetryPolicy>;
 }
 pub async fn start_process_tracker<
     T: SchedulerAppState + 'static,
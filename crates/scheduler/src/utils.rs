@@ -14,7 +14,7 @@ use super::{
 };
 use crate::{
     configs::settings::SchedulerSettings, consumer::types::ProcessTrackerBatch, errors,
-    flow::SchedulerFlow, metrics, SchedulerInterface, SchedulerSessionState,
+    flow::SchedulerFlow, metrics, queue::SchedulerQueue, SchedulerInterface, SchedulerSessionState,
 };
 
 pub async fn divide_and_append_tasks<T>(
@@ -22,6 +22,7 @@ pub async fn divide_and_append_tasks<T>(
     flow: SchedulerFlow,
     tasks: Vec<storage::ProcessTracker>,
     settings: &SchedulerSettings,
+    queue: &SchedulerQueue,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
     T: SchedulerInterface + Send + Sync + ?Sized,
@@ -31,7 +32,7 @@ where
     #[allow(clippy::as_conversions)]
     metrics::BATCHES_CREATED.add(&metrics::CONTEXT, batches.len() as u64, &[]); // Metrics
     for batch in batches {
-        let result = update_status_and_append(state, flow, batch).await;
+        let result = update_status_and_append(state, flow, batch, queue).await;
         match result {
             Ok(_) => (),
             Err(error) => logger::error!(?error),
@@ -44,6 +45,7 @@ pub async fn update_status_and_append<T>(
     state: &T,
     flow: SchedulerFlow,
     pt_batch: ProcessTrackerBatch,
+    queue: &SchedulerQueue,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
     T: SchedulerInterface + Send + Sync + ?Sized,
@@ -95,17 +97,7 @@ where
         }
     }?;
 
-    let field_value_pairs = pt_batch.to_redis_field_value_pairs()?;
-
-    match state
-        .stream_append_entry(
-            &pt_batch.stream_name,
-            &RedisEntryId::AutoGeneratedID,
-            field_value_pairs,
-        )
-        .await
-        .change_context(errors::ProcessTrackerError::BatchInsertionFailed)
-    {
+    match queue.append_batch(state.as_scheduler(), &pt_batch).await {
         Ok(x) => Ok(x),
         Err(mut err) => {
             let update_res = state
@@ -267,6 +259,7 @@ pub fn get_time_from_delta(delta: Option<i32>) -> Option<time::PrimitiveDateTime
 pub async fn consumer_operation_handler<E, T>(
     state: T,
     settings: sync::Arc<SchedulerSettings>,
+    queue: SchedulerQueue,
     error_handler_fun: E,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) where
@@ -274,7 +267,7 @@ pub async fn consumer_operation_handler<E, T>(
     E: FnOnce(error_stack::Report<errors::ProcessTrackerError>),
     T: SchedulerSessionState + Send + Sync + 'static,
 {
-    match consumer::consumer_operations(&state, &settings, workflow_selector).await {
+    match consumer::consumer_operations(&state, &settings, &queue, workflow_selector).await {
         Ok(_) => (),
         Err(err) => error_handler_fun(err),
     }
@@ -238,6 +238,19 @@ impl<T: DatabaseStore> KVRouterStore<T> {
         format!("{{{}}}_{}", shard_key, self.drainer_stream_name)
     }
 
+    /// Returns the drainer stream the Redis KV entries of the partition key are appended to
+    pub fn get_drainer_stream_name_by_partition_key(
+        &self,
+        partition_key: redis::kv_store::PartitionKey<'_>,
+    ) -> String {
+        // The shard only depends on the partition key, and not on the type of the entry
+        let shard_key = <diesel_models::reverse_lookup::ReverseLookup as redis::kv_store::KvStorePartition>::shard_key(
+            partition_key,
+            self.drainer_num_partitions,
+        );
+        self.get_drainer_stream_name(&shard_key)
+    }
+
     pub async fn push_to_drainer_stream<R>(
         &self,
         redis_entry: diesel_models::kv::TypedSql,
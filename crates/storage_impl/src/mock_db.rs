@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use diesel_models::{self as store};
 use error_stack::ResultExt;
@@ -60,6 +60,12 @@ pub struct MockDb {
     pub user_key_store: Arc<Mutex<Vec<store::user_key_store::UserKeyStore>>>,
     pub user_authentication_methods:
         Arc<Mutex<Vec<store::user_authentication_method::UserAuthenticationMethod>>>,
+    pub routing_algorithms: Arc<Mutex<Vec<store::routing_algorithm::RoutingAlgorithm>>>,
+    pub generic_links: Arc<Mutex<Vec<store::generic_link::GenericLink>>>,
+    pub gateway_status_maps: Arc<Mutex<Vec<store::gsm::GatewayStatusMap>>>,
+    pub blocklists: Arc<Mutex<Vec<store::blocklist::Blocklist>>>,
+    /// Fields of the Redis KV hashes, by partition key
+    pub kv_hashes: Arc<Mutex<HashMap<String, HashMap<String, String>>>>,
 }
 
 impl MockDb {
@@ -105,6 +111,11 @@ impl MockDb {
             roles: Default::default(),
             user_key_store: Default::default(),
             user_authentication_methods: Default::default(),
+            routing_algorithms: Default::default(),
+            generic_links: Default::default(),
+            gateway_status_maps: Default::default(),
+            blocklists: Default::default(),
+            kv_hashes: Default::default(),
         })
     }
 }
@@ -1,4 +1,12 @@
-use std::{any::Any, borrow::Cow, fmt::Debug, sync::Arc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use common_utils::{
     errors::{self, CustomResult},
@@ -6,7 +14,7 @@ use common_utils::{
 };
 use dyn_clone::DynClone;
 use error_stack::{Report, ResultExt};
-use moka::future::Cache as MokaCache;
+use moka::{future::Cache as MokaCache, notification::RemovalCause};
 use once_cell::sync::Lazy;
 use redis_interface::{errors::RedisError, RedisConnectionPool, RedisValue};
 use router_env::{
@@ -72,6 +80,25 @@ pub static PM_FILTERS_CGRAPH_CACHE: Lazy<Cache> = Lazy::new(|| {
     )
 });
 
+/// Returns all the in-memory caches
+pub fn get_all_caches() -> [&'static Cache; 7] {
+    [
+        &CONFIG_CACHE,
+        &ACCOUNTS_CACHE,
+        &ROUTING_CACHE,
+        &DECISION_MANAGER_CACHE,
+        &SURCHARGE_CACHE,
+        &CGRAPH_CACHE,
+        &PM_FILTERS_CGRAPH_CACHE,
+    ]
+}
+
+pub fn find_cache_by_name(name: &str) -> Option<&'static Cache> {
+    get_all_caches()
+        .into_iter()
+        .find(|cache| cache.name().eq_ignore_ascii_case(name))
+}
+
 /// Trait which defines the behaviour of types that's gonna be stored in Cache
 pub trait Cacheable: Any + Send + Sync + DynClone {
     fn as_any(&self) -> &dyn Any;
@@ -93,6 +120,11 @@ pub enum CacheKind<'a> {
     CGraph(Cow<'a, str>),
     PmFiltersCGraph(Cow<'a, str>),
     All(Cow<'a, str>),
+    /// Keys matching the glob pattern, in the cache with the specified name or in all the caches
+    Pattern {
+        cache: Option<Cow<'a, str>>,
+        pattern: Cow<'a, str>,
+    },
 }
 
 impl<'a> TryFrom<CacheRedact<'a>> for RedisValue {
@@ -135,7 +167,42 @@ dyn_clone::clone_trait_object!(Cacheable);
 
 pub struct Cache {
     name: &'static str,
-    inner: MokaCache<String, Arc<dyn Cacheable>>,
+    inner: MokaCache<String, CacheEntry>,
+    statistics: Arc<CacheCounters>,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    value: Arc<dyn Cacheable>,
+    /// Estimated size of the key and the value in bytes
+    size: u64,
+}
+
+/// Counters of the operations on a cache, since the application was started
+#[derive(Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+    size: AtomicU64,
+}
+
+/// Statistics of a cache on this instance of the application
+#[derive(Debug, Clone)]
+pub struct CacheStatistics {
+    pub name: &'static str,
+    pub entry_count: u64,
+    pub hit_count: u64,
+    pub miss_count: u64,
+    /// Entries removed on expiry, or to keep the cache within its capacity
+    pub eviction_count: u64,
+    /// Entries removed by the application
+    pub invalidation_count: u64,
+    /// Estimated size of the entries in bytes. Values pushed directly are estimated by their
+    /// shallow size, and values populated through `get_or_populate_in_memory` by their serialized
+    /// size.
+    pub estimated_size: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -168,8 +235,22 @@ impl Cache {
         time_to_idle: u64,
         max_capacity: Option<u64>,
     ) -> Self {
+        let statistics = Arc::new(CacheCounters::default());
+        let counters = statistics.clone();
+
         // Record the metrics of manual invalidation of cache entry by the application
-        let eviction_listener = move |_, _, cause| {
+        let eviction_listener = move |_, entry: CacheEntry, cause| {
+            counters.size.fetch_sub(entry.size, Ordering::Relaxed);
+            match cause {
+                RemovalCause::Expired | RemovalCause::Size => {
+                    counters.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+                RemovalCause::Explicit => {
+                    counters.invalidations.fetch_add(1, Ordering::Relaxed);
+                }
+                RemovalCause::Replaced => {}
+            }
+
             metrics::IN_MEMORY_CACHE_EVICTION_COUNT.add(
                 &metrics::CONTEXT,
                 1,
@@ -191,11 +272,31 @@ impl Cache {
         Self {
             name,
             inner: cache_builder.build(),
+            statistics,
         }
     }
 
     pub async fn push<T: Cacheable>(&self, key: CacheKey, val: T) {
-        self.inner.insert(key.into(), Arc::new(val)).await;
+        self.push_with_size(key, val, std::mem::size_of::<T>())
+            .await;
+    }
+
+    /// Pushes the value along with an estimate of its size in bytes, used in the statistics of
+    /// the cache
+    pub async fn push_with_size<T: Cacheable>(&self, key: CacheKey, val: T, size: usize) {
+        let key = String::from(key);
+        let size = u64::try_from(key.len().saturating_add(size)).unwrap_or(u64::MAX);
+
+        self.statistics.size.fetch_add(size, Ordering::Relaxed);
+        self.inner
+            .insert(
+                key,
+                CacheEntry {
+                    value: Arc::new(val),
+                    size,
+                },
+            )
+            .await;
     }
 
     pub async fn get_val<T: Clone + Cacheable>(&self, key: CacheKey) -> Option<T> {
@@ -203,12 +304,14 @@ impl Cache {
 
         // Add cache hit and cache miss metrics
         if val.is_some() {
+            self.statistics.hits.fetch_add(1, Ordering::Relaxed);
             metrics::IN_MEMORY_CACHE_HIT.add(
                 &metrics::CONTEXT,
                 1,
                 &add_attributes([("cache_type", self.name)]),
             );
         } else {
+            self.statistics.misses.fetch_add(1, Ordering::Relaxed);
             metrics::IN_MEMORY_CACHE_MISS.add(
                 &metrics::CONTEXT,
                 1,
@@ -216,7 +319,7 @@ impl Cache {
             );
         }
 
-        let val = (*val?).as_any().downcast_ref::<T>().cloned();
+        let val = (*val?.value).as_any().downcast_ref::<T>().cloned();
 
         val
     }
@@ -244,6 +347,59 @@ impl Cache {
         self.name
     }
 
+    pub async fn get_statistics(&self) -> CacheStatistics {
+        self.run_pending_tasks().await;
+
+        CacheStatistics {
+            name: self.name,
+            entry_count: self.get_entry_count(),
+            hit_count: self.statistics.hits.load(Ordering::Relaxed),
+            miss_count: self.statistics.misses.load(Ordering::Relaxed),
+            eviction_count: self.statistics.evictions.load(Ordering::Relaxed),
+            invalidation_count: self.statistics.invalidations.load(Ordering::Relaxed),
+            estimated_size: self.statistics.size.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the keys of the tenant that start with the specified prefix, in sorted order
+    pub fn get_keys_by_prefix(&self, tenant: &str, prefix: &str, limit: usize) -> Vec<String> {
+        let mut keys = self
+            .inner
+            .iter()
+            .filter_map(|(key, _)| {
+                strip_tenant(&key, tenant)
+                    .filter(|key| key.starts_with(prefix))
+                    .map(ToOwned::to_owned)
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys.truncate(limit);
+        keys
+    }
+
+    /// Removes the keys of the tenant that match the glob pattern, and returns the removed keys
+    pub async fn remove_by_pattern(&self, tenant: &str, pattern: &str) -> Vec<String> {
+        let keys = self
+            .inner
+            .iter()
+            .filter_map(|(key, _)| {
+                strip_tenant(&key, tenant)
+                    .filter(|key| matches_pattern(pattern, key))
+                    .map(ToOwned::to_owned)
+            })
+            .collect::<Vec<_>>();
+
+        for key in &keys {
+            self.remove(CacheKey {
+                key: key.clone(),
+                prefix: tenant.to_owned(),
+            })
+            .await;
+        }
+
+        keys
+    }
+
     pub async fn record_entry_count_metric(&self) {
         self.run_pending_tasks().await;
 
@@ -255,6 +411,53 @@ impl Cache {
     }
 }
 
+/// Returns the key without the tenant prefix added by `CacheKey`, if the key belongs to the tenant
+fn strip_tenant<'a>(key: &'a str, tenant: &str) -> Option<&'a str> {
+    if tenant.is_empty() {
+        Some(key)
+    } else {
+        key.strip_prefix(tenant)?.strip_prefix(':')
+    }
+}
+
+/// Matches the key against a glob pattern, where `*` matches any sequence of characters and `?`
+/// matches any single character
+pub fn matches_pattern(pattern: &str, key: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let key = key.chars().collect::<Vec<_>>();
+
+    // Position in the pattern of the last `*`, and the position in the key it was matched up to
+    let mut backtrack = None;
+    let (mut pattern_index, mut key_index) = (0, 0);
+
+    while key_index < key.len() {
+        match (pattern.get(pattern_index), key.get(key_index)) {
+            (Some('*'), _) => {
+                backtrack = Some((pattern_index, key_index));
+                pattern_index += 1;
+            }
+            (Some(pattern_char), Some(key_char))
+                if *pattern_char == '?' || pattern_char == key_char =>
+            {
+                pattern_index += 1;
+                key_index += 1;
+            }
+            _ => match backtrack {
+                Some((star_index, matched_index)) => {
+                    backtrack = Some((star_index, matched_index + 1));
+                    pattern_index = star_index + 1;
+                    key_index = matched_index + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern
+        .get(pattern_index..)
+        .is_some_and(|rest| rest.iter().all(|pattern_char| *pattern_char == '*'))
+}
+
 #[instrument(skip_all)]
 pub async fn get_or_populate_redis<T, F, Fut>(
     redis: &Arc<RedisConnectionPool>,
@@ -318,13 +521,15 @@ where
         Ok(val)
     } else {
         let val = get_or_populate_redis(redis, key, fun).await?;
+        let size = serde_json::to_vec(&val).map_or(0, |serialized| serialized.len());
         cache
-            .push(
+            .push_with_size(
                 CacheKey {
                     key: key.to_string(),
                     prefix: redis.key_prefix.clone(),
                 },
                 val.clone(),
+                size,
             )
             .await;
         Ok(val)
@@ -501,6 +706,89 @@ mod cache_tests {
         );
     }
 
+    #[tokio::test]
+    async fn statistics_of_cache() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        let key = CacheKey {
+            key: "key".to_string(),
+            prefix: "prefix".to_string(),
+        };
+        cache
+            .push_with_size(key.clone(), "val".to_string(), 10)
+            .await;
+        cache.get_val::<String>(key.clone()).await;
+        cache
+            .get_val::<String>(CacheKey {
+                key: "missing".to_string(),
+                prefix: "prefix".to_string(),
+            })
+            .await;
+
+        let statistics = cache.get_statistics().await;
+        assert_eq!(statistics.entry_count, 1);
+        assert_eq!(statistics.hit_count, 1);
+        assert_eq!(statistics.miss_count, 1);
+        // The key is stored as `prefix:key`
+        assert_eq!(statistics.estimated_size, 20);
+
+        cache.remove(key).await;
+        let statistics = cache.get_statistics().await;
+        assert_eq!(statistics.entry_count, 0);
+        assert_eq!(statistics.invalidation_count, 1);
+        assert_eq!(statistics.estimated_size, 0);
+    }
+
+    #[tokio::test]
+    async fn list_and_remove_keys_by_pattern() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        for (key, prefix) in [
+            ("routing_merchant_1", "tenant"),
+            ("routing_merchant_2", "tenant"),
+            ("cgraph_merchant_1", "tenant"),
+            ("routing_merchant_1", "other_tenant"),
+        ] {
+            cache
+                .push(
+                    CacheKey {
+                        key: key.to_string(),
+                        prefix: prefix.to_string(),
+                    },
+                    "val".to_string(),
+                )
+                .await;
+        }
+
+        assert_eq!(
+            cache.get_keys_by_prefix("tenant", "routing_", 10),
+            vec!["routing_merchant_1", "routing_merchant_2"]
+        );
+        assert_eq!(cache.get_keys_by_prefix("tenant", "", 1).len(), 1);
+
+        let mut removed = cache.remove_by_pattern("tenant", "*_merchant_1").await;
+        removed.sort();
+        assert_eq!(removed, vec!["cgraph_merchant_1", "routing_merchant_1"]);
+        assert_eq!(
+            cache.get_keys_by_prefix("tenant", "", 10),
+            vec!["routing_merchant_2"]
+        );
+        assert_eq!(
+            cache.get_keys_by_prefix("other_tenant", "", 10),
+            vec!["routing_merchant_1"]
+        );
+    }
+
+    #[test]
+    fn glob_pattern_matching() {
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("*", "routing_merchant"));
+        assert!(matches_pattern("routing_*", "routing_merchant"));
+        assert!(matches_pattern("*_merchant_?", "cgraph_merchant_1"));
+        assert!(matches_pattern("a*b*c", "aXbYbZc"));
+        assert!(!matches_pattern("routing_*", "cgraph_merchant"));
+        assert!(!matches_pattern("*_merchant_?", "cgraph_merchant_10"));
+        assert!(!matches_pattern("key", "key_1"));
+    }
+
     #[tokio::test]
     async fn eviction_on_time_test() {
         let cache = Cache::new("test", 2, 2, None);
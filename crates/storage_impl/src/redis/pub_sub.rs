@@ -5,8 +5,9 @@ use redis_interface::{errors as redis_errors, PubsubInterface, RedisValue};
 use router_env::{logger, tracing::Instrument};
 
 use crate::redis::cache::{
-    CacheKey, CacheKind, CacheRedact, ACCOUNTS_CACHE, CGRAPH_CACHE, CONFIG_CACHE,
-    DECISION_MANAGER_CACHE, PM_FILTERS_CGRAPH_CACHE, ROUTING_CACHE, SURCHARGE_CACHE,
+    find_cache_by_name, get_all_caches, CacheKey, CacheKind, CacheRedact, ACCOUNTS_CACHE,
+    CGRAPH_CACHE, CONFIG_CACHE, DECISION_MANAGER_CACHE, PM_FILTERS_CGRAPH_CACHE, ROUTING_CACHE,
+    SURCHARGE_CACHE,
 };
 
 #[async_trait::async_trait]
@@ -164,6 +165,37 @@ impl PubSubInterface for std::sync::Arc<redis_interface::RedisConnectionPool> {
                                 .await;
                             key
                         }
+                        CacheKind::Pattern { cache, pattern } => {
+                            let caches = match cache {
+                                Some(name) => {
+                                    find_cache_by_name(&name).into_iter().collect::<Vec<_>>()
+                                }
+                                None => get_all_caches().to_vec(),
+                            };
+
+                            // The Redis copies of the keys are deleted as well, so that they are
+                            // not populated back into the cache
+                            for cache in caches {
+                                for key in cache.remove_by_pattern(&message.tenant, &pattern).await
+                                {
+                                    self.delete_key(&key)
+                                        .await
+                                        .map_err(|err| {
+                                            logger::error!(
+                                                "Error while deleting redis key: {err:?}"
+                                            )
+                                        })
+                                        .ok();
+                                }
+                            }
+
+                            logger::debug!(
+                                key_prefix=?message.tenant.clone(),
+                                channel_name=?channel_name,
+                                "Done invalidating keys matching {pattern}"
+                            );
+                            continue;
+                        }
                         CacheKind::All(key) => {
                             CONFIG_CACHE
                                 .remove(CacheKey {
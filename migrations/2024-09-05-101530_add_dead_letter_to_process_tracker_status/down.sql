@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+UPDATE process_tracker SET status = 'finish' WHERE status = 'dead_letter';
+
+ALTER TYPE "ProcessTrackerStatus" RENAME TO "ProcessTrackerStatusOld";
+
+CREATE TYPE "ProcessTrackerStatus" AS ENUM (
+    'processing',
+    'new',
+    'pending',
+    'process_started',
+    'finish'
+);
+
+ALTER TABLE process_tracker
+ALTER COLUMN status TYPE "ProcessTrackerStatus" USING status::TEXT::"ProcessTrackerStatus";
+
+DROP TYPE "ProcessTrackerStatusOld";
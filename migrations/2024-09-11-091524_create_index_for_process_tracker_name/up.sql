@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX IF NOT EXISTS process_tracker_name_schedule_time_index ON process_tracker (name, schedule_time DESC);